@@ -28,7 +28,7 @@ use sc_consensus::{
 };
 use sc_service::{
 	config::{
-		DatabaseSource, KeepBlocks, KeystoreConfig, NetworkConfiguration, OffchainWorkerConfig,
+		BlocksPruning, DatabaseSource, KeystoreConfig, NetworkConfiguration, OffchainWorkerConfig,
 		PruningMode, TransactionStorageMode, WasmExecutionMethod,
 	},
 	BasePath, Configuration, Role,
@@ -74,8 +74,9 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		database: DatabaseSource::RocksDb { path: root.join("db"), cache_size: 128 },
 		state_cache_size: 67108864,
 		state_cache_child_ratio: None,
+		trie_cache_size: 67108864,
 		state_pruning: PruningMode::ArchiveAll,
-		keep_blocks: KeepBlocks::All,
+		blocks_pruning: BlocksPruning::KeepAll,
 		transaction_storage: TransactionStorageMode::BlockBody,
 		chain_spec: spec,
 		wasm_method: WasmExecutionMethod::Compiled,
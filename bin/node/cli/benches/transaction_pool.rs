@@ -24,7 +24,7 @@ use node_runtime::{constants::currency::*, BalancesCall, SudoCall};
 use sc_client_api::execution_extensions::ExecutionStrategies;
 use sc_service::{
 	config::{
-		DatabaseSource, KeepBlocks, KeystoreConfig, NetworkConfiguration, OffchainWorkerConfig,
+		BlocksPruning, DatabaseSource, KeystoreConfig, NetworkConfiguration, OffchainWorkerConfig,
 		PruningMode, TransactionPoolOptions, TransactionStorageMode, WasmExecutionMethod,
 	},
 	BasePath, Configuration, Role,
@@ -57,7 +57,9 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		transaction_pool: TransactionPoolOptions {
 			ready: PoolLimit { count: 100_000, total_bytes: 100 * 1024 * 1024 },
 			future: PoolLimit { count: 100_000, total_bytes: 100 * 1024 * 1024 },
+			sender: None,
 			reject_future_transactions: false,
+			journal_path: None,
 		},
 		network: network_config,
 		keystore: KeystoreConfig::InMemory,
@@ -65,8 +67,9 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		database: DatabaseSource::RocksDb { path: root.join("db"), cache_size: 128 },
 		state_cache_size: 67108864,
 		state_cache_child_ratio: None,
+		trie_cache_size: 67108864,
 		state_pruning: PruningMode::ArchiveAll,
-		keep_blocks: KeepBlocks::All,
+		blocks_pruning: BlocksPruning::KeepAll,
 		transaction_storage: TransactionStorageMode::BlockBody,
 		chain_spec: spec,
 		wasm_method: WasmExecutionMethod::Interpreted,
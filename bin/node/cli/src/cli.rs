@@ -70,18 +70,33 @@ pub enum Subcommand {
 	/// Validate blocks.
 	CheckBlock(sc_cli::CheckBlockCmd),
 
+	/// Check the consistency of the database.
+	CheckDb(sc_cli::CheckDbCmd),
+
 	/// Export blocks.
 	ExportBlocks(sc_cli::ExportBlocksCmd),
 
+	/// Export a binary snapshot of the state of a given block.
+	ExportSnapshot(sc_cli::ExportSnapshotCmd),
+
 	/// Export the state of a given block into a chain spec.
 	ExportState(sc_cli::ExportStateCmd),
 
 	/// Import blocks.
 	ImportBlocks(sc_cli::ImportBlocksCmd),
 
+	/// Start a new database from a state snapshot.
+	ImportSnapshot(sc_cli::ImportSnapshotCmd),
+
 	/// Remove the whole chain.
 	PurgeChain(sc_cli::PurgeChainCmd),
 
+	/// Migrate the RocksDb database to ParityDb.
+	///
+	/// Only databases with archive state pruning (`--pruning archive`) can be migrated. Once the
+	/// new database is verified, the RocksDb database is moved aside to `<path>.rocksdb-backup`.
+	MigrateDb(sc_cli::MigrateDbCmd),
+
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 }
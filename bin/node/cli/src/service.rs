@@ -270,7 +270,7 @@ pub fn new_partial(
 				},
 			};
 
-			node_rpc::create_full(deps).map_err(Into::into)
+			node_rpc::create_full::<_, _, _, _, node_runtime::Call>(deps).map_err(Into::into)
 		};
 
 		(rpc_extensions_builder, rpc_setup)
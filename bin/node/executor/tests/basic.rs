@@ -26,13 +26,14 @@ use sp_runtime::{
 	traits::Hash as HashT, transaction_validity::InvalidTransaction, ApplyExtrinsicResult,
 };
 
-use node_primitives::{Balance, Hash};
+use node_primitives::{AccountId, Balance, Hash};
 use node_runtime::{
 	constants::{currency::*, time::SLOT_DURATION},
 	Balances, Call, CheckedExtrinsic, Event, Header, Runtime, System, TransactionPayment,
 	UncheckedExtrinsic,
 };
 use node_testing::keyring::*;
+use pallet_contracts_primitives::ContractCallTrace;
 use wat;
 
 pub mod common;
@@ -684,8 +685,6 @@ fn deploying_wasm_contract_should_work() {
 
 	let addr = pallet_contracts::Pallet::<Runtime>::contract_address(&charlie(), &transfer_ch, &[]);
 
-	let subsistence = pallet_contracts::Pallet::<Runtime>::subsistence_threshold();
-
 	let time = 42 * 1000;
 	let b = construct_block(
 		&mut new_test_ext(compact_code_unwrap()),
@@ -700,8 +699,9 @@ fn deploying_wasm_contract_should_work() {
 				signed: Some((charlie(), signed_extra(0, 0))),
 				function: Call::Contracts(
 					pallet_contracts::Call::instantiate_with_code::<Runtime> {
-						endowment: 1000 * DOLLARS + subsistence,
+						endowment: 1000 * DOLLARS,
 						gas_limit: 500_000_000,
+						storage_deposit_limit: None,
 						code: transfer_code,
 						data: Vec::new(),
 						salt: Vec::new(),
@@ -714,6 +714,7 @@ fn deploying_wasm_contract_should_work() {
 					dest: sp_runtime::MultiAddress::Id(addr.clone()),
 					value: 10,
 					gas_limit: 500_000_000,
+					storage_deposit_limit: None,
 					data: vec![0x00, 0x01, 0x02, 0x03],
 				}),
 			},
@@ -734,6 +735,85 @@ fn deploying_wasm_contract_should_work() {
 	});
 }
 
+#[test]
+fn trace_block_traces_contract_calls_of_all_extrinsics() {
+	let transfer_code = wat::parse_str(CODE_TRANSFER).unwrap();
+	let transfer_ch = <Runtime as frame_system::Config>::Hashing::hash(&transfer_code);
+
+	let addr = pallet_contracts::Pallet::<Runtime>::contract_address(&charlie(), &transfer_ch, &[]);
+	let call = Call::Contracts(pallet_contracts::Call::call::<Runtime> {
+		dest: sp_runtime::MultiAddress::Id(addr.clone()),
+		value: 10,
+		gas_limit: 500_000_000,
+		storage_deposit_limit: None,
+		data: vec![0x00, 0x01, 0x02, 0x03],
+	});
+
+	let time = 42 * 1000;
+	let b = construct_block(
+		&mut new_test_ext(compact_code_unwrap()),
+		1,
+		GENESIS_HASH.into(),
+		vec![
+			CheckedExtrinsic {
+				signed: None,
+				function: Call::Timestamp(pallet_timestamp::Call::set { now: time }),
+			},
+			CheckedExtrinsic {
+				signed: Some((charlie(), signed_extra(0, 0))),
+				function: Call::Contracts(
+					pallet_contracts::Call::instantiate_with_code::<Runtime> {
+						endowment: 1000 * DOLLARS,
+						gas_limit: 500_000_000,
+						storage_deposit_limit: None,
+						code: transfer_code,
+						data: Vec::new(),
+						salt: Vec::new(),
+					},
+				),
+			},
+			CheckedExtrinsic {
+				signed: Some((alice(), signed_extra(0, 0))),
+				function: Call::Balances(pallet_balances::Call::transfer {
+					dest: bob().into(),
+					value: 69 * DOLLARS,
+				}),
+			},
+			CheckedExtrinsic {
+				signed: Some((charlie(), signed_extra(1, 0))),
+				function: Call::Utility(pallet_utility::Call::batch {
+					calls: vec![call.clone(), call],
+				}),
+			},
+		],
+		(time / SLOT_DURATION).into(),
+	);
+
+	// The block is traced on top of the state of its parent.
+	let mut t = new_test_ext(compact_code_unwrap());
+	let traces = executor_call::<NeverNativeValue, fn() -> _>(
+		&mut t,
+		"ContractsApi_trace_block",
+		&b.0,
+		false,
+		None,
+	)
+	.0
+	.unwrap()
+	.into_encoded();
+	let traces =
+		Vec::<ContractCallTrace<AccountId, Balance, Hash>>::decode(&mut &traces[..]).unwrap();
+
+	assert_eq!(
+		traces
+			.iter()
+			.map(|trace| (trace.extrinsic_index, trace.origin.clone()))
+			.collect::<Vec<_>>(),
+		vec![(1, charlie()), (3, charlie()), (3, charlie())],
+	);
+	assert!(traces.iter().all(|trace| trace.result.result.is_ok()));
+}
+
 #[test]
 fn wasm_big_block_import_fails() {
 	let mut t = new_test_ext(compact_code_unwrap());
@@ -184,6 +184,8 @@ pub struct StateDbMemoryInfo {
 pub struct MemoryInfo {
 	/// Size of state cache.
 	pub state_cache: MemorySize,
+	/// Size of trie node and per-block storage value cache.
+	pub trie_cache: MemorySize,
 	/// Size of backend database cache.
 	pub database_cache: MemorySize,
 	/// Size of the state db.
@@ -215,6 +217,16 @@ pub struct IoInfo {
 	pub state_writes_cache: u64,
 	/// State write (trie nodes) to backend db.
 	pub state_writes_nodes: u64,
+	/// Trie nodes found in the trie cache since the backend was opened.
+	///
+	/// This and the other cache counters below only ever grow.
+	pub trie_nodes_cache_hits: u64,
+	/// Trie nodes read from backend db since the backend was opened.
+	pub trie_nodes_cache_misses: u64,
+	/// State values found in the per-block value cache since the backend was opened.
+	pub state_values_cache_hits: u64,
+	/// State values read from the trie since the backend was opened.
+	pub state_values_cache_misses: u64,
 }
 
 /// Usage statistics for running client instance.
@@ -234,10 +246,12 @@ impl fmt::Display for UsageInfo {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		write!(
 			f,
-			"caches: ({} state, {} db overlay), \
+			"caches: ({} state, {} trie, {} db overlay), \
 			 state db: ({} non-canonical, {} pruning, {} pinned), \
-			 i/o: ({} tx, {} write, {} read, {} avg tx, {}/{} key cache reads/total, {} trie nodes writes)",
+			 i/o: ({} tx, {} write, {} read, {} avg tx, {}/{} key cache reads/total, \
+			 {}/{} trie node cache hits/misses, {} trie nodes writes)",
 			self.memory.state_cache,
+			self.memory.trie_cache,
 			self.memory.database_cache,
 			self.memory.state_db.non_canonical,
 			self.memory.state_db.pruning.unwrap_or_default(),
@@ -248,6 +262,8 @@ impl fmt::Display for UsageInfo {
 			self.io.average_transaction_size,
 			self.io.state_reads_cache,
 			self.io.state_reads,
+			self.io.trie_nodes_cache_hits,
+			self.io.trie_nodes_cache_misses,
 			self.io.state_writes_nodes,
 		)
 	}
@@ -0,0 +1,97 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{DatabaseParams, PruningParams, SharedParams},
+	CliConfiguration,
+};
+use log::{info, warn};
+use sc_client_api::{BlockBackend, HeaderBackend};
+use sc_service::{BlocksPruning, DatabaseSource};
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+use structopt::StructOpt;
+
+/// The `check-db` command used to check the consistency of the database.
+#[derive(Debug, StructOpt, Clone)]
+pub struct CheckDbCmd {
+	/// Also iterate the full state of the finalized block to find missing trie nodes.
+	#[structopt(long)]
+	pub state: bool,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl CheckDbCmd {
+	/// Run the check-db command
+	pub fn run<B, BA, C>(
+		&self,
+		client: Arc<C>,
+		backend: Arc<BA>,
+		database: &DatabaseSource,
+		blocks_pruning: BlocksPruning,
+	) -> error::Result<()>
+	where
+		B: BlockT,
+		BA: sc_client_api::backend::Backend<B>,
+		C: HeaderBackend<B> + BlockBackend<B>,
+	{
+		let start = std::time::Instant::now();
+		let report =
+			sc_service::chain_ops::check_db(client, backend, database, blocks_pruning, self.state)?;
+		for issue in &report.issues {
+			warn!("{}", issue);
+		}
+		match report.keys {
+			Some(keys) => info!("Checked {} blocks and {} storage keys", report.blocks, keys),
+			None => info!("Checked {} blocks", report.blocks),
+		}
+		println!("Completed in {} ms.", start.elapsed().as_millis());
+
+		if report.issues.is_empty() {
+			Ok(())
+		} else {
+			Err(format!("Found {} issues in the database", report.issues.len()).into())
+		}
+	}
+}
+
+impl CliConfiguration for CheckDbCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
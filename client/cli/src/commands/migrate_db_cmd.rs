@@ -0,0 +1,116 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2018-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{DatabaseParams, SharedParams},
+	CliConfiguration,
+};
+use sc_service::DatabaseSource;
+use sp_runtime::traits::Block as BlockT;
+use std::io::{self, Write};
+use structopt::StructOpt;
+
+/// The `migrate-db` command used to migrate the RocksDB database to ParityDB.
+#[derive(Debug, StructOpt, Clone)]
+pub struct MigrateDbCmd {
+	/// Skip interactive prompt by answering yes automatically.
+	#[structopt(short = "y")]
+	pub yes: bool,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl MigrateDbCmd {
+	/// Run the migrate-db command
+	pub fn run<B: BlockT>(&self, database_config: DatabaseSource) -> error::Result<()> {
+		let (rocksdb_path, paritydb_path, cache_size) = match database_config {
+			DatabaseSource::RocksDb { path, cache_size } => {
+				// `<base>/db/<role>` is migrated to `<base>/paritydb/<role>`
+				let paritydb_path = match (path.parent().and_then(|p| p.parent()), path.file_name())
+				{
+					(Some(base), Some(role)) => base.join("paritydb").join(role),
+					_ => {
+						return Err(error::Error::Input(format!(
+							"Cannot derive the ParityDb path from {:?}",
+							path
+						)))
+					}
+				};
+				(path, paritydb_path, cache_size)
+			}
+			DatabaseSource::Auto { paritydb_path, rocksdb_path, cache_size } => {
+				(rocksdb_path, paritydb_path, cache_size)
+			}
+			_ => return Err(error::Error::Input("Only RocksDb databases can be migrated".into())),
+		};
+
+		if !self.yes {
+			print!(
+				"Are you sure to migrate {:?} to {:?} and move it aside? [y/N]: ",
+				&rocksdb_path, &paritydb_path
+			);
+			io::stdout().flush().expect("failed to flush stdout");
+
+			let mut input = String::new();
+			io::stdin().read_line(&mut input)?;
+			let input = input.trim();
+
+			match input.chars().nth(0) {
+				Some('y') | Some('Y') => {}
+				_ => {
+					println!("Aborted");
+					return Ok(());
+				}
+			}
+		}
+
+		let summary = sc_service::migrate_database::<B>(
+			&DatabaseSource::RocksDb { path: rocksdb_path.clone(), cache_size },
+			&DatabaseSource::ParityDb { path: paritydb_path.clone() },
+		)?;
+		println!(
+			"Migrated {} entries ({} bytes) from {:?} to {:?}.",
+			summary.entries, summary.bytes, &rocksdb_path, &paritydb_path,
+		);
+		println!(
+			"{:?} was moved to {:?}, remove it once the node runs on the new database. Start the \
+			 node with `--database paritydb` or without `--database`, `--database rocksdb` would \
+			 sync a new database from scratch.",
+			&rocksdb_path, &summary.source_backup,
+		);
+
+		Ok(())
+	}
+}
+
+impl CliConfiguration for MigrateDbCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
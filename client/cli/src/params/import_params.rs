@@ -76,6 +76,12 @@ pub struct ImportParams {
 	/// Specify the state cache size.
 	#[structopt(long = "state-cache-size", value_name = "Bytes", default_value = "67108864")]
 	pub state_cache_size: usize,
+
+	/// Specify the size of the cache of trie nodes and state values shared across blocks.
+	///
+	/// A quarter of it caches state values per block, the rest trie nodes. `0` disables it.
+	#[structopt(long = "trie-cache-size", value_name = "Bytes", default_value = "67108864")]
+	pub trie_cache_size: usize,
 }
 
 impl ImportParams {
@@ -84,6 +90,11 @@ impl ImportParams {
 		self.state_cache_size
 	}
 
+	/// Specify the trie cache size.
+	pub fn trie_cache_size(&self) -> usize {
+		self.trie_cache_size
+	}
+
 	/// Get the WASM execution method from the parameters
 	pub fn wasm_method(&self) -> sc_service::config::WasmExecutionMethod {
 		self.wasm_method.into()
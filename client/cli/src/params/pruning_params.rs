@@ -17,7 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::error;
-use sc_service::{KeepBlocks, PruningMode, Role};
+use sc_service::{BlocksPruning, PruningMode, Role};
 use structopt::StructOpt;
 
 /// Parameters to define the pruning mode
@@ -30,11 +30,17 @@ pub struct PruningParams {
 	/// 256 blocks.
 	#[structopt(long = "pruning", value_name = "PRUNING_MODE")]
 	pub pruning: Option<String>,
-	/// Specify the number of finalized blocks to keep in the database.
+	/// Specify the blocks pruning mode, a number of finalized blocks to keep, 'archive' or
+	/// 'archive-canonical'.
+	///
+	/// This only affects block bodies and justifications, headers are always kept. 'archive'
+	/// keeps every block that was ever imported, 'archive-canonical' keeps all finalized blocks
+	/// but discards the blocks of displaced forks. A number keeps that many of the most recent
+	/// finalized blocks and also discards displaced forks.
 	///
 	/// Default is to keep all blocks.
-	#[structopt(long, value_name = "COUNT")]
-	pub keep_blocks: Option<u32>,
+	#[structopt(long = "blocks-pruning", alias = "keep-blocks", value_name = "PRUNING_MODE")]
+	pub blocks_pruning: Option<String>,
 }
 
 impl PruningParams {
@@ -65,10 +71,14 @@ impl PruningParams {
 	}
 
 	/// Get the block pruning value from the parameters
-	pub fn keep_blocks(&self) -> error::Result<KeepBlocks> {
-		Ok(match self.keep_blocks {
-			Some(n) => KeepBlocks::Some(n),
-			None => KeepBlocks::All,
+	pub fn blocks_pruning(&self) -> error::Result<BlocksPruning> {
+		Ok(match &self.blocks_pruning {
+			Some(ref s) if s == "archive" => BlocksPruning::KeepAll,
+			Some(ref s) if s == "archive-canonical" => BlocksPruning::KeepFinalized,
+			None => BlocksPruning::KeepAll,
+			Some(s) => BlocksPruning::Some(s.parse().map_err(|_| {
+				error::Error::Input("Invalid blocks pruning mode specified".to_string())
+			})?),
 		})
 	}
 }
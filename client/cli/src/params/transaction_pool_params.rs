@@ -16,9 +16,13 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use sc_service::config::TransactionPoolOptions;
+use sc_service::config::{TransactionPoolOptions, TransactionPoolSenderLimit};
+use std::path::PathBuf;
 use structopt::StructOpt;
 
+/// Default name of the transaction pool journal, relative to the chain's config directory.
+const DEFAULT_JOURNAL_PATH: &str = "transaction-pool.journal";
+
 /// Parameters used to create the pool configuration.
 #[derive(Debug, StructOpt, Clone)]
 pub struct TransactionPoolParams {
@@ -29,6 +33,28 @@ pub struct TransactionPoolParams {
 	/// Maximum number of kilobytes of all transactions stored in the pool.
 	#[structopt(long = "pool-kbytes", value_name = "COUNT", default_value = "20480")]
 	pub pool_kbytes: usize,
+
+	/// Maximum number of transactions of a single sender in the transaction pool.
+	///
+	/// When a sender exceeds this limit, its most recent transactions are dropped, starting with
+	/// the ones that are not yet ready. Not limited by default.
+	#[structopt(long = "pool-sender-limit", value_name = "COUNT")]
+	pub pool_sender_limit: Option<usize>,
+
+	/// Number of leading bytes of the first tag provided by a transaction that identify its
+	/// sender.
+	///
+	/// FRAME based chains provide the encoded account id of the sender followed by its nonce, so
+	/// this is the length of an encoded account id.
+	#[structopt(long = "pool-sender-tag-prefix", value_name = "BYTES", default_value = "32")]
+	pub pool_sender_tag_prefix: usize,
+
+	/// Persist ready and future transactions across node restarts.
+	///
+	/// The transactions are journaled to a file in the chain's config directory and
+	/// re-validated when the node starts again.
+	#[structopt(long = "pool-journal")]
+	pub pool_journal: bool,
 }
 
 impl TransactionPoolParams {
@@ -45,6 +71,15 @@ impl TransactionPoolParams {
 		opts.future.count = self.pool_limit / factor;
 		opts.future.total_bytes = self.pool_kbytes * 1024 / factor;
 
+		// per sender
+		opts.sender = self.pool_sender_limit.map(|count| TransactionPoolSenderLimit {
+			count,
+			tag_prefix: self.pool_sender_tag_prefix,
+		});
+
+		// journal, resolved against the chain's config directory
+		opts.journal_path = self.pool_journal.then(|| PathBuf::from(DEFAULT_JOURNAL_PATH));
+
 		opts
 	}
 }
@@ -34,10 +34,12 @@ pub mod offchain;
 pub mod bench;
 
 mod children;
+mod migration;
 #[cfg(feature = "with-parity-db")]
 mod parity_db;
 mod stats;
 mod storage_cache;
+mod trie_cache;
 #[cfg(any(feature = "with-kvdb-rocksdb", test))]
 mod upgrade;
 mod utils;
@@ -55,6 +57,7 @@ use std::{
 use crate::{
 	stats::StateUsageStats,
 	storage_cache::{new_shared_cache, CachingState, SharedCache, SyncingCachingState},
+	trie_cache::{SharedTrieCache, TrieCache},
 	utils::{meta_keys, read_db, read_meta, DatabaseType, Meta},
 };
 use codec::{Decode, Encode};
@@ -96,6 +99,7 @@ pub use sp_database::Database;
 
 #[cfg(any(feature = "with-kvdb-rocksdb", test))]
 pub use bench::BenchmarkingState;
+pub use migration::{migrate_database, MigrationSummary};
 
 const CACHE_HEADERS: usize = 8;
 
@@ -286,22 +290,29 @@ pub struct DatabaseSettings {
 	pub state_cache_size: usize,
 	/// Ratio of cache size dedicated to child tries.
 	pub state_cache_child_ratio: Option<(usize, usize)>,
+	/// Size of the trie node and per-block storage value cache in bytes.
+	pub trie_cache_size: usize,
 	/// State pruning mode.
 	pub state_pruning: PruningMode,
 	/// Where to find the database.
 	pub source: DatabaseSource,
 	/// Block pruning mode.
-	pub keep_blocks: KeepBlocks,
+	pub blocks_pruning: BlocksPruning,
 	/// Block body/Transaction storage scheme.
 	pub transaction_storage: TransactionStorageMode,
 }
 
 /// Block pruning settings.
-#[derive(Debug, Clone, Copy)]
-pub enum KeepBlocks {
-	/// Keep full block history.
-	All,
-	/// Keep N recent finalized blocks.
+///
+/// Unlike [`PruningMode`], which only applies to the state, this controls which block bodies and
+/// justifications are kept. Headers are always kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlocksPruning {
+	/// Keep full block history, of every block that was ever imported.
+	KeepAll,
+	/// Keep full finalized block history, discarding the blocks of displaced forks.
+	KeepFinalized,
+	/// Keep N recent finalized blocks, discarding the blocks of displaced forks.
 	Some(u32),
 }
 
@@ -375,6 +386,30 @@ impl DatabaseSource {
 			DatabaseSource::Custom(..) => false,
 		}
 	}
+
+	/// Column and key under which the database stores the state trie node with the given `hash`,
+	/// found at `prefix` in the trie.
+	///
+	/// Databases without reference counting store the nodes under their hash prefixed with their
+	/// position in the trie, the others under their hash alone.
+	pub fn state_node_key<H: hash_db::Hasher>(
+		&self,
+		hash: &H::Out,
+		prefix: Prefix,
+	) -> (u32, Vec<u8>) {
+		let prefix_keys = match self {
+			DatabaseSource::RocksDb { .. } => true,
+			DatabaseSource::ParityDb { .. } => false,
+			// an existing RocksDB database is opened in priority
+			DatabaseSource::Auto { rocksdb_path, .. } => {
+				cfg!(feature = "with-kvdb-rocksdb") && rocksdb_path.exists()
+			}
+			DatabaseSource::Custom(db) => !db.supports_ref_counting(),
+		};
+		let key =
+			if prefix_keys { prefixed_key::<H>(hash, prefix) } else { hash.as_ref().to_vec() };
+		(columns::STATE, key)
+	}
 }
 
 impl std::fmt::Display for DatabaseSource {
@@ -884,18 +919,24 @@ impl<Block: BlockT> sc_client_api::backend::BlockImportOperation<Block>
 struct StorageDb<Block: BlockT> {
 	pub db: Arc<dyn Database<DbHash>>,
 	pub state_db: StateDb<Block::Hash, Vec<u8>>,
+	pub trie_cache: SharedTrieCache<Block::Hash>,
 	prefix_keys: bool,
 }
 
 impl<Block: BlockT> sp_state_machine::Storage<HashFor<Block>> for StorageDb<Block> {
 	fn get(&self, key: &Block::Hash, prefix: Prefix) -> Result<Option<DBValue>, String> {
+		let get = |key: &[u8]| {
+			self.trie_cache.node(key, || {
+				self.state_db
+					.get(key, self)
+					.map_err(|e| format!("Database backend error: {:?}", e))
+			})
+		};
 		if self.prefix_keys {
-			let key = prefixed_key::<HashFor<Block>>(key, prefix);
-			self.state_db.get(&key, self)
+			get(&prefixed_key::<HashFor<Block>>(key, prefix))
 		} else {
-			self.state_db.get(key.as_ref(), self)
+			get(key.as_ref())
 		}
-		.map_err(|e| format!("Database backend error: {:?}", e))
 	}
 }
 
@@ -995,7 +1036,7 @@ pub struct Backend<Block: BlockT> {
 	shared_cache: SharedCache<Block>,
 	import_lock: Arc<RwLock<()>>,
 	is_archive: bool,
-	keep_blocks: KeepBlocks,
+	blocks_pruning: BlocksPruning,
 	transaction_storage: TransactionStorageMode,
 	io_stats: FrozenForDuration<(kvdb::IoStats, StateUsageInfo)>,
 	state_usage: Arc<StateUsageStats>,
@@ -1015,7 +1056,7 @@ impl<Block: BlockT> Backend<Block> {
 	#[cfg(any(test, feature = "test-helpers"))]
 	pub fn new_test(keep_blocks: u32, canonicalization_delay: u64) -> Self {
 		Self::new_test_with_tx_storage(
-			keep_blocks,
+			BlocksPruning::Some(keep_blocks),
 			canonicalization_delay,
 			TransactionStorageMode::BlockBody,
 		)
@@ -1024,18 +1065,24 @@ impl<Block: BlockT> Backend<Block> {
 	/// Create new memory-backed client backend for tests.
 	#[cfg(any(test, feature = "test-helpers"))]
 	pub fn new_test_with_tx_storage(
-		keep_blocks: u32,
+		blocks_pruning: BlocksPruning,
 		canonicalization_delay: u64,
 		transaction_storage: TransactionStorageMode,
 	) -> Self {
 		let db = kvdb_memorydb::create(crate::utils::NUM_COLUMNS);
 		let db = sp_database::as_database(db);
+		let state_pruning = match blocks_pruning {
+			BlocksPruning::KeepAll => PruningMode::ArchiveAll,
+			BlocksPruning::KeepFinalized => PruningMode::ArchiveCanonical,
+			BlocksPruning::Some(keep_blocks) => PruningMode::keep_blocks(keep_blocks),
+		};
 		let db_setting = DatabaseSettings {
 			state_cache_size: 16777216,
 			state_cache_child_ratio: Some((50, 100)),
-			state_pruning: PruningMode::keep_blocks(keep_blocks),
+			trie_cache_size: 0,
+			state_pruning,
 			source: DatabaseSource::Custom(db),
-			keep_blocks: KeepBlocks::Some(keep_blocks),
+			blocks_pruning,
 			transaction_storage,
 		};
 
@@ -1056,8 +1103,12 @@ impl<Block: BlockT> Backend<Block> {
 			&StateMetaDb(&*db),
 		)
 		.map_err(map_e)?;
-		let storage_db =
-			StorageDb { db: db.clone(), state_db, prefix_keys: !db.supports_ref_counting() };
+		let storage_db = StorageDb {
+			db: db.clone(),
+			state_db,
+			trie_cache: Arc::new(TrieCache::new(config.trie_cache_size)),
+			prefix_keys: !db.supports_ref_counting(),
+		};
 		let offchain_storage = offchain::LocalStorage::new(db.clone());
 
 		let backend = Backend {
@@ -1073,7 +1124,7 @@ impl<Block: BlockT> Backend<Block> {
 			is_archive: is_archive_pruning,
 			io_stats: FrozenForDuration::new(std::time::Duration::from_secs(1)),
 			state_usage: Arc::new(StateUsageStats::new()),
-			keep_blocks: config.keep_blocks.clone(),
+			blocks_pruning: config.blocks_pruning,
 			transaction_storage: config.transaction_storage.clone(),
 			genesis_state: RwLock::new(None),
 		};
@@ -1653,32 +1704,44 @@ impl<Block: BlockT> Backend<Block> {
 		finalized: NumberFor<Block>,
 		displaced: &FinalizationDisplaced<Block::Hash, NumberFor<Block>>,
 	) -> ClientResult<()> {
-		if let KeepBlocks::Some(keep_blocks) = self.keep_blocks {
+		if let BlocksPruning::Some(blocks_pruning) = self.blocks_pruning {
 			// Always keep the last finalized block
-			let keep = std::cmp::max(keep_blocks, 1);
+			let keep = std::cmp::max(blocks_pruning, 1);
 			if finalized >= keep.into() {
 				let number = finalized.saturating_sub(keep.into());
 				self.prune_block(transaction, BlockId::<Block>::number(number))?;
 			}
+		}
 
-			// Also discard all blocks from displaced branches
-			for h in displaced.leaves() {
-				let mut number = finalized;
-				let mut hash = h.clone();
-				// Follow displaced chains back until we reach a finalized block.
-				// Since leaves are discarded due to finality, they can't have parents
-				// that are canonical, but not yet finalized. So we stop deletig as soon as
-				// we reach canonical chain.
-				while self.blockchain.hash(number)? != Some(hash.clone()) {
-					let id = BlockId::<Block>::hash(hash.clone());
-					match self.blockchain.header(id)? {
-						Some(header) => {
-							self.prune_block(transaction, id)?;
-							number = header.number().saturating_sub(One::one());
-							hash = header.parent_hash().clone();
-						}
-						None => break,
+		if self.blocks_pruning != BlocksPruning::KeepAll {
+			self.prune_displaced_branches(transaction, finalized, displaced)?;
+		}
+		Ok(())
+	}
+
+	fn prune_displaced_branches(
+		&self,
+		transaction: &mut Transaction<DbHash>,
+		finalized: NumberFor<Block>,
+		displaced: &FinalizationDisplaced<Block::Hash, NumberFor<Block>>,
+	) -> ClientResult<()> {
+		// Discard all blocks from displaced branches
+		for h in displaced.leaves() {
+			let mut number = finalized;
+			let mut hash = h.clone();
+			// Follow displaced chains back until we reach a finalized block.
+			// Since leaves are discarded due to finality, they can't have parents
+			// that are canonical, but not yet finalized. So we stop deletig as soon as
+			// we reach canonical chain.
+			while self.blockchain.hash(number)? != Some(hash.clone()) {
+				let id = BlockId::<Block>::hash(hash.clone());
+				match self.blockchain.header(id)? {
+					Some(header) => {
+						self.prune_block(transaction, id)?;
+						number = header.number().saturating_sub(One::one());
+						hash = header.parent_hash().clone();
 					}
+					None => break,
 				}
 			}
 		}
@@ -1700,6 +1763,13 @@ impl<Block: BlockT> Backend<Block> {
 					columns::BODY,
 					id,
 				)?;
+				utils::remove_from_db(
+					transaction,
+					&*self.storage.db,
+					columns::KEY_LOOKUP,
+					columns::JUSTIFICATIONS,
+					id,
+				)?;
 				match self.transaction_storage {
 					TransactionStorageMode::BlockBody => {}
 					TransactionStorageMode::StorageChain => {
@@ -1983,13 +2053,15 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 				self.state_usage.take(),
 			)
 		});
+		let trie_cache_stats = self.storage.trie_cache.stats();
 		let database_cache = MemorySize::from_bytes(0);
 		let state_cache =
 			MemorySize::from_bytes((*&self.shared_cache).read().used_storage_cache_size());
+		let trie_cache = MemorySize::from_bytes(self.storage.trie_cache.used_size());
 		let state_db = self.storage.state_db.memory_info();
 
 		Some(UsageInfo {
-			memory: MemoryInfo { state_cache, database_cache, state_db },
+			memory: MemoryInfo { state_cache, trie_cache, database_cache, state_db },
 			io: IoInfo {
 				transactions: io_stats.transactions,
 				bytes_read: io_stats.bytes_read,
@@ -2002,6 +2074,10 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 				state_writes_cache: state_stats.overlay_writes.ops,
 				state_reads_cache: state_stats.cache_reads.ops,
 				state_writes_nodes: state_stats.nodes_writes.ops,
+				trie_nodes_cache_hits: trie_cache_stats.node_hits,
+				trie_nodes_cache_misses: trie_cache_stats.node_misses,
+				state_values_cache_hits: trie_cache_stats.value_hits,
+				state_values_cache_misses: trie_cache_stats.value_misses,
 			},
 		})
 	}
@@ -2218,7 +2294,8 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 					let state =
 						RefTrackingState::new(db_state, self.storage.clone(), Some(hash.clone()));
 					let caching_state =
-						CachingState::new(state, self.shared_cache.clone(), Some(hash));
+						CachingState::new(state, self.shared_cache.clone(), Some(hash))
+							.with_trie_cache(self.storage.trie_cache.clone());
 					Ok(SyncingCachingState::new(
 						caching_state,
 						self.state_usage.clone(),
@@ -2371,9 +2448,10 @@ pub(crate) mod tests {
 			DatabaseSettings {
 				state_cache_size: 16777216,
 				state_cache_child_ratio: Some((50, 100)),
+				trie_cache_size: 0,
 				state_pruning: PruningMode::keep_blocks(1),
 				source: DatabaseSource::Custom(backing),
-				keep_blocks: KeepBlocks::All,
+				blocks_pruning: BlocksPruning::KeepAll,
 				transaction_storage: TransactionStorageMode::BlockBody,
 			},
 			0,
@@ -3031,7 +3109,8 @@ pub(crate) mod tests {
 	#[test]
 	fn prune_blocks_on_finalize() {
 		for storage in &[TransactionStorageMode::BlockBody, TransactionStorageMode::StorageChain] {
-			let backend = Backend::<Block>::new_test_with_tx_storage(2, 0, *storage);
+			let backend =
+				Backend::<Block>::new_test_with_tx_storage(BlocksPruning::Some(2), 0, *storage);
 			let mut blocks = Vec::new();
 			let mut prev_hash = Default::default();
 			for i in 0..5 {
@@ -3067,8 +3146,11 @@ pub(crate) mod tests {
 
 	#[test]
 	fn prune_blocks_on_finalize_with_fork() {
-		let backend =
-			Backend::<Block>::new_test_with_tx_storage(2, 10, TransactionStorageMode::StorageChain);
+		let backend = Backend::<Block>::new_test_with_tx_storage(
+			BlocksPruning::Some(2),
+			10,
+			TransactionStorageMode::StorageChain,
+		);
 		let mut blocks = Vec::new();
 		let mut prev_hash = Default::default();
 		for i in 0..5 {
@@ -3124,10 +3206,76 @@ pub(crate) mod tests {
 		assert_eq!(Some(vec![4.into()]), bc.body(BlockId::hash(blocks[4])).unwrap());
 	}
 
+	#[test]
+	fn keep_finalized_blocks_and_prune_displaced_forks() {
+		let backend = Backend::<Block>::new_test_with_tx_storage(
+			BlocksPruning::KeepFinalized,
+			10,
+			TransactionStorageMode::BlockBody,
+		);
+		let mut blocks = Vec::new();
+		let mut prev_hash = Default::default();
+		for i in 0..5 {
+			let hash = insert_block(
+				&backend,
+				i,
+				prev_hash,
+				None,
+				Default::default(),
+				vec![i.into()],
+				None,
+			);
+			blocks.push(hash);
+			prev_hash = hash;
+		}
+
+		// insert a fork at block 2
+		let fork_hash_root = insert_block(
+			&backend,
+			2,
+			blocks[1],
+			None,
+			sp_core::H256::random(),
+			vec![2.into()],
+			None,
+		);
+		let fork_hash_leaf = insert_block(
+			&backend,
+			3,
+			fork_hash_root,
+			None,
+			H256::random(),
+			vec![3.into(), 11.into()],
+			None,
+		);
+		let mut op = backend.begin_operation().unwrap();
+		backend.begin_state_operation(&mut op, BlockId::Hash(blocks[4])).unwrap();
+		op.mark_head(BlockId::Hash(blocks[4])).unwrap();
+		backend.commit_operation(op).unwrap();
+
+		for i in 1..5 {
+			let mut op = backend.begin_operation().unwrap();
+			backend.begin_state_operation(&mut op, BlockId::Hash(blocks[4])).unwrap();
+			op.mark_finalized(BlockId::Hash(blocks[i]), None).unwrap();
+			backend.commit_operation(op).unwrap();
+		}
+
+		let bc = backend.blockchain();
+		for i in 0..5u64 {
+			assert_eq!(Some(vec![i.into()]), bc.body(BlockId::hash(blocks[i as usize])).unwrap());
+		}
+		assert_eq!(None, bc.body(BlockId::hash(fork_hash_root)).unwrap());
+		assert_eq!(None, bc.body(BlockId::hash(fork_hash_leaf)).unwrap());
+		assert!(bc.header(BlockId::hash(fork_hash_leaf)).unwrap().is_some());
+	}
+
 	#[test]
 	fn renew_transaction_storage() {
-		let backend =
-			Backend::<Block>::new_test_with_tx_storage(2, 10, TransactionStorageMode::StorageChain);
+		let backend = Backend::<Block>::new_test_with_tx_storage(
+			BlocksPruning::Some(2),
+			10,
+			TransactionStorageMode::StorageChain,
+		);
 		let mut blocks = Vec::new();
 		let mut prev_hash = Default::default();
 		let x1 = ExtrinsicWrapper::from(0u64).encode();
@@ -3173,8 +3321,11 @@ pub(crate) mod tests {
 
 	#[test]
 	fn remove_leaf_block_works() {
-		let backend =
-			Backend::<Block>::new_test_with_tx_storage(2, 10, TransactionStorageMode::StorageChain);
+		let backend = Backend::<Block>::new_test_with_tx_storage(
+			BlocksPruning::Some(2),
+			10,
+			TransactionStorageMode::StorageChain,
+		);
 		let mut blocks = Vec::new();
 		let mut prev_hash = Default::default();
 		for i in 0..2 {
@@ -3270,4 +3421,183 @@ pub(crate) mod tests {
 
 		assert_eq!(backend.blockchain().info().finalized_hash, block1);
 	}
+
+	fn new_test_with_trie_cache(keep_blocks: u32, canonicalization_delay: u64) -> Backend<Block> {
+		let db = kvdb_memorydb::create(crate::utils::NUM_COLUMNS);
+		Backend::<Block>::new(
+			DatabaseSettings {
+				// reads go through the trie cache only
+				state_cache_size: 0,
+				state_cache_child_ratio: None,
+				trie_cache_size: 1 << 20,
+				state_pruning: PruningMode::keep_blocks(keep_blocks),
+				source: DatabaseSource::Custom(sp_database::as_database(db)),
+				blocks_pruning: BlocksPruning::KeepAll,
+				transaction_storage: TransactionStorageMode::BlockBody,
+			},
+			canonicalization_delay,
+		)
+		.unwrap()
+	}
+
+	/// Imports a block as the best one, applying `changes` to the state of its parent.
+	fn insert_block_with_changes(
+		backend: &Backend<Block>,
+		number: u64,
+		parent_hash: H256,
+		changes: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+		extrinsics_root: H256,
+	) -> H256 {
+		let mut op = backend.begin_operation().unwrap();
+		let parent = if number == 0 { Default::default() } else { parent_hash };
+		backend.begin_state_operation(&mut op, BlockId::Hash(parent)).unwrap();
+
+		let (root, overlay) =
+			op.old_state.storage_root(changes.iter().map(|(k, v)| (&k[..], v.as_deref())));
+		let header = Header {
+			number,
+			parent_hash,
+			state_root: root,
+			digest: Default::default(),
+			extrinsics_root,
+		};
+		if number == 0 {
+			op.reset_storage(Storage {
+				top: changes.into_iter().filter_map(|(k, v)| Some((k, v?))).collect(),
+				children_default: Default::default(),
+			})
+			.unwrap();
+		} else {
+			op.update_db_storage(overlay).unwrap();
+			op.update_storage(changes, Vec::new()).unwrap();
+		}
+		op.set_block_data(header.clone(), Some(vec![]), None, None, NewBlockState::Best)
+			.unwrap();
+		backend.commit_operation(op).unwrap();
+
+		header.hash()
+	}
+
+	fn storage_at(backend: &Backend<Block>, hash: H256, key: &[u8]) -> Option<Vec<u8>> {
+		backend.state_at(BlockId::Hash(hash)).unwrap().storage(key).unwrap()
+	}
+
+	#[test]
+	fn trie_cache_reads_states_of_both_forks_after_reorg() {
+		let backend = new_test_with_trie_cache(10, 10);
+		let block0 = insert_block_with_changes(
+			&backend,
+			0,
+			Default::default(),
+			vec![(b"key".to_vec(), Some(b"genesis".to_vec()))],
+			Default::default(),
+		);
+		let block1a = insert_block_with_changes(
+			&backend,
+			1,
+			block0,
+			vec![(b"key".to_vec(), Some(b"a".to_vec()))],
+			H256::repeat_byte(1),
+		);
+		assert_eq!(storage_at(&backend, block1a, b"key"), Some(b"a".to_vec()));
+
+		// a fork of the same height becomes the best block
+		let block1b = insert_block_with_changes(
+			&backend,
+			1,
+			block0,
+			vec![(b"key".to_vec(), Some(b"b".to_vec()))],
+			H256::repeat_byte(2),
+		);
+		assert_eq!(backend.blockchain().info().best_hash, block1b);
+		let block2b = insert_block_with_changes(
+			&backend,
+			2,
+			block1b,
+			vec![(b"other".to_vec(), Some(b"b".to_vec()))],
+			Default::default(),
+		);
+
+		// every block keeps reading its own state, cached or not
+		for _ in 0..2 {
+			assert_eq!(storage_at(&backend, block0, b"key"), Some(b"genesis".to_vec()));
+			assert_eq!(storage_at(&backend, block1a, b"key"), Some(b"a".to_vec()));
+			assert_eq!(storage_at(&backend, block1b, b"key"), Some(b"b".to_vec()));
+			assert_eq!(storage_at(&backend, block2b, b"key"), Some(b"b".to_vec()));
+			assert_eq!(storage_at(&backend, block1a, b"other"), None);
+			assert_eq!(storage_at(&backend, block2b, b"other"), Some(b"b".to_vec()));
+		}
+		assert!(backend.storage.trie_cache.stats().value_hits > 0);
+	}
+
+	#[test]
+	fn trie_cache_does_not_serve_pruned_states() {
+		let backend = new_test_with_trie_cache(1, 0);
+		let mut hashes = vec![insert_block_with_changes(
+			&backend,
+			0,
+			Default::default(),
+			vec![(b"key".to_vec(), Some(vec![0]))],
+			Default::default(),
+		)];
+		for i in 1..5u8 {
+			let hash = insert_block_with_changes(
+				&backend,
+				i as u64,
+				hashes[i as usize - 1],
+				vec![(b"key".to_vec(), Some(vec![i]))],
+				Default::default(),
+			);
+			// fill the caches with the state of the block
+			assert_eq!(storage_at(&backend, hash, b"key"), Some(vec![i]));
+			hashes.push(hash);
+		}
+
+		assert!(backend.state_at(BlockId::Hash(hashes[1])).is_err());
+		assert_eq!(storage_at(&backend, hashes[4], b"key"), Some(vec![4]));
+	}
+
+	#[test]
+	fn trie_cache_hits_are_reported_in_usage_info() {
+		let backend = new_test_with_trie_cache(10, 10);
+		let block0 =
+			insert_block_with_changes(&backend, 0, Default::default(), vec![], Default::default());
+		// the genesis state is read from memory, bypassing the caches
+		let block1 = insert_block_with_changes(
+			&backend,
+			1,
+			block0,
+			vec![(b"key1".to_vec(), Some(vec![1])), (b"key2".to_vec(), Some(vec![2]))],
+			Default::default(),
+		);
+
+		// the root node is read from the database once, the value of `key1` once as well
+		assert_eq!(storage_at(&backend, block1, b"key1"), Some(vec![1]));
+		assert_eq!(storage_at(&backend, block1, b"key1"), Some(vec![1]));
+		assert_eq!(storage_at(&backend, block1, b"key2"), Some(vec![2]));
+
+		let io = backend.usage_info().unwrap().io;
+		assert_eq!((io.state_values_cache_hits, io.state_values_cache_misses), (1, 2));
+		assert!(io.trie_nodes_cache_hits > 0);
+		assert!(io.trie_nodes_cache_misses > 0);
+		assert!(backend.usage_info().unwrap().memory.trie_cache.as_bytes() > 0);
+	}
+
+	#[test]
+	fn state_node_key_depends_on_the_database() {
+		let hash = <BlakeTwo256 as Hash>::hash(b"node");
+		let prefix = (&[0x12][..], Some(0x30));
+		let prefixed = prefixed_key::<BlakeTwo256>(&hash, prefix);
+
+		let rocksdb = DatabaseSource::RocksDb { path: "db".into(), cache_size: 0 };
+		assert_eq!(
+			rocksdb.state_node_key::<BlakeTwo256>(&hash, prefix),
+			(columns::STATE, prefixed),
+		);
+		let paritydb = DatabaseSource::ParityDb { path: "db".into() };
+		assert_eq!(
+			paritydb.state_node_key::<BlakeTwo256>(&hash, prefix),
+			(columns::STATE, hash.as_ref().to_vec()),
+		);
+	}
 }
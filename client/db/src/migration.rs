@@ -0,0 +1,294 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Offline migration of databases between backends.
+
+use std::{
+	fmt,
+	path::{Path, PathBuf},
+};
+
+use sp_runtime::traits::Block as BlockT;
+
+use crate::DatabaseSource;
+
+/// Summary of a successful migration.
+#[derive(Debug, Default)]
+pub struct MigrationSummary {
+	/// Number of copied entries.
+	pub entries: u64,
+	/// Number of copied bytes, keys and values.
+	pub bytes: u64,
+	/// Where the source database was moved to.
+	pub source_backup: PathBuf,
+}
+
+/// Migrate the database at `source` to a new database at `target`.
+///
+/// The source database is first upgraded to the current database version, as the node does when
+/// opening it. The new database is built next to `target` and only moved in place once the
+/// headers and state roots of the best and finalized blocks were verified to match the source, so
+/// an interrupted migration never leaves a partial database at `target`. The source is then moved
+/// aside to `<source>.rocksdb-backup`, so that a node picking whichever database exists opens the
+/// new one. `target` is moved in place first, so a node started after an interruption between
+/// both renames still opens the source. `target` and the backup path must not exist yet.
+///
+/// Only migrating from RocksDB to ParityDB is supported, since ParityDB doesn't keep the keys of
+/// most of its columns. The state of the source database must not be pruned, as the journals of
+/// pruned state reference trie nodes by backend specific keys.
+pub fn migrate_database<Block: BlockT>(
+	source: &DatabaseSource,
+	target: &DatabaseSource,
+) -> sp_blockchain::Result<MigrationSummary> {
+	match (source, target) {
+		(
+			DatabaseSource::RocksDb { path: source, .. },
+			DatabaseSource::ParityDb { path: target },
+		) => rocksdb_to_paritydb::<Block>(source, target),
+		(DatabaseSource::ParityDb { .. }, DatabaseSource::RocksDb { .. }) => Err(migration_err(
+			"ParityDb doesn't keep the keys of most columns, it can't be migrated to RocksDb",
+		)),
+		(source, target) => {
+			Err(migration_err(format!("Can't migrate a {} database to {}", source, target)))
+		}
+	}
+}
+
+#[cfg(all(any(feature = "with-kvdb-rocksdb", test), feature = "with-parity-db"))]
+fn rocksdb_to_paritydb<Block: BlockT>(
+	source: &Path,
+	target: &Path,
+) -> sp_blockchain::Result<MigrationSummary> {
+	use crate::{columns, utils, utils::DatabaseType, DbHash, DB_HASH_LEN};
+	use kvdb::KeyValueDB;
+	use sc_state_db::PruningMode;
+	use sp_database::Transaction;
+	use std::fs;
+
+	/// Key of the state pruning mode in the `STATE_META` column, see `sc_state_db`.
+	const STATE_PRUNING_MODE_KEY: &[u8] = b"mode";
+	/// Size of the changes committed to the new database at once.
+	const BATCH_SIZE: usize = 64 * 1024 * 1024;
+
+	let backup = source.with_extension("rocksdb-backup");
+	for path in [target, &backup] {
+		if path.exists() {
+			return Err(migration_err(format!("{} already exists", path.display())));
+		}
+	}
+
+	// bring the source database to the current version first
+	crate::upgrade::upgrade_db::<Block>(source, DatabaseType::Full).map_err(migration_err)?;
+	let mut config = kvdb_rocksdb::DatabaseConfig::with_columns(utils::NUM_COLUMNS);
+	config.create_if_missing = false;
+	let source_db = kvdb_rocksdb::Database::open(&config, source).map_err(migration_err)?;
+
+	match source_db
+		.get(columns::STATE_META, STATE_PRUNING_MODE_KEY)
+		.map_err(migration_err)?
+	{
+		Some(mode) if mode.as_slice() != PruningMode::ArchiveAll.id() => {
+			return Err(migration_err(format!(
+				"Only databases with archive state pruning can be migrated, found `{}`",
+				String::from_utf8_lossy(&mode),
+			)));
+		}
+		_ => {}
+	}
+
+	// left over by an interrupted migration
+	let tmp = target.with_extension("migrating");
+	if tmp.exists() {
+		fs::remove_dir_all(&tmp).map_err(migration_err)?;
+	}
+	let target_db =
+		crate::parity_db::open::<DbHash>(&tmp, DatabaseType::Full, true).map_err(migration_err)?;
+
+	let mut summary = MigrationSummary { source_backup: backup, ..Default::default() };
+	for column in 0..utils::NUM_COLUMNS {
+		let mut transaction = Transaction::new();
+		let mut pending = 0;
+		let mut entries = 0;
+		for (key, value) in source_db.iter(column) {
+			// RocksDB prefixes the keys of trie nodes, ParityDB only uses their hash.
+			let key = if column == columns::STATE {
+				&key[key.len().saturating_sub(DB_HASH_LEN)..]
+			} else {
+				&key[..]
+			};
+			transaction.set(column, key, &value);
+			pending += key.len() + value.len();
+			entries += 1;
+			if pending >= BATCH_SIZE {
+				target_db.commit(std::mem::take(&mut transaction))?;
+				summary.bytes += pending as u64;
+				pending = 0;
+			}
+		}
+		target_db.commit(transaction)?;
+		summary.bytes += pending as u64;
+		summary.entries += entries;
+		log::info!(target: "db", "Migrated {} entries of column {}", entries, column);
+	}
+	drop(target_db);
+	drop(source_db);
+
+	let source_db = sp_database::as_database(
+		kvdb_rocksdb::Database::open(&config, source).map_err(migration_err)?,
+	);
+	let target_db =
+		crate::parity_db::open::<DbHash>(&tmp, DatabaseType::Full, false).map_err(migration_err)?;
+	verify::<Block>(&*source_db, &*target_db)?;
+	drop(target_db);
+	drop(source_db);
+
+	if let Some(parent) = target.parent() {
+		fs::create_dir_all(parent).map_err(migration_err)?;
+	}
+	fs::rename(&tmp, target).map_err(migration_err)?;
+	fs::rename(source, &summary.source_backup).map_err(migration_err)?;
+
+	Ok(summary)
+}
+
+#[cfg(not(all(any(feature = "with-kvdb-rocksdb", test), feature = "with-parity-db")))]
+fn rocksdb_to_paritydb<Block: BlockT>(
+	_source: &Path,
+	_target: &Path,
+) -> sp_blockchain::Result<MigrationSummary> {
+	Err(migration_err("`with-kvdb-rocksdb` and `with-parity-db` features are required"))
+}
+
+/// Check that the best and finalized blocks of both databases have the same headers and state
+/// roots.
+#[cfg(all(any(feature = "with-kvdb-rocksdb", test), feature = "with-parity-db"))]
+fn verify<Block: BlockT>(
+	source: &dyn sp_database::Database<crate::DbHash>,
+	target: &dyn sp_database::Database<crate::DbHash>,
+) -> sp_blockchain::Result<()> {
+	use crate::{columns, utils};
+	use sp_runtime::{generic::BlockId, traits::Header as HeaderT};
+
+	let meta = utils::read_meta::<Block>(source, columns::HEADER)?;
+	let target_meta = utils::read_meta::<Block>(target, columns::HEADER)?;
+
+	for (name, hash, target_hash) in [
+		("best", meta.best_hash, target_meta.best_hash),
+		("finalized", meta.finalized_hash, target_meta.finalized_hash),
+	] {
+		if hash != target_hash {
+			return Err(migration_err(format!(
+				"{} block mismatch, expected {:?}, found {:?}",
+				name, hash, target_hash,
+			)));
+		}
+
+		let id = BlockId::<Block>::Hash(hash);
+		let header = utils::read_header::<Block>(source, columns::KEY_LOOKUP, columns::HEADER, id)?;
+		if header != utils::read_header::<Block>(target, columns::KEY_LOOKUP, columns::HEADER, id)?
+		{
+			return Err(migration_err(format!("{} block {:?} header mismatch", name, hash)));
+		}
+
+		if let Some(header) = header {
+			// The root node has an empty prefix, so its key is the same for both backends.
+			let root = header.state_root();
+			if source.get(columns::STATE, root.as_ref())
+				!= target.get(columns::STATE, root.as_ref())
+			{
+				return Err(migration_err(format!(
+					"{} block {:?} state root {:?} mismatch",
+					name, hash, root,
+				)));
+			}
+		}
+	}
+
+	Ok(())
+}
+
+fn migration_err(e: impl fmt::Display) -> sp_blockchain::Error {
+	sp_blockchain::Error::Backend(format!("Database migration failed: {}", e))
+}
+
+// `with-parity-db` is enabled for the tests of this crate by its dev-dependency on itself
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		tests::{insert_block, Block},
+		Backend, BlocksPruning, DatabaseSettings, PruningMode, TransactionStorageMode,
+	};
+	use sc_client_api::blockchain::Backend as _;
+	use sp_runtime::generic::BlockId;
+
+	fn settings(source: DatabaseSource) -> DatabaseSettings {
+		DatabaseSettings {
+			state_cache_size: 0,
+			state_cache_child_ratio: None,
+			trie_cache_size: 0,
+			state_pruning: PruningMode::ArchiveAll,
+			source,
+			blocks_pruning: BlocksPruning::KeepAll,
+			transaction_storage: TransactionStorageMode::BlockBody,
+		}
+	}
+
+	#[test]
+	fn migrates_rocksdb_to_paritydb() {
+		let dir = tempfile::tempdir().unwrap();
+		let source = DatabaseSource::RocksDb { path: dir.path().join("db"), cache_size: 16 };
+		let target = DatabaseSource::ParityDb { path: dir.path().join("paritydb") };
+
+		let mut blocks = Vec::new();
+		{
+			let backend = Backend::<Block>::new(settings(source.clone()), 0).unwrap();
+			let mut prev_hash = Default::default();
+			for i in 0..3 {
+				let hash = insert_block(
+					&backend,
+					i,
+					prev_hash,
+					None,
+					Default::default(),
+					vec![i.into()],
+					None,
+				);
+				blocks.push(hash);
+				prev_hash = hash;
+			}
+		}
+
+		let summary = migrate_database::<Block>(&source, &target).unwrap();
+		assert!(summary.entries > 0);
+		assert_eq!(summary.source_backup, dir.path().join("db.rocksdb-backup"));
+		assert!(summary.source_backup.exists());
+		assert!(!dir.path().join("db").exists());
+		assert!(migrate_database::<Block>(&source, &target).is_err());
+
+		let backend = Backend::<Block>::new(settings(target), 0).unwrap();
+		let blockchain = sc_client_api::backend::Backend::blockchain(&backend);
+		assert_eq!(blockchain.info().best_hash, blocks[2]);
+		for (i, hash) in blocks.into_iter().enumerate() {
+			assert_eq!(
+				blockchain.body(BlockId::Hash(hash)).unwrap(),
+				Some(vec![(i as u64).into()])
+			);
+		}
+	}
+}
@@ -20,7 +20,7 @@
 //! Tracks changes over the span of a few recent blocks and handles forks
 //! by tracking/removing cache entries for conflicting changes.
 
-use crate::{stats::StateUsageStats, utils::Meta};
+use crate::{stats::StateUsageStats, trie_cache::SharedTrieCache, utils::Meta};
 use hash_db::Hasher;
 use linked_hash_map::{Entry, LinkedHashMap};
 use log::trace;
@@ -54,7 +54,7 @@ pub struct Cache<B: BlockT> {
 	modifications: VecDeque<BlockChanges<B::Header>>,
 }
 
-struct LRUMap<K, V>(LinkedHashMap<K, V>, usize, usize);
+pub(crate) struct LRUMap<K, V>(LinkedHashMap<K, V>, usize, usize);
 
 /// Internal trait similar to `heapsize` but using
 /// a simply estimation.
@@ -62,7 +62,7 @@ struct LRUMap<K, V>(LinkedHashMap<K, V>, usize, usize);
 /// This should not be made public, it is implementation
 /// detail trait. If it need to become public please
 /// consider using `malloc_size_of`.
-trait EstimateSize {
+pub(crate) trait EstimateSize {
 	/// Return a size estimation of additional size needed
 	/// to cache this struct (in bytes).
 	fn estimate_size(&self) -> usize;
@@ -96,6 +96,11 @@ impl<T: EstimateSize> EstimateSize for (T, T) {
 }
 
 impl<K: EstimateSize + Eq + StdHash, V: EstimateSize> LRUMap<K, V> {
+	/// Create an empty map holding at most `limit` bytes.
+	pub(crate) fn new(limit: usize) -> Self {
+		LRUMap(LinkedHashMap::new(), 0, limit)
+	}
+
 	fn remove(&mut self, k: &K) {
 		let map = &mut self.0;
 		let storage_used_size = &mut self.1;
@@ -105,7 +110,7 @@ impl<K: EstimateSize + Eq + StdHash, V: EstimateSize> LRUMap<K, V> {
 		}
 	}
 
-	fn add(&mut self, k: K, v: V) {
+	pub(crate) fn add(&mut self, k: K, v: V) {
 		let lmap = &mut self.0;
 		let storage_used_size = &mut self.1;
 		let limit = self.2;
@@ -137,7 +142,7 @@ impl<K: EstimateSize + Eq + StdHash, V: EstimateSize> LRUMap<K, V> {
 		}
 	}
 
-	fn get<Q: ?Sized>(&mut self, k: &Q) -> Option<&mut V>
+	pub(crate) fn get<Q: ?Sized>(&mut self, k: &Q) -> Option<&mut V>
 	where
 		K: std::borrow::Borrow<Q>,
 		Q: StdHash + Eq,
@@ -145,7 +150,7 @@ impl<K: EstimateSize + Eq + StdHash, V: EstimateSize> LRUMap<K, V> {
 		self.0.get_refresh(k)
 	}
 
-	fn used_size(&self) -> usize {
+	pub(crate) fn used_size(&self) -> usize {
 		self.1
 	}
 	fn clear(&mut self) {
@@ -308,6 +313,8 @@ pub struct CachingState<S, B: BlockT> {
 	state: S,
 	/// Cache data.
 	cache: CacheChanges<B>,
+	/// Trie node and per-block value cache.
+	trie_cache: Option<SharedTrieCache<B::Hash>>,
 }
 
 impl<S, B: BlockT> std::fmt::Debug for CachingState<S, B> {
@@ -460,6 +467,37 @@ impl<S: StateBackend<HashFor<B>>, B: BlockT> CachingState<S, B> {
 				}),
 				parent_hash,
 			},
+			trie_cache: None,
+		}
+	}
+
+	/// Read the values missing from the state caches through the per-block value cache of
+	/// `trie_cache`.
+	pub(crate) fn with_trie_cache(mut self, trie_cache: SharedTrieCache<B::Hash>) -> Self {
+		self.trie_cache = Some(trie_cache);
+		self
+	}
+
+	/// Read a value from the backing state, through the per-block value cache if enabled.
+	///
+	/// Returns whether the value was found in the cache along with the value.
+	fn read_value(
+		&self,
+		child_info: Option<&ChildInfo>,
+		key: &[u8],
+	) -> Result<(Option<StorageValue>, bool), S::Error> {
+		let read = || match child_info {
+			Some(child_info) => self.state.child_storage(child_info, key),
+			None => self.state.storage(key),
+		};
+		match (&self.trie_cache, &self.cache.parent_hash) {
+			(Some(trie_cache), Some(block)) => trie_cache.value(
+				block,
+				child_info.map(|child_info| child_info.storage_key()),
+				key,
+				read,
+			),
+			_ => read().map(|value| (value, false)),
 		}
 	}
 
@@ -543,11 +581,11 @@ impl<S: StateBackend<HashFor<B>>, B: BlockT> StateBackend<HashFor<B>> for Cachin
 			}
 		}
 		trace!("Cache miss: {:?}", HexDisplay::from(&key));
-		let value = self.state.storage(key)?;
+		let (value, cached) = self.read_value(None, key)?;
 		RwLockUpgradableReadGuard::upgrade(local_cache)
 			.storage
 			.insert(key.to_vec(), value.clone());
-		self.usage.tally_key_read(key, value.as_ref(), false);
+		self.usage.tally_key_read(key, value.as_ref(), cached);
 		Ok(value)
 	}
 
@@ -597,10 +635,10 @@ impl<S: StateBackend<HashFor<B>>, B: BlockT> StateBackend<HashFor<B>> for Cachin
 			}
 		}
 		trace!("Cache miss: {:?}", key);
-		let value = self.state.child_storage(child_info, &key.1[..])?;
+		let (value, cached) = self.read_value(Some(child_info), &key.1[..])?;
 
 		// just pass it through the usage counter
-		let value = self.usage.tally_child_key_read(&key, value, false);
+		let value = self.usage.tally_child_key_read(&key, value, cached);
 
 		RwLockUpgradableReadGuard::upgrade(local_cache)
 			.child_storage
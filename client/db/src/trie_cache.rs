@@ -0,0 +1,321 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Caches shared by the states of all blocks.
+//!
+//! Trie nodes are cached under the key they are stored with in the database. That key is derived
+//! from the node hash and, if the database prefixes its keys, from the position of the node in
+//! the trie. So a cached node is valid for every block, the node cache never needs to be
+//! invalidated and it never returns a node the database doesn't hold under the requested key.
+//!
+//! Likewise the state of a given block never changes, so storage values cached per block survive
+//! block imports and reorgs, unlike the canonical state cache in `storage_cache`.
+
+use crate::storage_cache::{EstimateSize, LRUMap};
+use parking_lot::Mutex;
+use sp_state_machine::{DBValue, StorageValue};
+use std::{
+	collections::hash_map::DefaultHasher,
+	hash::{Hash as StdHash, Hasher as StdHasher},
+	sync::{
+		atomic::{AtomicU64, Ordering as AtomicOrdering},
+		Arc,
+	},
+};
+
+/// Share of the trie cache size used by the per-block value cache, the rest holds trie nodes.
+const VALUE_CACHE_RATIO: (usize, usize) = (1, 4);
+
+/// Maximal number of shards of each cache.
+const MAX_SHARDS: usize = 16;
+
+/// Minimal size of a shard in bytes, smaller caches are split into fewer shards.
+const MIN_SHARD_SIZE: usize = 1024 * 1024;
+
+/// LRU map split into shards by the hash of the keys, each with its own lock and an equal share
+/// of the size, so that concurrent reads of different keys rarely contend.
+struct ShardedLRUMap<K, V> {
+	shards: Vec<Mutex<LRUMap<K, V>>>,
+}
+
+impl<K: EstimateSize + Eq + StdHash, V: EstimateSize> ShardedLRUMap<K, V> {
+	fn new(size: usize) -> Self {
+		let count = (size / MIN_SHARD_SIZE).clamp(1, MAX_SHARDS);
+		ShardedLRUMap {
+			shards: (0..count).map(|_| Mutex::new(LRUMap::new(size / count))).collect(),
+		}
+	}
+
+	/// Returns the shard holding `key`.
+	fn shard(&self, key: &K) -> &Mutex<LRUMap<K, V>> {
+		if self.shards.len() == 1 {
+			return &self.shards[0];
+		}
+		let mut hasher = DefaultHasher::new();
+		key.hash(&mut hasher);
+		&self.shards[hasher.finish() as usize % self.shards.len()]
+	}
+
+	fn used_size(&self) -> usize {
+		self.shards.iter().map(|shard| shard.lock().used_size()).sum()
+	}
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct NodeKey(Vec<u8>);
+
+impl EstimateSize for NodeKey {
+	fn estimate_size(&self) -> usize {
+		self.0.capacity()
+	}
+}
+
+#[derive(PartialEq, Eq, Hash)]
+struct ValueKey<H> {
+	block: H,
+	child_storage_key: Option<Vec<u8>>,
+	key: Vec<u8>,
+}
+
+impl<H: AsRef<[u8]>> EstimateSize for ValueKey<H> {
+	fn estimate_size(&self) -> usize {
+		self.block.as_ref().len()
+			+ self.child_storage_key.as_ref().map_or(0, |k| k.capacity())
+			+ self.key.capacity()
+	}
+}
+
+/// Hits and misses of the trie cache since it was created.
+///
+/// The counters are never reset, so that every consumer can compute the hits and misses of its
+/// own reporting interval.
+#[derive(Clone, Debug, Default)]
+pub struct TrieCacheStats {
+	/// Trie nodes found in the cache.
+	pub node_hits: u64,
+	/// Trie nodes read from the database.
+	pub node_misses: u64,
+	/// Storage values found in the cache.
+	pub value_hits: u64,
+	/// Storage values read from the trie.
+	pub value_misses: u64,
+}
+
+/// Trie node cache keyed by database key and storage value cache keyed by block, both bounded in
+/// bytes and evicting the least recently used entries.
+///
+/// Both caches are sharded, a read only locks the shard of the requested entry.
+pub struct TrieCache<H> {
+	nodes: ShardedLRUMap<NodeKey, DBValue>,
+	values: ShardedLRUMap<ValueKey<H>, Option<StorageValue>>,
+	enabled: bool,
+	node_hits: AtomicU64,
+	node_misses: AtomicU64,
+	value_hits: AtomicU64,
+	value_misses: AtomicU64,
+}
+
+/// Trie cache shared by the backend and its states.
+pub type SharedTrieCache<H> = Arc<TrieCache<H>>;
+
+impl<H: AsRef<[u8]> + Clone + Eq + StdHash> TrieCache<H> {
+	/// Create a new cache using at most `size` bytes. A size of `0` disables the cache.
+	pub fn new(size: usize) -> Self {
+		let values_size = size * VALUE_CACHE_RATIO.0 / VALUE_CACHE_RATIO.1;
+		TrieCache {
+			nodes: ShardedLRUMap::new(size - values_size),
+			values: ShardedLRUMap::new(values_size),
+			enabled: size > 0,
+			node_hits: 0.into(),
+			node_misses: 0.into(),
+			value_hits: 0.into(),
+			value_misses: 0.into(),
+		}
+	}
+
+	/// Get the trie node stored under `key` in the database, calling `read` on a cache miss.
+	pub fn node<E>(
+		&self,
+		key: &[u8],
+		read: impl FnOnce() -> Result<Option<DBValue>, E>,
+	) -> Result<Option<DBValue>, E> {
+		if !self.enabled {
+			return read();
+		}
+		let key = NodeKey(key.to_vec());
+		let shard = self.nodes.shard(&key);
+		if let Some(node) = shard.lock().get(&key) {
+			self.node_hits.fetch_add(1, AtomicOrdering::Relaxed);
+			return Ok(Some(node.clone()));
+		}
+		self.node_misses.fetch_add(1, AtomicOrdering::Relaxed);
+		// the shard isn't locked while reading from the database
+		let node = read()?;
+		if let Some(node) = &node {
+			shard.lock().add(key, node.clone());
+		}
+		Ok(node)
+	}
+
+	/// Get the value of `key` in the state of `block`, calling `read` on a cache miss.
+	///
+	/// Returns whether the value was found in the cache along with the value.
+	pub fn value<E>(
+		&self,
+		block: &H,
+		child_storage_key: Option<&[u8]>,
+		key: &[u8],
+		read: impl FnOnce() -> Result<Option<StorageValue>, E>,
+	) -> Result<(Option<StorageValue>, bool), E> {
+		if !self.enabled {
+			return read().map(|value| (value, false));
+		}
+		let key = ValueKey {
+			block: block.clone(),
+			child_storage_key: child_storage_key.map(|k| k.to_vec()),
+			key: key.to_vec(),
+		};
+		let shard = self.values.shard(&key);
+		if let Some(value) = shard.lock().get(&key) {
+			self.value_hits.fetch_add(1, AtomicOrdering::Relaxed);
+			return Ok((value.clone(), true));
+		}
+		self.value_misses.fetch_add(1, AtomicOrdering::Relaxed);
+		let value = read()?;
+		shard.lock().add(key, value.clone());
+		Ok((value, false))
+	}
+
+	/// Returns the used memory size of the cache in bytes.
+	pub fn used_size(&self) -> usize {
+		self.nodes.used_size() + self.values.used_size()
+	}
+
+	/// Returns the hits and misses since the cache was created.
+	pub fn stats(&self) -> TrieCacheStats {
+		TrieCacheStats {
+			node_hits: self.node_hits.load(AtomicOrdering::Relaxed),
+			node_misses: self.node_misses.load(AtomicOrdering::Relaxed),
+			value_hits: self.value_hits.load(AtomicOrdering::Relaxed),
+			value_misses: self.value_misses.load(AtomicOrdering::Relaxed),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::H256;
+
+	#[test]
+	fn caches_nodes_by_key() {
+		let cache = TrieCache::<H256>::new(1024);
+		let read = || Ok::<_, ()>(Some(vec![1, 2, 3]));
+
+		assert_eq!(cache.node(&[1; 32], read), Ok(Some(vec![1, 2, 3])));
+		assert_eq!(
+			cache.node(&[1; 32], || panic!("node is cached")),
+			Ok::<_, ()>(Some(vec![1, 2, 3]))
+		);
+		// missing nodes are not cached
+		assert_eq!(cache.node(&[2; 32], || Ok::<_, ()>(None)), Ok(None));
+		assert_eq!(cache.node(&[2; 32], || Ok::<_, ()>(Some(vec![4]))), Ok(Some(vec![4])));
+
+		let stats = cache.stats();
+		assert_eq!((stats.node_hits, stats.node_misses), (1, 3));
+		// reading the stats doesn't reset them
+		assert_eq!(cache.stats().node_misses, 3);
+	}
+
+	#[test]
+	fn caches_nodes_under_prefixed_keys_separately() {
+		let cache = TrieCache::<H256>::new(1024);
+		let hash = H256::repeat_byte(1);
+		let key = |prefix: &[u8]| [prefix, hash.as_bytes()].concat();
+
+		assert_eq!(cache.node(&key(b"a"), || Ok::<_, ()>(Some(vec![1]))), Ok(Some(vec![1])));
+		// the node with the same hash is missing under another prefix
+		assert_eq!(cache.node(&key(b"b"), || Ok::<_, ()>(None)), Ok(None));
+		assert_eq!(cache.node(&key(b"a"), || panic!("node is cached")), Ok::<_, ()>(Some(vec![1])));
+	}
+
+	#[test]
+	fn caches_values_per_block() {
+		let cache = TrieCache::<H256>::new(1024);
+		let (block1, block2) = (H256::repeat_byte(1), H256::repeat_byte(2));
+
+		assert_eq!(
+			cache.value(&block1, None, b"key", || Ok::<_, ()>(Some(vec![1]))),
+			Ok((Some(vec![1]), false))
+		);
+		assert_eq!(
+			cache.value(&block1, None, b"key", || panic!("value is cached")),
+			Ok::<_, ()>((Some(vec![1]), true))
+		);
+		// missing values are cached as well
+		assert_eq!(cache.value(&block2, None, b"key", || Ok::<_, ()>(None)), Ok((None, false)));
+		assert_eq!(
+			cache.value(&block2, None, b"key", || panic!("value is cached")),
+			Ok::<_, ()>((None, true))
+		);
+		assert_eq!(
+			cache.value(&block1, Some(b"child"), b"key", || Ok::<_, ()>(Some(vec![2]))),
+			Ok((Some(vec![2]), false)),
+		);
+
+		let stats = cache.stats();
+		assert_eq!((stats.value_hits, stats.value_misses), (2, 3));
+	}
+
+	#[test]
+	fn evicts_least_recently_used_nodes() {
+		// 32 bytes hash and 32 bytes node per entry, a quarter of the size goes to values
+		let cache = TrieCache::<H256>::new(128 * 4 / 3 + 1);
+		for i in 0..3u8 {
+			let _ = cache.node(&[i; 32], || Ok::<_, ()>(Some(vec![i; 32])));
+		}
+		assert!(cache.used_size() <= 128);
+		assert_eq!(cache.node(&[2; 32], || Ok::<_, ()>(None)), Ok(Some(vec![2; 32])));
+		assert_eq!(cache.node(&[0; 32], || Ok::<_, ()>(None)), Ok(None));
+	}
+
+	#[test]
+	fn shards_large_caches() {
+		let cache = TrieCache::<H256>::new(64 * 1024 * 1024);
+		assert_eq!(cache.nodes.shards.len(), MAX_SHARDS);
+		assert_eq!(cache.values.shards.len(), MAX_SHARDS);
+		assert_eq!(TrieCache::<H256>::new(1024).nodes.shards.len(), 1);
+
+		for i in 0..=255u8 {
+			let _ = cache.node(&[i; 32], || Ok::<_, ()>(Some(vec![i; 32])));
+		}
+		assert_eq!(cache.used_size(), 256 * 64);
+		for i in 0..=255u8 {
+			assert_eq!(cache.node(&[i; 32], || Ok::<_, ()>(None)), Ok(Some(vec![i; 32])));
+		}
+		assert_eq!(cache.stats().node_hits, 256);
+	}
+
+	#[test]
+	fn zero_size_disables_cache() {
+		let cache = TrieCache::<H256>::new(0);
+		let _ = cache.node(&[1; 32], || Ok::<_, ()>(Some(vec![1])));
+		assert_eq!(cache.node(&[1; 32], || Ok::<_, ()>(None)), Ok(None));
+		assert_eq!(cache.used_size(), 0);
+	}
+}
@@ -175,7 +175,7 @@ fn version_file_path(path: &Path) -> PathBuf {
 mod tests {
 	use super::*;
 	use crate::{
-		tests::Block, DatabaseSettings, DatabaseSource, KeepBlocks, TransactionStorageMode,
+		tests::Block, BlocksPruning, DatabaseSettings, DatabaseSource, TransactionStorageMode,
 	};
 	use sc_state_db::PruningMode;
 
@@ -192,9 +192,10 @@ mod tests {
 			&DatabaseSettings {
 				state_cache_size: 0,
 				state_cache_child_ratio: None,
+				trie_cache_size: 0,
 				state_pruning: PruningMode::ArchiveAll,
 				source: DatabaseSource::RocksDb { path: db_path.to_owned(), cache_size: 128 },
-				keep_blocks: KeepBlocks::All,
+				blocks_pruning: BlocksPruning::KeepAll,
 				transaction_storage: TransactionStorageMode::BlockBody,
 			},
 			db_type,
@@ -578,7 +578,7 @@ impl<'a, 'b> codec::Input for JoinInput<'a, 'b> {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::{KeepBlocks, TransactionStorageMode};
+	use crate::{BlocksPruning, TransactionStorageMode};
 	use codec::Input;
 	use sc_state_db::PruningMode;
 	use sp_runtime::testing::{Block as RawBlock, ExtrinsicWrapper};
@@ -691,9 +691,10 @@ mod tests {
 		DatabaseSettings {
 			state_cache_size: 0,
 			state_cache_child_ratio: None,
+			trie_cache_size: 0,
 			state_pruning: PruningMode::ArchiveAll,
 			source,
-			keep_blocks: KeepBlocks::All,
+			blocks_pruning: BlocksPruning::KeepAll,
 			transaction_storage: TransactionStorageMode::BlockBody,
 		}
 	}
@@ -66,6 +66,8 @@ pub async fn build<B: BlockT, C, P>(
 	let mut display = display::InformantDisplay::new(format.clone());
 
 	let client_1 = client.clone();
+	// Trie node cache hits and misses at the last display.
+	let mut last_trie_cache_counts = (0, 0);
 
 	let display_notifications = interval(Duration::from_millis(5000))
 		.filter_map(|_| async {
@@ -75,6 +77,13 @@ pub async fn build<B: BlockT, C, P>(
 		.for_each(move |net_status| {
 			let info = client_1.usage_info();
 			if let Some(ref usage) = info.usage {
+				// The backend counts since it was opened, display the last interval only.
+				let mut usage = usage.clone();
+				let counts = (usage.io.trie_nodes_cache_hits, usage.io.trie_nodes_cache_misses);
+				usage.io.trie_nodes_cache_hits = counts.0.saturating_sub(last_trie_cache_counts.0);
+				usage.io.trie_nodes_cache_misses =
+					counts.1.saturating_sub(last_trie_cache_counts.1);
+				last_trie_cache_counts = counts;
 				trace!(target: "usage", "Usage statistics: {}", usage);
 			} else {
 				trace!(
@@ -33,6 +33,9 @@ pub enum Error {
 	/// Client error.
 	#[error("Client error: {}", .0)]
 	Client(#[from] Box<dyn std::error::Error + Send>),
+	/// The block is known, but its body was pruned.
+	#[error("Block {0} body was pruned, the node keeps bodies of recent blocks only")]
+	BlockBodyPruned(String),
 	/// Other error type.
 	#[error("{0}")]
 	Other(String),
@@ -49,6 +52,11 @@ impl From<Error> for rpc::Error {
 				message,
 				data: None,
 			},
+			Error::BlockBodyPruned(_) => rpc::Error {
+				code: rpc::ErrorCode::ServerError(BASE_ERROR + 2),
+				message: e.to_string(),
+				data: None,
+			},
 			e => errors::internal(e),
 		}
 	}
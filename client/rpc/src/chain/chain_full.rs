@@ -18,7 +18,11 @@
 
 //! Blockchain API backend for full nodes.
 
-use super::{client_err, error::FutureResult, ChainBackend};
+use super::{
+	client_err,
+	error::{Error, FutureResult},
+	ChainBackend,
+};
 use futures::FutureExt;
 use jsonrpc_pubsub::manager::SubscriptionManager;
 use sc_client_api::{BlockBackend, BlockchainEvents};
@@ -66,7 +70,17 @@ where
 	}
 
 	fn block(&self, hash: Option<Block::Hash>) -> FutureResult<Option<SignedBlock<Block>>> {
-		let res = self.client.block(&BlockId::Hash(self.unwrap_or_best(hash))).map_err(client_err);
+		let hash = self.unwrap_or_best(hash);
+		let res = match self.client.block(&BlockId::Hash(hash)).map_err(client_err) {
+			// The header is kept when the body is pruned, so a known header without a block
+			// means that its body was pruned.
+			Ok(None) => match self.client.header(BlockId::Hash(hash)).map_err(client_err) {
+				Ok(Some(_)) => Err(Error::BlockBodyPruned(format!("{:?}", hash))),
+				Ok(None) => Ok(None),
+				Err(e) => Err(e),
+			},
+			res => res,
+		};
 		async move { res }.boxed()
 	}
 }
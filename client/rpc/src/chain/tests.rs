@@ -111,6 +111,26 @@ fn should_return_a_block() {
 	assert_matches!(executor::block_on(api.block(Some(H256::from_low_u64_be(5)).into())), Ok(None));
 }
 
+#[test]
+fn should_report_pruned_block_body() {
+	let mut client = Arc::new(TestClientBuilder::with_pruning_window(1).build());
+	let api = new_full(client.clone(), SubscriptionManager::new(Arc::new(TaskExecutor)));
+
+	let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	let pruned_hash = block.hash();
+	executor::block_on(client.import_as_final(BlockOrigin::Own, block)).unwrap();
+	let block = client.new_block(Default::default()).unwrap().build().unwrap().block;
+	let block_hash = block.hash();
+	executor::block_on(client.import_as_final(BlockOrigin::Own, block)).unwrap();
+
+	assert_matches!(
+		executor::block_on(api.block(Some(pruned_hash).into())),
+		Err(Error::BlockBodyPruned(_))
+	);
+	assert_matches!(executor::block_on(api.block(Some(block_hash).into())), Ok(Some(_)));
+	assert_matches!(executor::block_on(api.block(Some(H256::from_low_u64_be(5)).into())), Ok(None));
+}
+
 #[test]
 fn should_return_block_hash() {
 	let mut client = Arc::new(substrate_test_runtime_client::new());
@@ -0,0 +1,358 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2020-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	config::{BlocksPruning, DatabaseSource},
+	error::Error,
+};
+use codec::Encode;
+use hash_db::{Hasher, Prefix};
+use log::info;
+use parking_lot::Mutex;
+use sc_client_api::{backend::Backend, BlockBackend, HeaderBackend};
+use sp_core::{
+	hexdisplay::HexDisplay,
+	storage::{well_known_keys, ChildInfo},
+};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Hash, HashFor, Header as HeaderT, NumberFor, One, Zero},
+};
+use sp_state_machine::{Backend as StateBackend, DBValue, TrieBackend, TrieBackendStorage};
+
+use std::{fmt, sync::Arc};
+
+/// An inconsistency found by [`check_db`].
+#[derive(Debug)]
+pub enum DbIssue<B: BlockT> {
+	/// No canonical header is stored for the block number.
+	MissingHeader(NumberFor<B>),
+	/// The parent hash of a header doesn't match the hash of the previous canonical block.
+	InvalidParent {
+		/// Number of the block.
+		number: NumberFor<B>,
+		/// Hash of the block.
+		hash: B::Hash,
+		/// Parent hash of the header.
+		parent_hash: B::Hash,
+		/// Hash of the previous canonical block.
+		expected: B::Hash,
+	},
+	/// The body of a block which should be kept is missing.
+	MissingBody {
+		/// Number of the block.
+		number: NumberFor<B>,
+		/// Hash of the block.
+		hash: B::Hash,
+	},
+	/// The extrinsics of a block don't match its `extrinsics_root`.
+	InvalidBody {
+		/// Number of the block.
+		number: NumberFor<B>,
+		/// Hash of the block.
+		hash: B::Hash,
+		/// `extrinsics_root` of the header.
+		expected: B::Hash,
+		/// Root of the stored extrinsics.
+		found: B::Hash,
+	},
+	/// A trie node of the finalized state is missing.
+	MissingTrieNode {
+		/// Storage key of the child trie, `None` for the top trie.
+		child_trie: Option<Vec<u8>>,
+		/// Column of the node.
+		column: u32,
+		/// Key of the node in the column.
+		key: Vec<u8>,
+	},
+}
+
+impl<B: BlockT> fmt::Display for DbIssue<B> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			DbIssue::MissingHeader(number) => write!(f, "Missing header of block #{}", number),
+			DbIssue::InvalidParent { number, hash, parent_hash, expected } => write!(
+				f,
+				"Block #{} ({:?}) has parent {:?}, expected {:?}",
+				number, hash, parent_hash, expected,
+			),
+			DbIssue::MissingBody { number, hash } => {
+				write!(f, "Missing body of block #{} ({:?})", number, hash)
+			}
+			DbIssue::InvalidBody { number, hash, expected, found } => write!(
+				f,
+				"Body of block #{} ({:?}) has extrinsics root {:?}, expected {:?}",
+				number, hash, found, expected,
+			),
+			DbIssue::MissingTrieNode { child_trie, column, key } => {
+				write!(
+					f,
+					"Missing trie node in column {}, key 0x{}",
+					column,
+					HexDisplay::from(key)
+				)?;
+				if let Some(child_trie) = child_trie {
+					write!(f, " of child trie 0x{}", HexDisplay::from(child_trie))?;
+				}
+				Ok(())
+			}
+		}
+	}
+}
+
+/// Result of [`check_db`].
+#[derive(Debug)]
+pub struct CheckDbReport<B: BlockT> {
+	/// Number of checked canonical blocks.
+	pub blocks: u64,
+	/// Number of iterated storage keys, `None` if the state wasn't checked.
+	pub keys: Option<u64>,
+	/// Found inconsistencies.
+	pub issues: Vec<DbIssue<B>>,
+}
+
+/// Check the consistency of the database.
+///
+/// Walks the canonical headers from genesis to the best block, verifying the parent links and
+/// that the bodies match the `extrinsics_root` of the headers. Bodies discarded according to
+/// `blocks_pruning` are skipped. If `check_state` is set, the full state of the finalized block,
+/// including the child tries, is iterated to find missing trie nodes. They are reported with the
+/// key `database` stores them under.
+pub fn check_db<B, BA, C>(
+	client: Arc<C>,
+	backend: Arc<BA>,
+	database: &DatabaseSource,
+	blocks_pruning: BlocksPruning,
+	check_state: bool,
+) -> Result<CheckDbReport<B>, Error>
+where
+	B: BlockT,
+	BA: Backend<B>,
+	C: HeaderBackend<B> + BlockBackend<B>,
+{
+	let info = client.info();
+	let mut report = CheckDbReport { blocks: 0, keys: None, issues: Vec::new() };
+
+	let mut number = Zero::zero();
+	let mut parent: Option<B::Hash> = None;
+	while number <= info.best_number {
+		let header = match client.hash(number)? {
+			Some(hash) => client.header(BlockId::Hash(hash))?.map(|header| (hash, header)),
+			None => None,
+		};
+		let (hash, header) = match header {
+			Some(header) => header,
+			None => {
+				report.issues.push(DbIssue::MissingHeader(number));
+				parent = None;
+				number += One::one();
+				continue;
+			}
+		};
+
+		if let Some(expected) = parent {
+			if *header.parent_hash() != expected {
+				report.issues.push(DbIssue::InvalidParent {
+					number,
+					hash,
+					parent_hash: *header.parent_hash(),
+					expected,
+				});
+			}
+		}
+
+		let pruned = match blocks_pruning {
+			BlocksPruning::Some(keep) => number + keep.into() <= info.finalized_number,
+			BlocksPruning::KeepAll | BlocksPruning::KeepFinalized => false,
+		};
+		match client.block_body(&BlockId::Hash(hash))? {
+			Some(extrinsics) => {
+				let found = HashFor::<B>::ordered_trie_root(
+					extrinsics.iter().map(Encode::encode).collect(),
+				);
+				if found != *header.extrinsics_root() {
+					report.issues.push(DbIssue::InvalidBody {
+						number,
+						hash,
+						expected: *header.extrinsics_root(),
+						found,
+					});
+				}
+			}
+			None if !pruned => report.issues.push(DbIssue::MissingBody { number, hash }),
+			None => {}
+		}
+
+		report.blocks += 1;
+		if report.blocks % 10_000 == 0 {
+			info!("Checked {} blocks, found {} issues", report.blocks, report.issues.len());
+		}
+
+		parent = Some(hash);
+		number += One::one();
+	}
+
+	if check_state {
+		info!("Checking the state of the finalized block #{}", info.finalized_number);
+		let state = backend.state_at(BlockId::Hash(info.finalized_hash))?;
+		let trie = state
+			.as_trie_backend()
+			.ok_or_else(|| Error::Other("State backend doesn't support trie iteration".into()))?;
+		let storage = NodeTracker::new(trie.backend_storage(), database);
+		let trie = TrieBackend::new(storage, *trie.root());
+
+		let mut keys = 0;
+		let mut child_tries = Vec::new();
+		check_trie(&trie, None, &mut report.issues, |key| {
+			keys += 1;
+			if let Some(storage_key) =
+				key.strip_prefix(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX)
+			{
+				child_tries.push(ChildInfo::new_default(storage_key));
+			}
+		})?;
+		for child_info in child_tries {
+			check_trie(&trie, Some(&child_info), &mut report.issues, |_| keys += 1)?;
+		}
+		report.keys = Some(keys);
+	}
+
+	Ok(report)
+}
+
+/// Iterate all keys of the top or of a child trie, reporting the missing nodes.
+///
+/// A missing node hides the keys below it, the iteration resumes after them.
+fn check_trie<B, S>(
+	trie: &TrieBackend<NodeTracker<S>, HashFor<B>>,
+	child_info: Option<&ChildInfo>,
+	issues: &mut Vec<DbIssue<B>>,
+	mut f: impl FnMut(&[u8]),
+) -> Result<(), Error>
+where
+	B: BlockT,
+	S: TrieBackendStorage<HashFor<B>>,
+{
+	let keyspace = child_info.map(|info| info.keyspace().len()).unwrap_or(0);
+	let mut start_at: Option<Vec<u8>> = None;
+	loop {
+		let result = trie.essence().apply_to_key_values_while(
+			child_info,
+			None,
+			start_at.as_deref(),
+			|key, _| {
+				f(&key);
+				true
+			},
+			false,
+		);
+		if let Err(e) = result {
+			let MissingNode { column, key, prefix, nibble } =
+				trie.backend_storage().missing.lock().take().ok_or_else(|| Error::Other(e))?;
+			issues.push(DbIssue::MissingTrieNode {
+				child_trie: child_info.map(|info| info.storage_key().to_vec()),
+				column,
+				key,
+			});
+
+			// child trie nodes are prefixed with the keyspace of the child trie
+			let next = prefix.get(keyspace..).and_then(|prefix| next_key_after(prefix, nibble));
+			match next {
+				Some(next) if start_at.as_ref().map_or(true, |start_at| next > *start_at) => {
+					start_at = Some(next)
+				}
+				_ => break,
+			}
+		} else {
+			break;
+		}
+	}
+
+	Ok(())
+}
+
+/// First key after all the keys below the trie node at `prefix`, `None` if there is none.
+fn next_key_after(prefix: &[u8], nibble: Option<u8>) -> Option<Vec<u8>> {
+	let mut key = prefix.to_vec();
+	// an odd nibble is kept in the high bits
+	if let Some(nibble) = nibble.map(|nibble| nibble & 0xf0) {
+		if nibble < 0xf0 {
+			key.push(nibble + 0x10);
+			return Some(key);
+		}
+	}
+	while let Some(last) = key.pop() {
+		if last < u8::MAX {
+			key.push(last + 1);
+			return Some(key);
+		}
+	}
+	None
+}
+
+/// A trie node which wasn't found.
+struct MissingNode {
+	/// Column and key of the node in the database.
+	column: u32,
+	key: Vec<u8>,
+	/// Prefix of the node in the trie and the odd nibble of the prefix.
+	prefix: Vec<u8>,
+	nibble: Option<u8>,
+}
+
+/// Trie storage remembering the last node which wasn't found.
+struct NodeTracker<'a, S> {
+	storage: &'a S,
+	database: &'a DatabaseSource,
+	missing: Mutex<Option<MissingNode>>,
+}
+
+impl<'a, S> NodeTracker<'a, S> {
+	fn new(storage: &'a S, database: &'a DatabaseSource) -> Self {
+		Self { storage, database, missing: Mutex::new(None) }
+	}
+}
+
+impl<'a, H: Hasher, S: TrieBackendStorage<H>> TrieBackendStorage<H> for NodeTracker<'a, S> {
+	type Overlay = S::Overlay;
+
+	fn get(&self, key: &H::Out, prefix: Prefix) -> Result<Option<DBValue>, String> {
+		let value = self.storage.get(key, prefix)?;
+		if value.is_none() {
+			let (column, key) = self.database.state_node_key::<H>(key, prefix);
+			*self.missing.lock() =
+				Some(MissingNode { column, key, prefix: prefix.0.to_vec(), nibble: prefix.1 });
+		}
+		Ok(value)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::next_key_after;
+
+	#[test]
+	fn next_key_after_skips_subtree() {
+		assert_eq!(next_key_after(&[], None), None);
+		assert_eq!(next_key_after(&[0x12], None), Some(vec![0x13]));
+		assert_eq!(next_key_after(&[0x12, 0xff], None), Some(vec![0x13]));
+		assert_eq!(next_key_after(&[0xff, 0xff], None), None);
+		assert_eq!(next_key_after(&[0x12], Some(0x30)), Some(vec![0x12, 0x40]));
+		assert_eq!(next_key_after(&[0x12], Some(0xf0)), Some(vec![0x13]));
+	}
+}
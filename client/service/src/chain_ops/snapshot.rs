@@ -0,0 +1,497 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Compact binary snapshots of the state at a given block.
+//!
+//! A snapshot is the SCALE encoding of:
+//! - the magic bytes `SNAPSHOT` and the format version,
+//! - the header and the justifications of the block,
+//! - a stream of [`Record`]s: the key value pairs of the top trie, then every child trie introduced
+//!   by a [`Record::ChildTrie`] followed by its pairs, terminated by [`Record::End`],
+//! - a checksum over all the previous items, `checksum = blake2_256(checksum ++ item)` starting
+//!   from zero.
+
+use crate::error::Error;
+use codec::{Decode, Encode};
+use futures::{future, prelude::*};
+use log::{info, warn};
+use sc_client_api::{backend::Backend, BlockBackend, HeaderBackend};
+use sc_consensus::{
+	import_queue::{BlockImportError, BlockImportStatus, ImportQueue, IncomingBlock, Link},
+	ImportedState,
+};
+use sp_consensus::BlockOrigin;
+use sp_core::{
+	hashing::blake2_256,
+	hexdisplay::HexDisplay,
+	storage::{well_known_keys, ChildInfo},
+};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, HashFor, Header as HeaderT, NumberFor, Zero},
+	Justifications,
+};
+use sp_state_machine::{Backend as StateBackend, KeyValueStates, KeyValueStorageLevel};
+use std::{
+	collections::HashMap,
+	io::{Read, Write},
+	pin::Pin,
+	sync::Arc,
+	task::Poll,
+};
+
+/// Magic bytes at the start of every snapshot.
+const SNAPSHOT_MAGIC: [u8; 8] = *b"SNAPSHOT";
+
+/// Version of the snapshot format.
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// An entry of the state stream of a snapshot.
+#[derive(Encode, Decode)]
+enum Record {
+	/// A key value pair of the current trie.
+	Pair(Vec<u8>, Vec<u8>),
+	/// The following pairs belong to the default child trie with the given storage key.
+	ChildTrie(Vec<u8>),
+	/// End of the state stream.
+	End,
+}
+
+/// Writes snapshot items while updating the checksum.
+struct SnapshotWriter<W> {
+	output: W,
+	checksum: [u8; 32],
+}
+
+impl<W: Write> SnapshotWriter<W> {
+	fn write(&mut self, item: &impl Encode) -> Result<(), Error> {
+		let encoded = item.encode();
+		self.checksum = blake2_256(&[&self.checksum[..], &encoded].concat());
+		self.output.write_all(&encoded)?;
+		Ok(())
+	}
+
+	fn finish(mut self) -> Result<(), Error> {
+		self.output.write_all(&self.checksum)?;
+		self.output.flush()?;
+		Ok(())
+	}
+}
+
+/// Reads snapshot items while updating the checksum.
+struct SnapshotReader<R> {
+	input: R,
+	checksum: [u8; 32],
+	/// Bytes of the item being decoded.
+	item: Vec<u8>,
+}
+
+impl<R: Read> codec::Input for SnapshotReader<R> {
+	fn remaining_len(&mut self) -> Result<Option<usize>, codec::Error> {
+		Ok(None)
+	}
+
+	fn read(&mut self, into: &mut [u8]) -> Result<(), codec::Error> {
+		self.input.read_exact(into).map_err(|_| "Unexpected end of snapshot")?;
+		self.item.extend_from_slice(into);
+		Ok(())
+	}
+}
+
+impl<R: Read> SnapshotReader<R> {
+	fn read<T: Decode>(&mut self) -> Result<T, Error> {
+		let item = T::decode(self)
+			.map_err(|e| Error::Other(format!("Failed to decode snapshot: {}", e)))?;
+		self.checksum = blake2_256(&[&self.checksum[..], &self.item].concat());
+		self.item.clear();
+		Ok(item)
+	}
+
+	fn finish(mut self) -> Result<(), Error> {
+		let mut checksum = [0u8; 32];
+		self.input.read_exact(&mut checksum)?;
+		if checksum != self.checksum {
+			return Err(Error::Other("Snapshot checksum mismatch".into()));
+		}
+		Ok(())
+	}
+}
+
+/// Export a snapshot of the state at the given `block` to `output`. If `block` is `None`, the
+/// last finalized block will be used.
+///
+/// Every trie is walked once, the pairs are written as they are visited.
+pub fn export_snapshot<B, BA, C>(
+	client: Arc<C>,
+	backend: Arc<BA>,
+	block: Option<BlockId<B>>,
+	output: impl Write,
+) -> Result<(), Error>
+where
+	B: BlockT,
+	BA: Backend<B>,
+	C: HeaderBackend<B> + BlockBackend<B>,
+{
+	let block = block.unwrap_or_else(|| BlockId::Hash(client.info().finalized_hash));
+	let header = client
+		.header(block)?
+		.ok_or_else(|| Error::Other(format!("Unknown block {}", block)))?;
+	let block = BlockId::Hash(header.hash());
+	let justifications = client.justifications(&block)?;
+	if justifications.is_none() && !header.number().is_zero() {
+		warn!(
+			"Block #{} has no justification, the snapshot can't prove its finality",
+			header.number()
+		);
+	}
+	let state = backend.state_at(block)?;
+
+	let mut writer = SnapshotWriter { output, checksum: [0; 32] };
+	writer.write(&SNAPSHOT_MAGIC)?;
+	writer.write(&SNAPSHOT_VERSION)?;
+	writer.write(&header)?;
+	writer.write(&justifications)?;
+
+	let mut child_tries = Vec::new();
+	let mut entries = write_trie::<B, _, _>(&state, None, &mut writer, |key| {
+		if let Some(storage_key) =
+			key.strip_prefix(well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX)
+		{
+			child_tries.push(storage_key.to_vec());
+		}
+	})?;
+	for storage_key in child_tries {
+		let child_info = ChildInfo::new_default(&storage_key);
+		writer.write(&Record::ChildTrie(storage_key))?;
+		entries += write_trie::<B, _, _>(&state, Some(&child_info), &mut writer, |_| ())?;
+	}
+	writer.write(&Record::End)?;
+	writer.finish()?;
+
+	info!(
+		"Exported {} storage entries of block #{} ({:?})",
+		entries,
+		header.number(),
+		header.hash()
+	);
+	Ok(())
+}
+
+/// Write the pairs of the top or of a child trie, calling `f` with every key.
+///
+/// Returns the number of pairs written.
+fn write_trie<B, S, W>(
+	state: &S,
+	child_info: Option<&ChildInfo>,
+	writer: &mut SnapshotWriter<W>,
+	mut f: impl FnMut(&[u8]),
+) -> Result<u64, Error>
+where
+	B: BlockT,
+	S: StateBackend<HashFor<B>>,
+	W: Write,
+{
+	let mut entries = 0;
+	let mut written = Ok(());
+	state
+		.apply_to_key_values_while(
+			child_info,
+			None,
+			None,
+			|key, value| {
+				f(&key);
+				entries += 1;
+				written = writer.write(&Record::Pair(key, value));
+				written.is_ok()
+			},
+			false,
+		)
+		.map_err(|e| Error::Other(format!("Failed to read the state: {}", e)))?;
+	written.map(|()| entries)
+}
+
+/// Read a snapshot, returning the header, the justifications and the state of the block.
+///
+/// The import queue takes the state of a block as a whole, so the pairs are decoded straight
+/// into their level of the returned state without being buffered elsewhere.
+fn read_snapshot<B: BlockT>(
+	input: impl Read,
+) -> Result<(B::Header, Option<Justifications>, KeyValueStates), Error> {
+	let mut reader = SnapshotReader { input, checksum: [0; 32], item: Vec::new() };
+	if reader.read::<[u8; 8]>()? != SNAPSHOT_MAGIC {
+		return Err(Error::Other("Not a snapshot".into()));
+	}
+	let version = reader.read::<u32>()?;
+	if version != SNAPSHOT_VERSION {
+		return Err(Error::Other(format!("Unsupported snapshot version {}", version)));
+	}
+	let header = reader.read::<B::Header>()?;
+	let justifications = reader.read::<Option<Justifications>>()?;
+
+	let mut levels = vec![KeyValueStorageLevel {
+		state_root: Vec::new(),
+		parent_storage_keys: Vec::new(),
+		key_values: Vec::new(),
+	}];
+	// the child trie roots are not part of the imported top trie, they are recomputed
+	let mut child_roots = HashMap::new();
+	loop {
+		match reader.read::<Record>()? {
+			Record::Pair(key, value) => {
+				if levels.len() == 1 && well_known_keys::is_child_storage_key(&key) {
+					child_roots.insert(key, value);
+				} else {
+					let level = levels.last_mut().expect("the top level is never removed; qed");
+					level.key_values.push((key, value));
+				}
+			}
+			Record::ChildTrie(storage_key) => {
+				let prefixed_storage_key =
+					ChildInfo::new_default(&storage_key).prefixed_storage_key().into_inner();
+				let state_root = child_roots.remove(&prefixed_storage_key).ok_or_else(|| {
+					Error::Other(format!(
+						"Missing root of child trie 0x{}",
+						HexDisplay::from(&storage_key)
+					))
+				})?;
+				levels.push(KeyValueStorageLevel {
+					state_root,
+					parent_storage_keys: vec![prefixed_storage_key],
+					key_values: Vec::new(),
+				});
+			}
+			Record::End => break,
+		}
+	}
+	reader.finish()?;
+
+	Ok((header, justifications, KeyValueStates(levels)))
+}
+
+/// Import a snapshot created by [`export_snapshot`] into a database which only contains the
+/// genesis block.
+///
+/// The block is imported as finalized with its state, like at the end of a warp sync, so the
+/// consensus engines reset their data from the imported state. The state root is verified
+/// against the header, which itself is trusted.
+pub fn import_snapshot<B, IQ, C>(
+	client: Arc<C>,
+	mut import_queue: IQ,
+	input: impl Read,
+) -> Pin<Box<dyn Future<Output = Result<(), Error>> + Send>>
+where
+	C: HeaderBackend<B> + Send + Sync + 'static,
+	B: BlockT,
+	IQ: ImportQueue<B> + 'static,
+{
+	struct WaitLink {
+		processed: bool,
+		has_error: bool,
+	}
+
+	impl<B: BlockT> Link<B> for WaitLink {
+		fn blocks_processed(
+			&mut self,
+			_imported: usize,
+			_num_expected_blocks: usize,
+			results: Vec<(Result<BlockImportStatus<NumberFor<B>>, BlockImportError>, B::Hash)>,
+		) {
+			for (result, hash) in results {
+				self.processed = true;
+				if let Err(err) = result {
+					warn!("There was an error importing snapshot block {:?}: {:?}", hash, err);
+					self.has_error = true;
+				}
+			}
+		}
+	}
+
+	if !client.info().best_number.is_zero() {
+		return future::ready(Err(Error::Other(
+			"Snapshots can only be imported into a database without blocks".into(),
+		)))
+		.boxed();
+	}
+
+	let (header, justifications, state) = match read_snapshot::<B>(input) {
+		Ok(snapshot) => snapshot,
+		Err(e) => return future::ready(Err(e)).boxed(),
+	};
+	let hash = header.hash();
+	let number = *header.number();
+	info!("Importing the state of block #{} ({:?})", number, hash);
+
+	import_queue.import_blocks(
+		BlockOrigin::File,
+		vec![IncomingBlock::<B> {
+			hash,
+			header: Some(header),
+			body: None,
+			indexed_body: None,
+			justifications,
+			origin: None,
+			allow_missing_state: true,
+			import_existing: true,
+			skip_execution: true,
+			state: Some(ImportedState { block: hash, state }),
+		}],
+	);
+
+	let mut link = WaitLink { processed: false, has_error: false };
+	let import = future::poll_fn(move |cx| {
+		import_queue.poll_actions(cx, &mut link);
+
+		if link.has_error {
+			return Poll::Ready(Err(Error::Other(format!("Failed to import block #{}", number))));
+		}
+		if link.processed {
+			info!("🎉 Imported snapshot. Finalized: #{}", client.info().finalized_number);
+			return Poll::Ready(Ok(()));
+		}
+
+		cx.waker().wake_by_ref();
+		Poll::Pending
+	});
+	Box::pin(import)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::executor::block_on;
+	use sc_block_builder::BlockBuilderProvider;
+	use sc_client_api::{StorageProvider, TransactionFor};
+	use sc_consensus::{BasicQueue, BlockImportParams, ForkChoiceStrategy, Verifier};
+	use sp_blockchain::well_known_cache_keys::Id as CacheKeyId;
+	use sp_core::storage::StorageKey;
+	use substrate_test_runtime_client::{prelude::*, runtime::Block, Backend};
+
+	/// Accepts every block as finalized, like a finality proof would.
+	struct FinalizingVerifier;
+
+	#[async_trait::async_trait]
+	impl Verifier<Block> for FinalizingVerifier {
+		async fn verify(
+			&mut self,
+			mut block: BlockImportParams<Block, ()>,
+		) -> Result<(BlockImportParams<Block, ()>, Option<Vec<(CacheKeyId, Vec<u8>)>>), String> {
+			block.finalized = true;
+			block.fork_choice = Some(ForkChoiceStrategy::LongestChain);
+			Ok((block, None))
+		}
+	}
+
+	fn new_import_queue(
+		client: Arc<TestClient>,
+	) -> BasicQueue<Block, TransactionFor<Backend, Block>> {
+		let spawner = sp_core::testing::TaskExecutor::new();
+		BasicQueue::new(FinalizingVerifier, Box::new(client), None, &spawner, None)
+	}
+
+	#[test]
+	fn exported_snapshot_imports_into_fresh_client() {
+		let child_info = ChildInfo::new_default(b"child");
+		let builder = || {
+			TestClientBuilder::new().add_extra_child_storage(
+				&child_info,
+				b"child_key".to_vec(),
+				b"child_value".to_vec(),
+			)
+		};
+
+		let (mut client, backend) = builder().build_with_backend();
+		let mut block_builder = client.new_block(Default::default()).unwrap();
+		block_builder
+			.push_storage_change(b"snapshot_key".to_vec(), Some(b"snapshot_value".to_vec()))
+			.unwrap();
+		let block = block_builder.build().unwrap().block;
+		let hash = block.header.hash();
+		block_on(client.import_as_final(BlockOrigin::Own, block)).unwrap();
+
+		let mut output = Vec::new();
+		export_snapshot(Arc::new(client), backend, None, &mut output).unwrap();
+
+		let client = Arc::new(builder().build());
+		let import_queue = new_import_queue(client.clone());
+		block_on(import_snapshot(client.clone(), import_queue, &output[..])).unwrap();
+
+		let info = client.info();
+		assert_eq!((info.finalized_number, info.finalized_hash), (1, hash));
+		let block = BlockId::Hash(hash);
+		assert_eq!(
+			client
+				.storage(&block, &StorageKey(b"snapshot_key".to_vec()))
+				.unwrap()
+				.unwrap()
+				.0,
+			b"snapshot_value".to_vec(),
+		);
+		assert_eq!(
+			client
+				.child_storage(&block, &child_info, &StorageKey(b"child_key".to_vec()))
+				.unwrap()
+				.unwrap()
+				.0,
+			b"child_value".to_vec(),
+		);
+
+		// a snapshot can't be imported on top of other blocks
+		let import_queue = new_import_queue(client.clone());
+		assert!(block_on(import_snapshot(client, import_queue, &output[..])).is_err());
+	}
+
+	#[test]
+	fn snapshot_roundtrip_detects_corruption() {
+		let header = <Block as BlockT>::Header::new(
+			7,
+			Default::default(),
+			Default::default(),
+			Default::default(),
+			Default::default(),
+		);
+		let child_info = ChildInfo::new_default(b"child");
+		let mut output = Vec::new();
+		let mut writer = SnapshotWriter { output: &mut output, checksum: [0; 32] };
+		writer.write(&SNAPSHOT_MAGIC).unwrap();
+		writer.write(&SNAPSHOT_VERSION).unwrap();
+		writer.write(&header).unwrap();
+		writer.write(&Option::<Justifications>::None).unwrap();
+		writer.write(&Record::Pair(b"key".to_vec(), b"value".to_vec())).unwrap();
+		writer
+			.write(&Record::Pair(child_info.prefixed_storage_key().into_inner(), vec![1; 32]))
+			.unwrap();
+		writer.write(&Record::ChildTrie(b"child".to_vec())).unwrap();
+		writer
+			.write(&Record::Pair(b"child_key".to_vec(), b"child_value".to_vec()))
+			.unwrap();
+		writer.write(&Record::End).unwrap();
+		writer.finish().unwrap();
+
+		let (read_header, justifications, state) = read_snapshot::<Block>(&output[..]).unwrap();
+		assert_eq!(read_header, header);
+		assert!(justifications.is_none());
+		assert_eq!(state.0.len(), 2);
+		assert_eq!(state.0[0].key_values, vec![(b"key".to_vec(), b"value".to_vec())]);
+		assert_eq!(state.0[1].state_root, vec![1; 32]);
+		assert_eq!(state.0[1].key_values, vec![(b"child_key".to_vec(), b"child_value".to_vec())],);
+
+		let value = output.len() - 40;
+		output[value] ^= 1;
+		assert!(read_snapshot::<Block>(&output[..]).is_err());
+	}
+}
@@ -19,7 +19,9 @@
 //! Service configuration.
 
 pub use sc_client_api::execution_extensions::{ExecutionStrategies, ExecutionStrategy};
-pub use sc_client_db::{Database, DatabaseSource, KeepBlocks, PruningMode, TransactionStorageMode};
+pub use sc_client_db::{
+	BlocksPruning, Database, DatabaseSource, PruningMode, TransactionStorageMode,
+};
 pub use sc_executor::WasmExecutionMethod;
 pub use sc_network::{
 	config::{
@@ -33,7 +35,9 @@ pub use sc_network::{
 use prometheus_endpoint::Registry;
 use sc_chain_spec::ChainSpec;
 pub use sc_telemetry::TelemetryEndpoints;
-pub use sc_transaction_pool::Options as TransactionPoolOptions;
+pub use sc_transaction_pool::{
+	Options as TransactionPoolOptions, PoolSenderLimit as TransactionPoolSenderLimit,
+};
 use sp_core::crypto::SecretString;
 use std::{
 	io,
@@ -67,10 +71,12 @@ pub struct Configuration {
 	pub state_cache_size: usize,
 	/// Size in percent of cache size dedicated to child tries
 	pub state_cache_child_ratio: Option<usize>,
+	/// Size of the trie node and per-block state value cache in Bytes
+	pub trie_cache_size: usize,
 	/// State pruning settings.
 	pub state_pruning: PruningMode,
 	/// Number of blocks to keep in the db.
-	pub keep_blocks: KeepBlocks,
+	pub blocks_pruning: BlocksPruning,
 	/// Transaction storage scheme.
 	pub transaction_storage: TransactionStorageMode,
 	/// Chain configuration.
@@ -20,7 +20,7 @@ use std::{convert::TryFrom, time::SystemTime};
 
 use crate::config::Configuration;
 use futures_timer::Delay;
-use prometheus_endpoint::{register, Gauge, GaugeVec, Opts, PrometheusError, Registry, U64};
+use prometheus_endpoint::{register, Gauge, GaugeVec, Opts, PrometheusError, Registry, F64, U64};
 use sc_client_api::{ClientInfo, UsageProvider};
 use sc_network::{config::Role, NetworkService, NetworkStatus};
 use sc_telemetry::{telemetry, TelemetryHandle, SUBSTRATE_INFO};
@@ -43,6 +43,8 @@ struct PrometheusMetrics {
 	database_cache: Gauge<U64>,
 	state_cache: Gauge<U64>,
 	state_db: GaugeVec<U64>,
+	trie_cache: Gauge<U64>,
+	trie_cache_hit_ratio: GaugeVec<F64>,
 }
 
 impl PrometheusMetrics {
@@ -120,6 +122,20 @@ impl PrometheusMetrics {
 				)?,
 				registry,
 			)?,
+			trie_cache: register(
+				Gauge::new("trie_cache_bytes", "Trie node and state value cache size in bytes")?,
+				registry,
+			)?,
+			trie_cache_hit_ratio: register(
+				GaugeVec::new(
+					Opts::new(
+						"trie_cache_hit_ratio",
+						"Share of the lookups served by the trie cache since the last update",
+					),
+					&["cache"],
+				)?,
+				registry,
+			)?,
 		})
 	}
 }
@@ -132,6 +148,8 @@ pub struct MetricsService {
 	last_update: Instant,
 	last_total_bytes_inbound: u64,
 	last_total_bytes_outbound: u64,
+	/// Trie node and state value cache hits and misses at the last update.
+	last_trie_cache_counts: [(u64, u64); 2],
 	telemetry: Option<TelemetryHandle>,
 }
 
@@ -143,6 +161,7 @@ impl MetricsService {
 			metrics: None,
 			last_total_bytes_inbound: 0,
 			last_total_bytes_outbound: 0,
+			last_trie_cache_counts: Default::default(),
 			last_update: Instant::now(),
 			telemetry,
 		}
@@ -171,6 +190,7 @@ impl MetricsService {
 			metrics: Some(p),
 			last_total_bytes_inbound: 0,
 			last_total_bytes_outbound: 0,
+			last_trie_cache_counts: Default::default(),
 			last_update: Instant::now(),
 			telemetry,
 		})
@@ -261,6 +281,26 @@ impl MetricsService {
 					.state_db
 					.with_label_values(&["pinned"])
 					.set(info.memory.state_db.pinned.as_bytes() as u64);
+
+				metrics.trie_cache.set(info.memory.trie_cache.as_bytes() as u64);
+				let counts = [
+					("nodes", info.io.trie_nodes_cache_hits, info.io.trie_nodes_cache_misses),
+					("values", info.io.state_values_cache_hits, info.io.state_values_cache_misses),
+				];
+				for ((cache, total_hits, total_misses), last) in
+					counts.into_iter().zip(&mut self.last_trie_cache_counts)
+				{
+					// The counters only ever grow, rate the lookups since the last update.
+					let hits = total_hits.saturating_sub(last.0);
+					let misses = total_misses.saturating_sub(last.1);
+					*last = (total_hits, total_misses);
+					if hits + misses > 0 {
+						metrics
+							.trie_cache_hit_ratio
+							.with_label_values(&[cache])
+							.set(hits as f64 / (hits + misses) as f64);
+					}
+				}
 			}
 		}
 
@@ -22,15 +22,19 @@ use parity_scale_codec::{Decode, Encode, Joiner};
 use sc_block_builder::BlockBuilderProvider;
 use sc_client_api::{in_mem, BlockBackend, BlockchainEvents, StorageProvider};
 use sc_client_db::{
-	Backend, DatabaseSettings, DatabaseSource, KeepBlocks, PruningMode, TransactionStorageMode,
+	Backend, BlocksPruning, DatabaseSettings, DatabaseSource, PruningMode, TransactionStorageMode,
 };
 use sc_consensus::{
 	BlockCheckParams, BlockImport, BlockImportParams, ForkChoiceStrategy, ImportResult,
 };
-use sc_service::client::{new_in_mem, Client, LocalCallExecutor};
+use sc_service::{
+	chain_ops::{check_db, CheckDbReport, DbIssue},
+	client::{new_in_mem, Client, LocalCallExecutor},
+};
 use sp_api::ProvideRuntimeApi;
 use sp_consensus::{BlockOrigin, BlockStatus, Error as ConsensusError, SelectChain};
 use sp_core::{testing::TaskExecutor, H256};
+use sp_database::Database as _;
 use sp_runtime::{
 	generic::BlockId,
 	traits::{BlakeTwo256, Block as BlockT, Header as HeaderT},
@@ -1052,8 +1056,9 @@ fn doesnt_import_blocks_that_revert_finality() {
 			DatabaseSettings {
 				state_cache_size: 1 << 20,
 				state_cache_child_ratio: None,
+				trie_cache_size: 1 << 20,
 				state_pruning: PruningMode::ArchiveAll,
-				keep_blocks: KeepBlocks::All,
+				blocks_pruning: BlocksPruning::KeepAll,
 				transaction_storage: TransactionStorageMode::BlockBody,
 				source: DatabaseSource::RocksDb { path: tmp.path().into(), cache_size: 1024 },
 			},
@@ -1263,8 +1268,9 @@ fn returns_status_for_pruned_blocks() {
 			DatabaseSettings {
 				state_cache_size: 1 << 20,
 				state_cache_child_ratio: None,
+				trie_cache_size: 1 << 20,
 				state_pruning: PruningMode::keep_blocks(1),
-				keep_blocks: KeepBlocks::All,
+				blocks_pruning: BlocksPruning::KeepAll,
 				transaction_storage: TransactionStorageMode::BlockBody,
 				source: DatabaseSource::RocksDb { path: tmp.path().into(), cache_size: 1024 },
 			},
@@ -1424,6 +1430,164 @@ fn returns_status_for_pruned_blocks() {
 	);
 }
 
+/// Columns of the client database corrupted by the check-db tests.
+const STATE_COLUMN: u32 = 1;
+const HEADER_COLUMN: u32 = 4;
+const BODY_COLUMN: u32 = 5;
+
+/// Opens a client on top of `db`, as the check-db command does.
+fn check_db_client(db: Arc<sp_database::MemDb>) -> (TestClient, Arc<Backend<Block>>) {
+	let backend = Arc::new(
+		Backend::new(
+			DatabaseSettings {
+				state_cache_size: 1 << 20,
+				state_cache_child_ratio: None,
+				trie_cache_size: 0,
+				state_pruning: PruningMode::ArchiveAll,
+				blocks_pruning: BlocksPruning::KeepAll,
+				transaction_storage: TransactionStorageMode::BlockBody,
+				source: DatabaseSource::Custom(db),
+			},
+			u64::MAX,
+		)
+		.unwrap(),
+	);
+	let client = TestClientBuilder::with_backend(backend.clone())
+		.add_extra_child_storage(
+			&ChildInfo::new_default(b"child"),
+			b"first".to_vec(),
+			vec![0u8; 32],
+		)
+		.build();
+	(client, backend)
+}
+
+/// Creates a database with three blocks containing a transfer each, two of them finalized.
+///
+/// Returns the database and the hashes of the blocks.
+fn check_db_database() -> (Arc<sp_database::MemDb>, Vec<Hash>) {
+	let db = Arc::new(sp_database::MemDb::new());
+	let (mut client, _) = check_db_client(db.clone());
+
+	let mut hashes = vec![client.info().genesis_hash];
+	for nonce in 0..3 {
+		let mut builder = client.new_block(Default::default()).unwrap();
+		builder
+			.push_transfer(Transfer {
+				from: AccountKeyring::Alice.into(),
+				to: AccountKeyring::Ferdie.into(),
+				amount: 42,
+				nonce,
+			})
+			.unwrap();
+		let block = builder.build().unwrap().block;
+		hashes.push(block.hash());
+		block_on(client.import(BlockOrigin::Own, block)).unwrap();
+	}
+	client.finalize_block(BlockId::Number(2), None).unwrap();
+
+	(db, hashes)
+}
+
+/// Runs check-db on a freshly opened client, so that no cache hides the corruption.
+fn check_db_report(db: Arc<sp_database::MemDb>) -> CheckDbReport<Block> {
+	let (client, backend) = check_db_client(db.clone());
+	let database = DatabaseSource::Custom(db);
+	check_db(Arc::new(client), backend, &database, BlocksPruning::KeepAll, true).unwrap()
+}
+
+/// Key of a block in the header and body columns.
+fn lookup_key(number: u32, hash: Hash) -> Vec<u8> {
+	let mut key = number.to_be_bytes().to_vec();
+	key.extend_from_slice(hash.as_ref());
+	key
+}
+
+#[test]
+fn check_db_finds_no_issues_in_consistent_database() {
+	let (db, _) = check_db_database();
+
+	let report = check_db_report(db);
+	assert!(report.issues.is_empty(), "{:?}", report.issues);
+	assert_eq!(report.blocks, 4);
+	// the child trie key and its root in the top trie
+	assert!(report.keys.unwrap() > 2);
+}
+
+#[test]
+fn check_db_finds_missing_state_node() {
+	let (db, _) = check_db_database();
+	let child_info = ChildInfo::new_default(b"child");
+	let root = {
+		let (client, _) = check_db_client(db.clone());
+		let finalized = BlockId::Hash(client.info().finalized_hash);
+		let key = StorageKey(child_info.prefixed_storage_key().into_inner());
+		Hash::from_slice(&client.storage(&finalized, &key).unwrap().unwrap().0)
+	};
+	// nodes of a child trie are prefixed with its keyspace
+	let key = sp_trie::prefixed_key::<BlakeTwo256>(&root, (child_info.keyspace(), None));
+	let mut transaction = sp_database::Transaction::<Hash>::new();
+	transaction.remove(STATE_COLUMN, &key);
+	db.commit(transaction).unwrap();
+
+	let report = check_db_report(db);
+	match &report.issues[..] {
+		[DbIssue::MissingTrieNode { child_trie, column, key: missing }] => {
+			assert_eq!(child_trie.as_deref(), Some(&b"child"[..]));
+			assert_eq!(*column, STATE_COLUMN);
+			assert_eq!(*missing, key);
+		}
+		issues => panic!("Unexpected issues: {:?}", issues),
+	}
+	assert_eq!(report.blocks, 4);
+}
+
+#[test]
+fn check_db_finds_body_not_matching_extrinsics_root() {
+	let (db, hashes) = check_db_database();
+	let mut transaction = sp_database::Transaction::<Hash>::new();
+	transaction.set_from_vec(
+		BODY_COLUMN,
+		&lookup_key(1, hashes[1]),
+		Vec::<substrate_test_runtime_client::runtime::Extrinsic>::new().encode(),
+	);
+	db.commit(transaction).unwrap();
+
+	let report = check_db_report(db);
+	match &report.issues[..] {
+		[DbIssue::InvalidBody { number, hash, expected, found }] => {
+			assert_eq!(*number, 1);
+			assert_eq!(*hash, hashes[1]);
+			assert_eq!(*found, Layout::<BlakeTwo256>::ordered_trie_root(Vec::<Vec<u8>>::new()));
+			assert_ne!(found, expected);
+		}
+		issues => panic!("Unexpected issues: {:?}", issues),
+	}
+}
+
+#[test]
+fn check_db_finds_broken_parent_link() {
+	let (db, hashes) = check_db_database();
+	let key = lookup_key(2, hashes[2]);
+	let encoded = sp_database::Database::<Hash>::get(&*db, HEADER_COLUMN, &key).unwrap();
+	let mut header = Header::decode(&mut &encoded[..]).unwrap();
+	header.parent_hash = Default::default();
+	let mut transaction = sp_database::Transaction::<Hash>::new();
+	transaction.set_from_vec(HEADER_COLUMN, &key, header.encode());
+	db.commit(transaction).unwrap();
+
+	let report = check_db_report(db);
+	match &report.issues[..] {
+		[DbIssue::InvalidParent { number, hash, parent_hash, expected }] => {
+			assert_eq!(*number, 2);
+			assert_eq!(*hash, hashes[2]);
+			assert_eq!(*parent_hash, Default::default());
+			assert_eq!(*expected, hashes[1]);
+		}
+		issues => panic!("Unexpected issues: {:?}", issues),
+	}
+}
+
 #[test]
 fn storage_keys_iter_prefix_and_start_key_works() {
 	let child_info = ChildInfo::new_default(b"child");
@@ -20,7 +20,12 @@
 //!
 //! For a more full-featured pool, have a look at the `pool` module.
 
-use std::{collections::HashSet, fmt, hash, sync::Arc};
+use std::{
+	cmp::Ordering,
+	collections::{HashMap, HashSet},
+	fmt, hash,
+	sync::Arc,
+};
 
 use log::{debug, trace, warn};
 use sc_transaction_pool_api::{error, InPoolTransaction, PoolStatus};
@@ -218,23 +223,34 @@ pub struct BasePool<Hash: hash::Hash + Eq, Ex> {
 	/// transactions to future in case they were just stuck in verification.
 	recently_pruned: [HashSet<Tag>; RECENTLY_PRUNED_TAGS],
 	recently_pruned_index: usize,
+	/// Maximal number of transactions of a single sender, if any.
+	sender_limit: Option<SenderLimit>,
+	/// Transactions of every sender in both queues, in the order they entered the pool.
+	///
+	/// Only maintained if there is a `sender_limit`.
+	senders: HashMap<Vec<u8>, Vec<Hash>>,
+	/// Senders that exceeded the `sender_limit` since the limits were last enforced.
+	exceeding_senders: HashSet<Vec<u8>>,
 }
 
 impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> Default for BasePool<Hash, Ex> {
 	fn default() -> Self {
-		Self::new(false)
+		Self::new(false, None)
 	}
 }
 
 impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash, Ex> {
-	/// Create new pool given reject_future_transactions flag.
-	pub fn new(reject_future_transactions: bool) -> Self {
+	/// Create new pool given reject_future_transactions flag and an optional per-sender limit.
+	pub fn new(reject_future_transactions: bool, sender_limit: Option<SenderLimit>) -> Self {
 		Self {
 			reject_future_transactions,
 			future: Default::default(),
 			ready: Default::default(),
 			recently_pruned: Default::default(),
 			recently_pruned_index: 0,
+			sender_limit,
+			senders: Default::default(),
+			exceeding_senders: Default::default(),
 		}
 	}
 
@@ -287,16 +303,58 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			}
 
 			let hash = tx.transaction.hash.clone();
+			self.note_added(&tx.transaction);
 			self.future.import(tx);
 			return Ok(Imported::Future { hash });
 		}
 
+		self.note_added(&tx.transaction);
 		self.import_to_ready(tx)
 	}
 
+	/// Adds a transaction entering the pool to the transactions of its sender.
+	fn note_added(&mut self, tx: &Transaction<Hash, Ex>) {
+		let limit = match self.sender_limit {
+			Some(ref limit) => limit,
+			None => return,
+		};
+		if let Some(sender) = limit.sender(tx) {
+			let hashes = self.senders.entry(sender.to_vec()).or_default();
+			hashes.push(tx.hash.clone());
+			if hashes.len() > limit.count {
+				self.exceeding_senders.insert(sender.to_vec());
+			}
+		}
+	}
+
+	/// Removes transactions leaving the pool from the transactions of their senders.
+	fn note_removed<'a>(&mut self, txs: impl IntoIterator<Item = &'a Transaction<Hash, Ex>>)
+	where
+		Hash: 'a,
+		Ex: 'a,
+	{
+		let limit = match self.sender_limit {
+			Some(ref limit) => limit,
+			None => return,
+		};
+		for tx in txs {
+			let sender = match limit.sender(tx) {
+				Some(sender) => sender,
+				None => continue,
+			};
+			if let Some(hashes) = self.senders.get_mut(sender) {
+				hashes.retain(|hash| *hash != tx.hash);
+				if hashes.is_empty() {
+					self.senders.remove(sender);
+				}
+			}
+		}
+	}
+
 	/// Imports transaction to ready queue.
 	///
-	/// NOTE the transaction has to have all requirements satisfied.
+	/// NOTE the transaction has to have all requirements satisfied and has to be already noted as
+	/// part of the pool, since every transaction dropped here is removed from the pool.
 	fn import_to_ready(
 		&mut self,
 		tx: WaitingTransaction<Hash, Ex>,
@@ -316,6 +374,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 
 			// import this transaction
 			let current_hash = tx.transaction.hash.clone();
+			let current = self.sender_limit.as_ref().map(|_| tx.transaction.clone());
 			match self.ready.import(tx) {
 				Ok(mut replaced) => {
 					if !first {
@@ -323,10 +382,12 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 					}
 					// The transactions were removed from the ready pool. We might attempt to
 					// re-import them.
+					self.note_removed(replaced.iter().map(|tx| &**tx));
 					removed.append(&mut replaced);
 				}
 				// transaction failed to be imported.
 				Err(e) => {
+					self.note_removed(current.as_deref());
 					if first {
 						debug!(target: "txpool", "[{:?}] Error importing: {:?}", current_hash, e);
 						return Err(e);
@@ -346,7 +407,8 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		if removed.iter().any(|tx| tx.hash == hash) {
 			// We still need to remove all transactions that we promoted
 			// since they depend on each other and will never get to the best iterator.
-			self.ready.remove_subtree(&promoted);
+			let promoted = self.ready.remove_subtree(&promoted);
+			self.note_removed(promoted.iter().map(|tx| &**tx));
 
 			debug!(target: "txpool", "[{:?}] Cycle detected, bailing.", hash);
 			return Err(error::Error::CycleDetected);
@@ -384,15 +446,21 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	/// Makes sure that the transactions in the queues stay within provided limits.
 	///
 	/// Removes and returns worst transactions from the queues and all transactions that depend on
-	/// them. Technically the worst transaction should be evaluated by computing the entire pending
-	/// set. We use a simplified approach to remove the transaction that occupies the pool for the
-	/// longest time.
+	/// them, together with the reason of their eviction. The most recent transactions of the
+	/// senders that exceeded the sender limit since the last call are removed first. Then the
+	/// transactions with the lowest priority per byte are removed until the queues fit their
+	/// limits. Among transactions paying the same, the one that occupies the pool for the longest
+	/// time goes first.
 	pub fn enforce_limits(
 		&mut self,
 		ready: &Limit,
 		future: &Limit,
-	) -> Vec<Arc<Transaction<Hash, Ex>>> {
-		let mut removed = vec![];
+	) -> Vec<(EvictionReason, Arc<Transaction<Hash, Ex>>)> {
+		let mut removed = self
+			.enforce_sender_limit()
+			.into_iter()
+			.map(|tx| (EvictionReason::SenderLimit, tx))
+			.collect::<Vec<_>>();
 
 		while ready.is_exceeded(self.ready.len(), self.ready.bytes()) {
 			// find the worst transaction
@@ -400,7 +468,11 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 				let transaction = &current.transaction;
 				match minimal {
 					None => Some(transaction.clone()),
-					Some(ref tx) if tx.insertion_id > transaction.insertion_id => {
+					Some(ref tx)
+						if cmp_fee_per_byte(&transaction.transaction, &tx.transaction)
+							.then_with(|| transaction.insertion_id.cmp(&tx.insertion_id))
+							.is_lt() =>
+					{
 						Some(transaction.clone())
 					}
 					other => other,
@@ -408,7 +480,11 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			});
 
 			if let Some(minimal) = minimal {
-				removed.append(&mut self.remove_subtree(&[minimal.transaction.hash.clone()]))
+				removed.extend(
+					self.remove_subtree(&[minimal.transaction.hash.clone()])
+						.into_iter()
+						.map(|tx| (EvictionReason::ReadyLimit, tx)),
+				)
 			} else {
 				break;
 			}
@@ -418,12 +494,22 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			// find the worst transaction
 			let minimal = self.future.fold(|minimal, current| match minimal {
 				None => Some(current.clone()),
-				Some(ref tx) if tx.imported_at > current.imported_at => Some(current.clone()),
+				Some(ref tx)
+					if cmp_fee_per_byte(&current.transaction, &tx.transaction)
+						.then_with(|| current.imported_at.cmp(&tx.imported_at))
+						.is_lt() =>
+				{
+					Some(current.clone())
+				}
 				other => other,
 			});
 
 			if let Some(minimal) = minimal {
-				removed.append(&mut self.remove_subtree(&[minimal.transaction.hash.clone()]))
+				removed.extend(
+					self.remove_subtree(&[minimal.transaction.hash.clone()])
+						.into_iter()
+						.map(|tx| (EvictionReason::FutureLimit, tx)),
+				)
 			} else {
 				break;
 			}
@@ -432,6 +518,43 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 		removed
 	}
 
+	/// Returns true if some sender exceeded the sender limit since the limits were last enforced.
+	pub fn is_sender_limit_exceeded(&self) -> bool {
+		!self.exceeding_senders.is_empty()
+	}
+
+	/// Removes the most recent transactions of every sender that exceeded the sender limit since
+	/// the last call and all transactions that depend on them.
+	///
+	/// Future transactions of a sender are removed before its ready ones, since they are the
+	/// furthest from being included in a block.
+	fn enforce_sender_limit(&mut self) -> Vec<Arc<Transaction<Hash, Ex>>> {
+		let limit = match self.sender_limit {
+			Some(ref limit) => limit.count,
+			None => return vec![],
+		};
+
+		let mut removed = vec![];
+		for sender in std::mem::take(&mut self.exceeding_senders) {
+			let hashes = match self.senders.get(&sender) {
+				Some(hashes) if hashes.len() > limit => hashes.clone(),
+				_ => continue,
+			};
+			// transactions of the sender, in the order in which they are evicted
+			let (future, ready): (Vec<_>, Vec<_>) =
+				hashes.into_iter().rev().partition(|hash| self.future.contains(hash));
+			for hash in future.into_iter().chain(ready) {
+				if self.senders.get(&sender).map_or(0, |hashes| hashes.len()) <= limit {
+					break;
+				}
+				// the transaction is already gone if it depended on a previously removed one
+				removed.extend(self.remove_subtree(&[hash]));
+			}
+		}
+
+		removed
+	}
+
 	/// Removes all transactions represented by the hashes and all other transactions
 	/// that depend on them.
 	///
@@ -443,12 +566,15 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 	pub fn remove_subtree(&mut self, hashes: &[Hash]) -> Vec<Arc<Transaction<Hash, Ex>>> {
 		let mut removed = self.ready.remove_subtree(hashes);
 		removed.extend(self.future.remove(hashes));
+		self.note_removed(removed.iter().map(|tx| &**tx));
 		removed
 	}
 
 	/// Removes and returns all transactions from the future queue.
 	pub fn clear_future(&mut self) -> Vec<Arc<Transaction<Hash, Ex>>> {
-		self.future.clear()
+		let removed = self.future.clear();
+		self.note_removed(removed.iter().map(|tx| &**tx));
+		removed
 	}
 
 	/// Prunes transactions that provide given list of tags.
@@ -472,6 +598,7 @@ impl<Hash: hash::Hash + Member + Serialize, Ex: std::fmt::Debug> BasePool<Hash,
 			// store the tags for next submission
 			recently_pruned.insert(tag);
 		}
+		self.note_removed(pruned.iter().map(|tx| &**tx));
 
 		let mut promoted = vec![];
 		let mut failed = vec![];
@@ -516,6 +643,58 @@ impl Limit {
 	}
 }
 
+/// Per-sender limit
+///
+/// The pool does not know the senders of transactions, so a transaction is attributed to the
+/// sender identified by a prefix of the first tag it provides. For FRAME based chains this tag is
+/// the encoded `(AccountId, Nonce)` pair, so a prefix as long as the encoded account id
+/// identifies the sender.
+#[derive(Debug, Clone, parity_util_mem::MallocSizeOf)]
+pub struct SenderLimit {
+	/// Maximal number of transactions of a single sender in both queues.
+	pub count: usize,
+	/// Length of the prefix of the first provided tag that identifies the sender.
+	pub tag_prefix: usize,
+}
+
+impl SenderLimit {
+	/// Returns the sender of the transaction or `None` if the transaction provides no tags.
+	pub fn sender<'a, Hash, Ex>(&self, tx: &'a Transaction<Hash, Ex>) -> Option<&'a [u8]> {
+		tx.provides.first().map(|tag| &tag[..self.tag_prefix.min(tag.len())])
+	}
+}
+
+/// Reason of evicting a transaction when enforcing the limits of the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvictionReason {
+	/// The ready queue exceeded its limit.
+	ReadyLimit,
+	/// The future queue exceeded its limit.
+	FutureLimit,
+	/// The sender of the transaction exceeded its limit.
+	SenderLimit,
+}
+
+impl EvictionReason {
+	/// Returns the name of the reason, as used in the metrics.
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::ReadyLimit => "ready_limit",
+			Self::FutureLimit => "future_limit",
+			Self::SenderLimit => "sender_limit",
+		}
+	}
+}
+
+/// Compares transactions by their priority per byte of encoding.
+///
+/// Priority is the only measure of what a transaction pays that the pool knows about; for FRAME
+/// based chains it grows with the fee, so this approximates comparing the fee per byte.
+fn cmp_fee_per_byte<Hash, Ex>(a: &Transaction<Hash, Ex>, b: &Transaction<Hash, Ex>) -> Ordering {
+	let (a_bytes, b_bytes) = (a.bytes.max(1) as u128, b.bytes.max(1) as u128);
+	(a.priority as u128 * b_bytes).cmp(&(b.priority as u128 * a_bytes))
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1050,4 +1229,156 @@ source: TransactionSource::External, requires: [03, 02], provides: [04], data: [
 		assert_eq!(pool.reject_future_transactions, true);
 		assert_eq!(pool.future.len(), 1);
 	}
+
+	#[test]
+	fn should_evict_transactions_with_lowest_fee_per_byte_first() {
+		// given
+		let mut pool = pool();
+		let limit = Limit { count: 2, total_bytes: 1024 };
+		pool.import(Transaction {
+			data: vec![1u8],
+			hash: 1,
+			priority: 10,
+			provides: vec![vec![1]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+		pool.import(Transaction {
+			data: vec![2u8],
+			bytes: 5,
+			hash: 2,
+			priority: 10,
+			provides: vec![vec![2]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+		pool.import(Transaction {
+			data: vec![3u8],
+			hash: 3,
+			priority: 5,
+			provides: vec![vec![3]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+
+		// when
+		let removed = pool.enforce_limits(&limit, &limit);
+
+		// then
+		assert_eq!(removed.len(), 1);
+		assert_eq!(removed[0].0, EvictionReason::ReadyLimit);
+		assert_eq!(removed[0].1.hash, 2);
+		assert_eq!(pool.ready().count(), 2);
+	}
+
+	#[test]
+	fn should_evict_future_transactions_of_sender_over_limit_first() {
+		// given
+		let mut pool = BasePool::new(false, Some(SenderLimit { count: 1, tag_prefix: 1 }));
+		let limit = Limit { count: 100, total_bytes: 1024 };
+		// sender 1: one ready and two future transactions
+		pool.import(Transaction {
+			data: vec![1u8],
+			hash: 1,
+			provides: vec![vec![1, 0]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+		pool.import(Transaction {
+			data: vec![2u8],
+			hash: 2,
+			requires: vec![vec![1, 5]],
+			provides: vec![vec![1, 6]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+		pool.import(Transaction {
+			data: vec![3u8],
+			hash: 3,
+			requires: vec![vec![1, 7]],
+			provides: vec![vec![1, 8]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+		// sender 2: one ready transaction
+		pool.import(Transaction {
+			data: vec![4u8],
+			hash: 4,
+			provides: vec![vec![2, 0]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+
+		// when
+		let removed = pool.enforce_limits(&limit, &limit);
+
+		// then
+		let mut removed = removed
+			.into_iter()
+			.map(|(reason, tx)| {
+				assert_eq!(reason, EvictionReason::SenderLimit);
+				tx.hash
+			})
+			.collect::<Vec<_>>();
+		removed.sort();
+		assert_eq!(removed, vec![2, 3]);
+		assert_eq!(pool.ready().count(), 2);
+		assert_eq!(pool.future.len(), 0);
+	}
+
+	#[test]
+	fn should_track_sender_transactions_when_they_leave_the_pool() {
+		// given
+		let mut pool = BasePool::new(false, Some(SenderLimit { count: 2, tag_prefix: 1 }));
+		let limit = Limit { count: 100, total_bytes: 1024 };
+		pool.import(Transaction {
+			data: vec![1u8],
+			hash: 1,
+			provides: vec![vec![1, 0]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+		pool.import(Transaction {
+			data: vec![2u8],
+			hash: 2,
+			requires: vec![vec![1, 0]],
+			provides: vec![vec![1, 1]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+		assert!(!pool.is_sender_limit_exceeded());
+
+		// when
+		pool.prune_tags(vec![vec![1, 0]]);
+		pool.import(Transaction {
+			data: vec![3u8],
+			hash: 3,
+			requires: vec![vec![1, 1]],
+			provides: vec![vec![1, 2]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+
+		// then
+		assert!(!pool.is_sender_limit_exceeded());
+		assert_eq!(pool.senders.get(&vec![1u8][..]).unwrap(), &vec![2, 3]);
+
+		// when
+		pool.import(Transaction {
+			data: vec![4u8],
+			hash: 4,
+			requires: vec![vec![1, 2]],
+			provides: vec![vec![1, 3]],
+			..DEFAULT_TX.clone()
+		})
+		.unwrap();
+
+		// then
+		assert!(pool.is_sender_limit_exceeded());
+		let removed = pool.enforce_limits(&limit, &limit);
+		assert_eq!(removed.len(), 1);
+		assert_eq!(removed[0].1.hash, 4);
+		assert!(!pool.is_sender_limit_exceeded());
+		assert_eq!(pool.ready().count(), 2);
+	}
 }
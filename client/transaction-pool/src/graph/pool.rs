@@ -16,7 +16,7 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
 
 use futures::{channel::mpsc::Receiver, Future};
 use sc_transaction_pool_api::error;
@@ -34,6 +34,7 @@ use super::{
 	validated_pool::{IsValidator, ValidatedPool, ValidatedTransaction},
 	watcher::Watcher,
 };
+use crate::metrics::MetricsLink;
 
 /// Modification notification event stream type;
 pub type EventStream<H> = Receiver<H>;
@@ -106,8 +107,13 @@ pub struct Options {
 	pub ready: base::Limit,
 	/// Future queue limits.
 	pub future: base::Limit,
+	/// Per-sender limits, applied to both queues. No per-sender limit is enforced when `None`.
+	pub sender: Option<base::SenderLimit>,
 	/// Reject future transactions.
 	pub reject_future_transactions: bool,
+	/// File to which ready and future transactions are journaled so that they survive a restart
+	/// of the node. Journaling is disabled when `None`.
+	pub journal_path: Option<PathBuf>,
 }
 
 impl Default for Options {
@@ -115,7 +121,9 @@ impl Default for Options {
 		Self {
 			ready: base::Limit { count: 8192, total_bytes: 20 * 1024 * 1024 },
 			future: base::Limit { count: 512, total_bytes: 1 * 1024 * 1024 },
+			sender: None,
 			reject_future_transactions: false,
+			journal_path: None,
 		}
 	}
 }
@@ -145,7 +153,17 @@ where
 impl<B: ChainApi> Pool<B> {
 	/// Create a new transaction pool.
 	pub fn new(options: Options, is_validator: IsValidator, api: Arc<B>) -> Self {
-		Self { validated_pool: Arc::new(ValidatedPool::new(options, is_validator, api)) }
+		Self::with_metrics(options, is_validator, api, Default::default())
+	}
+
+	/// Create a new transaction pool reporting to the given metrics.
+	pub(crate) fn with_metrics(
+		options: Options,
+		is_validator: IsValidator,
+		api: Arc<B>,
+		metrics: MetricsLink,
+	) -> Self {
+		Self { validated_pool: Arc::new(ValidatedPool::new(options, is_validator, api, metrics)) }
 	}
 
 	/// Imports a bunch of unverified extrinsics to the pool
@@ -43,6 +43,7 @@ use super::{
 	rotator::PoolRotator,
 	watcher::Watcher,
 };
+use crate::metrics::MetricsLink;
 
 /// Pre-validated transaction. Validated pool only accepts transactions wrapped in this enum.
 #[derive(Debug)]
@@ -109,6 +110,7 @@ pub struct ValidatedPool<B: ChainApi> {
 	pool: RwLock<base::BasePool<ExtrinsicHash<B>, ExtrinsicFor<B>>>,
 	import_notification_sinks: Mutex<Vec<Sender<ExtrinsicHash<B>>>>,
 	rotator: PoolRotator<ExtrinsicHash<B>>,
+	metrics: MetricsLink,
 }
 
 impl<B: ChainApi> parity_util_mem::MallocSizeOf for ValidatedPool<B>
@@ -123,8 +125,14 @@ where
 
 impl<B: ChainApi> ValidatedPool<B> {
 	/// Create a new transaction pool.
-	pub fn new(options: Options, is_validator: IsValidator, api: Arc<B>) -> Self {
-		let base_pool = base::BasePool::new(options.reject_future_transactions);
+	pub fn new(
+		options: Options,
+		is_validator: IsValidator,
+		api: Arc<B>,
+		metrics: MetricsLink,
+	) -> Self {
+		let base_pool =
+			base::BasePool::new(options.reject_future_transactions, options.sender.clone());
 		Self {
 			is_validator,
 			options,
@@ -133,6 +141,7 @@ impl<B: ChainApi> ValidatedPool<B> {
 			pool: RwLock::new(base_pool),
 			import_notification_sinks: Default::default(),
 			rotator: Default::default(),
+			metrics,
 		}
 	}
 
@@ -239,29 +248,37 @@ impl<B: ChainApi> ValidatedPool<B> {
 	}
 
 	fn enforce_limits(&self) -> HashSet<ExtrinsicHash<B>> {
-		let status = self.pool.read().status();
+		let (status, sender_limit_exceeded) = {
+			let pool = self.pool.read();
+			(pool.status(), pool.is_sender_limit_exceeded())
+		};
 		let ready_limit = &self.options.ready;
 		let future_limit = &self.options.future;
+		let sender_limit = self.options.sender.as_ref();
 
 		log::debug!(target: "txpool", "Pool Status: {:?}", status);
 		if ready_limit.is_exceeded(status.ready, status.ready_bytes)
 			|| future_limit.is_exceeded(status.future, status.future_bytes)
+			|| sender_limit_exceeded
 		{
 			log::debug!(
 				target: "txpool",
-				"Enforcing limits ({}/{}kB ready, {}/{}kB future",
+				"Enforcing limits ({}/{}kB ready, {}/{}kB future, {:?} per sender",
 				ready_limit.count, ready_limit.total_bytes / 1024,
 				future_limit.count, future_limit.total_bytes / 1024,
+				sender_limit.map(|limit| limit.count),
 			);
 
 			// clean up the pool
 			let removed = {
 				let mut pool = self.pool.write();
-				let removed = pool
-					.enforce_limits(ready_limit, future_limit)
-					.into_iter()
-					.map(|x| x.hash)
-					.collect::<HashSet<_>>();
+				let evicted = pool.enforce_limits(ready_limit, future_limit);
+				self.metrics.report(|metrics| {
+					for (reason, _) in &evicted {
+						metrics.evicted_transactions.with_label_values(&[reason.as_str()]).inc();
+					}
+				});
+				let removed = evicted.into_iter().map(|(_, x)| x.hash).collect::<HashSet<_>>();
 				// ban all removed transactions
 				self.rotator.ban(&Instant::now(), removed.iter().copied());
 				removed
@@ -645,6 +662,16 @@ impl<B: ChainApi> ValidatedPool<B> {
 			.collect()
 	}
 
+	/// Returns the source and extrinsic of every transaction in the pool, ready transactions first
+	/// in the order they would be included in a block, followed by future ones.
+	pub fn transactions(&self) -> Vec<(TransactionSource, ExtrinsicFor<B>)> {
+		let pool = self.pool.read();
+		pool.ready()
+			.map(|tx| (tx.source, tx.data.clone()))
+			.chain(pool.futures().map(|tx| (tx.source, tx.data.clone())))
+			.collect()
+	}
+
 	/// Returns pool status.
 	pub fn status(&self) -> PoolStatus {
 		self.pool.read().status()
@@ -0,0 +1,275 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Journal of pending transactions, kept on disk across node restarts.
+//!
+//! The journal is a single file holding the ready and future transactions of the pool. It is
+//! rewritten periodically while the node runs and once more when the pool is dropped. The file
+//! is written by a background task, so that maintaining or dropping the pool never waits on the
+//! disk. On startup the journaled transactions are loaded and handed back to the pool, which
+//! re-validates them like any other submission.
+
+use codec::{Decode, Encode};
+use futures::prelude::*;
+use parking_lot::Mutex;
+use sc_transaction_pool_api::TransactionSource;
+use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver, TracingUnboundedSender};
+use std::{
+	fs, io,
+	path::{Path, PathBuf},
+	pin::Pin,
+	time::{Duration, Instant},
+};
+
+/// Version of the journal file format.
+const JOURNAL_VERSION: u32 = 1;
+
+/// Minimal time between two periodic writes of the journal.
+const JOURNAL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// On-disk content of the journal.
+///
+/// Transactions are stored encoded on their own, so that a transaction which cannot be decoded
+/// anymore does not prevent the others from being restored.
+#[derive(Encode, Decode)]
+struct JournalFile {
+	version: u32,
+	transactions: Vec<(TransactionSource, Vec<u8>)>,
+}
+
+/// Journal of the transactions of a pool.
+pub struct Journal<Ex> {
+	path: PathBuf,
+	restored: Mutex<Vec<(TransactionSource, Ex)>>,
+	last_write: Mutex<Option<Instant>>,
+	to_writer: TracingUnboundedSender<JournalFile>,
+}
+
+impl<Ex: Encode + Decode> Journal<Ex> {
+	/// Open the journal at `path`, loading any transactions journaled by a previous run.
+	///
+	/// A missing or unreadable journal is not an error; the pool simply starts empty.
+	///
+	/// Also returns the task writing the journal to disk, which must be spawned. It ends after
+	/// the last write requested before the journal is dropped.
+	pub fn open(path: PathBuf) -> (Self, Pin<Box<dyn Future<Output = ()> + Send>>) {
+		let restored = match read(&path) {
+			Ok(restored) => {
+				log::info!(
+					target: "txpool",
+					"Restored {} transactions from journal at {}",
+					restored.len(),
+					path.display(),
+				);
+				restored
+			}
+			Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+			Err(e) => {
+				log::warn!(
+					target: "txpool",
+					"Failed to read transaction pool journal at {}: {}",
+					path.display(),
+					e,
+				);
+				Vec::new()
+			}
+		};
+
+		let (to_writer, from_journal) = tracing_unbounded("mpsc_txpool_journal");
+		let writer = run_writer(path.clone(), from_journal).boxed();
+		let journal =
+			Self { path, restored: Mutex::new(restored), last_write: Mutex::new(None), to_writer };
+		(journal, writer)
+	}
+
+	/// Take the transactions restored from the journal which were not yet handed to the pool.
+	pub fn take_restored(&self) -> Vec<(TransactionSource, Ex)> {
+		std::mem::take(&mut *self.restored.lock())
+	}
+
+	/// Write the given transactions to the journal, unless it was written less than
+	/// `JOURNAL_INTERVAL` ago.
+	pub fn write_periodic<'a>(
+		&self,
+		transactions: impl IntoIterator<Item = (TransactionSource, &'a Ex)>,
+	) where
+		Ex: 'a,
+	{
+		{
+			let mut last_write = self.last_write.lock();
+			if last_write.map_or(false, |at| at.elapsed() < JOURNAL_INTERVAL) {
+				return;
+			}
+			*last_write = Some(Instant::now());
+		}
+		self.write(transactions);
+	}
+
+	/// Write the given transactions to the journal, replacing its previous content.
+	///
+	/// Transactions restored from the journal that were not yet handed back to the pool are
+	/// kept, so that they are not lost if the node is stopped again before re-importing them.
+	///
+	/// The transactions are encoded right away, the file is written by the background task.
+	pub fn write<'a>(&self, transactions: impl IntoIterator<Item = (TransactionSource, &'a Ex)>)
+	where
+		Ex: 'a,
+	{
+		let restored = self.restored.lock();
+		let transactions: Vec<_> = restored
+			.iter()
+			.map(|(source, xt)| (*source, xt.encode()))
+			.chain(transactions.into_iter().map(|(source, xt)| (source, xt.encode())))
+			.collect();
+
+		let file = JournalFile { version: JOURNAL_VERSION, transactions };
+		if self.to_writer.unbounded_send(file).is_err() {
+			log::warn!(
+				target: "txpool",
+				"Failed to write transaction pool journal to {}: writer task is gone",
+				self.path.display(),
+			);
+		}
+	}
+}
+
+/// Write the content sent by the journal to `path`, until the journal is dropped.
+async fn run_writer(path: PathBuf, mut from_journal: TracingUnboundedReceiver<JournalFile>) {
+	while let Some(mut file) = from_journal.next().await {
+		// only the most recent content is worth writing
+		while let Ok(Some(next)) = from_journal.try_next() {
+			file = next;
+		}
+
+		let count = file.transactions.len();
+		match write(&path, file) {
+			Ok(()) => log::debug!(
+				target: "txpool",
+				"Journaled {} transactions to {}",
+				count,
+				path.display(),
+			),
+			Err(e) => log::warn!(
+				target: "txpool",
+				"Failed to write transaction pool journal to {}: {}",
+				path.display(),
+				e,
+			),
+		}
+	}
+}
+
+fn read<Ex: Decode>(path: &Path) -> io::Result<Vec<(TransactionSource, Ex)>> {
+	let bytes = fs::read(path)?;
+	let file = JournalFile::decode(&mut &bytes[..])
+		.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+	if file.version != JOURNAL_VERSION {
+		return Err(io::Error::new(
+			io::ErrorKind::InvalidData,
+			format!("unsupported journal version {}", file.version),
+		));
+	}
+
+	let total = file.transactions.len();
+	let transactions: Vec<_> = file
+		.transactions
+		.into_iter()
+		.filter_map(|(source, data)| Ex::decode(&mut &data[..]).ok().map(|xt| (source, xt)))
+		.collect();
+	if transactions.len() < total {
+		log::debug!(
+			target: "txpool",
+			"Skipped {} journaled transactions that could not be decoded",
+			total - transactions.len(),
+		);
+	}
+	Ok(transactions)
+}
+
+/// Write the journal through a temporary file, so that a crash while writing never leaves a
+/// truncated journal behind.
+fn write(path: &Path, file: JournalFile) -> io::Result<()> {
+	if let Some(parent) = path.parent() {
+		fs::create_dir_all(parent)?;
+	}
+	let tmp = path.with_extension("tmp");
+	fs::write(&tmp, file.encode())?;
+	fs::rename(&tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use futures::executor::block_on;
+
+	/// Write `transactions` and wait until they are on disk.
+	fn write_and_close(
+		(journal, writer): (Journal<Vec<u8>>, impl Future<Output = ()>),
+		transactions: &[(TransactionSource, Vec<u8>)],
+	) {
+		journal.write(transactions.iter().map(|(source, xt)| (*source, xt)));
+		drop(journal);
+		block_on(writer);
+	}
+
+	#[test]
+	fn transactions_survive_reopening() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("txpool").join("journal");
+
+		let (journal, writer) = Journal::<Vec<u8>>::open(path.clone());
+		assert!(journal.take_restored().is_empty());
+
+		let transactions = vec![
+			(TransactionSource::Local, vec![1u8, 2, 3]),
+			(TransactionSource::External, vec![4u8]),
+		];
+		write_and_close((journal, writer), &transactions);
+
+		let (journal, _) = Journal::<Vec<u8>>::open(path);
+		assert_eq!(journal.take_restored(), transactions);
+		assert!(journal.take_restored().is_empty());
+	}
+
+	#[test]
+	fn restored_transactions_are_kept_until_taken() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("journal");
+
+		write_and_close(Journal::open(path.clone()), &[(TransactionSource::External, vec![1u8])]);
+
+		// Nothing was handed to the pool, so the restored transaction is written again.
+		write_and_close(Journal::open(path.clone()), &[(TransactionSource::Local, vec![2u8])]);
+
+		let (journal, _) = Journal::<Vec<u8>>::open(path);
+		assert_eq!(
+			journal.take_restored(),
+			vec![(TransactionSource::External, vec![1u8]), (TransactionSource::Local, vec![2u8])],
+		);
+	}
+
+	#[test]
+	fn corrupted_journal_is_ignored() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("journal");
+		fs::write(&path, b"not a journal").unwrap();
+
+		let (journal, _) = Journal::<Vec<u8>>::open(path);
+		assert!(journal.take_restored().is_empty());
+	}
+}
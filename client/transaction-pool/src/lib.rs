@@ -24,6 +24,7 @@
 
 mod api;
 mod graph;
+mod journal;
 mod metrics;
 mod revalidation;
 
@@ -44,7 +45,10 @@ use futures::{
 	future::{self, ready},
 	prelude::*,
 };
-pub use graph::{base_pool::Limit as PoolLimit, ChainApi, Options, Pool, Transaction};
+pub use graph::{
+	base_pool::{Limit as PoolLimit, SenderLimit as PoolSenderLimit},
+	ChainApi, Options, Pool, Transaction,
+};
 use parking_lot::Mutex;
 use std::{
 	collections::{HashMap, HashSet},
@@ -92,6 +96,7 @@ where
 	revalidation_queue: Arc<revalidation::RevalidationQueue<PoolApi>>,
 	ready_poll: Arc<Mutex<ReadyPoll<ReadyIteratorFor<PoolApi>, Block>>>,
 	metrics: PrometheusMetrics,
+	journal: Option<Arc<journal::Journal<graph::ExtrinsicFor<PoolApi>>>>,
 }
 
 struct ReadyPoll<T, Block: BlockT> {
@@ -185,6 +190,7 @@ where
 				revalidation_strategy: Arc::new(Mutex::new(RevalidationStrategy::Always)),
 				ready_poll: Default::default(),
 				metrics: Default::default(),
+				journal: None,
 			},
 			background_task,
 			notifier,
@@ -202,7 +208,18 @@ where
 		spawner: impl SpawnEssentialNamed,
 		best_block_number: NumberFor<Block>,
 	) -> Self {
-		let pool = Arc::new(graph::Pool::new(options, is_validator, pool_api.clone()));
+		let journal = options.journal_path.clone().map(|path| {
+			let (journal, writer) = journal::Journal::open(path);
+			spawner.spawn_essential_blocking("txpool-journal", Some("transaction-pool"), writer);
+			Arc::new(journal)
+		});
+		let metrics = PrometheusMetrics::new(prometheus);
+		let pool = Arc::new(graph::Pool::with_metrics(
+			options,
+			is_validator,
+			pool_api.clone(),
+			metrics.clone(),
+		));
 		let (revalidation_queue, background_task) = match revalidation_type {
 			RevalidationType::Light => {
 				(revalidation::RevalidationQueue::new(pool_api.clone(), pool.clone()), None)
@@ -229,7 +246,8 @@ where
 				RevalidationType::Full => RevalidationStrategy::Always,
 			})),
 			ready_poll: Arc::new(Mutex::new(ReadyPoll::new(best_block_number))),
-			metrics: PrometheusMetrics::new(prometheus),
+			metrics,
+			journal,
 		}
 	}
 
@@ -611,6 +629,7 @@ where
 				let revalidation_queue = self.revalidation_queue.clone();
 				let ready_poll = self.ready_poll.clone();
 				let metrics = self.metrics.clone();
+				let journal = self.journal.clone();
 
 				async move {
 					// We keep track of everything we prune so that later we won't add
@@ -721,6 +740,14 @@ where
 
 						revalidation_strategy.lock().clear();
 					}
+
+					if let Some(journal) = journal {
+						resubmit_journaled(&id, &*pool, journal.take_restored()).await;
+
+						let transactions = pool.validated_pool().transactions();
+						journal
+							.write_periodic(transactions.iter().map(|(source, xt)| (*source, xt)));
+					}
 				}
 				.boxed()
 			}
@@ -741,6 +768,59 @@ where
 	}
 }
 
+impl<PoolApi, Block> Drop for BasicPool<PoolApi, Block>
+where
+	Block: BlockT,
+	PoolApi: graph::ChainApi<Block = Block>,
+{
+	fn drop(&mut self) {
+		if let Some(ref journal) = self.journal {
+			let transactions = self.pool.validated_pool().transactions();
+			journal.write(transactions.iter().map(|(source, xt)| (*source, xt)));
+		}
+	}
+}
+
+/// Re-validate and import the transactions restored from the journal at the given block.
+async fn resubmit_journaled<Api: graph::ChainApi>(
+	at: &BlockId<Api::Block>,
+	pool: &graph::Pool<Api>,
+	transactions: Vec<(TransactionSource, graph::ExtrinsicFor<Api>)>,
+) {
+	if transactions.is_empty() {
+		return;
+	}
+
+	// Local transactions keep their priority, all the others are resubmitted as external.
+	let (local, external): (Vec<_>, Vec<_>) = transactions
+		.into_iter()
+		.partition(|(source, _)| *source == TransactionSource::Local);
+	for (source, transactions) in
+		[(TransactionSource::Local, local), (TransactionSource::External, external)]
+	{
+		if transactions.is_empty() {
+			continue;
+		}
+
+		let count = transactions.len();
+		match pool.submit_at(at, source, transactions.into_iter().map(|(_, xt)| xt)).await {
+			Ok(results) => log::info!(
+				target: "txpool",
+				"Re-imported {} of {} {:?} transactions from journal",
+				results.iter().filter(|r| r.is_ok()).count(),
+				count,
+				source,
+			),
+			Err(e) => log::warn!(
+				target: "txpool",
+				"[{:?}] Error re-importing journaled transactions: {:?}",
+				at,
+				e,
+			),
+		}
+	}
+}
+
 /// Inform the transaction pool about imported and finalized blocks.
 pub async fn notification_future<Client, Pool, Block>(client: Arc<Client>, txpool: Arc<Pool>)
 where
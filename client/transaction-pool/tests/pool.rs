@@ -20,9 +20,11 @@
 use codec::Encode;
 use futures::{
 	executor::{block_on, block_on_stream},
+	future::BoxFuture,
 	prelude::*,
 	task::Poll,
 };
+use parking_lot::Mutex;
 use sc_block_builder::BlockBuilderProvider;
 use sc_client_api::client::BlockchainEvents;
 use sc_transaction_pool::{test_helpers::*, *};
@@ -30,12 +32,13 @@ use sc_transaction_pool_api::{
 	ChainEvent, MaintainedTransactionPool, TransactionPool, TransactionStatus,
 };
 use sp_consensus::BlockOrigin;
+use sp_core::traits::SpawnEssentialNamed;
 use sp_runtime::{
 	generic::BlockId,
 	traits::Block as _,
 	transaction_validity::{InvalidTransaction, TransactionSource, ValidTransaction},
 };
-use std::{collections::BTreeSet, convert::TryInto, sync::Arc};
+use std::{collections::BTreeSet, convert::TryInto, path::Path, sync::Arc};
 use substrate_test_runtime_client::{
 	runtime::{Block, Extrinsic, Hash, Header, Index, Transfer},
 	AccountKeyring::*,
@@ -1007,3 +1010,78 @@ fn stale_transactions_are_pruned() {
 	assert_eq!(pool.status().future, 0);
 	assert_eq!(pool.status().ready, 0);
 }
+
+/// Keeps the tasks spawned by a pool, so that a test can run them to completion.
+#[derive(Clone, Default)]
+struct TaskCollector(Arc<Mutex<Vec<BoxFuture<'static, ()>>>>);
+
+impl SpawnEssentialNamed for TaskCollector {
+	fn spawn_essential_blocking(
+		&self,
+		_: &'static str,
+		_: Option<&'static str>,
+		future: BoxFuture<'static, ()>,
+	) {
+		self.0.lock().push(future);
+	}
+
+	fn spawn_essential(
+		&self,
+		_: &'static str,
+		_: Option<&'static str>,
+		future: BoxFuture<'static, ()>,
+	) {
+		self.0.lock().push(future);
+	}
+}
+
+fn journaled_pool(
+	path: &Path,
+	api: Arc<TestApi>,
+	spawner: TaskCollector,
+) -> BasicPool<TestApi, Block> {
+	let options = Options { journal_path: Some(path.to_owned()), ..Default::default() };
+	BasicPool::with_revalidation_type(
+		options,
+		true.into(),
+		api,
+		None,
+		RevalidationType::Light,
+		spawner,
+		0,
+	)
+}
+
+#[test]
+fn journaled_transactions_are_restored_after_restart() {
+	let dir = tempfile::tempdir().unwrap();
+	let path = dir.path().join("journal");
+
+	let spawner = TaskCollector::default();
+	let pool = journaled_pool(&path, Arc::new(TestApi::with_alice_nonce(209)), spawner.clone());
+	block_on(pool.submit_one(&BlockId::number(0), TransactionSource::Local, uxt(Alice, 209)))
+		.unwrap();
+	block_on(pool.submit_one(&BlockId::number(0), SOURCE, uxt(Alice, 211))).unwrap();
+	assert_eq!((pool.status().ready, pool.status().future), (1, 1));
+
+	// the pool is journaled when dropped, the file is written once the writer task is run
+	drop(pool);
+	spawner.0.lock().drain(..).for_each(block_on);
+
+	let api = Arc::new(TestApi::with_alice_nonce(209));
+	let pool = journaled_pool(&path, api.clone(), TaskCollector::default());
+	assert_eq!((pool.status().ready, pool.status().future), (0, 0));
+
+	// the restored transactions are re-validated at the next block
+	let header = api.push_block(1, vec![], true);
+	block_on(pool.maintain(block_event(header)));
+	let mut restored: Vec<_> = pool
+		.pool()
+		.validated_pool()
+		.transactions()
+		.into_iter()
+		.map(|(source, xt)| (source, xt.transfer().nonce))
+		.collect();
+	restored.sort_by_key(|(_, nonce)| *nonce);
+	assert_eq!(restored, vec![(TransactionSource::Local, 209), (SOURCE, 211)]);
+}
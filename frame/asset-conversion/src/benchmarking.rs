@@ -0,0 +1,215 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Asset Conversion pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::{traits::tokens::fungible::Mutate as MutateFungible, BoundedVec};
+use frame_system::RawOrigin as SystemOrigin;
+use sp_std::convert::TryInto;
+
+use crate::Pallet as AssetConversion;
+
+/// `n` units of the native currency, where a unit is its existential deposit.
+fn units<T: Config>(n: u32) -> T::Balance {
+	T::Currency::minimum_balance().max(One::one()) * n.into()
+}
+
+fn create_asset<T: Config>(owner: &T::AccountId, id: u32) -> MultiAssetIdOf<T>
+where
+	T::Assets: Create<T::AccountId>,
+	T::AssetId: From<u32>,
+{
+	let id: T::AssetId = id.into();
+	assert!(T::Assets::create(id, owner.clone(), true, One::one()).is_ok());
+	assert!(T::Assets::mint_into(id, owner, units::<T>(1_000_000_000)).is_ok());
+	NativeOrAssetId::Asset(id)
+}
+
+fn create_funded_pool<T: Config>(
+	caller: &T::AccountId,
+	asset1: MultiAssetIdOf<T>,
+	asset2: MultiAssetIdOf<T>,
+) {
+	assert!(AssetConversion::<T>::create_pool(
+		SystemOrigin::Signed(caller.clone()).into(),
+		asset1,
+		asset2,
+	)
+	.is_ok());
+	assert!(AssetConversion::<T>::add_liquidity(
+		SystemOrigin::Signed(caller.clone()).into(),
+		asset1,
+		asset2,
+		units::<T>(1_000_000),
+		units::<T>(1_000_000),
+		Zero::zero(),
+		Zero::zero(),
+		caller.clone(),
+	)
+	.is_ok());
+}
+
+/// Fund the caller and set up a chain of pools along the longest possible swap path, starting
+/// with the native currency.
+fn create_swap_path<T: Config>(
+) -> (T::AccountId, BoundedVec<MultiAssetIdOf<T>, T::MaxSwapPathLength>)
+where
+	T::Currency: MutateFungible<T::AccountId>,
+	T::Assets: Create<T::AccountId>,
+	T::AssetId: From<u32>,
+{
+	let caller: T::AccountId = whitelisted_caller();
+	assert!(T::Currency::mint_into(&caller, units::<T>(1_000_000_000)).is_ok());
+
+	let mut path = vec![NativeOrAssetId::Native];
+	for id in 1..T::MaxSwapPathLength::get() {
+		let asset = create_asset::<T>(&caller, id);
+		create_funded_pool::<T>(&caller, path[path.len() - 1], asset);
+		path.push(asset);
+	}
+	(caller, path.try_into().unwrap())
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
+	let events = frame_system::Pallet::<T>::events();
+	let system_event: <T as frame_system::Config>::Event = generic_event.into();
+	// compare to the last event record
+	let frame_system::EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+benchmarks! {
+	where_clause {
+		where
+			T::Currency: MutateFungible<T::AccountId>,
+			T::Assets: Create<T::AccountId>,
+			T::AssetId: From<u32>,
+	}
+
+	create_pool {
+		let caller: T::AccountId = whitelisted_caller();
+		assert!(T::Currency::mint_into(&caller, T::PoolSetupFee::get() + units::<T>(1_000)).is_ok());
+		let asset = create_asset::<T>(&caller, 1);
+		let lp_token = NextPoolAssetId::<T>::get();
+	}: _(SystemOrigin::Signed(caller.clone()), NativeOrAssetId::Native, asset)
+	verify {
+		let pool_id = (NativeOrAssetId::Native, asset);
+		assert_last_event::<T>(Event::PoolCreated { creator: caller, pool_id, lp_token }.into());
+	}
+
+	add_liquidity {
+		let caller: T::AccountId = whitelisted_caller();
+		assert!(T::Currency::mint_into(&caller, units::<T>(1_000_000_000)).is_ok());
+		let asset = create_asset::<T>(&caller, 1);
+		assert!(AssetConversion::<T>::create_pool(
+			SystemOrigin::Signed(caller.clone()).into(),
+			NativeOrAssetId::Native,
+			asset,
+		)
+		.is_ok());
+		let amount = units::<T>(1_000_000);
+	}: _(
+		SystemOrigin::Signed(caller.clone()),
+		NativeOrAssetId::Native,
+		asset,
+		amount,
+		amount,
+		amount,
+		amount,
+		caller.clone()
+	)
+	verify {
+		let pool_id = (NativeOrAssetId::Native, asset);
+		let pool_account = AssetConversion::<T>::get_pool_account(&pool_id);
+		assert_eq!(T::Currency::balance(&pool_account), amount);
+		assert_eq!(T::Assets::balance(1u32.into(), &pool_account), amount);
+	}
+
+	remove_liquidity {
+		let caller: T::AccountId = whitelisted_caller();
+		assert!(T::Currency::mint_into(&caller, units::<T>(1_000_000_000)).is_ok());
+		let asset = create_asset::<T>(&caller, 1);
+		create_funded_pool::<T>(&caller, NativeOrAssetId::Native, asset);
+		let lp_token = Pools::<T>::get(&(NativeOrAssetId::Native, asset)).unwrap().lp_token;
+		let lp_balance = T::PoolAssets::balance(lp_token, &caller);
+		let burn = lp_balance / 2u32.into();
+	}: _(
+		SystemOrigin::Signed(caller.clone()),
+		NativeOrAssetId::Native,
+		asset,
+		burn,
+		One::one(),
+		One::one(),
+		caller.clone()
+	)
+	verify {
+		assert_eq!(T::PoolAssets::balance(lp_token, &caller), lp_balance - burn);
+	}
+
+	swap_exact_tokens_for_tokens {
+		let (caller, path) = create_swap_path::<T>();
+		let asset_out = path[path.len() - 1];
+		let balance_before = match asset_out {
+			NativeOrAssetId::Asset(id) => T::Assets::balance(id, &caller),
+			NativeOrAssetId::Native => T::Currency::balance(&caller),
+		};
+	}: _(
+		SystemOrigin::Signed(caller.clone()),
+		path,
+		units::<T>(1_000),
+		One::one(),
+		caller.clone(),
+		false
+	)
+	verify {
+		let balance_after = match asset_out {
+			NativeOrAssetId::Asset(id) => T::Assets::balance(id, &caller),
+			NativeOrAssetId::Native => T::Currency::balance(&caller),
+		};
+		assert!(balance_after > balance_before);
+	}
+
+	swap_tokens_for_exact_tokens {
+		let (caller, path) = create_swap_path::<T>();
+		let asset_out = path[path.len() - 1];
+		let balance_before = match asset_out {
+			NativeOrAssetId::Asset(id) => T::Assets::balance(id, &caller),
+			NativeOrAssetId::Native => T::Currency::balance(&caller),
+		};
+		let amount_out = units::<T>(100);
+	}: _(
+		SystemOrigin::Signed(caller.clone()),
+		path,
+		amount_out,
+		units::<T>(1_000),
+		caller.clone(),
+		false
+	)
+	verify {
+		let balance_after = match asset_out {
+			NativeOrAssetId::Asset(id) => T::Assets::balance(id, &caller),
+			NativeOrAssetId::Native => T::Currency::balance(&caller),
+		};
+		assert_eq!(balance_after, balance_before + amount_out);
+	}
+
+	impl_benchmark_test_suite!(AssetConversion, crate::mock::new_test_ext(), crate::mock::Test);
+}
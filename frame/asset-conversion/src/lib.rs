@@ -0,0 +1,833 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Asset Conversion Pallet
+//!
+//! A constant-product automated market maker in the style of Uniswap V2.
+//!
+//! Anyone can create a pool for a pair of assets, where an asset is either the native currency
+//! or one of the assets of [`Config::Assets`]. Liquidity providers deposit both assets into the
+//! pool in the ratio of its current reserves and receive liquidity provider (LP) tokens in
+//! return, which are minted through [`Config::PoolAssets`]. The LP tokens can later be burned to
+//! withdraw a proportional share of the reserves.
+//!
+//! Swaps move along a path of one or more pools. Each pool keeps `reserve_in * reserve_out`
+//! constant, less a fee of [`Config::LPFee`] per mille of the input amount which is left in the
+//! pool for the liquidity providers. Every liquidity and swap call takes a minimum or maximum
+//! amount so the caller can bound the slippage they are willing to accept.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! * `create_pool`: Create an empty pool for a pair of assets.
+//! * `add_liquidity`: Provide liquidity to a pool and receive LP tokens.
+//! * `remove_liquidity`: Burn LP tokens and withdraw the underlying assets.
+//! * `swap_exact_tokens_for_tokens`: Swap an exact input amount for as much output as possible.
+//! * `swap_tokens_for_exact_tokens`: Swap as little input as possible for an exact output amount.
+//!
+//! The [`AssetConversionApi`] runtime API quotes prices against the current reserves.
+//!
+//! ## Related Modules
+//!
+//! * [`System`](../frame_system/index.html)
+//! * [`Assets`](../pallet_assets/index.html)
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+mod types;
+pub use types::*;
+
+use codec::Codec;
+use frame_support::{
+	ensure,
+	traits::tokens::{
+		fungible::{Inspect as InspectFungible, Transfer as TransferFungible},
+		fungibles::{Create, Inspect, Mutate, Transfer},
+	},
+	transactional, PalletId,
+};
+use sp_core::U256;
+use sp_runtime::{
+	traits::{
+		AccountIdConversion, AtLeast32BitUnsigned, CheckedAdd, Hash, MaybeDisplay, One,
+		UniqueSaturatedInto, Zero,
+	},
+	ArithmeticError, DispatchError,
+};
+use sp_std::prelude::*;
+
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::generate_storage_info]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The native currency.
+		type Currency: InspectFungible<Self::AccountId, Balance = Self::Balance>
+			+ TransferFungible<Self::AccountId>;
+
+		/// The units in which balances of both the native currency and the assets are recorded.
+		type Balance: Member
+			+ Parameter
+			+ AtLeast32BitUnsigned
+			+ Default
+			+ Copy
+			+ MaybeSerializeDeserialize
+			+ MaxEncodedLen
+			+ TypeInfo;
+
+		/// Identifier for the assets that can be traded.
+		type AssetId: Member
+			+ Parameter
+			+ Copy
+			+ Ord
+			+ MaybeSerializeDeserialize
+			+ MaxEncodedLen
+			+ TypeInfo;
+
+		/// The assets that can be traded against each other and against the native currency.
+		type Assets: Inspect<Self::AccountId, AssetId = Self::AssetId, Balance = Self::Balance>
+			+ Mutate<Self::AccountId>
+			+ Transfer<Self::AccountId>;
+
+		/// Identifier for the liquidity provider tokens.
+		type PoolAssetId: Member
+			+ Parameter
+			+ AtLeast32BitUnsigned
+			+ Default
+			+ Copy
+			+ MaybeSerializeDeserialize
+			+ MaxEncodedLen
+			+ TypeInfo;
+
+		/// The registry in which the liquidity provider tokens are created. This should not be
+		/// the same registry as [`Config::Assets`], so that LP token ids cannot clash with the
+		/// ids of tradeable assets, and nobody else should be able to create assets in it. Ids
+		/// that are taken anyway are skipped when creating a pool.
+		type PoolAssets: Inspect<Self::AccountId, AssetId = Self::PoolAssetId, Balance = Self::Balance>
+			+ Create<Self::AccountId>
+			+ Mutate<Self::AccountId>;
+
+		/// The fee taken on every swap, in parts per thousand of the input amount.
+		#[pallet::constant]
+		type LPFee: Get<u32>;
+
+		/// The amount of LP tokens that is minted to the pool itself when liquidity is first
+		/// added. These can never be withdrawn, so a pool is never fully drained.
+		#[pallet::constant]
+		type MintMinLiquidity: Get<Self::Balance>;
+
+		/// The maximum number of assets in a swap path, i.e. one more than the number of pools
+		/// a single swap can go through.
+		#[pallet::constant]
+		type MaxSwapPathLength: Get<u32>;
+
+		/// The fee in the native currency charged for creating a pool.
+		///
+		/// Each pool permanently occupies storage: its entry in [`Pools`], its LP token in
+		/// [`Config::PoolAssets`] and the account holding its reserves.
+		#[pallet::constant]
+		type PoolSetupFee: Get<Self::Balance>;
+
+		/// The account the [`Config::PoolSetupFee`] is paid to.
+		type PoolSetupFeeReceiver: Get<Self::AccountId>;
+
+		/// The pallet's id, used for deriving the accounts of the pools.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The pools that exist, keyed by the ordered pair of assets they trade.
+	#[pallet::storage]
+	pub type Pools<T: Config> =
+		StorageMap<_, Blake2_128Concat, PoolIdOf<T>, PoolInfo<T::PoolAssetId>, OptionQuery>;
+
+	/// The id of the LP token to be created with the next pool.
+	#[pallet::storage]
+	pub type NextPoolAssetId<T: Config> = StorageValue<_, T::PoolAssetId, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A pool has been created.
+		PoolCreated {
+			/// The account that created the pool.
+			creator: T::AccountId,
+			/// The pair of assets traded by the pool.
+			pool_id: PoolIdOf<T>,
+			/// The id of the pool's liquidity provider token.
+			lp_token: T::PoolAssetId,
+		},
+		/// Liquidity has been added to a pool.
+		LiquidityAdded {
+			/// The account that provided the liquidity.
+			who: T::AccountId,
+			/// The account the LP tokens were minted to.
+			mint_to: T::AccountId,
+			/// The pool the liquidity was added to.
+			pool_id: PoolIdOf<T>,
+			/// The amount of the first asset of the pool that was deposited.
+			amount1_provided: T::Balance,
+			/// The amount of the second asset of the pool that was deposited.
+			amount2_provided: T::Balance,
+			/// The id of the pool's liquidity provider token.
+			lp_token: T::PoolAssetId,
+			/// The amount of LP tokens minted.
+			lp_token_minted: T::Balance,
+		},
+		/// Liquidity has been removed from a pool.
+		LiquidityRemoved {
+			/// The account that removed the liquidity.
+			who: T::AccountId,
+			/// The account the assets were sent to.
+			withdraw_to: T::AccountId,
+			/// The pool the liquidity was removed from.
+			pool_id: PoolIdOf<T>,
+			/// The amount of the first asset of the pool that was withdrawn.
+			amount1: T::Balance,
+			/// The amount of the second asset of the pool that was withdrawn.
+			amount2: T::Balance,
+			/// The id of the pool's liquidity provider token.
+			lp_token: T::PoolAssetId,
+			/// The amount of LP tokens burned.
+			lp_token_burned: T::Balance,
+		},
+		/// Assets have been swapped along a path of pools.
+		SwapExecuted {
+			/// The account that paid for the swap.
+			who: T::AccountId,
+			/// The account that received the output of the swap.
+			send_to: T::AccountId,
+			/// The assets the swap went through, starting with the input asset.
+			path: BoundedVec<MultiAssetIdOf<T>, T::MaxSwapPathLength>,
+			/// The amount of the input asset paid.
+			amount_in: T::Balance,
+			/// The amount of the output asset received.
+			amount_out: T::Balance,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Both assets of a pool must be different.
+		EqualAssets,
+		/// One of the assets of the pool does not exist in [`Config::Assets`].
+		AssetNotFound,
+		/// A pool for this pair of assets already exists.
+		PoolExists,
+		/// No pool exists for this pair of assets.
+		PoolNotFound,
+		/// The supplied amount must be greater than zero.
+		ZeroAmount,
+		/// The pool has no liquidity to trade against.
+		ZeroLiquidity,
+		/// The amount of the first asset to be deposited is below the supplied minimum.
+		AssetOneDepositDidNotMeetMinimum,
+		/// The amount of the second asset to be deposited is below the supplied minimum.
+		AssetTwoDepositDidNotMeetMinimum,
+		/// The amount of the first asset to be withdrawn is below the supplied minimum.
+		AssetOneWithdrawalDidNotMeetMinimum,
+		/// The amount of the second asset to be withdrawn is below the supplied minimum.
+		AssetTwoWithdrawalDidNotMeetMinimum,
+		/// The liquidity provided is too small to mint any LP tokens.
+		InsufficientLiquidityMinted,
+		/// The amount received from a swap would be below the supplied minimum.
+		ProvidedMinimumNotSufficientForSwap,
+		/// The amount to be paid for a swap would exceed the supplied maximum.
+		ProvidedMaximumNotSufficientForSwap,
+		/// The pool does not hold enough of the requested asset.
+		InsufficientLiquidity,
+		/// The swap path must contain at least two and at most `MaxSwapPathLength` assets, and
+		/// no asset may appear in it more than once.
+		InvalidPath,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create an empty pool for `asset1` and `asset2`.
+		///
+		/// The origin must be Signed and pays [`Config::PoolSetupFee`] to
+		/// [`Config::PoolSetupFeeReceiver`]. Both assets must either be the native currency or
+		/// exist in [`Config::Assets`]. A new LP token is created for the pool, with the pool's
+		/// account as its admin.
+		///
+		/// Emits `PoolCreated` event when successful.
+		#[pallet::weight(T::WeightInfo::create_pool())]
+		#[transactional]
+		pub fn create_pool(
+			origin: OriginFor<T>,
+			asset1: MultiAssetIdOf<T>,
+			asset2: MultiAssetIdOf<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(asset1 != asset2, Error::<T>::EqualAssets);
+			for asset in [asset1, asset2] {
+				if let NativeOrAssetId::Asset(id) = asset {
+					ensure!(T::Assets::asset_exists(id), Error::<T>::AssetNotFound);
+				}
+			}
+
+			let pool_id = Self::get_pool_id(asset1, asset2);
+			ensure!(!Pools::<T>::contains_key(&pool_id), Error::<T>::PoolExists);
+
+			let setup_fee = T::PoolSetupFee::get();
+			if !setup_fee.is_zero() {
+				T::Currency::transfer(&sender, &T::PoolSetupFeeReceiver::get(), setup_fee, false)?;
+			}
+
+			let pool_account = Self::get_pool_account(&pool_id);
+			// The pool account holds the reserves even if they are all assets that do not
+			// provide for their holder.
+			frame_system::Pallet::<T>::inc_providers(&pool_account);
+
+			let mut lp_token = NextPoolAssetId::<T>::get();
+			while T::PoolAssets::asset_exists(lp_token) {
+				lp_token = lp_token.checked_add(&One::one()).ok_or(ArithmeticError::Overflow)?;
+			}
+			let next_lp_token =
+				lp_token.checked_add(&One::one()).ok_or(ArithmeticError::Overflow)?;
+			T::PoolAssets::create(lp_token, pool_account, false, One::one())?;
+			NextPoolAssetId::<T>::put(next_lp_token);
+
+			Pools::<T>::insert(pool_id, PoolInfo { lp_token });
+			Self::deposit_event(Event::PoolCreated { creator: sender, pool_id, lp_token });
+			Ok(())
+		}
+
+		/// Provide liquidity to the pool of `asset1` and `asset2`.
+		///
+		/// The origin must be Signed. At most `amount1_desired` of `asset1` and
+		/// `amount2_desired` of `asset2` are deposited, in the ratio of the pool's current
+		/// reserves; the first deposit into an empty pool sets that ratio. The call fails if
+		/// less than `amount1_min` or `amount2_min` would be deposited, which bounds how far the
+		/// reserves may have moved since the caller looked at them. The LP tokens are minted to
+		/// `mint_to`.
+		///
+		/// Emits `LiquidityAdded` event when successful.
+		#[pallet::weight(T::WeightInfo::add_liquidity())]
+		#[transactional]
+		pub fn add_liquidity(
+			origin: OriginFor<T>,
+			asset1: MultiAssetIdOf<T>,
+			asset2: MultiAssetIdOf<T>,
+			amount1_desired: T::Balance,
+			amount2_desired: T::Balance,
+			amount1_min: T::Balance,
+			amount2_min: T::Balance,
+			mint_to: T::AccountId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let pool_id = Self::get_pool_id(asset1, asset2);
+			// Keep the amounts in the same order as the assets of the pool.
+			let (amount1_desired, amount2_desired, amount1_min, amount2_min) =
+				if pool_id.0 == asset1 {
+					(amount1_desired, amount2_desired, amount1_min, amount2_min)
+				} else {
+					(amount2_desired, amount1_desired, amount2_min, amount1_min)
+				};
+			ensure!(
+				!amount1_desired.is_zero() && !amount2_desired.is_zero(),
+				Error::<T>::ZeroAmount
+			);
+
+			let pool = Pools::<T>::get(&pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let pool_account = Self::get_pool_account(&pool_id);
+			let (asset1, asset2) = &pool_id;
+			let reserve1 = Self::get_balance(&pool_account, asset1);
+			let reserve2 = Self::get_balance(&pool_account, asset2);
+
+			let (amount1, amount2) = if reserve1.is_zero() || reserve2.is_zero() {
+				(amount1_desired, amount2_desired)
+			} else {
+				let amount2_optimal = Self::quote(&amount1_desired, &reserve1, &reserve2)?;
+				if amount2_optimal <= amount2_desired {
+					(amount1_desired, amount2_optimal)
+				} else {
+					let amount1_optimal = Self::quote(&amount2_desired, &reserve2, &reserve1)?;
+					(amount1_optimal.min(amount1_desired), amount2_desired)
+				}
+			};
+			ensure!(amount1 >= amount1_min, Error::<T>::AssetOneDepositDidNotMeetMinimum);
+			ensure!(amount2 >= amount2_min, Error::<T>::AssetTwoDepositDidNotMeetMinimum);
+
+			Self::transfer(asset1, &sender, &pool_account, amount1, true)?;
+			Self::transfer(asset2, &sender, &pool_account, amount2, true)?;
+
+			let total_supply = T::PoolAssets::total_issuance(pool.lp_token);
+			let lp_token_amount = if total_supply.is_zero() {
+				let liquidity = Self::calc_initial_liquidity(&amount1, &amount2)?;
+				let min_liquidity = T::MintMinLiquidity::get();
+				ensure!(liquidity > min_liquidity, Error::<T>::InsufficientLiquidityMinted);
+				T::PoolAssets::mint_into(pool.lp_token, &pool_account, min_liquidity)?;
+				liquidity - min_liquidity
+			} else {
+				let side1 = Self::mul_div(&amount1, &total_supply, &reserve1)?;
+				let side2 = Self::mul_div(&amount2, &total_supply, &reserve2)?;
+				side1.min(side2)
+			};
+			ensure!(!lp_token_amount.is_zero(), Error::<T>::InsufficientLiquidityMinted);
+			T::PoolAssets::mint_into(pool.lp_token, &mint_to, lp_token_amount)?;
+
+			Self::deposit_event(Event::LiquidityAdded {
+				who: sender,
+				mint_to,
+				pool_id,
+				amount1_provided: amount1,
+				amount2_provided: amount2,
+				lp_token: pool.lp_token,
+				lp_token_minted: lp_token_amount,
+			});
+			Ok(())
+		}
+
+		/// Burn `lp_token_burn` LP tokens of the pool of `asset1` and `asset2` and withdraw the
+		/// corresponding share of its reserves to `withdraw_to`.
+		///
+		/// The origin must be Signed and hold the LP tokens. The call fails if less than
+		/// `amount1_min_receive` of `asset1` or `amount2_min_receive` of `asset2` would be
+		/// withdrawn.
+		///
+		/// Emits `LiquidityRemoved` event when successful.
+		#[pallet::weight(T::WeightInfo::remove_liquidity())]
+		#[transactional]
+		pub fn remove_liquidity(
+			origin: OriginFor<T>,
+			asset1: MultiAssetIdOf<T>,
+			asset2: MultiAssetIdOf<T>,
+			lp_token_burn: T::Balance,
+			amount1_min_receive: T::Balance,
+			amount2_min_receive: T::Balance,
+			withdraw_to: T::AccountId,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let pool_id = Self::get_pool_id(asset1, asset2);
+			let (amount1_min_receive, amount2_min_receive) = if pool_id.0 == asset1 {
+				(amount1_min_receive, amount2_min_receive)
+			} else {
+				(amount2_min_receive, amount1_min_receive)
+			};
+			ensure!(!lp_token_burn.is_zero(), Error::<T>::ZeroAmount);
+
+			let pool = Pools::<T>::get(&pool_id).ok_or(Error::<T>::PoolNotFound)?;
+			let pool_account = Self::get_pool_account(&pool_id);
+			let (asset1, asset2) = &pool_id;
+			let reserve1 = Self::get_balance(&pool_account, asset1);
+			let reserve2 = Self::get_balance(&pool_account, asset2);
+
+			let total_supply = T::PoolAssets::total_issuance(pool.lp_token);
+			ensure!(!total_supply.is_zero(), Error::<T>::ZeroLiquidity);
+
+			let amount1 = Self::mul_div(&lp_token_burn, &reserve1, &total_supply)?;
+			let amount2 = Self::mul_div(&lp_token_burn, &reserve2, &total_supply)?;
+			ensure!(
+				!amount1.is_zero() && amount1 >= amount1_min_receive,
+				Error::<T>::AssetOneWithdrawalDidNotMeetMinimum
+			);
+			ensure!(
+				!amount2.is_zero() && amount2 >= amount2_min_receive,
+				Error::<T>::AssetTwoWithdrawalDidNotMeetMinimum
+			);
+
+			T::PoolAssets::burn_from(pool.lp_token, &sender, lp_token_burn)?;
+			Self::transfer(asset1, &pool_account, &withdraw_to, amount1, true)?;
+			Self::transfer(asset2, &pool_account, &withdraw_to, amount2, true)?;
+
+			Self::deposit_event(Event::LiquidityRemoved {
+				who: sender,
+				withdraw_to,
+				pool_id,
+				amount1,
+				amount2,
+				lp_token: pool.lp_token,
+				lp_token_burned: lp_token_burn,
+			});
+			Ok(())
+		}
+
+		/// Swap exactly `amount_in` of the first asset of `path` for as much as possible of the
+		/// last asset of `path`, going through the pool of each consecutive pair of assets.
+		///
+		/// The origin must be Signed. The call fails if less than `amount_out_min` would be
+		/// received. The output is sent to `send_to`. If `keep_alive` is set, the payment may
+		/// not reap the sender's account.
+		///
+		/// Emits `SwapExecuted` event when successful.
+		#[pallet::weight(T::WeightInfo::swap_exact_tokens_for_tokens())]
+		#[transactional]
+		pub fn swap_exact_tokens_for_tokens(
+			origin: OriginFor<T>,
+			path: BoundedVec<MultiAssetIdOf<T>, T::MaxSwapPathLength>,
+			amount_in: T::Balance,
+			amount_out_min: T::Balance,
+			send_to: T::AccountId,
+			keep_alive: bool,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(!amount_in.is_zero() && !amount_out_min.is_zero(), Error::<T>::ZeroAmount);
+			Self::validate_swap_path(&path)?;
+
+			let amounts = Self::get_amounts_out(&amount_in, &path)?;
+			let amount_out = *amounts.last().ok_or(Error::<T>::InvalidPath)?;
+			ensure!(amount_out >= amount_out_min, Error::<T>::ProvidedMinimumNotSufficientForSwap);
+
+			Self::do_swap(sender, &amounts, path, send_to, keep_alive)
+		}
+
+		/// Swap as little as possible of the first asset of `path` for exactly `amount_out` of
+		/// the last asset of `path`, going through the pool of each consecutive pair of assets.
+		///
+		/// The origin must be Signed. The call fails if more than `amount_in_max` would have to
+		/// be paid. The output is sent to `send_to`. If `keep_alive` is set, the payment may not
+		/// reap the sender's account.
+		///
+		/// Emits `SwapExecuted` event when successful.
+		#[pallet::weight(T::WeightInfo::swap_tokens_for_exact_tokens())]
+		#[transactional]
+		pub fn swap_tokens_for_exact_tokens(
+			origin: OriginFor<T>,
+			path: BoundedVec<MultiAssetIdOf<T>, T::MaxSwapPathLength>,
+			amount_out: T::Balance,
+			amount_in_max: T::Balance,
+			send_to: T::AccountId,
+			keep_alive: bool,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(!amount_out.is_zero() && !amount_in_max.is_zero(), Error::<T>::ZeroAmount);
+			Self::validate_swap_path(&path)?;
+
+			let amounts = Self::get_amounts_in(&amount_out, &path)?;
+			let amount_in = *amounts.first().ok_or(Error::<T>::InvalidPath)?;
+			ensure!(amount_in <= amount_in_max, Error::<T>::ProvidedMaximumNotSufficientForSwap);
+
+			Self::do_swap(sender, &amounts, path, send_to, keep_alive)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Move `amounts[0]` of `path[0]` from `sender` into the first pool, then each
+		/// `amounts[i + 1]` of `path[i + 1]` out of the `i`-th pool into the next one, or to
+		/// `send_to` for the last pool.
+		fn do_swap(
+			sender: T::AccountId,
+			amounts: &[T::Balance],
+			path: BoundedVec<MultiAssetIdOf<T>, T::MaxSwapPathLength>,
+			send_to: T::AccountId,
+			keep_alive: bool,
+		) -> DispatchResult {
+			let first_pool = Self::get_pool_account(&Self::get_pool_id(path[0], path[1]));
+			Self::transfer(&path[0], &sender, &first_pool, amounts[0], keep_alive)?;
+
+			let mut pool_account = first_pool;
+			for (i, pair) in path.windows(2).enumerate() {
+				let to = match path.get(i + 2) {
+					Some(next) => Self::get_pool_account(&Self::get_pool_id(pair[1], *next)),
+					None => send_to.clone(),
+				};
+				Self::transfer(&pair[1], &pool_account, &to, amounts[i + 1], true)?;
+				pool_account = to;
+			}
+
+			Self::deposit_event(Event::SwapExecuted {
+				who: sender,
+				send_to,
+				amount_in: amounts[0],
+				amount_out: amounts[amounts.len() - 1],
+				path,
+			});
+			Ok(())
+		}
+
+		/// Transfer `amount` of `asset` from `from` to `to`.
+		fn transfer(
+			asset: &MultiAssetIdOf<T>,
+			from: &T::AccountId,
+			to: &T::AccountId,
+			amount: T::Balance,
+			keep_alive: bool,
+		) -> Result<T::Balance, DispatchError> {
+			match asset {
+				NativeOrAssetId::Native => T::Currency::transfer(from, to, amount, keep_alive),
+				NativeOrAssetId::Asset(id) =>
+					T::Assets::transfer(*id, from, to, amount, keep_alive),
+			}
+		}
+
+		/// The balance of `asset` held by `owner`.
+		fn get_balance(owner: &T::AccountId, asset: &MultiAssetIdOf<T>) -> T::Balance {
+			match asset {
+				NativeOrAssetId::Native => T::Currency::balance(owner),
+				NativeOrAssetId::Asset(id) => T::Assets::balance(*id, owner),
+			}
+		}
+
+		/// Check that `path` is at least two assets long, doesn't visit any asset twice and that
+		/// all of its pools exist.
+		fn validate_swap_path(
+			path: &BoundedVec<MultiAssetIdOf<T>, T::MaxSwapPathLength>,
+		) -> DispatchResult {
+			ensure!(path.len() >= 2, Error::<T>::InvalidPath);
+			for (i, asset) in path.iter().enumerate() {
+				ensure!(!path[i + 1..].contains(asset), Error::<T>::InvalidPath);
+			}
+			for pair in path.windows(2) {
+				let pool_id = Self::get_pool_id(pair[0], pair[1]);
+				ensure!(Pools::<T>::contains_key(&pool_id), Error::<T>::PoolNotFound);
+			}
+			Ok(())
+		}
+
+		/// The id of the pool trading `asset1` and `asset2`, with the assets in ascending order.
+		pub fn get_pool_id(asset1: MultiAssetIdOf<T>, asset2: MultiAssetIdOf<T>) -> PoolIdOf<T> {
+			if asset1 <= asset2 {
+				(asset1, asset2)
+			} else {
+				(asset2, asset1)
+			}
+		}
+
+		/// The account holding the reserves of the pool `pool_id`.
+		pub fn get_pool_account(pool_id: &PoolIdOf<T>) -> T::AccountId {
+			T::PalletId::get().into_sub_account(T::Hashing::hash_of(pool_id))
+		}
+
+		/// The reserves of `asset1` and `asset2` in their pool, in that order.
+		pub fn get_reserves(
+			asset1: &MultiAssetIdOf<T>,
+			asset2: &MultiAssetIdOf<T>,
+		) -> Result<(T::Balance, T::Balance), Error<T>> {
+			let pool_id = Self::get_pool_id(*asset1, *asset2);
+			ensure!(Pools::<T>::contains_key(&pool_id), Error::<T>::PoolNotFound);
+			let pool_account = Self::get_pool_account(&pool_id);
+
+			let reserve1 = Self::get_balance(&pool_account, asset1);
+			let reserve2 = Self::get_balance(&pool_account, asset2);
+			ensure!(!reserve1.is_zero() && !reserve2.is_zero(), Error::<T>::ZeroLiquidity);
+			Ok((reserve1, reserve2))
+		}
+
+		/// The amounts of each asset of `path` that a swap of exactly `amount_in` moves.
+		pub fn get_amounts_out(
+			amount_in: &T::Balance,
+			path: &[MultiAssetIdOf<T>],
+		) -> Result<Vec<T::Balance>, DispatchError> {
+			let mut amounts = Vec::with_capacity(path.len());
+			amounts.push(*amount_in);
+			let mut amount = *amount_in;
+			for pair in path.windows(2) {
+				let (reserve_in, reserve_out) = Self::get_reserves(&pair[0], &pair[1])?;
+				amount = Self::get_amount_out(&amount, &reserve_in, &reserve_out)?;
+				amounts.push(amount);
+			}
+			Ok(amounts)
+		}
+
+		/// The amounts of each asset of `path` that a swap for exactly `amount_out` moves.
+		pub fn get_amounts_in(
+			amount_out: &T::Balance,
+			path: &[MultiAssetIdOf<T>],
+		) -> Result<Vec<T::Balance>, DispatchError> {
+			let mut amounts = Vec::with_capacity(path.len());
+			amounts.push(*amount_out);
+			let mut amount = *amount_out;
+			for pair in path.windows(2).rev() {
+				let (reserve_in, reserve_out) = Self::get_reserves(&pair[0], &pair[1])?;
+				amount = Self::get_amount_in(&amount, &reserve_in, &reserve_out)?;
+				amounts.push(amount);
+			}
+			amounts.reverse();
+			Ok(amounts)
+		}
+
+		/// The amount of `asset2` that `amount` of `asset1` buys from their pool, with the swap
+		/// fee deducted if `include_fee` is set. `None` if the pool does not exist or is empty.
+		pub fn quote_price_exact_tokens_for_tokens(
+			asset1: MultiAssetIdOf<T>,
+			asset2: MultiAssetIdOf<T>,
+			amount: T::Balance,
+			include_fee: bool,
+		) -> Option<T::Balance> {
+			let (reserve1, reserve2) = Self::get_reserves(&asset1, &asset2).ok()?;
+			if include_fee {
+				Self::get_amount_out(&amount, &reserve1, &reserve2).ok()
+			} else {
+				Self::quote(&amount, &reserve1, &reserve2).ok()
+			}
+		}
+
+		/// The amount of `asset1` needed to buy `amount` of `asset2` from their pool, with the
+		/// swap fee added if `include_fee` is set. `None` if the pool does not exist or is empty.
+		pub fn quote_price_tokens_for_exact_tokens(
+			asset1: MultiAssetIdOf<T>,
+			asset2: MultiAssetIdOf<T>,
+			amount: T::Balance,
+			include_fee: bool,
+		) -> Option<T::Balance> {
+			let (reserve1, reserve2) = Self::get_reserves(&asset1, &asset2).ok()?;
+			if include_fee {
+				Self::get_amount_in(&amount, &reserve1, &reserve2).ok()
+			} else {
+				Self::quote(&amount, &reserve2, &reserve1).ok()
+			}
+		}
+
+		/// The amount of the other asset that is worth `amount` at the given reserves.
+		pub fn quote(
+			amount: &T::Balance,
+			reserve1: &T::Balance,
+			reserve2: &T::Balance,
+		) -> Result<T::Balance, DispatchError> {
+			Self::mul_div(amount, reserve2, reserve1)
+		}
+
+		/// The amount of the output asset that is bought with `amount_in`, after the swap fee.
+		pub fn get_amount_out(
+			amount_in: &T::Balance,
+			reserve_in: &T::Balance,
+			reserve_out: &T::Balance,
+		) -> Result<T::Balance, DispatchError> {
+			ensure!(!reserve_in.is_zero() && !reserve_out.is_zero(), Error::<T>::ZeroLiquidity);
+
+			let amount_in_with_fee = Self::to_u256(amount_in)
+				.checked_mul(U256::from(1000u32.saturating_sub(T::LPFee::get())))
+				.ok_or(ArithmeticError::Overflow)?;
+			let numerator = amount_in_with_fee
+				.checked_mul(Self::to_u256(reserve_out))
+				.ok_or(ArithmeticError::Overflow)?;
+			let denominator = Self::to_u256(reserve_in)
+				.checked_mul(U256::from(1000u32))
+				.and_then(|r| r.checked_add(amount_in_with_fee))
+				.ok_or(ArithmeticError::Overflow)?;
+
+			Self::from_u256(numerator / denominator)
+		}
+
+		/// The amount of the input asset that buys `amount_out`, including the swap fee.
+		pub fn get_amount_in(
+			amount_out: &T::Balance,
+			reserve_in: &T::Balance,
+			reserve_out: &T::Balance,
+		) -> Result<T::Balance, DispatchError> {
+			ensure!(!reserve_in.is_zero() && !reserve_out.is_zero(), Error::<T>::ZeroLiquidity);
+			ensure!(amount_out < reserve_out, Error::<T>::InsufficientLiquidity);
+
+			let numerator = Self::to_u256(reserve_in)
+				.checked_mul(Self::to_u256(amount_out))
+				.and_then(|n| n.checked_mul(U256::from(1000u32)))
+				.ok_or(ArithmeticError::Overflow)?;
+			let denominator = Self::to_u256(&(*reserve_out - *amount_out))
+				.checked_mul(U256::from(1000u32.saturating_sub(T::LPFee::get())))
+				.ok_or(ArithmeticError::Overflow)?;
+			ensure!(!denominator.is_zero(), Error::<T>::ZeroLiquidity);
+
+			// Round up so the pool never loses out.
+			Self::from_u256(numerator / denominator + U256::one())
+		}
+
+		/// The LP tokens backing the first deposit into a pool: the geometric mean of the
+		/// deposited amounts.
+		fn calc_initial_liquidity(
+			amount1: &T::Balance,
+			amount2: &T::Balance,
+		) -> Result<T::Balance, DispatchError> {
+			let product = Self::to_u256(amount1)
+				.checked_mul(Self::to_u256(amount2))
+				.ok_or(ArithmeticError::Overflow)?;
+			Self::from_u256(product.integer_sqrt())
+		}
+
+		/// `a * b / c`, without overflowing in the intermediate product.
+		fn mul_div(
+			a: &T::Balance,
+			b: &T::Balance,
+			c: &T::Balance,
+		) -> Result<T::Balance, DispatchError> {
+			ensure!(!c.is_zero(), Error::<T>::ZeroLiquidity);
+			let product = Self::to_u256(a)
+				.checked_mul(Self::to_u256(b))
+				.ok_or(ArithmeticError::Overflow)?;
+			Self::from_u256(product / Self::to_u256(c))
+		}
+
+		fn to_u256(value: &T::Balance) -> U256 {
+			// Balances are at most 128 bits wide, so this never saturates.
+			let value: u128 = (*value).unique_saturated_into();
+			U256::from(value)
+		}
+
+		fn from_u256(value: U256) -> Result<T::Balance, DispatchError> {
+			let value: u128 = value.try_into().map_err(|_| ArithmeticError::Overflow)?;
+			value.try_into().map_err(|_| ArithmeticError::Overflow.into())
+		}
+	}
+}
+
+sp_api::decl_runtime_apis! {
+	/// Quotes prices against the current reserves of the asset conversion pools.
+	pub trait AssetConversionApi<Balance, AssetId> where
+		Balance: Codec + MaybeDisplay,
+		AssetId: Codec,
+	{
+		/// The amount of `asset2` that exactly `amount` of `asset1` would buy, with or without
+		/// the swap fee.
+		fn quote_price_exact_tokens_for_tokens(
+			asset1: AssetId,
+			asset2: AssetId,
+			amount: Balance,
+			include_fee: bool,
+		) -> Option<Balance>;
+
+		/// The amount of `asset1` needed to buy exactly `amount` of `asset2`, with or without
+		/// the swap fee.
+		fn quote_price_tokens_for_exact_tokens(
+			asset1: AssetId,
+			asset2: AssetId,
+			amount: Balance,
+			include_fee: bool,
+		) -> Option<Balance>;
+
+		/// The reserves of `asset1` and `asset2` in their pool, in that order.
+		fn get_reserves(asset1: AssetId, asset2: AssetId) -> Option<(Balance, Balance)>;
+	}
+}
@@ -0,0 +1,491 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for Asset Conversion pallet.
+
+use super::*;
+use crate::{mock::*, Error, Event};
+use frame_support::{assert_noop, assert_ok, BoundedVec};
+
+type NativeOrAssetIdOf = NativeOrAssetId<u32>;
+
+const NATIVE: NativeOrAssetIdOf = NativeOrAssetId::Native;
+
+fn asset(id: u32) -> NativeOrAssetIdOf {
+	NativeOrAssetId::Asset(id)
+}
+
+fn create_asset(id: u32, owner: u128, balance: u128) {
+	assert_ok!(Assets::force_create(Origin::root(), id, owner, true, 1));
+	assert_ok!(<Assets as Mutate<u128>>::mint_into(id, &owner, balance));
+}
+
+fn balance(who: u128, asset: NativeOrAssetIdOf) -> u128 {
+	match asset {
+		NativeOrAssetId::Native => Balances::free_balance(who),
+		NativeOrAssetId::Asset(id) => Assets::balance(id, who),
+	}
+}
+
+fn pool_balance(
+	asset1: NativeOrAssetIdOf,
+	asset2: NativeOrAssetIdOf,
+	of: NativeOrAssetIdOf,
+) -> u128 {
+	balance(AssetConversion::get_pool_account(&AssetConversion::get_pool_id(asset1, asset2)), of)
+}
+
+fn path(assets: Vec<NativeOrAssetIdOf>) -> BoundedVec<NativeOrAssetIdOf, MaxSwapPathLength> {
+	assets.try_into().unwrap()
+}
+
+/// Create a pool of native currency and `id`, with reserves of 10_000 and 1_000 provided by
+/// account 1.
+fn setup_pool(id: u32) {
+	create_asset(id, 1, 2_000);
+	assert_ok!(AssetConversion::create_pool(Origin::signed(1), NATIVE, asset(id)));
+	assert_ok!(AssetConversion::add_liquidity(
+		Origin::signed(1),
+		NATIVE,
+		asset(id),
+		10_000,
+		1_000,
+		10_000,
+		1_000,
+		1,
+	));
+}
+
+#[test]
+fn create_pool_works() {
+	new_test_ext().execute_with(|| {
+		create_asset(2, 1, 1_000);
+
+		assert_ok!(AssetConversion::create_pool(Origin::signed(1), asset(2), NATIVE));
+
+		let pool_id = (NATIVE, asset(2));
+		assert_eq!(Pools::<Test>::get(&pool_id), Some(PoolInfo { lp_token: 0 }));
+		assert_eq!(NextPoolAssetId::<Test>::get(), 1);
+		assert_eq!(<PoolAssets as Inspect<u128>>::minimum_balance(0), 1);
+		System::assert_last_event(Event::PoolCreated { creator: 1, pool_id, lp_token: 0 }.into());
+	});
+}
+
+#[test]
+fn create_pool_skips_taken_lp_token_ids() {
+	new_test_ext().execute_with(|| {
+		create_asset(2, 1, 1_000);
+		create_asset(3, 1, 1_000);
+		// someone else takes the id of the next LP token
+		assert_ok!(PoolAssets::create(Origin::signed(2), 0, 2, 1));
+
+		assert_ok!(AssetConversion::create_pool(Origin::signed(1), NATIVE, asset(2)));
+		assert_eq!(Pools::<Test>::get(&(NATIVE, asset(2))), Some(PoolInfo { lp_token: 1 }));
+		assert_eq!(NextPoolAssetId::<Test>::get(), 2);
+
+		assert_ok!(AssetConversion::create_pool(Origin::signed(1), NATIVE, asset(3)));
+		assert_eq!(Pools::<Test>::get(&(NATIVE, asset(3))), Some(PoolInfo { lp_token: 2 }));
+	});
+}
+
+#[test]
+fn create_pool_rejects_equal_or_existing_pairs() {
+	new_test_ext().execute_with(|| {
+		create_asset(2, 1, 1_000);
+
+		assert_noop!(
+			AssetConversion::create_pool(Origin::signed(1), asset(2), asset(2)),
+			Error::<Test>::EqualAssets
+		);
+		assert_ok!(AssetConversion::create_pool(Origin::signed(1), NATIVE, asset(2)));
+		assert_noop!(
+			AssetConversion::create_pool(Origin::signed(1), asset(2), NATIVE),
+			Error::<Test>::PoolExists
+		);
+	});
+}
+
+#[test]
+fn create_pool_rejects_unknown_assets() {
+	new_test_ext().execute_with(|| {
+		create_asset(2, 1, 1_000);
+
+		assert_noop!(
+			AssetConversion::create_pool(Origin::signed(1), NATIVE, asset(3)),
+			Error::<Test>::AssetNotFound
+		);
+		assert_noop!(
+			AssetConversion::create_pool(Origin::signed(1), asset(3), asset(2)),
+			Error::<Test>::AssetNotFound
+		);
+	});
+}
+
+#[test]
+fn create_pool_charges_setup_fee() {
+	new_test_ext().execute_with(|| {
+		create_asset(2, 1, 1_000);
+		let receiver = PoolSetupFeeReceiver::get();
+
+		assert_ok!(AssetConversion::create_pool(Origin::signed(1), NATIVE, asset(2)));
+		assert_eq!(balance(1, NATIVE), 100_000 - PoolSetupFee::get());
+		assert_eq!(balance(receiver, NATIVE), PoolSetupFee::get());
+
+		// account 4 cannot pay the fee, so no pool is created.
+		create_asset(3, 1, 1_000);
+		assert_noop!(
+			AssetConversion::create_pool(Origin::signed(4), NATIVE, asset(3)),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+		assert_eq!(Pools::<Test>::get(&(NATIVE, asset(3))), None);
+	});
+}
+
+#[test]
+fn add_liquidity_works() {
+	new_test_ext().execute_with(|| {
+		setup_pool(2);
+
+		// sqrt(10_000 * 1_000) = 3_162, of which `MintMinLiquidity` stays with the pool.
+		let pool_account = AssetConversion::get_pool_account(&(NATIVE, asset(2)));
+		assert_eq!(PoolAssets::balance(0, 1), 3_062);
+		assert_eq!(PoolAssets::balance(0, pool_account), 100);
+		assert_eq!(pool_balance(NATIVE, asset(2), NATIVE), 10_000);
+		assert_eq!(pool_balance(NATIVE, asset(2), asset(2)), 1_000);
+		System::assert_last_event(
+			Event::LiquidityAdded {
+				who: 1,
+				mint_to: 1,
+				pool_id: (NATIVE, asset(2)),
+				amount1_provided: 10_000,
+				amount2_provided: 1_000,
+				lp_token: 0,
+				lp_token_minted: 3_062,
+			}
+			.into(),
+		);
+
+		// Later deposits keep the ratio of the reserves, whichever order the assets are given in.
+		assert_ok!(<Assets as Mutate<u128>>::mint_into(2, &2, 200));
+		assert_ok!(AssetConversion::add_liquidity(
+			Origin::signed(2),
+			asset(2),
+			NATIVE,
+			100,
+			2_000,
+			100,
+			1_000,
+			2,
+		));
+		assert_eq!(balance(2, asset(2)), 100);
+		assert_eq!(balance(2, NATIVE), 9_000);
+		assert_eq!(PoolAssets::balance(0, 2), 316);
+	});
+}
+
+#[test]
+fn add_liquidity_respects_minimums() {
+	new_test_ext().execute_with(|| {
+		setup_pool(2);
+		assert_ok!(<Assets as Mutate<u128>>::mint_into(2, &2, 100));
+
+		// Only 1_000 of the native currency would be deposited for 100 of the asset.
+		assert_noop!(
+			AssetConversion::add_liquidity(
+				Origin::signed(2),
+				NATIVE,
+				asset(2),
+				2_000,
+				100,
+				1_500,
+				100,
+				2
+			),
+			Error::<Test>::AssetOneDepositDidNotMeetMinimum
+		);
+		assert_noop!(
+			AssetConversion::add_liquidity(Origin::signed(2), NATIVE, asset(2), 0, 100, 0, 100, 2),
+			Error::<Test>::ZeroAmount
+		);
+		assert_noop!(
+			AssetConversion::add_liquidity(Origin::signed(2), NATIVE, asset(3), 100, 100, 0, 0, 2),
+			Error::<Test>::PoolNotFound
+		);
+	});
+}
+
+#[test]
+fn first_deposit_must_exceed_min_liquidity() {
+	new_test_ext().execute_with(|| {
+		create_asset(2, 1, 1_000);
+		assert_ok!(AssetConversion::create_pool(Origin::signed(1), NATIVE, asset(2)));
+
+		// sqrt(100 * 100) = 100 is not more than `MintMinLiquidity`.
+		assert_noop!(
+			AssetConversion::add_liquidity(Origin::signed(1), NATIVE, asset(2), 100, 100, 0, 0, 1),
+			Error::<Test>::InsufficientLiquidityMinted
+		);
+	});
+}
+
+#[test]
+fn remove_liquidity_works() {
+	new_test_ext().execute_with(|| {
+		setup_pool(2);
+
+		assert_noop!(
+			AssetConversion::remove_liquidity(
+				Origin::signed(1),
+				NATIVE,
+				asset(2),
+				1_581,
+				0,
+				501,
+				3
+			),
+			Error::<Test>::AssetTwoWithdrawalDidNotMeetMinimum
+		);
+		assert_ok!(AssetConversion::remove_liquidity(
+			Origin::signed(1),
+			asset(2),
+			NATIVE,
+			1_581,
+			500,
+			5_000,
+			3,
+		));
+
+		assert_eq!(PoolAssets::balance(0, 1), 1_481);
+		assert_eq!(balance(3, NATIVE), 15_000);
+		assert_eq!(balance(3, asset(2)), 500);
+		assert_eq!(pool_balance(NATIVE, asset(2), NATIVE), 5_000);
+		assert_eq!(pool_balance(NATIVE, asset(2), asset(2)), 500);
+		System::assert_last_event(
+			Event::LiquidityRemoved {
+				who: 1,
+				withdraw_to: 3,
+				pool_id: (NATIVE, asset(2)),
+				amount1: 5_000,
+				amount2: 500,
+				lp_token: 0,
+				lp_token_burned: 1_581,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn swap_exact_tokens_for_tokens_works() {
+	new_test_ext().execute_with(|| {
+		setup_pool(2);
+
+		// 1_000 * 997 * 1_000 / (10_000 * 1_000 + 1_000 * 997) = 90.66
+		assert_noop!(
+			AssetConversion::swap_exact_tokens_for_tokens(
+				Origin::signed(2),
+				path(vec![NATIVE, asset(2)]),
+				1_000,
+				91,
+				2,
+				true,
+			),
+			Error::<Test>::ProvidedMinimumNotSufficientForSwap
+		);
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			Origin::signed(2),
+			path(vec![NATIVE, asset(2)]),
+			1_000,
+			90,
+			2,
+			true,
+		));
+
+		assert_eq!(balance(2, NATIVE), 9_000);
+		assert_eq!(balance(2, asset(2)), 90);
+		assert_eq!(pool_balance(NATIVE, asset(2), NATIVE), 11_000);
+		assert_eq!(pool_balance(NATIVE, asset(2), asset(2)), 910);
+		System::assert_last_event(
+			Event::SwapExecuted {
+				who: 2,
+				send_to: 2,
+				path: path(vec![NATIVE, asset(2)]),
+				amount_in: 1_000,
+				amount_out: 90,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn swap_tokens_for_exact_tokens_works() {
+	new_test_ext().execute_with(|| {
+		setup_pool(2);
+
+		// 10_000 * 90 * 1_000 / ((1_000 - 90) * 997) + 1 = 992.98
+		assert_noop!(
+			AssetConversion::swap_tokens_for_exact_tokens(
+				Origin::signed(2),
+				path(vec![NATIVE, asset(2)]),
+				90,
+				991,
+				2,
+				true,
+			),
+			Error::<Test>::ProvidedMaximumNotSufficientForSwap
+		);
+		assert_ok!(AssetConversion::swap_tokens_for_exact_tokens(
+			Origin::signed(2),
+			path(vec![NATIVE, asset(2)]),
+			90,
+			1_000,
+			2,
+			true,
+		));
+
+		assert_eq!(balance(2, NATIVE), 10_000 - 992);
+		assert_eq!(balance(2, asset(2)), 90);
+		assert_noop!(
+			AssetConversion::swap_tokens_for_exact_tokens(
+				Origin::signed(2),
+				path(vec![NATIVE, asset(2)]),
+				910,
+				10_000,
+				2,
+				true,
+			),
+			Error::<Test>::InsufficientLiquidity
+		);
+	});
+}
+
+#[test]
+fn swap_goes_through_multiple_pools() {
+	new_test_ext().execute_with(|| {
+		setup_pool(2);
+		setup_pool(3);
+		assert_ok!(<Assets as Mutate<u128>>::mint_into(2, &2, 100));
+
+		let route = path(vec![asset(2), NATIVE, asset(3)]);
+		assert_eq!(AssetConversion::get_amounts_out(&100, &route), Ok(vec![100, 906, 82]));
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			Origin::signed(2),
+			route,
+			100,
+			82,
+			3,
+			false,
+		));
+
+		assert_eq!(balance(2, asset(2)), 0);
+		assert_eq!(balance(3, asset(3)), 82);
+		assert_eq!(pool_balance(NATIVE, asset(2), NATIVE), 10_000 - 906);
+		assert_eq!(pool_balance(NATIVE, asset(3), NATIVE), 10_000 + 906);
+	});
+}
+
+#[test]
+fn swap_rejects_invalid_paths() {
+	new_test_ext().execute_with(|| {
+		setup_pool(2);
+
+		assert_noop!(
+			AssetConversion::swap_exact_tokens_for_tokens(
+				Origin::signed(2),
+				path(vec![NATIVE]),
+				100,
+				1,
+				2,
+				true,
+			),
+			Error::<Test>::InvalidPath
+		);
+		assert_noop!(
+			AssetConversion::swap_exact_tokens_for_tokens(
+				Origin::signed(2),
+				path(vec![NATIVE, NATIVE]),
+				100,
+				1,
+				2,
+				true,
+			),
+			Error::<Test>::InvalidPath
+		);
+		assert_noop!(
+			AssetConversion::swap_exact_tokens_for_tokens(
+				Origin::signed(2),
+				path(vec![NATIVE, asset(3)]),
+				100,
+				1,
+				2,
+				true,
+			),
+			Error::<Test>::PoolNotFound
+		);
+	});
+}
+
+#[test]
+fn swap_rejects_paths_revisiting_an_asset() {
+	new_test_ext().execute_with(|| {
+		setup_pool(2);
+		setup_pool(3);
+
+		for assets in [vec![NATIVE, asset(2), NATIVE], vec![asset(2), NATIVE, asset(3), NATIVE]] {
+			assert_noop!(
+				AssetConversion::swap_exact_tokens_for_tokens(
+					Origin::signed(2),
+					path(assets),
+					100,
+					1,
+					2,
+					true,
+				),
+				Error::<Test>::InvalidPath
+			);
+		}
+	});
+}
+
+#[test]
+fn quotes_match_swaps() {
+	new_test_ext().execute_with(|| {
+		setup_pool(2);
+
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(NATIVE, asset(2), 1_000, true),
+			Some(90)
+		);
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(NATIVE, asset(2), 1_000, false),
+			Some(100)
+		);
+		assert_eq!(
+			AssetConversion::quote_price_tokens_for_exact_tokens(NATIVE, asset(2), 90, true),
+			Some(992)
+		);
+		assert_eq!(
+			AssetConversion::quote_price_tokens_for_exact_tokens(NATIVE, asset(2), 90, false),
+			Some(900)
+		);
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(NATIVE, asset(3), 1_000, true),
+			None
+		);
+	});
+}
@@ -0,0 +1,63 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Various basic types for use in the asset conversion pallet.
+
+use super::*;
+use frame_support::pallet_prelude::*;
+
+/// An asset that can be traded through the pools: either the chain's native currency or one of
+/// the assets managed by [`Config::Assets`].
+#[derive(
+	Copy,
+	Clone,
+	Encode,
+	Decode,
+	Eq,
+	PartialEq,
+	Ord,
+	PartialOrd,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub enum NativeOrAssetId<AssetId> {
+	/// The native currency, e.g. DOT.
+	Native,
+	/// An asset, identified by its id within [`Config::Assets`].
+	Asset(AssetId),
+}
+
+impl<AssetId> NativeOrAssetId<AssetId> {
+	/// Whether this refers to the native currency.
+	pub fn is_native(&self) -> bool {
+		matches!(self, NativeOrAssetId::Native)
+	}
+}
+
+/// The asset identifier used by this pallet.
+pub type MultiAssetIdOf<T> = NativeOrAssetId<<T as Config>::AssetId>;
+
+/// A pool is identified by the pair of assets it trades, in ascending order.
+pub type PoolIdOf<T> = (MultiAssetIdOf<T>, MultiAssetIdOf<T>);
+
+/// Information stored for each pool.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct PoolInfo<PoolAssetId> {
+	/// The id of the liquidity provider token minted for this pool.
+	pub lp_token: PoolAssetId,
+}
@@ -0,0 +1,162 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_asset_conversion
+//!
+//! NOTE: hand-written estimates, based on the storage each call accesses and on the benchmarked
+//! weights of the `pallet_assets` calls it makes. No benchmark run produced these numbers; replace
+//! them with the output of the command below on reference hardware.
+
+// Command to regenerate:
+// ./target/production/substrate
+// benchmark
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=pallet_asset_conversion
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --output=./frame/asset-conversion/src/weights.rs
+// --template=./.maintain/frame-weight-template.hbs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_asset_conversion.
+pub trait WeightInfo {
+	fn create_pool() -> Weight;
+	fn add_liquidity() -> Weight;
+	fn remove_liquidity() -> Weight;
+	fn swap_exact_tokens_for_tokens() -> Weight;
+	fn swap_tokens_for_exact_tokens() -> Weight;
+}
+
+/// Weights for pallet_asset_conversion using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: Assets Asset (r:1 w:0)
+	// Storage: AssetConversion Pools (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: AssetConversion NextPoolAssetId (r:1 w:1)
+	// Storage: PoolAssets Asset (r:1 w:1)
+	fn create_pool() -> Weight {
+		(97_305_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: AssetConversion Pools (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: PoolAssets Asset (r:1 w:1)
+	// Storage: PoolAssets Account (r:2 w:2)
+	fn add_liquidity() -> Weight {
+		(126_902_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: AssetConversion Pools (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: PoolAssets Asset (r:1 w:1)
+	// Storage: PoolAssets Account (r:1 w:1)
+	fn remove_liquidity() -> Weight {
+		(115_733_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	// Storage: AssetConversion Pools (r:2 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	fn swap_exact_tokens_for_tokens() -> Weight {
+		(149_816_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: AssetConversion Pools (r:2 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	fn swap_tokens_for_exact_tokens() -> Weight {
+		(150_245_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	// Storage: Assets Asset (r:1 w:0)
+	// Storage: AssetConversion Pools (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: AssetConversion NextPoolAssetId (r:1 w:1)
+	// Storage: PoolAssets Asset (r:1 w:1)
+	fn create_pool() -> Weight {
+		(97_305_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// Storage: AssetConversion Pools (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: PoolAssets Asset (r:1 w:1)
+	// Storage: PoolAssets Account (r:2 w:2)
+	fn add_liquidity() -> Weight {
+		(126_902_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	// Storage: AssetConversion Pools (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Assets Asset (r:1 w:1)
+	// Storage: Assets Account (r:2 w:2)
+	// Storage: PoolAssets Asset (r:1 w:1)
+	// Storage: PoolAssets Account (r:1 w:1)
+	fn remove_liquidity() -> Weight {
+		(115_733_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	// Storage: AssetConversion Pools (r:2 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	fn swap_exact_tokens_for_tokens() -> Weight {
+		(149_816_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	// Storage: AssetConversion Pools (r:2 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Assets Asset (r:2 w:2)
+	// Storage: Assets Account (r:4 w:4)
+	fn swap_tokens_for_exact_tokens() -> Weight {
+		(150_245_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+}
@@ -45,34 +45,44 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	pub(super) fn new_account(
 		who: &T::AccountId,
 		d: &mut AssetDetails<T::Balance, T::AccountId, DepositBalanceOf<T, I>>,
-	) -> Result<bool, DispatchError> {
+		maybe_deposit: Option<DepositBalanceOf<T, I>>,
+	) -> Result<ExistenceReason<DepositBalanceOf<T, I>>, DispatchError> {
 		let accounts = d.accounts.checked_add(1).ok_or(ArithmeticError::Overflow)?;
-		let is_sufficient = if d.is_sufficient {
+		let reason = if let Some(deposit) = maybe_deposit {
+			ExistenceReason::DepositHeld(deposit)
+		} else if d.is_sufficient {
 			frame_system::Pallet::<T>::inc_sufficients(who);
 			d.sufficients += 1;
-			true
+			ExistenceReason::Sufficient
 		} else {
 			frame_system::Pallet::<T>::inc_consumers(who).map_err(|_| Error::<T, I>::NoProvider)?;
-			false
+			ExistenceReason::Consumer
 		};
 		d.accounts = accounts;
-		Ok(is_sufficient)
+		Ok(reason)
 	}
 
+	/// Undo the references placed by `new_account` once the balance of `who` dropped below the
+	/// minimum. An account held by a deposit is kept around with a zero balance until the deposit
+	/// is taken.
 	pub(super) fn dead_account(
 		what: T::AssetId,
 		who: &T::AccountId,
 		d: &mut AssetDetails<T::Balance, T::AccountId, DepositBalanceOf<T, I>>,
-		sufficient: bool,
-	) {
-		if sufficient {
-			d.sufficients = d.sufficients.saturating_sub(1);
-			frame_system::Pallet::<T>::dec_sufficients(who);
-		} else {
-			frame_system::Pallet::<T>::dec_consumers(who);
+		reason: &ExistenceReason<DepositBalanceOf<T, I>>,
+	) -> DeadConsequence {
+		match *reason {
+			ExistenceReason::Consumer => frame_system::Pallet::<T>::dec_consumers(who),
+			ExistenceReason::Sufficient => {
+				d.sufficients = d.sufficients.saturating_sub(1);
+				frame_system::Pallet::<T>::dec_sufficients(who);
+			},
+			ExistenceReason::DepositRefunded => {},
+			ExistenceReason::DepositHeld(_) => return DeadConsequence::Keep,
 		}
 		d.accounts = d.accounts.saturating_sub(1);
-		T::Freezer::died(what, who)
+		T::Freezer::died(what, who);
+		DeadConsequence::Remove
 	}
 
 	pub(super) fn can_increase(
@@ -84,17 +94,26 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			Some(details) => details,
 			None => return DepositConsequence::UnknownAsset,
 		};
-		if details.supply.checked_add(&amount).is_none() {
-			return DepositConsequence::Overflow;
+		if details.status == AssetStatus::Destroying {
+			return DepositConsequence::UnknownAsset;
 		}
-		let account = Account::<T, I>::get(id, who);
-		if account.balance.checked_add(&amount).is_none() {
+		if details.supply.checked_add(&amount).is_none() {
 			return DepositConsequence::Overflow;
 		}
-		if account.balance.is_zero() {
-			if amount < details.min_balance {
+		let (balance, exists) = match Account::<T, I>::try_get(id, who) {
+			Ok(account) => (account.balance, true),
+			Err(()) => (Zero::zero(), false),
+		};
+		match balance.checked_add(&amount) {
+			None => return DepositConsequence::Overflow,
+			// An account held by a deposit may have a zero balance, but credits to it must still
+			// reach the minimum.
+			Some(new_balance) if new_balance < details.min_balance => {
 				return DepositConsequence::BelowMinimum;
-			}
+			},
+			Some(_) => {},
+		}
+		if !exists {
 			if !details.is_sufficient && frame_system::Pallet::<T>::providers(who) == 0 {
 				return DepositConsequence::CannotCreate;
 			}
@@ -121,9 +140,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		if details.supply.checked_sub(&amount).is_none() {
 			return Underflow;
 		}
-		if details.is_frozen {
+		if details.status == AssetStatus::Frozen {
 			return Frozen;
 		}
+		if details.status == AssetStatus::Destroying {
+			return UnknownAsset;
+		}
 		let account = Account::<T, I>::get(id, who);
 		if account.is_frozen {
 			return Frozen;
@@ -163,7 +185,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		keep_alive: bool,
 	) -> Result<T::Balance, DispatchError> {
 		let details = Asset::<T, I>::get(id).ok_or_else(|| Error::<T, I>::Unknown)?;
-		ensure!(!details.is_frozen, Error::<T, I>::Frozen);
+		ensure!(details.status != AssetStatus::Frozen, Error::<T, I>::Frozen);
+		ensure!(details.status != AssetStatus::Destroying, Error::<T, I>::AssetNotLive);
 
 		let account = Account::<T, I>::get(id, who);
 		ensure!(!account.is_frozen, Error::<T, I>::Frozen);
@@ -307,15 +330,25 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 			check(details)?;
 
-			Account::<T, I>::try_mutate(id, beneficiary, |t| -> DispatchResult {
-				let new_balance = t.balance.saturating_add(amount);
-				ensure!(new_balance >= details.min_balance, TokenError::BelowMinimum);
-				if t.balance.is_zero() {
-					t.sufficient = Self::new_account(beneficiary, details)?;
-				}
-				t.balance = new_balance;
-				Ok(())
-			})?;
+			Account::<T, I>::try_mutate_exists(
+				id,
+				beneficiary,
+				|maybe_account| -> DispatchResult {
+					let balance = maybe_account.as_ref().map_or_else(Zero::zero, |a| a.balance);
+					let new_balance = balance.saturating_add(amount);
+					ensure!(new_balance >= details.min_balance, TokenError::BelowMinimum);
+					let mut account = match maybe_account.take() {
+						Some(account) => account,
+						None => AssetBalance {
+							reason: Self::new_account(beneficiary, details, None)?,
+							..Default::default()
+						},
+					};
+					account.balance = new_balance;
+					*maybe_account = Some(account);
+					Ok(())
+				},
+			)?;
 			Ok(())
 		})?;
 		Ok(())
@@ -387,8 +420,10 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				account.balance = account.balance.saturating_sub(actual);
 				*maybe_account = if account.balance < details.min_balance {
 					debug_assert!(account.balance.is_zero(), "checked in prep; qed");
-					Self::dead_account(id, target, details, account.sufficient);
-					None
+					match Self::dead_account(id, target, details, &account.reason) {
+						DeadConsequence::Remove => None,
+						DeadConsequence::Keep => Some(account),
+					}
 				} else {
 					Some(account)
 				};
@@ -459,25 +494,30 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			debug_assert!(source_account.balance >= debit, "checked in prep; qed");
 			source_account.balance = source_account.balance.saturating_sub(debit);
 
-			Account::<T, I>::try_mutate(id, &dest, |a| -> DispatchResult {
-				// Calculate new balance; this will not saturate since it's already checked in prep.
-				debug_assert!(a.balance.checked_add(&credit).is_some(), "checked in prep; qed");
-				let new_balance = a.balance.saturating_add(credit);
-
+			Account::<T, I>::try_mutate_exists(id, &dest, |maybe_account| -> DispatchResult {
 				// Create a new account if there wasn't one already.
-				if a.balance.is_zero() {
-					a.sufficient = Self::new_account(&dest, details)?;
-				}
+				let mut a = match maybe_account.take() {
+					Some(a) => a,
+					None => AssetBalance {
+						reason: Self::new_account(&dest, details, None)?,
+						..Default::default()
+					},
+				};
 
-				a.balance = new_balance;
+				// Calculate new balance; this will not saturate since it's already checked in prep.
+				debug_assert!(a.balance.checked_add(&credit).is_some(), "checked in prep; qed");
+				a.balance = a.balance.saturating_add(credit);
+				*maybe_account = Some(a);
 				Ok(())
 			})?;
 
 			// Remove source account if it's now dead.
 			if source_account.balance < details.min_balance {
 				debug_assert!(source_account.balance.is_zero(), "checked in prep; qed");
-				Self::dead_account(id, &source, details, source_account.sufficient);
-				Account::<T, I>::remove(id, &source);
+				match Self::dead_account(id, &source, details, &source_account.reason) {
+					DeadConsequence::Remove => Account::<T, I>::remove(id, &source),
+					DeadConsequence::Keep => Account::<T, I>::insert(id, &source, &source_account),
+				}
 			} else {
 				Account::<T, I>::insert(id, &source, &source_account)
 			}
@@ -525,38 +565,118 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				accounts: 0,
 				sufficients: 0,
 				approvals: 0,
-				is_frozen: false,
+				status: AssetStatus::Live,
 			},
 		);
 		Self::deposit_event(Event::ForceCreated { asset_id: id, owner });
 		Ok(())
 	}
 
-	/// Destroy an existing asset.
+	/// Start the process of destroying an asset, by setting the asset status to `Destroying`.
+	///
+	/// Once started, transfers, mints and all other operations on the asset are disallowed and
+	/// its accounts and approvals may be removed with `do_destroy_accounts` and
+	/// `do_destroy_approvals` before finishing with `do_finish_destroy`.
 	///
 	/// * `id`: The asset you want to destroy.
-	/// * `witness`: Witness data needed about the current state of the asset, used to confirm
-	///   complexity of the operation.
 	/// * `maybe_check_owner`: An optional check before destroying the asset, if the provided
 	///   account is the owner of that asset. Can be used for authorization checks.
-	pub(super) fn do_destroy(
+	pub(super) fn do_start_destroy(
 		id: T::AssetId,
-		witness: DestroyWitness,
 		maybe_check_owner: Option<T::AccountId>,
-	) -> Result<DestroyWitness, DispatchError> {
-		Asset::<T, I>::try_mutate_exists(id, |maybe_details| {
-			let mut details = maybe_details.take().ok_or(Error::<T, I>::Unknown)?;
+	) -> DispatchResult {
+		Asset::<T, I>::try_mutate_exists(id, |maybe_details| -> DispatchResult {
+			let details = maybe_details.as_mut().ok_or(Error::<T, I>::Unknown)?;
 			if let Some(check_owner) = maybe_check_owner {
 				ensure!(details.owner == check_owner, Error::<T, I>::NoPermission);
 			}
-			ensure!(details.accounts <= witness.accounts, Error::<T, I>::BadWitness);
-			ensure!(details.sufficients <= witness.sufficients, Error::<T, I>::BadWitness);
-			ensure!(details.approvals <= witness.approvals, Error::<T, I>::BadWitness);
+			ensure!(details.status != AssetStatus::Destroying, Error::<T, I>::IncorrectStatus);
+			details.status = AssetStatus::Destroying;
+
+			Self::deposit_event(Event::DestructionStarted { asset_id: id });
+			Ok(())
+		})
+	}
 
-			for (who, v) in Account::<T, I>::drain_prefix(id) {
-				Self::dead_account(id, &who, &mut details, v.sufficient);
+	/// Destroy up to `max_items` accounts of an asset which is being destroyed, returning any
+	/// deposits held for them.
+	///
+	/// Returns the number of accounts that were removed.
+	pub(super) fn do_destroy_accounts(
+		id: T::AssetId,
+		max_items: u32,
+	) -> Result<u32, DispatchError> {
+		let mut removed_accounts = 0;
+		let mut remaining_accounts = 0;
+		Asset::<T, I>::try_mutate_exists(id, |maybe_details| -> DispatchResult {
+			let details = maybe_details.as_mut().ok_or(Error::<T, I>::Unknown)?;
+			ensure!(details.status == AssetStatus::Destroying, Error::<T, I>::IncorrectStatus);
+
+			for (who, mut v) in Account::<T, I>::drain_prefix(id) {
+				if let Some(deposit) = v.reason.take_deposit() {
+					T::Currency::unreserve(&who, deposit);
+				}
+				let consequence = Self::dead_account(id, &who, details, &v.reason);
+				debug_assert_eq!(consequence, DeadConsequence::Remove);
+				removed_accounts += 1;
+				if removed_accounts >= max_items {
+					break
+				}
 			}
-			debug_assert_eq!(details.accounts, 0);
+			remaining_accounts = details.accounts;
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::AccountsDestroyed {
+			asset_id: id,
+			accounts_destroyed: removed_accounts,
+			accounts_remaining: remaining_accounts,
+		});
+		Ok(removed_accounts)
+	}
+
+	/// Destroy up to `max_items` approvals of an asset which is being destroyed, returning the
+	/// deposits held for them.
+	///
+	/// Returns the number of approvals that were removed.
+	pub(super) fn do_destroy_approvals(
+		id: T::AssetId,
+		max_items: u32,
+	) -> Result<u32, DispatchError> {
+		let mut removed_approvals = 0;
+		let mut remaining_approvals = 0;
+		Asset::<T, I>::try_mutate_exists(id, |maybe_details| -> DispatchResult {
+			let details = maybe_details.as_mut().ok_or(Error::<T, I>::Unknown)?;
+			ensure!(details.status == AssetStatus::Destroying, Error::<T, I>::IncorrectStatus);
+
+			for ((owner, _), approval) in Approvals::<T, I>::drain_prefix((&id,)) {
+				T::Currency::unreserve(&owner, approval.deposit);
+				details.approvals.saturating_dec();
+				removed_approvals += 1;
+				if removed_approvals >= max_items {
+					break
+				}
+			}
+			remaining_approvals = details.approvals;
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::ApprovalsDestroyed {
+			asset_id: id,
+			approvals_destroyed: removed_approvals,
+			approvals_remaining: remaining_approvals,
+		});
+		Ok(removed_approvals)
+	}
+
+	/// Complete the destruction of an asset once all of its accounts and approvals are gone,
+	/// removing its metadata and returning the deposits held by the owner.
+	pub(super) fn do_finish_destroy(id: T::AssetId) -> DispatchResult {
+		Asset::<T, I>::try_mutate_exists(id, |maybe_details| -> DispatchResult {
+			let details = maybe_details.take().ok_or(Error::<T, I>::Unknown)?;
+			ensure!(details.status == AssetStatus::Destroying, Error::<T, I>::IncorrectStatus);
+			ensure!(details.accounts == 0, Error::<T, I>::InUse);
+			ensure!(details.approvals == 0, Error::<T, I>::InUse);
 			debug_assert_eq!(details.sufficients, 0);
 
 			let metadata = Metadata::<T, I>::take(&id);
@@ -564,20 +684,67 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				&details.owner,
 				details.deposit.saturating_add(metadata.deposit),
 			);
-
-			for ((owner, _), approval) in Approvals::<T, I>::drain_prefix((&id,)) {
-				T::Currency::unreserve(&owner, approval.deposit);
-			}
 			Self::deposit_event(Event::Destroyed { asset_id: id });
-
-			Ok(DestroyWitness {
-				accounts: details.accounts,
-				sufficients: details.sufficients,
-				approvals: details.approvals,
-			})
+			Ok(())
 		})
 	}
 
+	/// Create an account of asset `id` for `who`, kept around by a deposit reserved from `who`
+	/// rather than by a reference, so that it may hold a zero balance.
+	pub(super) fn do_touch(id: T::AssetId, who: T::AccountId) -> DispatchResult {
+		ensure!(!Account::<T, I>::contains_key(id, &who), Error::<T, I>::AlreadyExists);
+		let deposit = T::AssetAccountDeposit::get();
+		let mut details = Asset::<T, I>::get(&id).ok_or(Error::<T, I>::Unknown)?;
+		ensure!(details.status == AssetStatus::Live, Error::<T, I>::AssetNotLive);
+		let reason = Self::new_account(&who, &mut details, Some(deposit))?;
+		T::Currency::reserve(&who, deposit)?;
+		Asset::<T, I>::insert(&id, details);
+		Account::<T, I>::insert(
+			id,
+			&who,
+			AssetBalance {
+				balance: Zero::zero(),
+				is_frozen: false,
+				reason,
+				extra: T::Extra::default(),
+			},
+		);
+		Self::deposit_event(Event::Touched { asset_id: id, who, deposit });
+		Ok(())
+	}
+
+	/// Return the deposit held for the account of asset `id` of `who` and remove the account.
+	///
+	/// Any balance left in the account is burned if `allow_burn` is set; otherwise the refund
+	/// fails if the balance is not zero.
+	pub(super) fn do_refund(id: T::AssetId, who: T::AccountId, allow_burn: bool) -> DispatchResult {
+		let mut account =
+			Account::<T, I>::try_get(id, &who).map_err(|_| Error::<T, I>::NoDeposit)?;
+		let deposit = account.reason.take_deposit().ok_or(Error::<T, I>::NoDeposit)?;
+		let mut details = Asset::<T, I>::get(&id).ok_or(Error::<T, I>::Unknown)?;
+
+		ensure!(account.balance.is_zero() || allow_burn, Error::<T, I>::WouldBurn);
+		ensure!(details.status == AssetStatus::Live, Error::<T, I>::AssetNotLive);
+		ensure!(!account.is_frozen, Error::<T, I>::Frozen);
+
+		T::Currency::unreserve(&who, deposit);
+		let consequence = Self::dead_account(id, &who, &mut details, &account.reason);
+		debug_assert_eq!(consequence, DeadConsequence::Remove);
+		details.supply = details.supply.saturating_sub(account.balance);
+		Account::<T, I>::remove(id, &who);
+		Asset::<T, I>::insert(&id, details);
+
+		if !account.balance.is_zero() {
+			Self::deposit_event(Event::Burned {
+				asset_id: id,
+				owner: who.clone(),
+				balance: account.balance,
+			});
+		}
+		Self::deposit_event(Event::Refunded { asset_id: id, who, deposit });
+		Ok(())
+	}
+
 	/// Creates an approval from `owner` to spend `amount` of asset `id` tokens by 'delegate'
 	/// while reserving `T::ApprovalDeposit` from owner
 	///
@@ -589,7 +756,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		amount: T::Balance,
 	) -> DispatchResult {
 		let mut d = Asset::<T, I>::get(id).ok_or(Error::<T, I>::Unknown)?;
-		ensure!(!d.is_frozen, Error::<T, I>::Frozen);
+		ensure!(d.status == AssetStatus::Live, Error::<T, I>::AssetNotLive);
 		Approvals::<T, I>::try_mutate(
 			(id, &owner, &delegate),
 			|maybe_approved| -> DispatchResult {
@@ -678,6 +845,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			symbol.clone().try_into().map_err(|_| Error::<T, I>::BadMetadata)?;
 
 		let d = Asset::<T, I>::get(id).ok_or(Error::<T, I>::Unknown)?;
+		ensure!(d.status == AssetStatus::Live, Error::<T, I>::AssetNotLive);
 		ensure!(from == &d.owner, Error::<T, I>::NoPermission);
 
 		Metadata::<T, I>::try_mutate_exists(id, |metadata| {
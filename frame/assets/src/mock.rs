@@ -89,8 +89,10 @@ impl pallet_balances::Config for Test {
 
 parameter_types! {
 	pub const AssetDeposit: u64 = 1;
+	pub const AssetAccountDeposit: u64 = 10;
 	pub const ApprovalDeposit: u64 = 1;
 	pub const StringLimit: u32 = 50;
+	pub const RemoveItemsLimit: u32 = 5;
 	pub const MetadataDepositBase: u64 = 1;
 	pub const MetadataDepositPerByte: u64 = 1;
 }
@@ -104,8 +106,10 @@ impl Config for Test {
 	type AssetDeposit = AssetDeposit;
 	type MetadataDepositBase = MetadataDepositBase;
 	type MetadataDepositPerByte = MetadataDepositPerByte;
+	type AssetAccountDeposit = AssetAccountDeposit;
 	type ApprovalDeposit = ApprovalDeposit;
 	type StringLimit = StringLimit;
+	type RemoveItemsLimit = RemoveItemsLimit;
 	type Freezer = TestFreezer;
 	type WeightInfo = ();
 	type Extra = ();
@@ -19,7 +19,10 @@
 
 use super::*;
 use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok, traits::Currency};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{fungibles::Inspect, Currency},
+};
 use pallet_balances::Error as BalancesError;
 use sp_runtime::{traits::ConvertInto, TokenError};
 
@@ -191,8 +194,10 @@ fn lifecycle_should_work() {
 		assert_ok!(Assets::mint(Origin::signed(1), 0, 20, 100));
 		assert_eq!(Account::<Test>::iter_prefix(0).count(), 2);
 
-		let w = Asset::<Test>::get(0).unwrap().destroy_witness();
-		assert_ok!(Assets::destroy(Origin::signed(1), 0, w));
+		assert_ok!(Assets::start_destroy(Origin::signed(1), 0));
+		assert_ok!(Assets::destroy_accounts(Origin::signed(1), 0));
+		assert_ok!(Assets::destroy_approvals(Origin::signed(1), 0));
+		assert_ok!(Assets::finish_destroy(Origin::signed(1), 0));
 		assert_eq!(Balances::reserved_balance(&1), 0);
 
 		assert!(!Asset::<Test>::contains_key(0));
@@ -211,8 +216,10 @@ fn lifecycle_should_work() {
 		assert_ok!(Assets::mint(Origin::signed(1), 0, 20, 100));
 		assert_eq!(Account::<Test>::iter_prefix(0).count(), 2);
 
-		let w = Asset::<Test>::get(0).unwrap().destroy_witness();
-		assert_ok!(Assets::destroy(Origin::root(), 0, w));
+		assert_ok!(Assets::start_destroy(Origin::root(), 0));
+		assert_ok!(Assets::destroy_accounts(Origin::signed(1), 0));
+		assert_ok!(Assets::destroy_approvals(Origin::signed(1), 0));
+		assert_ok!(Assets::finish_destroy(Origin::signed(1), 0));
 		assert_eq!(Balances::reserved_balance(&1), 0);
 
 		assert!(!Asset::<Test>::contains_key(0));
@@ -222,18 +229,74 @@ fn lifecycle_should_work() {
 }
 
 #[test]
-fn destroy_with_bad_witness_should_not_work() {
+fn destroy_should_remove_items_in_batches() {
 	new_test_ext().execute_with(|| {
 		Balances::make_free_balance_be(&1, 100);
 		assert_ok!(Assets::force_create(Origin::root(), 0, 1, true, 1));
-		let mut w = Asset::<Test>::get(0).unwrap().destroy_witness();
-		assert_ok!(Assets::mint(Origin::signed(1), 0, 10, 100));
-		// witness too low
-		assert_noop!(Assets::destroy(Origin::signed(1), 0, w), Error::<Test>::BadWitness);
-		// witness too high is okay though
-		w.accounts += 2;
-		w.sufficients += 2;
-		assert_ok!(Assets::destroy(Origin::signed(1), 0, w));
+		for who in 10..17 {
+			assert_ok!(Assets::mint(Origin::signed(1), 0, who, 100));
+		}
+		assert_eq!(Asset::<Test>::get(0).unwrap().accounts, 7);
+
+		// Items can only be removed once the destruction has started.
+		assert_noop!(
+			Assets::destroy_accounts(Origin::signed(1), 0),
+			Error::<Test>::IncorrectStatus
+		);
+		assert_noop!(Assets::finish_destroy(Origin::signed(1), 0), Error::<Test>::IncorrectStatus);
+
+		assert_ok!(Assets::start_destroy(Origin::signed(1), 0));
+		System::assert_last_event(mock::Event::Assets(crate::Event::DestructionStarted {
+			asset_id: 0,
+		}));
+		assert_noop!(Assets::start_destroy(Origin::signed(1), 0), Error::<Test>::IncorrectStatus);
+
+		// `RemoveItemsLimit` is 5 in the mock, so the accounts go in two batches.
+		assert_ok!(Assets::destroy_accounts(Origin::signed(2), 0));
+		System::assert_last_event(mock::Event::Assets(crate::Event::AccountsDestroyed {
+			asset_id: 0,
+			accounts_destroyed: 5,
+			accounts_remaining: 2,
+		}));
+		assert_noop!(Assets::finish_destroy(Origin::signed(2), 0), Error::<Test>::InUse);
+
+		assert_ok!(Assets::destroy_accounts(Origin::signed(2), 0));
+		System::assert_last_event(mock::Event::Assets(crate::Event::AccountsDestroyed {
+			asset_id: 0,
+			accounts_destroyed: 2,
+			accounts_remaining: 0,
+		}));
+		assert_eq!(Account::<Test>::iter_prefix(0).count(), 0);
+
+		assert_ok!(Assets::finish_destroy(Origin::signed(2), 0));
+		System::assert_last_event(mock::Event::Assets(crate::Event::Destroyed { asset_id: 0 }));
+		assert!(!Asset::<Test>::contains_key(0));
+	});
+}
+
+#[test]
+fn destroying_asset_should_not_be_usable() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		Balances::make_free_balance_be(&2, 100);
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, true, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		assert_ok!(Assets::start_destroy(Origin::signed(1), 0));
+
+		assert_noop!(Assets::mint(Origin::signed(1), 0, 1, 100), TokenError::UnknownAsset);
+		assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 50), Error::<Test>::AssetNotLive);
+		assert_noop!(Assets::burn(Origin::signed(1), 0, 1, 50), Error::<Test>::AssetNotLive);
+		assert_noop!(Assets::freeze(Origin::signed(1), 0, 1), Error::<Test>::AssetNotLive);
+		assert_noop!(Assets::thaw_asset(Origin::signed(1), 0), Error::<Test>::AssetNotLive);
+		assert_noop!(
+			Assets::approve_transfer(Origin::signed(1), 0, 2, 50),
+			Error::<Test>::AssetNotLive
+		);
+		assert_noop!(Assets::touch(Origin::signed(2), 0), Error::<Test>::AssetNotLive);
+		assert_noop!(
+			Assets::force_asset_status(Origin::root(), 0, 1, 1, 1, 1, 1, true, false),
+			Error::<Test>::AssetNotLive
+		);
 	});
 }
 
@@ -248,8 +311,10 @@ fn destroy_should_refund_approvals() {
 		assert_ok!(Assets::approve_transfer(Origin::signed(1), 0, 4, 50));
 		assert_eq!(Balances::reserved_balance(&1), 3);
 
-		let w = Asset::<Test>::get(0).unwrap().destroy_witness();
-		assert_ok!(Assets::destroy(Origin::signed(1), 0, w));
+		assert_ok!(Assets::start_destroy(Origin::signed(1), 0));
+		assert_ok!(Assets::destroy_accounts(Origin::signed(1), 0));
+		assert_ok!(Assets::destroy_approvals(Origin::signed(1), 0));
+		assert_ok!(Assets::finish_destroy(Origin::signed(1), 0));
 		assert_eq!(Balances::reserved_balance(&1), 0);
 
 		// all approvals are removed
@@ -282,6 +347,103 @@ fn non_providing_should_work() {
 	});
 }
 
+#[test]
+fn touching_and_refunding_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, false, 1));
+		Balances::make_free_balance_be(&1, 100);
+		Balances::make_free_balance_be(&2, 100);
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+
+		assert_noop!(Assets::refund(Origin::signed(2), 0, false), Error::<Test>::NoDeposit);
+		assert_ok!(Assets::touch(Origin::signed(2), 0));
+		assert_eq!(Balances::reserved_balance(&2), 10);
+		assert_eq!(Asset::<Test>::get(0).unwrap().accounts, 2);
+		assert_noop!(Assets::touch(Origin::signed(2), 0), Error::<Test>::AlreadyExists);
+		// The touched account holds no consumer reference on account 2.
+		assert_eq!(System::consumers(&2), 0);
+
+		assert_ok!(Assets::refund(Origin::signed(2), 0, false));
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert_eq!(Asset::<Test>::get(0).unwrap().accounts, 1);
+		assert!(!Account::<Test>::contains_key(0, &2));
+		System::assert_last_event(mock::Event::Assets(crate::Event::Refunded {
+			asset_id: 0,
+			who: 2,
+			deposit: 10,
+		}));
+
+		// Accounts that exist without a deposit have nothing to refund.
+		assert_noop!(Assets::refund(Origin::signed(1), 0, true), Error::<Test>::NoDeposit);
+	});
+}
+
+#[test]
+fn touched_account_should_not_need_consumer() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, false, 1));
+		Balances::make_free_balance_be(&1, 100);
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+
+		// Account 2 does not exist yet, hence cannot take a consumer reference...
+		assert_eq!(Assets::can_deposit(0, &2, 50), DepositConsequence::CannotCreate);
+		assert_noop!(Assets::transfer(Origin::signed(1), 0, 2, 50), TokenError::CannotCreate);
+
+		// ...while a touched account is held by its deposit instead.
+		Balances::make_free_balance_be(&2, 100);
+		assert_ok!(Assets::touch(Origin::signed(2), 0));
+		assert_eq!(Assets::can_deposit(0, &2, 50), DepositConsequence::Success);
+		assert_ok!(Assets::transfer(Origin::signed(1), 0, 2, 50));
+		assert_eq!(Assets::balance(0, 2), 50);
+		assert_eq!(System::consumers(&2), 0);
+
+		// Sending everything away keeps the account alive while the deposit is held.
+		assert_ok!(Assets::transfer(Origin::signed(2), 0, 1, 50));
+		assert_eq!(Assets::balance(0, 2), 0);
+		assert!(Account::<Test>::contains_key(0, &2));
+		assert_eq!(Asset::<Test>::get(0).unwrap().accounts, 2);
+	});
+}
+
+#[test]
+fn refund_with_balance_requires_burn() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, false, 1));
+		Balances::make_free_balance_be(&1, 100);
+		Balances::make_free_balance_be(&2, 100);
+		assert_ok!(Assets::touch(Origin::signed(2), 0));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 100));
+
+		assert_noop!(Assets::refund(Origin::signed(2), 0, false), Error::<Test>::WouldBurn);
+		assert_ok!(Assets::freeze(Origin::signed(1), 0, 2));
+		assert_noop!(Assets::refund(Origin::signed(2), 0, true), Error::<Test>::Frozen);
+		assert_ok!(Assets::thaw(Origin::signed(1), 0, 2));
+
+		assert_ok!(Assets::refund(Origin::signed(2), 0, true));
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert_eq!(Assets::balance(0, 2), 0);
+		assert_eq!(Assets::total_supply(0), 0);
+		assert_eq!(Asset::<Test>::get(0).unwrap().accounts, 0);
+	});
+}
+
+#[test]
+fn destroy_should_refund_touch_deposits() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&1, 100);
+		Balances::make_free_balance_be(&2, 100);
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, false, 1));
+		assert_ok!(Assets::touch(Origin::signed(2), 0));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 2, 100));
+		assert_eq!(Balances::reserved_balance(&2), 10);
+
+		assert_ok!(Assets::start_destroy(Origin::signed(1), 0));
+		assert_ok!(Assets::destroy_accounts(Origin::signed(1), 0));
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert!(!Account::<Test>::contains_key(0, &2));
+	});
+}
+
 #[test]
 fn min_balance_should_work() {
 	new_test_ext().execute_with(|| {
@@ -418,8 +580,7 @@ fn origin_guards_should_work() {
 			Assets::force_transfer(Origin::signed(2), 0, 1, 2, 100),
 			Error::<Test>::NoPermission
 		);
-		let w = Asset::<Test>::get(0).unwrap().destroy_witness();
-		assert_noop!(Assets::destroy(Origin::signed(2), 0, w), Error::<Test>::NoPermission);
+		assert_noop!(Assets::start_destroy(Origin::signed(2), 0), Error::<Test>::NoPermission);
 	});
 }
 
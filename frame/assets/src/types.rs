@@ -52,18 +52,23 @@ pub struct AssetDetails<Balance, AccountId, DepositBalance> {
 	pub(super) sufficients: u32,
 	/// The total number of approvals.
 	pub(super) approvals: u32,
-	/// Whether the asset is frozen for non-admin transfers.
-	pub(super) is_frozen: bool,
+	/// The status of the asset.
+	pub(super) status: AssetStatus,
 }
 
-impl<Balance, AccountId, DepositBalance> AssetDetails<Balance, AccountId, DepositBalance> {
-	pub fn destroy_witness(&self) -> DestroyWitness {
-		DestroyWitness {
-			accounts: self.accounts,
-			sufficients: self.sufficients,
-			approvals: self.approvals,
-		}
-	}
+/// The status of an asset class.
+///
+/// The encoding of `Live` and `Frozen` matches the `bool` previously stored in the asset's
+/// `is_frozen` field.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum AssetStatus {
+	/// The asset is active and able to be used.
+	Live,
+	/// The asset is frozen for non-admin transfers.
+	Frozen,
+	/// The asset is being destroyed and no longer usable. Once an asset enters this state it can
+	/// never go back to being `Live`.
+	Destroying,
 }
 
 /// Data concerning an approval.
@@ -76,14 +81,65 @@ pub struct Approval<Balance, DepositBalance> {
 	pub(super) deposit: DepositBalance,
 }
 
+/// The reason for an account's existence within an asset class, and so what must be undone when
+/// it goes away.
+///
+/// The first two variants encode exactly like the `bool` that used to record whether an account
+/// was given a self-sufficient reference.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum ExistenceReason<Balance> {
+	/// A consumer reference was placed on the account.
+	#[codec(index = 0)]
+	Consumer,
+	/// A self-sufficient reference was placed on the account.
+	#[codec(index = 1)]
+	Sufficient,
+	/// The account holder reserved a deposit, so no reference was placed and the account may
+	/// hold a zero balance.
+	#[codec(index = 2)]
+	DepositHeld(Balance),
+	/// The deposit was refunded, so the account will go away as soon as its balance drops below
+	/// the minimum.
+	#[codec(index = 3)]
+	DepositRefunded,
+}
+
+impl<Balance> Default for ExistenceReason<Balance> {
+	fn default() -> Self {
+		ExistenceReason::Consumer
+	}
+}
+
+impl<Balance> ExistenceReason<Balance> {
+	/// Take the held deposit, if any, marking it as refunded.
+	pub(crate) fn take_deposit(&mut self) -> Option<Balance> {
+		if !matches!(self, ExistenceReason::DepositHeld(_)) {
+			return None;
+		}
+		match sp_std::mem::replace(self, ExistenceReason::DepositRefunded) {
+			ExistenceReason::DepositHeld(deposit) => Some(deposit),
+			_ => None,
+		}
+	}
+}
+
+/// What happens to an account once its balance drops below the minimum.
+#[derive(Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub(super) enum DeadConsequence {
+	/// The account is removed.
+	Remove,
+	/// The account is kept with a zero balance, since a deposit is held for it.
+	Keep,
+}
+
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, Default, MaxEncodedLen, TypeInfo)]
-pub struct AssetBalance<Balance, Extra> {
+pub struct AssetBalance<Balance, DepositBalance, Extra> {
 	/// The balance.
 	pub(super) balance: Balance,
 	/// Whether the account is frozen.
 	pub(super) is_frozen: bool,
-	/// `true` if this balance gave the account a self-sufficient reference.
-	pub(super) sufficient: bool,
+	/// The reason for the account's existence.
+	pub(super) reason: ExistenceReason<DepositBalance>,
 	/// Additional "sidecar" data, in case some other pallet wants to use this storage item.
 	pub(super) extra: Extra,
 }
@@ -104,20 +160,6 @@ pub struct AssetMetadata<DepositBalance, BoundedString> {
 	pub(super) is_frozen: bool,
 }
 
-/// Witness data for the destroy transactions.
-#[derive(Copy, Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
-pub struct DestroyWitness {
-	/// The number of accounts holding the asset.
-	#[codec(compact)]
-	pub(super) accounts: u32,
-	/// The number of accounts holding the asset with a self-sufficient reference.
-	#[codec(compact)]
-	pub(super) sufficients: u32,
-	/// The number of transfer-approvals of the asset.
-	#[codec(compact)]
-	pub(super) approvals: u32,
-}
-
 /// Trait for allowing a minimum balance on the account to be specified, beyond the
 /// `minimum_balance` of the asset. This is additive - the `minimum_balance` of the asset must be
 /// met *and then* anything here in addition.
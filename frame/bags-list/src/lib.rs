@@ -201,6 +201,11 @@ pub mod pallet {
 				"thresholds must strictly increase, and have no duplicates",
 			);
 		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_: BlockNumberFor<T>) -> Result<(), &'static str> {
+			List::<T>::sanity_check()
+		}
 	}
 }
 
@@ -263,12 +268,12 @@ impl<T: Config> SortedListProvider<T::AccountId> for Pallet<T> {
 		List::<T>::regenerate(all, weight_of)
 	}
 
-	#[cfg(feature = "std")]
+	#[cfg(any(feature = "std", feature = "try-runtime"))]
 	fn sanity_check() -> Result<(), &'static str> {
 		List::<T>::sanity_check()
 	}
 
-	#[cfg(not(feature = "std"))]
+	#[cfg(not(any(feature = "std", feature = "try-runtime")))]
 	fn sanity_check() -> Result<(), &'static str> {
 		Ok(())
 	}
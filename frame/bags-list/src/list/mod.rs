@@ -386,7 +386,7 @@ impl<T: Config> List<T> {
 	/// * length of this list is in sync with `ListNodes::count()`,
 	/// * and sanity-checks all bags and nodes. This will cascade down all the checks and makes sure
 	/// all bags and nodes are checked per *any* update to `List`.
-	#[cfg(feature = "std")]
+	#[cfg(any(feature = "std", feature = "try-runtime"))]
 	pub(crate) fn sanity_check() -> Result<(), &'static str> {
 		use frame_support::ensure;
 		let mut seen_in_list = BTreeSet::new();
@@ -432,7 +432,7 @@ impl<T: Config> List<T> {
 		Ok(())
 	}
 
-	#[cfg(not(feature = "std"))]
+	#[cfg(not(any(feature = "std", feature = "try-runtime")))]
 	pub(crate) fn sanity_check() -> Result<(), &'static str> {
 		Ok(())
 	}
@@ -618,7 +618,7 @@ impl<T: Config> Bag<T> {
 	/// * Ensures head has no prev.
 	/// * Ensures tail has no next.
 	/// * Ensures there are no loops, traversal from head to tail is correct.
-	#[cfg(feature = "std")]
+	#[cfg(any(feature = "std", feature = "try-runtime"))]
 	fn sanity_check(&self) -> Result<(), &'static str> {
 		frame_support::ensure!(
 			self.head()
@@ -650,7 +650,7 @@ impl<T: Config> Bag<T> {
 		Ok(())
 	}
 
-	#[cfg(not(feature = "std"))]
+	#[cfg(not(any(feature = "std", feature = "try-runtime")))]
 	fn sanity_check(&self) -> Result<(), &'static str> {
 		Ok(())
 	}
@@ -663,7 +663,7 @@ impl<T: Config> Bag<T> {
 	}
 
 	/// Check if the bag contains a node with `id`.
-	#[cfg(feature = "std")]
+	#[cfg(any(feature = "std", feature = "try-runtime"))]
 	fn contains(&self, id: &T::AccountId) -> bool {
 		self.iter().find(|n| n.id() == id).is_some()
 	}
@@ -755,7 +755,7 @@ impl<T: Config> Node<T> {
 		self.bag_upper
 	}
 
-	#[cfg(feature = "std")]
+	#[cfg(any(feature = "std", feature = "try-runtime"))]
 	fn sanity_check(&self) -> Result<(), &'static str> {
 		let expected_bag = Bag::<T>::get(self.bag_upper).ok_or("bag not found for node")?;
 
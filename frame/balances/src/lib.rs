@@ -245,6 +245,14 @@ pub mod pallet {
 	#[pallet::generate_storage_info]
 	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		/// Transfer some liquid free balance to another account.
@@ -733,6 +741,21 @@ impl<T: Config<I>, I: 'static> Drop for DustCleaner<T, I> {
 	}
 }
 
+#[cfg(any(feature = "try-runtime", test))]
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Ensure that the total issuance is equal to the sum of the free and reserved balances of all
+	/// accounts.
+	///
+	/// Every account with a balance has at least one provider reference, so all of them can be
+	/// found in the accounts of `frame_system`, whichever `AccountStore` is used.
+	pub(crate) fn do_try_state() -> Result<(), &'static str> {
+		let total = frame_system::Account::<T>::iter_keys()
+			.fold(T::Balance::zero(), |acc, who| acc.saturating_add(Self::account(&who).total()));
+		ensure!(total == Self::total_issuance(), "total issuance is not the sum of all balances");
+		Ok(())
+	}
+}
+
 impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// Get the free balance of an account.
 	pub fn free_balance(who: impl sp_std::borrow::Borrow<T::AccountId>) -> T::Balance {
@@ -56,7 +56,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn basic_locking_should_work() {
-			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build_and_execute_with(|| {
 				assert_eq!(Balances::free_balance(1), 10);
 				Balances::set_lock(ID_1, &1, 9, WithdrawReasons::all());
 				assert_noop!(
@@ -68,7 +68,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn account_should_be_reaped() {
-			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build_and_execute_with(|| {
 				assert_eq!(Balances::free_balance(1), 10);
 				assert_ok!(<Balances as Currency<_>>::transfer(&1, &2, 10, AllowDeath));
 				// Check that the account is dead.
@@ -78,7 +78,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn reap_failed_due_to_provider_and_consumer() {
-			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build_and_execute_with(|| {
 				// SCENARIO: only one provider and there are remaining consumers.
 				assert_ok!(System::inc_consumers(&1));
 				assert!(!System::can_dec_provider(&1));
@@ -102,7 +102,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn partial_locking_should_work() {
-			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build_and_execute_with(|| {
 				Balances::set_lock(ID_1, &1, 5, WithdrawReasons::all());
 				assert_ok!(<Balances as Currency<_>>::transfer(&1, &2, 1, AllowDeath));
 			});
@@ -110,7 +110,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn lock_removal_should_work() {
-			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build_and_execute_with(|| {
 				Balances::set_lock(ID_1, &1, u64::MAX, WithdrawReasons::all());
 				Balances::remove_lock(ID_1, &1);
 				assert_ok!(<Balances as Currency<_>>::transfer(&1, &2, 1, AllowDeath));
@@ -119,7 +119,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn lock_replacement_should_work() {
-			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build_and_execute_with(|| {
 				Balances::set_lock(ID_1, &1, u64::MAX, WithdrawReasons::all());
 				Balances::set_lock(ID_1, &1, 5, WithdrawReasons::all());
 				assert_ok!(<Balances as Currency<_>>::transfer(&1, &2, 1, AllowDeath));
@@ -128,7 +128,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn double_locking_should_work() {
-			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build_and_execute_with(|| {
 				Balances::set_lock(ID_1, &1, 5, WithdrawReasons::all());
 				Balances::set_lock(ID_2, &1, 5, WithdrawReasons::all());
 				assert_ok!(<Balances as Currency<_>>::transfer(&1, &2, 1, AllowDeath));
@@ -137,7 +137,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn combination_locking_should_work() {
-			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build_and_execute_with(|| {
 				Balances::set_lock(ID_1, &1, u64::MAX, WithdrawReasons::empty());
 				Balances::set_lock(ID_2, &1, 0, WithdrawReasons::all());
 				assert_ok!(<Balances as Currency<_>>::transfer(&1, &2, 1, AllowDeath));
@@ -146,7 +146,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn lock_value_extension_should_work() {
-			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build_and_execute_with(|| {
 				Balances::set_lock(ID_1, &1, 5, WithdrawReasons::all());
 				assert_noop!(
 					<Balances as Currency<_>>::transfer(&1, &2, 6, AllowDeath),
@@ -170,8 +170,7 @@ macro_rules! decl_tests {
 			<$ext_builder>::default()
 				.existential_deposit(1)
 				.monied(true)
-				.build()
-				.execute_with(|| {
+				.build_and_execute_with(|| {
 					pallet_transaction_payment::NextFeeMultiplier::<$test>::put(
 						Multiplier::saturating_from_integer(1)
 					);
@@ -221,7 +220,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn lock_block_number_extension_should_work() {
-			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build_and_execute_with(|| {
 				Balances::set_lock(ID_1, &1, 10, WithdrawReasons::all());
 				assert_noop!(
 					<Balances as Currency<_>>::transfer(&1, &2, 6, AllowDeath),
@@ -243,7 +242,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn lock_reasons_extension_should_work() {
-			<$ext_builder>::default().existential_deposit(1).monied(true).build().execute_with(|| {
+			<$ext_builder>::default().existential_deposit(1).monied(true).build_and_execute_with(|| {
 				Balances::set_lock(ID_1, &1, 10, WithdrawReasons::TRANSFER);
 				assert_noop!(
 					<Balances as Currency<_>>::transfer(&1, &2, 6, AllowDeath),
@@ -267,8 +266,7 @@ macro_rules! decl_tests {
 			<$ext_builder>::default()
 				.existential_deposit(10)
 				.monied(true)
-				.build()
-				.execute_with(|| {
+				.build_and_execute_with(|| {
 					// account 5 should not exist
 					// ext_deposit is 10, value is 9, not satisfies for ext_deposit
 					assert_noop!(
@@ -284,8 +282,7 @@ macro_rules! decl_tests {
 			<$ext_builder>::default()
 				.existential_deposit(256 * 1)
 				.monied(true)
-				.build()
-				.execute_with(|| {
+				.build_and_execute_with(|| {
 					System::inc_account_nonce(&2);
 					assert_eq!(Balances::total_balance(&2), 256 * 20);
 
@@ -311,7 +308,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn reward_should_work() {
-			<$ext_builder>::default().monied(true).build().execute_with(|| {
+			<$ext_builder>::default().monied(true).build_and_execute_with(|| {
 				assert_eq!(Balances::total_balance(&1), 10);
 				assert_ok!(Balances::deposit_into_existing(&1, 10).map(drop));
 				System::assert_last_event(Event::Balances(crate::Event::Deposit { who: 1, amount: 10 }));
@@ -325,8 +322,7 @@ macro_rules! decl_tests {
 			<$ext_builder>::default()
 				.existential_deposit(100)
 				.monied(true)
-				.build()
-				.execute_with(|| {
+				.build_and_execute_with(|| {
 					System::inc_account_nonce(&2);
 					assert_eq!(System::account_nonce(&2), 1);
 					assert_eq!(Balances::total_balance(&2), 2000);
@@ -340,7 +336,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn balance_works() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 42);
 				System::assert_has_event(Event::Balances(crate::Event::Deposit { who: 1, amount: 42 }));
 				assert_eq!(Balances::free_balance(1), 42);
@@ -354,7 +350,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn balance_transfer_works() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 111);
 				assert_ok!(Balances::transfer(Some(1).into(), 2, 69));
 				assert_eq!(Balances::total_balance(&1), 42);
@@ -364,7 +360,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn force_transfer_works() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 111);
 				assert_noop!(
 					Balances::force_transfer(Some(2).into(), 1, 2, 69),
@@ -378,7 +374,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn reserving_balance_should_work() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 111);
 
 				assert_eq!(Balances::total_balance(&1), 111);
@@ -395,7 +391,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn balance_transfer_when_reserved_should_not_work() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 111);
 				assert_ok!(Balances::reserve(&1, 69));
 				assert_noop!(
@@ -407,7 +403,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn deducting_balance_should_work() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 111);
 				assert_ok!(Balances::reserve(&1, 69));
 				assert_eq!(Balances::free_balance(1), 42);
@@ -416,6 +412,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn refunding_balance_should_work() {
+			// `mutate_account` reserves funds without issuing them, skewing the total issuance
 			<$ext_builder>::default().build().execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 42);
 				assert_ok!(Balances::mutate_account(&1, |a| a.reserved = 69));
@@ -427,7 +424,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn slashing_balance_should_work() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 111);
 				assert_ok!(Balances::reserve(&1, 69));
 				assert!(Balances::slash(&1, 69).1.is_zero());
@@ -439,7 +436,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn withdrawing_balance_should_work() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&2, 111);
 				let _ = Balances::withdraw(
 					&2, 11, WithdrawReasons::TRANSFER, ExistenceRequirement::KeepAlive
@@ -452,7 +449,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn slashing_incomplete_balance_should_work() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 42);
 				assert_ok!(Balances::reserve(&1, 21));
 				assert_eq!(Balances::slash(&1, 69).1, 27);
@@ -464,7 +461,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn unreserving_balance_should_work() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 111);
 				assert_ok!(Balances::reserve(&1, 111));
 				Balances::unreserve(&1, 42);
@@ -475,7 +472,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn slashing_reserved_balance_should_work() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 111);
 				assert_ok!(Balances::reserve(&1, 111));
 				assert_eq!(Balances::slash_reserved(&1, 42).1, 0);
@@ -487,7 +484,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn slashing_incomplete_reserved_balance_should_work() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 111);
 				assert_ok!(Balances::reserve(&1, 42));
 				assert_eq!(Balances::slash_reserved(&1, 69).1, 27);
@@ -499,7 +496,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn repatriating_reserved_balance_should_work() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 110);
 				let _ = Balances::deposit_creating(&2, 1);
 				assert_ok!(Balances::reserve(&1, 110));
@@ -516,7 +513,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn transferring_reserved_balance_should_work() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 110);
 				let _ = Balances::deposit_creating(&2, 1);
 				assert_ok!(Balances::reserve(&1, 110));
@@ -530,7 +527,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn transferring_reserved_balance_to_nonexistent_should_fail() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 111);
 				assert_ok!(Balances::reserve(&1, 111));
 				assert_noop!(Balances::repatriate_reserved(&1, &2, 42, Status::Free), Error::<$test, _>::DeadAccount);
@@ -539,7 +536,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn transferring_incomplete_reserved_balance_should_work() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 110);
 				let _ = Balances::deposit_creating(&2, 1);
 				assert_ok!(Balances::reserve(&1, 41));
@@ -553,7 +550,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn transferring_too_high_value_should_not_panic() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				Balances::make_free_balance_be(&1, u64::MAX);
 				Balances::make_free_balance_be(&2, 1);
 
@@ -569,7 +566,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn account_create_on_free_too_low_with_other() {
-			<$ext_builder>::default().existential_deposit(100).build().execute_with(|| {
+			<$ext_builder>::default().existential_deposit(100).build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 100);
 				assert_eq!(<TotalIssuance<$test>>::get(), 100);
 
@@ -582,7 +579,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn account_create_on_free_too_low() {
-			<$ext_builder>::default().existential_deposit(100).build().execute_with(|| {
+			<$ext_builder>::default().existential_deposit(100).build_and_execute_with(|| {
 				// No-op.
 				let _ = Balances::deposit_creating(&2, 50);
 				assert_eq!(Balances::free_balance(2), 0);
@@ -592,7 +589,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn account_removal_on_free_too_low() {
-			<$ext_builder>::default().existential_deposit(100).build().execute_with(|| {
+			<$ext_builder>::default().existential_deposit(100).build_and_execute_with(|| {
 				assert_eq!(<TotalIssuance<$test>>::get(), 0);
 
 				// Setup two accounts with free balance above the existential threshold.
@@ -619,7 +616,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn burn_must_work() {
-			<$ext_builder>::default().monied(true).build().execute_with(|| {
+			<$ext_builder>::default().monied(true).build_and_execute_with(|| {
 				let init_total_issuance = Balances::total_issuance();
 				let imbalance = Balances::burn(10);
 				assert_eq!(Balances::total_issuance(), init_total_issuance - 10);
@@ -630,7 +627,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn transfer_keep_alive_works() {
-			<$ext_builder>::default().existential_deposit(1).build().execute_with(|| {
+			<$ext_builder>::default().existential_deposit(1).build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 100);
 				assert_noop!(
 					Balances::transfer_keep_alive(Some(1).into(), 2, 100),
@@ -664,8 +661,7 @@ macro_rules! decl_tests {
 		fn dust_moves_between_free_and_reserved() {
 			<$ext_builder>::default()
 				.existential_deposit(100)
-				.build()
-				.execute_with(|| {
+				.build_and_execute_with(|| {
 					// Set balance to free and reserved at the existential deposit
 					assert_ok!(Balances::set_balance(RawOrigin::Root.into(), 1, 100, 0));
 					// Check balance
@@ -694,6 +690,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn account_deleted_when_just_dust() {
+			// the imbalances compared below are dropped too, skewing the total issuance
 			<$ext_builder>::default()
 				.existential_deposit(100)
 				.build()
@@ -717,8 +714,7 @@ macro_rules! decl_tests {
 		#[test]
 		fn emit_events_with_reserve_and_unreserve() {
 			<$ext_builder>::default()
-				.build()
-				.execute_with(|| {
+				.build_and_execute_with(|| {
 					let _ = Balances::deposit_creating(&1, 100);
 
 					System::set_block_number(2);
@@ -741,6 +737,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn emit_events_with_existential_deposit() {
+			// the imbalances compared below are dropped too, skewing the total issuance
 			<$ext_builder>::default()
 				.existential_deposit(100)
 				.build()
@@ -772,6 +769,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn emit_events_with_no_existential_deposit_suicide() {
+			// the imbalances compared below are dropped too, skewing the total issuance
 			<$ext_builder>::default()
 				.existential_deposit(1)
 				.build()
@@ -802,6 +800,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn slash_loop_works() {
+			// the imbalances compared below are dropped too, skewing the total issuance
 			<$ext_builder>::default()
 				.existential_deposit(100)
 				.build()
@@ -903,6 +902,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn slash_reserved_loop_works() {
+			// the imbalances compared below are dropped too, skewing the total issuance
 			<$ext_builder>::default()
 				.existential_deposit(100)
 				.build()
@@ -979,8 +979,7 @@ macro_rules! decl_tests {
 			// the account never existed to begin with, and shouldn't exist in the end.
 			<$ext_builder>::default()
 				.existential_deposit(0)
-				.build()
-				.execute_with(|| {
+				.build_and_execute_with(|| {
 					assert!(!frame_system::Account::<Test>::contains_key(&1337));
 
 					// Unreserve
@@ -1000,8 +999,7 @@ macro_rules! decl_tests {
 		fn transfer_keep_alive_all_free_succeed() {
 			<$ext_builder>::default()
 				.existential_deposit(100)
-				.build()
-				.execute_with(|| {
+				.build_and_execute_with(|| {
 					assert_ok!(Balances::set_balance(Origin::root(), 1, 100, 100));
 					assert_ok!(Balances::transfer_keep_alive(Some(1).into(), 2, 100));
 					assert_eq!(Balances::total_balance(&1), 100);
@@ -1013,8 +1011,7 @@ macro_rules! decl_tests {
 		fn transfer_all_works() {
 			<$ext_builder>::default()
 				.existential_deposit(100)
-				.build()
-				.execute_with(|| {
+				.build_and_execute_with(|| {
 					// setup
 					assert_ok!(Balances::set_balance(Origin::root(), 1, 200, 0));
 					assert_ok!(Balances::set_balance(Origin::root(), 2, 0, 0));
@@ -1051,7 +1048,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn named_reserve_should_work() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 111);
 
 				let id_1 = [1u8; 8];
@@ -1169,7 +1166,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn ensure_reserved_named_should_work() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 111);
 
 				let id = [1u8; 8];
@@ -1187,7 +1184,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn unreserve_all_named_should_work() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 111);
 
 				let id = [1u8; 8];
@@ -1204,7 +1201,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn slash_all_reserved_named_should_work() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 111);
 
 				let id = [1u8; 8];
@@ -1221,7 +1218,7 @@ macro_rules! decl_tests {
 
 		#[test]
 		fn repatriate_all_reserved_named_should_work() {
-			<$ext_builder>::default().build().execute_with(|| {
+			<$ext_builder>::default().build_and_execute_with(|| {
 				let _ = Balances::deposit_creating(&1, 111);
 				let _ = Balances::deposit_creating(&2, 10);
 				let _ = Balances::deposit_creating(&3, 10);
@@ -1239,5 +1236,15 @@ macro_rules! decl_tests {
 				assert_eq!(Balances::free_balance(&3), 25);
 			});
 		}
+
+		#[test]
+		fn try_state_detects_wrong_total_issuance() {
+			<$ext_builder>::default().monied(true).build().execute_with(|| {
+				assert_eq!(Balances::do_try_state(), Ok(()));
+
+				<TotalIssuance<$test>>::mutate(|t| *t += 1);
+				assert_eq!(Balances::do_try_state(), Err("total issuance is not the sum of all balances"));
+			});
+		}
 	}
 }
@@ -86,6 +86,7 @@ impl pallet_transaction_payment::Config for Test {
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
 	type WeightToFee = IdentityFee<u64>;
 	type FeeMultiplierUpdate = ();
+	type FeeHistoryLength = ();
 }
 parameter_types! {
 	pub const MaxLocks: u32 = 50;
@@ -151,6 +152,12 @@ impl ExtBuilder {
 		ext.execute_with(|| System::set_block_number(1));
 		ext
 	}
+	/// Build the externalities, run `test` in them and check the pallet invariants afterwards.
+	pub fn build_and_execute_with(self, test: impl FnOnce()) {
+		let mut ext = self.build();
+		ext.execute_with(test);
+		ext.execute_with(|| assert_eq!(Balances::do_try_state(), Ok(())));
+	}
 }
 
 decl_tests! { Test, ExtBuilder, EXISTENTIAL_DEPOSIT }
@@ -88,6 +88,7 @@ impl pallet_transaction_payment::Config for Test {
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
 	type WeightToFee = IdentityFee<u64>;
 	type FeeMultiplierUpdate = ();
+	type FeeHistoryLength = ();
 }
 
 pub struct OnDustRemoval;
@@ -71,6 +71,10 @@
 //! - `claim_bounty` - Claim a specific bounty amount from the Payout Address.
 //! - `unassign_curator` - Unassign an accepted curator from a specific earmark.
 //! - `close_bounty` - Cancel the earmark for a specific treasury amount and close the bounty.
+//!
+//! An active bounty may be split into child bounties by a pallet implementing
+//! `ChildBountyManager`, such as `pallet-child-bounties`. A bounty cannot be awarded or closed
+//! while it has active child bounties.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -123,6 +127,13 @@ pub struct Bounty<AccountId, Balance, BlockNumber> {
 	status: BountyStatus<AccountId, BlockNumber>,
 }
 
+impl<AccountId: Clone, Balance, BlockNumber: Clone> Bounty<AccountId, Balance, BlockNumber> {
+	/// Getter for bounty status, to be used for child bounties.
+	pub fn get_status(&self) -> BountyStatus<AccountId, BlockNumber> {
+		self.status.clone()
+	}
+}
+
 /// The status of a bounty proposal.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub enum BountyStatus<AccountId, BlockNumber> {
@@ -156,6 +167,30 @@ pub enum BountyStatus<AccountId, BlockNumber> {
 	},
 }
 
+/// The child bounty manager.
+pub trait ChildBountyManager<Balance> {
+	/// Get the active child bounties for a parent bounty.
+	fn child_bounties_count(bounty_id: BountyIndex) -> BountyIndex;
+
+	/// Get the total curator fees of the child bounties of a parent bounty.
+	fn children_curator_fees(bounty_id: BountyIndex) -> Balance;
+
+	/// Hook called when a parent bounty is removed, to clean up any child bounty data.
+	fn bounty_removed(bounty_id: BountyIndex);
+}
+
+impl<Balance: Zero> ChildBountyManager<Balance> for () {
+	fn child_bounties_count(_bounty_id: BountyIndex) -> BountyIndex {
+		Default::default()
+	}
+
+	fn children_curator_fees(_bounty_id: BountyIndex) -> Balance {
+		Zero::zero()
+	}
+
+	fn bounty_removed(_bounty_id: BountyIndex) {}
+}
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -200,6 +235,9 @@ pub mod pallet {
 
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
+
+		/// The child bounty manager.
+		type ChildBountyManager: ChildBountyManager<BalanceOf<Self>>;
 	}
 
 	#[pallet::error]
@@ -223,6 +261,8 @@ pub mod pallet {
 		PendingPayout,
 		/// The bounties cannot be claimed/closed because it's still in the countdown period.
 		Premature,
+		/// The bounty cannot be closed because it has active child bounties.
+		HasActiveChildBounty,
 	}
 
 	#[pallet::event]
@@ -510,6 +550,13 @@ pub mod pallet {
 
 			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
 				let mut bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+
+				// Ensure no active child bounties before processing the call.
+				ensure!(
+					T::ChildBountyManager::child_bounties_count(bounty_id) == 0,
+					Error::<T>::HasActiveChildBounty
+				);
+
 				match &bounty.status {
 					BountyStatus::Active { curator, .. } => {
 						ensure!(signer == *curator, Error::<T>::RequireCurator);
@@ -557,7 +604,11 @@ pub mod pallet {
 					);
 					let bounty_account = Self::bounty_account_id(bounty_id);
 					let balance = T::Currency::free_balance(&bounty_account);
-					let fee = bounty.fee.min(balance); // just to be safe
+					// The curator fees of child bounties were paid out of the child bounty funds,
+					// so they are subtracted from the fee of the parent bounty curator.
+					let children_fee = T::ChildBountyManager::children_curator_fees(bounty_id);
+					let fee = bounty.fee.saturating_sub(children_fee);
+					let fee = fee.min(balance); // just to be safe
 					let payout = balance.saturating_sub(fee);
 					let err_amount = T::Currency::unreserve(&curator, bounty.curator_deposit);
 					debug_assert!(err_amount.is_zero());
@@ -570,6 +621,7 @@ pub mod pallet {
 					*maybe_bounty = None;
 
 					BountyDescriptions::<T>::remove(bounty_id);
+					T::ChildBountyManager::bounty_removed(bounty_id);
 
 					Self::deposit_event(Event::<T>::BountyClaimed {
 						index: bounty_id,
@@ -607,6 +659,12 @@ pub mod pallet {
 				|maybe_bounty| -> DispatchResultWithPostInfo {
 					let bounty = maybe_bounty.as_ref().ok_or(Error::<T>::InvalidIndex)?;
 
+					// Ensure no active child bounties before processing the call.
+					ensure!(
+						T::ChildBountyManager::child_bounties_count(bounty_id) == 0,
+						Error::<T>::HasActiveChildBounty
+					);
+
 					match &bounty.status {
 						BountyStatus::Proposed => {
 							// The reject origin would like to cancel a proposed bounty.
@@ -663,6 +721,8 @@ pub mod pallet {
 					debug_assert!(res.is_ok());
 					*maybe_bounty = None;
 
+					T::ChildBountyManager::bounty_removed(bounty_id);
+
 					Self::deposit_event(Event::<T>::BountyCanceled { index: bounty_id });
 					Ok(Some(<T as Config>::WeightInfo::close_bounty_active()).into())
 				},
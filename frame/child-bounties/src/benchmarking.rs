@@ -0,0 +1,230 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Child-bounties pallet benchmarking.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::traits::OnInitialize;
+use frame_system::RawOrigin;
+
+use crate::Pallet as ChildBounties;
+use pallet_bounties::Pallet as Bounties;
+use pallet_treasury::Pallet as Treasury;
+
+const SEED: u32 = 0;
+
+fn setup_pot_account<T: Config>() {
+	let pot_account = Treasury::<T>::account_id();
+	let value = T::Currency::minimum_balance().saturating_mul(1_000_000_000u32.into());
+	let _ = T::Currency::make_free_balance_be(&pot_account, value);
+}
+
+// Create an active parent bounty, returning its curator and index.
+fn create_parent_bounty<T: Config>() -> Result<(T::AccountId, BountyIndex), &'static str> {
+	setup_pot_account::<T>();
+
+	let caller: T::AccountId = account("caller", 0, SEED);
+	let value: BalanceOf<T> = T::BountyValueMinimum::get().saturating_mul(100u32.into());
+	let fee = value / 2u32.into();
+	let deposit = T::BountyDepositBase::get() +
+		T::DataDepositPerByte::get() * T::MaximumReasonLength::get().into();
+	let _ = T::Currency::make_free_balance_be(&caller, deposit);
+	let curator: T::AccountId = account("curator", 0, SEED);
+	let _ = T::Currency::make_free_balance_be(&curator, fee);
+	let reason = vec![0; T::MaximumReasonLength::get() as usize];
+
+	Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
+	let bounty_id = pallet_bounties::BountyCount::<T>::get() - 1;
+	Bounties::<T>::approve_bounty(RawOrigin::Root.into(), bounty_id)?;
+	Treasury::<T>::on_initialize(T::BlockNumber::zero());
+	Bounties::<T>::propose_curator(
+		RawOrigin::Root.into(),
+		bounty_id,
+		T::Lookup::unlookup(curator.clone()),
+		fee,
+	)?;
+	Bounties::<T>::accept_curator(RawOrigin::Signed(curator.clone()).into(), bounty_id)?;
+	Ok((curator, bounty_id))
+}
+
+// The value of a child bounty, which is a quarter of the parent bounty.
+fn child_bounty_value<T: Config>() -> BalanceOf<T> {
+	T::BountyValueMinimum::get()
+		.saturating_mul(25u32.into())
+		.max(T::ChildBountyValueMinimum::get())
+}
+
+// Add a child bounty to an active parent bounty, returning the parent curator, parent index and
+// child index.
+fn create_child_bounty<T: Config>() -> Result<(T::AccountId, BountyIndex, BountyIndex), &'static str>
+{
+	let (parent_curator, bounty_id) = create_parent_bounty::<T>()?;
+	let reason = vec![0; T::MaximumReasonLength::get() as usize];
+	ChildBounties::<T>::add_child_bounty(
+		RawOrigin::Signed(parent_curator.clone()).into(),
+		bounty_id,
+		child_bounty_value::<T>(),
+		reason,
+	)?;
+	let child_bounty_id = ChildBountyCount::<T>::get() - 1;
+	Ok((parent_curator, bounty_id, child_bounty_id))
+}
+
+// Add a child bounty and make an account its active curator, returning the child curator, parent
+// index and child index.
+fn create_active_child_bounty<T: Config>(
+) -> Result<(T::AccountId, BountyIndex, BountyIndex), &'static str> {
+	let (parent_curator, bounty_id, child_bounty_id) = create_child_bounty::<T>()?;
+	let child_curator: T::AccountId = account("child-curator", 0, SEED);
+	let fee = child_bounty_value::<T>() / 2u32.into();
+	let _ = T::Currency::make_free_balance_be(&child_curator, fee);
+	ChildBounties::<T>::propose_curator(
+		RawOrigin::Signed(parent_curator).into(),
+		bounty_id,
+		child_bounty_id,
+		T::Lookup::unlookup(child_curator.clone()),
+		fee,
+	)?;
+	ChildBounties::<T>::accept_curator(
+		RawOrigin::Signed(child_curator.clone()).into(),
+		bounty_id,
+		child_bounty_id,
+	)?;
+	Ok((child_curator, bounty_id, child_bounty_id))
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::Event) {
+	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+benchmarks! {
+	add_child_bounty {
+		let d in 0 .. T::MaximumReasonLength::get();
+
+		let (parent_curator, bounty_id) = create_parent_bounty::<T>()?;
+		let value = child_bounty_value::<T>();
+		let description = vec![0; d as usize];
+	}: _(RawOrigin::Signed(parent_curator), bounty_id, value, description)
+	verify {
+		assert_last_event::<T>(Event::Added { index: bounty_id, child_index: 0 }.into())
+	}
+
+	propose_curator {
+		let (parent_curator, bounty_id, child_bounty_id) = create_child_bounty::<T>()?;
+		let child_curator: T::AccountId = account("child-curator", 0, SEED);
+		let child_curator_lookup = T::Lookup::unlookup(child_curator.clone());
+		let fee = child_bounty_value::<T>() / 2u32.into();
+	}: _(RawOrigin::Signed(parent_curator), bounty_id, child_bounty_id, child_curator_lookup, fee)
+	verify {
+		assert_eq!(
+			ChildBounties::<T>::child_bounties(bounty_id, child_bounty_id).unwrap().status,
+			ChildBountyStatus::CuratorProposed { curator: child_curator },
+		);
+	}
+
+	accept_curator {
+		let (parent_curator, bounty_id, child_bounty_id) = create_child_bounty::<T>()?;
+		let child_curator: T::AccountId = account("child-curator", 0, SEED);
+		let fee = child_bounty_value::<T>() / 2u32.into();
+		let _ = T::Currency::make_free_balance_be(&child_curator, fee);
+		ChildBounties::<T>::propose_curator(
+			RawOrigin::Signed(parent_curator).into(),
+			bounty_id,
+			child_bounty_id,
+			T::Lookup::unlookup(child_curator.clone()),
+			fee,
+		)?;
+	}: _(RawOrigin::Signed(child_curator.clone()), bounty_id, child_bounty_id)
+	verify {
+		assert_eq!(
+			ChildBounties::<T>::child_bounties(bounty_id, child_bounty_id).unwrap().status,
+			ChildBountyStatus::Active { curator: child_curator },
+		);
+	}
+
+	// Worst case when the child bounty curator is inactive and any sender unassigns them.
+	unassign_curator {
+		let (_, bounty_id, child_bounty_id) = create_active_child_bounty::<T>()?;
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(now + T::BountyUpdatePeriod::get() + 1u32.into());
+		let caller = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), bounty_id, child_bounty_id)
+	verify {
+		assert_eq!(
+			ChildBounties::<T>::child_bounties(bounty_id, child_bounty_id).unwrap().status,
+			ChildBountyStatus::Added,
+		);
+	}
+
+	award_child_bounty {
+		let (child_curator, bounty_id, child_bounty_id) = create_active_child_bounty::<T>()?;
+		let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+		let beneficiary_lookup = T::Lookup::unlookup(beneficiary.clone());
+	}: _(RawOrigin::Signed(child_curator), bounty_id, child_bounty_id, beneficiary_lookup)
+	verify {
+		assert_last_event::<T>(Event::Awarded {
+			index: bounty_id,
+			child_index: child_bounty_id,
+			beneficiary,
+		}.into())
+	}
+
+	claim_child_bounty {
+		let (child_curator, bounty_id, child_bounty_id) = create_active_child_bounty::<T>()?;
+		let beneficiary: T::AccountId = account("beneficiary", 0, SEED);
+		ChildBounties::<T>::award_child_bounty(
+			RawOrigin::Signed(child_curator).into(),
+			bounty_id,
+			child_bounty_id,
+			T::Lookup::unlookup(beneficiary.clone()),
+		)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(now + T::BountyDepositPayoutDelay::get());
+		let caller = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), bounty_id, child_bounty_id)
+	verify {
+		assert!(T::Currency::free_balance(&beneficiary) > Zero::zero());
+	}
+
+	// Best case scenario.
+	close_child_bounty_added {
+		let (_, bounty_id, child_bounty_id) = create_child_bounty::<T>()?;
+	}: close_child_bounty(RawOrigin::Root, bounty_id, child_bounty_id)
+	verify {
+		assert_last_event::<T>(Event::Canceled {
+			index: bounty_id,
+			child_index: child_bounty_id,
+		}.into())
+	}
+
+	// Worst case scenario.
+	close_child_bounty_active {
+		let (_, bounty_id, child_bounty_id) = create_active_child_bounty::<T>()?;
+	}: close_child_bounty(RawOrigin::Root, bounty_id, child_bounty_id)
+	verify {
+		assert_last_event::<T>(Event::Canceled {
+			index: bounty_id,
+			child_index: child_bounty_id,
+		}.into())
+	}
+
+	impl_benchmark_test_suite!(ChildBounties, crate::tests::new_test_ext(), crate::tests::Test)
+}
@@ -0,0 +1,595 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Child-bounties pallet tests.
+
+#![cfg(test)]
+
+use super::*;
+use crate as pallet_child_bounties;
+
+use frame_support::{
+	assert_noop, assert_ok, pallet_prelude::GenesisBuild, parameter_types, traits::OnInitialize,
+	weights::Weight, PalletId,
+};
+
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BadOrigin, BlakeTwo256, IdentityLookup},
+	Perbill,
+};
+
+use super::Event as ChildBountiesEvent;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+type BountiesError = pallet_bounties::Error<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Bounties: pallet_bounties::{Pallet, Call, Storage, Event<T>},
+		Treasury: pallet_treasury::{Pallet, Call, Storage, Config, Event<T>},
+		ChildBounties: pallet_child_bounties::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const MaximumBlockWeight: Weight = 1024;
+	pub const MaximumBlockLength: u32 = 2 * 1024;
+	pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u128;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+parameter_types! {
+	pub const ProposalBond: Permill = Permill::from_percent(5);
+	pub const ProposalBondMinimum: u64 = 1;
+	pub const SpendPeriod: u64 = 2;
+	pub const Burn: Permill = Permill::from_percent(50);
+	pub const DataDepositPerByte: u64 = 1;
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+	pub const MaxApprovals: u32 = 100;
+}
+impl pallet_treasury::Config for Test {
+	type PalletId = TreasuryPalletId;
+	type Currency = pallet_balances::Pallet<Test>;
+	type ApproveOrigin = frame_system::EnsureRoot<u128>;
+	type RejectOrigin = frame_system::EnsureRoot<u128>;
+	type Event = Event;
+	type OnSlash = ();
+	type ProposalBond = ProposalBond;
+	type ProposalBondMinimum = ProposalBondMinimum;
+	type SpendPeriod = SpendPeriod;
+	type Burn = Burn;
+	type BurnDestination = ();
+	type WeightInfo = ();
+	type SpendFunds = Bounties;
+	type MaxApprovals = MaxApprovals;
+}
+parameter_types! {
+	pub const BountyDepositBase: u64 = 80;
+	pub const BountyDepositPayoutDelay: u64 = 3;
+	pub const BountyUpdatePeriod: u32 = 20;
+	pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
+	pub const BountyValueMinimum: u64 = 5;
+	pub const MaximumReasonLength: u32 = 300;
+}
+impl pallet_bounties::Config for Test {
+	type Event = Event;
+	type BountyDepositBase = BountyDepositBase;
+	type BountyDepositPayoutDelay = BountyDepositPayoutDelay;
+	type BountyUpdatePeriod = BountyUpdatePeriod;
+	type BountyCuratorDeposit = BountyCuratorDeposit;
+	type BountyValueMinimum = BountyValueMinimum;
+	type DataDepositPerByte = DataDepositPerByte;
+	type MaximumReasonLength = MaximumReasonLength;
+	type WeightInfo = ();
+	type ChildBountyManager = ChildBounties;
+}
+parameter_types! {
+	pub const MaxActiveChildBountyCount: u32 = 2;
+	pub const ChildBountyValueMinimum: u64 = 1;
+	pub const ChildBountyCuratorDepositBase: Permill = Permill::from_percent(50);
+}
+impl pallet_child_bounties::Config for Test {
+	type Event = Event;
+	type MaxActiveChildBountyCount = MaxActiveChildBountyCount;
+	type ChildBountyValueMinimum = ChildBountyValueMinimum;
+	type ChildBountyCuratorDepositBase = ChildBountyCuratorDepositBase;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		// Total issuance will be 200 with treasury account initialized at ED.
+		balances: vec![(0, 100), (1, 98), (2, 1)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	GenesisBuild::<Test>::assimilate_storage(&pallet_treasury::GenesisConfig, &mut t).unwrap();
+	t.into()
+}
+
+fn last_event() -> ChildBountiesEvent<Test> {
+	System::events()
+		.into_iter()
+		.map(|r| r.event)
+		.filter_map(|e| if let Event::ChildBounties(inner) = e { Some(inner) } else { None })
+		.last()
+		.unwrap()
+}
+
+/// Create bounty 0 with a value of 50 and make account 4 its active curator with a fee of 6.
+fn create_active_parent_bounty() {
+	System::set_block_number(1);
+	Balances::make_free_balance_be(&Treasury::account_id(), 101);
+	assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+	assert_ok!(Bounties::approve_bounty(Origin::root(), 0));
+
+	System::set_block_number(2);
+	<Treasury as OnInitialize<u64>>::on_initialize(2);
+
+	assert_ok!(Bounties::propose_curator(Origin::root(), 0, 4, 6));
+	Balances::make_free_balance_be(&4, 10);
+	assert_ok!(Bounties::accept_curator(Origin::signed(4), 0));
+	assert_eq!(Balances::free_balance(4), 7);
+	assert_eq!(Balances::reserved_balance(4), 3);
+	assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 50);
+}
+
+/// Add child bounty 0 with a value of 10 to bounty 0 and make account 8 its active curator with
+/// a fee of 2.
+fn create_active_child_bounty() {
+	assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()));
+	assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 2));
+	Balances::make_free_balance_be(&8, 10);
+	assert_ok!(ChildBounties::accept_curator(Origin::signed(8), 0, 0));
+}
+
+#[test]
+fn genesis_config_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ChildBounties::child_bounty_count(), 0);
+		assert_eq!(ChildBounties::parent_child_bounties(0), 0);
+	});
+}
+
+#[test]
+fn add_child_bounty_works() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+
+		// Only the parent bounty curator can add a child bounty.
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(0), 0, 10, b"12345-p1".to_vec()),
+			BountiesError::RequireCurator,
+		);
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(4), 0, 0, b"12345-p1".to_vec()),
+			BountiesError::InvalidValue,
+		);
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, vec![0; 301]),
+			BountiesError::ReasonTooBig,
+		);
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(4), 0, 51, b"12345-p1".to_vec()),
+			Error::<Test>::InsufficientBountyBalance,
+		);
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(4), 1, 10, b"12345-p1".to_vec()),
+			BountiesError::InvalidIndex,
+		);
+
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()));
+		assert_eq!(last_event(), ChildBountiesEvent::Added { index: 0, child_index: 0 });
+
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 40);
+		assert_eq!(Balances::free_balance(ChildBounties::child_bounty_account_id(0)), 10);
+		assert_eq!(ChildBounties::child_bounty_count(), 1);
+		assert_eq!(ChildBounties::parent_child_bounties(0), 1);
+		assert_eq!(
+			ChildBounties::child_bounties(0, 0).unwrap(),
+			ChildBounty {
+				parent_bounty: 0,
+				value: 10,
+				fee: 0,
+				curator_deposit: 0,
+				status: ChildBountyStatus::Added,
+			}
+		);
+		assert_eq!(ChildBounties::child_bounty_descriptions(0).unwrap(), b"12345-p1".to_vec());
+	});
+}
+
+#[test]
+fn add_child_bounty_requires_active_parent() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+		assert_ok!(Bounties::approve_bounty(Origin::root(), 0));
+
+		System::set_block_number(2);
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+
+		assert_ok!(Bounties::propose_curator(Origin::root(), 0, 4, 6));
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()),
+			Error::<Test>::ParentBountyNotActive,
+		);
+	});
+}
+
+#[test]
+fn add_child_bounty_respects_max_active_count() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()));
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p2".to_vec()));
+		assert_noop!(
+			ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p3".to_vec()),
+			Error::<Test>::TooManyChildBounties,
+		);
+
+		// Closing one of them makes room for another.
+		assert_ok!(ChildBounties::close_child_bounty(Origin::signed(4), 0, 1));
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p3".to_vec()));
+		assert_eq!(ChildBounties::child_bounty_count(), 3);
+		assert_eq!(ChildBounties::parent_child_bounties(0), 2);
+	});
+}
+
+#[test]
+fn child_bounty_assign_curator_works() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()));
+
+		assert_noop!(
+			ChildBounties::propose_curator(Origin::signed(8), 0, 0, 8, 2),
+			BountiesError::RequireCurator,
+		);
+		assert_noop!(
+			ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 10),
+			BountiesError::InvalidFee,
+		);
+		assert_noop!(
+			ChildBounties::accept_curator(Origin::signed(8), 0, 0),
+			BountiesError::UnexpectedStatus,
+		);
+
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 2));
+		assert_eq!(ChildBounties::children_curator_fees(0), 2);
+		assert_eq!(
+			ChildBounties::child_bounties(0, 0).unwrap().status,
+			ChildBountyStatus::CuratorProposed { curator: 8 },
+		);
+
+		assert_noop!(
+			ChildBounties::accept_curator(Origin::signed(3), 0, 0),
+			BountiesError::RequireCurator,
+		);
+		assert_noop!(
+			ChildBounties::accept_curator(Origin::signed(8), 0, 0),
+			pallet_balances::Error::<Test, _>::InsufficientBalance,
+		);
+
+		Balances::make_free_balance_be(&8, 10);
+		assert_ok!(ChildBounties::accept_curator(Origin::signed(8), 0, 0));
+
+		let child_bounty = ChildBounties::child_bounties(0, 0).unwrap();
+		assert_eq!(child_bounty.fee, 2);
+		assert_eq!(child_bounty.curator_deposit, 1);
+		assert_eq!(child_bounty.status, ChildBountyStatus::Active { curator: 8 });
+		assert_eq!(Balances::free_balance(8), 9);
+		assert_eq!(Balances::reserved_balance(8), 1);
+	});
+}
+
+#[test]
+fn award_and_claim_child_bounty_works() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		create_active_child_bounty();
+
+		assert_noop!(
+			ChildBounties::award_child_bounty(Origin::signed(3), 0, 0, 7),
+			BountiesError::RequireCurator,
+		);
+		assert_noop!(
+			ChildBounties::claim_child_bounty(Origin::signed(7), 0, 0),
+			BountiesError::UnexpectedStatus,
+		);
+
+		assert_ok!(ChildBounties::award_child_bounty(Origin::signed(8), 0, 0, 7));
+		assert_eq!(
+			last_event(),
+			ChildBountiesEvent::Awarded { index: 0, child_index: 0, beneficiary: 7 }
+		);
+		assert_eq!(
+			ChildBounties::child_bounties(0, 0).unwrap().status,
+			ChildBountyStatus::PendingPayout { curator: 8, beneficiary: 7, unlock_at: 5 },
+		);
+
+		System::set_block_number(4);
+		assert_noop!(
+			ChildBounties::claim_child_bounty(Origin::signed(7), 0, 0),
+			BountiesError::Premature,
+		);
+
+		System::set_block_number(5);
+		assert_ok!(ChildBounties::claim_child_bounty(Origin::signed(7), 0, 0));
+		assert_eq!(
+			last_event(),
+			ChildBountiesEvent::Claimed { index: 0, child_index: 0, payout: 8, beneficiary: 7 }
+		);
+
+		// Curator gets the fee and the deposit back, the beneficiary gets the rest.
+		assert_eq!(Balances::free_balance(8), 12);
+		assert_eq!(Balances::reserved_balance(8), 0);
+		assert_eq!(Balances::free_balance(7), 8);
+		assert_eq!(Balances::free_balance(ChildBounties::child_bounty_account_id(0)), 0);
+
+		assert_eq!(ChildBounties::child_bounties(0, 0), None);
+		assert_eq!(ChildBounties::child_bounty_descriptions(0), None);
+		assert_eq!(ChildBounties::parent_child_bounties(0), 0);
+		assert_eq!(ChildBounties::children_curator_fees(0), 2);
+	});
+}
+
+#[test]
+fn parent_bounty_waits_for_child_bounties_and_pays_reduced_fee() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		create_active_child_bounty();
+
+		// The parent bounty cannot be awarded or closed while the child bounty is active.
+		assert_noop!(
+			Bounties::award_bounty(Origin::signed(4), 0, 9),
+			BountiesError::HasActiveChildBounty,
+		);
+		assert_noop!(
+			Bounties::close_bounty(Origin::root(), 0),
+			BountiesError::HasActiveChildBounty
+		);
+
+		assert_ok!(ChildBounties::award_child_bounty(Origin::signed(4), 0, 0, 7));
+		System::set_block_number(5);
+		assert_ok!(ChildBounties::claim_child_bounty(Origin::signed(7), 0, 0));
+
+		assert_ok!(Bounties::award_bounty(Origin::signed(4), 0, 9));
+		System::set_block_number(8);
+		assert_ok!(Bounties::claim_bounty(Origin::signed(9), 0));
+
+		// The parent curator fee of 6 is reduced by the child curator fee of 2.
+		assert_eq!(Balances::free_balance(4), 14);
+		assert_eq!(Balances::reserved_balance(4), 0);
+		assert_eq!(Balances::free_balance(9), 36);
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 0);
+
+		// Child bounty data of the parent is cleaned up.
+		assert_eq!(ChildBounties::children_curator_fees(0), 0);
+		assert!(!ChildrenCuratorFees::<Test>::contains_key(0));
+	});
+}
+
+#[test]
+fn close_added_child_bounty_works() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()));
+
+		assert_noop!(ChildBounties::close_child_bounty(Origin::signed(7), 0, 0), BadOrigin);
+
+		assert_ok!(ChildBounties::close_child_bounty(Origin::signed(4), 0, 0));
+		assert_eq!(last_event(), ChildBountiesEvent::Canceled { index: 0, child_index: 0 });
+
+		// Funds are returned to the parent bounty.
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 50);
+		assert_eq!(Balances::free_balance(ChildBounties::child_bounty_account_id(0)), 0);
+		assert_eq!(ChildBounties::child_bounties(0, 0), None);
+		assert_eq!(ChildBounties::child_bounty_descriptions(0), None);
+		assert_eq!(ChildBounties::parent_child_bounties(0), 0);
+	});
+}
+
+#[test]
+fn close_active_child_bounty_works() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		create_active_child_bounty();
+		assert_eq!(ChildBounties::children_curator_fees(0), 2);
+
+		assert_ok!(ChildBounties::close_child_bounty(Origin::root(), 0, 0));
+
+		// The child bounty curator gets their deposit back, but no fee.
+		assert_eq!(Balances::free_balance(8), 10);
+		assert_eq!(Balances::reserved_balance(8), 0);
+		assert_eq!(ChildBounties::children_curator_fees(0), 0);
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 50);
+	});
+}
+
+#[test]
+fn close_pending_payout_child_bounty_fails() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		create_active_child_bounty();
+		assert_ok!(ChildBounties::award_child_bounty(Origin::signed(8), 0, 0, 7));
+
+		assert_noop!(
+			ChildBounties::close_child_bounty(Origin::signed(4), 0, 0),
+			BountiesError::PendingPayout,
+		);
+
+		// The curator has to be unassigned first, which slashes their deposit.
+		assert_ok!(ChildBounties::unassign_curator(Origin::signed(4), 0, 0));
+		assert_eq!(Balances::free_balance(8), 9);
+		assert_eq!(Balances::reserved_balance(8), 0);
+		assert_ok!(ChildBounties::close_child_bounty(Origin::signed(4), 0, 0));
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 50);
+	});
+}
+
+#[test]
+fn reject_origin_can_close_child_bounty_of_inactive_parent() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()));
+		assert_ok!(Bounties::unassign_curator(Origin::root(), 0));
+
+		assert_noop!(
+			ChildBounties::close_child_bounty(Origin::signed(4), 0, 0),
+			Error::<Test>::ParentBountyNotActive,
+		);
+		assert_ok!(ChildBounties::close_child_bounty(Origin::root(), 0, 0));
+
+		// Now the parent bounty can be closed as well.
+		assert_ok!(Bounties::close_bounty(Origin::root(), 0));
+		assert_eq!(ChildBounties::parent_child_bounties(0), 0);
+	});
+}
+
+#[test]
+fn unassign_curator_of_added_child_bounty_fails() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()));
+
+		assert_noop!(
+			ChildBounties::unassign_curator(Origin::signed(4), 0, 0),
+			BountiesError::UnexpectedStatus,
+		);
+	});
+}
+
+#[test]
+fn unassign_proposed_curator_works() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		assert_ok!(ChildBounties::add_child_bounty(Origin::signed(4), 0, 10, b"12345-p1".to_vec()));
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 2));
+
+		assert_noop!(ChildBounties::unassign_curator(Origin::signed(5), 0, 0), BadOrigin);
+
+		// The proposed curator, the parent curator and the reject origin can all unassign.
+		assert_ok!(ChildBounties::unassign_curator(Origin::signed(8), 0, 0));
+		assert_eq!(ChildBounties::child_bounties(0, 0).unwrap().status, ChildBountyStatus::Added);
+		assert_eq!(ChildBounties::children_curator_fees(0), 0);
+
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 2));
+		assert_ok!(ChildBounties::unassign_curator(Origin::signed(4), 0, 0));
+		assert_eq!(ChildBounties::child_bounties(0, 0).unwrap().status, ChildBountyStatus::Added);
+
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 2));
+		assert_ok!(ChildBounties::unassign_curator(Origin::root(), 0, 0));
+		assert_eq!(ChildBounties::child_bounties(0, 0).unwrap().status, ChildBountyStatus::Added);
+		assert_eq!(ChildBounties::children_curator_fees(0), 0);
+	});
+}
+
+#[test]
+fn unassign_active_curator_works() {
+	new_test_ext().execute_with(|| {
+		create_active_parent_bounty();
+		create_active_child_bounty();
+		assert_eq!(Balances::free_balance(8), 9);
+		assert_eq!(Balances::reserved_balance(8), 1);
+
+		// The parent curator slashes the child bounty curator.
+		assert_ok!(ChildBounties::unassign_curator(Origin::signed(4), 0, 0));
+		assert_eq!(ChildBounties::child_bounties(0, 0).unwrap().status, ChildBountyStatus::Added);
+		assert_eq!(ChildBounties::children_curator_fees(0), 0);
+		assert_eq!(Balances::free_balance(8), 9);
+		assert_eq!(Balances::reserved_balance(8), 0);
+
+		// The child bounty curator can give up without being slashed.
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 2));
+		assert_ok!(ChildBounties::accept_curator(Origin::signed(8), 0, 0));
+		assert_ok!(ChildBounties::unassign_curator(Origin::signed(8), 0, 0));
+		assert_eq!(Balances::free_balance(8), 9);
+		assert_eq!(Balances::reserved_balance(8), 0);
+
+		// Anyone else has to wait until the curator is inactive.
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 2));
+		assert_ok!(ChildBounties::accept_curator(Origin::signed(8), 0, 0));
+		assert_noop!(
+			ChildBounties::unassign_curator(Origin::signed(5), 0, 0),
+			BountiesError::Premature,
+		);
+		System::set_block_number(23);
+		assert_ok!(ChildBounties::unassign_curator(Origin::signed(5), 0, 0));
+		assert_eq!(Balances::free_balance(8), 8);
+		assert_eq!(Balances::reserved_balance(8), 0);
+
+		// The reject origin slashes the child bounty curator.
+		assert_ok!(ChildBounties::propose_curator(Origin::signed(4), 0, 0, 8, 2));
+		assert_ok!(ChildBounties::accept_curator(Origin::signed(8), 0, 0));
+		assert_ok!(ChildBounties::unassign_curator(Origin::root(), 0, 0));
+		assert_eq!(Balances::free_balance(8), 7);
+		assert_eq!(Balances::reserved_balance(8), 0);
+		assert_eq!(ChildBounties::children_curator_fees(0), 0);
+	});
+}
@@ -334,6 +334,14 @@ pub mod pallet {
 		WrongProposalLength,
 	}
 
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), &'static str> {
+			Self::do_try_state()
+		}
+	}
+
 	// Note that councillor operations are assigned to the operational class.
 	#[pallet::call]
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
@@ -815,6 +823,53 @@ fn get_result_weight(result: DispatchResultWithPostInfo) -> Option<Weight> {
 	}
 }
 
+#[cfg(any(feature = "try-runtime", test))]
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Check the invariants of the collective:
+	///
+	/// * the members are sorted, and the prime, if any, is one of them.
+	/// * every ongoing proposal has exactly one proposal and one voting entry, and there are no
+	///   other proposal or voting entries.
+	/// * the index of every proposal was issued by `ProposalCount`, and only members have voted on
+	///   it, at most once.
+	pub(crate) fn do_try_state() -> Result<(), &'static str> {
+		let members = Self::members();
+		ensure!(members.windows(2).all(|w| w[0] <= w[1]), "members are not sorted");
+		if let Some(prime) = Self::prime() {
+			ensure!(members.binary_search(&prime).is_ok(), "prime is not a member");
+		}
+
+		let proposals = Self::proposals();
+		ensure!(
+			ProposalOf::<T, I>::iter_keys().count() == proposals.len(),
+			"number of proposals does not match the stored proposals"
+		);
+		ensure!(
+			Voting::<T, I>::iter_keys().count() == proposals.len(),
+			"number of votings does not match the stored proposals"
+		);
+
+		let proposal_count = Self::proposal_count();
+		for hash in proposals.iter() {
+			ensure!(ProposalOf::<T, I>::contains_key(hash), "proposal has no stored call");
+			let votes = Self::voting(hash).ok_or("proposal has no voting")?;
+			ensure!(votes.index < proposal_count, "proposal index was never issued");
+
+			let mut voters = votes.ayes.iter().chain(votes.nays.iter()).collect::<Vec<_>>();
+			ensure!(
+				voters.iter().all(|who| members.binary_search(who).is_ok()),
+				"a non-member has voted"
+			);
+			let voters_count = voters.len();
+			voters.sort();
+			voters.dedup();
+			ensure!(voters.len() == voters_count, "a member has voted more than once");
+		}
+
+		Ok(())
+	}
+}
+
 impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// Check whether `who` is a member of the collective.
 	pub fn is_member(who: &T::AccountId) -> bool {
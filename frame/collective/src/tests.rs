@@ -170,6 +170,17 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	ext
 }
 
+/// Run `test` in fresh externalities and check the invariants of every collective afterwards.
+pub fn build_and_execute(test: impl FnOnce()) {
+	let mut ext = new_test_ext();
+	ext.execute_with(test);
+	ext.execute_with(|| {
+		assert_eq!(Collective::do_try_state(), Ok(()));
+		assert_eq!(CollectiveMajority::do_try_state(), Ok(()));
+		assert_eq!(DefaultCollective::do_try_state(), Ok(()));
+	});
+}
+
 fn make_proposal(value: u64) -> Call {
 	Call::System(frame_system::Call::remark { remark: value.encode() })
 }
@@ -180,7 +191,7 @@ fn record(event: Event) -> EventRecord<Event, H256> {
 
 #[test]
 fn motions_basic_environment_works() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		assert_eq!(Collective::members(), vec![1, 2, 3]);
 		assert_eq!(*Collective::proposals(), Vec::<H256>::new());
 	});
@@ -188,7 +199,7 @@ fn motions_basic_environment_works() {
 
 #[test]
 fn close_works() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = make_proposal(42);
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
 		let proposal_weight = proposal.get_dispatch_info().weight;
@@ -248,7 +259,7 @@ fn close_works() {
 
 #[test]
 fn proposal_weight_limit_works_on_approve() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = Call::Collective(crate::Call::set_members {
 			new_members: vec![1, 2, 3],
 			prime: None,
@@ -278,7 +289,7 @@ fn proposal_weight_limit_works_on_approve() {
 
 #[test]
 fn proposal_weight_limit_ignored_on_disapprove() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = Call::Collective(crate::Call::set_members {
 			new_members: vec![1, 2, 3],
 			prime: None,
@@ -308,7 +319,7 @@ fn proposal_weight_limit_ignored_on_disapprove() {
 
 #[test]
 fn close_with_prime_works() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = make_proposal(42);
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
 		let proposal_weight = proposal.get_dispatch_info().weight;
@@ -368,7 +379,7 @@ fn close_with_prime_works() {
 
 #[test]
 fn close_with_voting_prime_works() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = make_proposal(42);
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
 		let proposal_weight = proposal.get_dispatch_info().weight;
@@ -432,7 +443,7 @@ fn close_with_voting_prime_works() {
 
 #[test]
 fn close_with_no_prime_but_majority_works() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = make_proposal(42);
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
 		let proposal_weight = proposal.get_dispatch_info().weight;
@@ -512,7 +523,7 @@ fn close_with_no_prime_but_majority_works() {
 
 #[test]
 fn removal_of_old_voters_votes_works() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = make_proposal(42);
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
 		let hash = BlakeTwo256::hash_of(&proposal);
@@ -560,7 +571,7 @@ fn removal_of_old_voters_votes_works() {
 
 #[test]
 fn removal_of_old_voters_votes_works_with_set_members() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = make_proposal(42);
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
 		let hash = BlakeTwo256::hash_of(&proposal);
@@ -608,7 +619,7 @@ fn removal_of_old_voters_votes_works_with_set_members() {
 
 #[test]
 fn propose_works() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = make_proposal(42);
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
 		let hash = proposal.blake2_256().into();
@@ -640,7 +651,7 @@ fn propose_works() {
 
 #[test]
 fn limit_active_proposals() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		for i in 0..MaxProposals::get() {
 			let proposal = make_proposal(i as u64);
 			let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
@@ -662,7 +673,7 @@ fn limit_active_proposals() {
 
 #[test]
 fn correct_validate_and_get_proposal() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = Call::Collective(crate::Call::set_members {
 			new_members: vec![1, 2, 3],
 			prime: None,
@@ -699,7 +710,7 @@ fn correct_validate_and_get_proposal() {
 
 #[test]
 fn motions_ignoring_non_collective_proposals_works() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = make_proposal(42);
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
 		assert_noop!(
@@ -711,7 +722,7 @@ fn motions_ignoring_non_collective_proposals_works() {
 
 #[test]
 fn motions_ignoring_non_collective_votes_works() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = make_proposal(42);
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
 		let hash: H256 = proposal.blake2_256().into();
@@ -730,7 +741,7 @@ fn motions_ignoring_non_collective_votes_works() {
 
 #[test]
 fn motions_ignoring_bad_index_collective_vote_works() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		System::set_block_number(3);
 		let proposal = make_proposal(42);
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
@@ -750,7 +761,7 @@ fn motions_ignoring_bad_index_collective_vote_works() {
 
 #[test]
 fn motions_vote_after_works() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = make_proposal(42);
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
 		let hash: H256 = proposal.blake2_256().into();
@@ -819,7 +830,7 @@ fn motions_vote_after_works() {
 
 #[test]
 fn motions_all_first_vote_free_works() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = make_proposal(42);
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
 		let hash: H256 = proposal.blake2_256().into();
@@ -877,7 +888,7 @@ fn motions_all_first_vote_free_works() {
 
 #[test]
 fn motions_reproposing_disapproved_works() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = make_proposal(42);
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
 		let proposal_weight = proposal.get_dispatch_info().weight;
@@ -903,7 +914,7 @@ fn motions_reproposing_disapproved_works() {
 
 #[test]
 fn motions_approval_with_enough_votes_and_lower_voting_threshold_works() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = Call::Democracy(mock_democracy::Call::external_propose_majority {});
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
 		let proposal_weight = proposal.get_dispatch_info().weight;
@@ -1019,7 +1030,7 @@ fn motions_approval_with_enough_votes_and_lower_voting_threshold_works() {
 
 #[test]
 fn motions_disapproval_works() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = make_proposal(42);
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
 		let proposal_weight = proposal.get_dispatch_info().weight;
@@ -1070,7 +1081,7 @@ fn motions_disapproval_works() {
 
 #[test]
 fn motions_approval_works() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = make_proposal(42);
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
 		let proposal_weight = proposal.get_dispatch_info().weight;
@@ -1125,7 +1136,7 @@ fn motions_approval_works() {
 
 #[test]
 fn motion_with_no_votes_closes_with_disapproval() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = make_proposal(42);
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
 		let proposal_weight = proposal.get_dispatch_info().weight;
@@ -1180,7 +1191,7 @@ fn close_disapprove_does_not_care_about_weight_or_len() {
 	// This test confirms that if you close a proposal that would be disapproved,
 	// we do not care about the proposal length or proposal weight since it will
 	// not be read from storage or executed.
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = make_proposal(42);
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
 		let hash: H256 = proposal.blake2_256().into();
@@ -1212,7 +1223,7 @@ fn close_disapprove_does_not_care_about_weight_or_len() {
 
 #[test]
 fn disapprove_proposal_works() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		let proposal = make_proposal(42);
 		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
 		let hash: H256 = proposal.blake2_256().into();
@@ -1269,7 +1280,7 @@ fn genesis_build_panics_with_duplicate_members() {
 
 #[test]
 fn migration_v4() {
-	new_test_ext().execute_with(|| {
+	build_and_execute(|| {
 		use frame_support::traits::PalletInfoAccess;
 
 		let old_pallet = "OldCollective";
@@ -1309,3 +1320,30 @@ fn migration_v4() {
 		crate::migrations::v4::post_migrate::<DefaultCollective, _>(old_pallet);
 	});
 }
+
+#[test]
+fn try_state_detects_unsorted_members() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Collective::do_try_state(), Ok(()));
+
+		Members::<Test, Instance1>::put(vec![3, 1, 2]);
+		assert_eq!(Collective::do_try_state(), Err("members are not sorted"));
+	});
+}
+
+#[test]
+fn try_state_detects_proposal_without_voting() {
+	new_test_ext().execute_with(|| {
+		let proposal = make_proposal(42);
+		let proposal_len: u32 = proposal.using_encoded(|p| p.len() as u32);
+		let hash = BlakeTwo256::hash_of(&proposal);
+		assert_ok!(Collective::propose(Origin::signed(1), 3, Box::new(proposal), proposal_len));
+		assert_eq!(Collective::do_try_state(), Ok(()));
+
+		Voting::<Test, Instance1>::remove(hash);
+		assert_eq!(
+			Collective::do_try_state(),
+			Err("number of votings does not match the stored proposals")
+		);
+	});
+}
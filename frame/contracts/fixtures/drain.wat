@@ -42,7 +42,7 @@
 					(i32.const 0)	;; Pointer to the buffer with value to transfer
 					(i32.const 8)	;; Length of the buffer with value to transfer
 				)
-				(i32.const 4) ;; ReturnCode::BelowSubsistenceThreshold
+				(i32.const 5) ;; ReturnCode::TransferFailed
 			)
 		)
 	)
@@ -25,7 +25,8 @@
 
 use codec::Codec;
 use pallet_contracts_primitives::{
-	Code, ContractExecResult, ContractInstantiateResult, GetStorageResult,
+	Code, CodeUploadResult, ContractCallTrace, ContractExecResult, ContractInstantiateResult,
+	GetStorageResult,
 };
 use sp_std::vec::Vec;
 
@@ -45,8 +46,9 @@ sp_api::decl_runtime_apis! {
 			dest: AccountId,
 			value: Balance,
 			gas_limit: u64,
+			storage_deposit_limit: Option<Balance>,
 			input_data: Vec<u8>,
-		) -> ContractExecResult;
+		) -> ContractExecResult<Balance>;
 
 		/// Instantiate a new contract.
 		///
@@ -55,10 +57,20 @@ sp_api::decl_runtime_apis! {
 			origin: AccountId,
 			endowment: Balance,
 			gas_limit: u64,
+			storage_deposit_limit: Option<Balance>,
 			code: Code<Hash>,
 			data: Vec<u8>,
 			salt: Vec<u8>,
-		) -> ContractInstantiateResult<AccountId>;
+		) -> ContractInstantiateResult<AccountId, Balance>;
+
+		/// Upload new code without instantiating a contract from it.
+		///
+		/// See `pallet_contracts::Pallet::upload_code`.
+		fn upload_code(
+			origin: AccountId,
+			code: Vec<u8>,
+			storage_deposit_limit: Option<Balance>,
+		) -> CodeUploadResult<Hash, Balance>;
 
 		/// Query a given storage key in a given contract.
 		///
@@ -69,5 +81,13 @@ sp_api::decl_runtime_apis! {
 			address: AccountId,
 			key: [u8; 32],
 		) -> GetStorageResult;
+
+		/// Apply the extrinsics of `block` and trace the contract calls and instantiations
+		/// they dispatch, with debug message recording enabled.
+		///
+		/// This must be called on the state of the parent of `block`. Calls nested in other
+		/// dispatchables like `utility::batch` are traced as well. Contract calls that are not
+		/// dispatched by an extrinsic, for example those made from `on_initialize`, are not.
+		fn trace_block(block: Block) -> Vec<ContractCallTrace<AccountId, Balance, Hash>>;
 	}
 }
@@ -17,12 +17,18 @@
 
 //! Node-specific RPC methods for interaction with contracts.
 
-use std::sync::Arc;
+use std::{marker::PhantomData, sync::Arc};
 
 use codec::Codec;
+use futures::{stream, task::Spawn, SinkExt, StreamExt};
 use jsonrpc_core::{Error, ErrorCode, Result};
 use jsonrpc_derive::rpc;
-use pallet_contracts_primitives::{Code, ContractExecResult, ContractInstantiateResult};
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use pallet_contracts_primitives::{
+	Code, CodeUploadResult, ContractCallTrace, ContractExecResult, ContractInstantiateResult,
+};
+use sc_client_api::BlockBackend;
+use sc_rpc_api::DenyUnsafe;
 use serde::{Deserialize, Serialize};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
@@ -37,6 +43,7 @@ pub use pallet_contracts_rpc_runtime_api::ContractsApi as ContractsRuntimeApi;
 
 const RUNTIME_ERROR: i64 = 1;
 const CONTRACT_DOESNT_EXIST: i64 = 2;
+const BLOCK_NOT_FOUND: i64 = 3;
 
 pub type Weight = u64;
 
@@ -78,6 +85,7 @@ pub struct CallRequest<AccountId> {
 	dest: AccountId,
 	value: NumberOrHex,
 	gas_limit: NumberOrHex,
+	storage_deposit_limit: Option<NumberOrHex>,
 	input_data: Bytes,
 }
 
@@ -89,11 +97,22 @@ pub struct InstantiateRequest<AccountId, Hash> {
 	origin: AccountId,
 	endowment: NumberOrHex,
 	gas_limit: NumberOrHex,
+	storage_deposit_limit: Option<NumberOrHex>,
 	code: Code<Hash>,
 	data: Bytes,
 	salt: Bytes,
 }
 
+/// A struct that encodes RPC parameters required for a call to upload a new code.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(deny_unknown_fields)]
+pub struct CodeUploadRequest<AccountId> {
+	origin: AccountId,
+	code: Bytes,
+	storage_deposit_limit: Option<NumberOrHex>,
+}
+
 /// Contracts RPC methods.
 #[rpc]
 pub trait ContractsApi<BlockHash, BlockNumber, AccountId, Balance, Hash> {
@@ -108,7 +127,7 @@ pub trait ContractsApi<BlockHash, BlockNumber, AccountId, Balance, Hash> {
 		&self,
 		call_request: CallRequest<AccountId>,
 		at: Option<BlockHash>,
-	) -> Result<ContractExecResult>;
+	) -> Result<ContractExecResult<Balance>>;
 
 	/// Instantiate a new contract.
 	///
@@ -121,7 +140,20 @@ pub trait ContractsApi<BlockHash, BlockNumber, AccountId, Balance, Hash> {
 		&self,
 		instantiate_request: InstantiateRequest<AccountId, Hash>,
 		at: Option<BlockHash>,
-	) -> Result<ContractInstantiateResult<AccountId>>;
+	) -> Result<ContractInstantiateResult<AccountId, Balance>>;
+
+	/// Upload new code without instantiating a contract from it.
+	///
+	/// This call is performed locally without submitting any transactions. Thus executing this
+	/// won't change any state.
+	///
+	/// This method is useful for UIs to dry-run code upload.
+	#[rpc(name = "contracts_upload_code")]
+	fn upload_code(
+		&self,
+		upload_request: CodeUploadRequest<AccountId>,
+		at: Option<BlockHash>,
+	) -> Result<CodeUploadResult<Hash, Balance>>;
 
 	/// Returns the value under a specified storage `key` in a contract given by `address` param,
 	/// or `None` if it is not set.
@@ -172,20 +204,23 @@ where
 		&self,
 		call_request: CallRequest<AccountId>,
 		at: Option<<Block as BlockT>::Hash>,
-	) -> Result<ContractExecResult> {
+	) -> Result<ContractExecResult<Balance>> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(at.unwrap_or_else(||
 			// If the block hash is not supplied assume the best block.
 			self.client.info().best_hash));
 
-		let CallRequest { origin, dest, value, gas_limit, input_data } = call_request;
+		let CallRequest { origin, dest, value, gas_limit, storage_deposit_limit, input_data } =
+			call_request;
 
 		let value: Balance = decode_hex(value, "balance")?;
 		let gas_limit: Weight = decode_hex(gas_limit, "weight")?;
+		let storage_deposit_limit: Option<Balance> =
+			storage_deposit_limit.map(|l| decode_hex(l, "balance")).transpose()?;
 		limit_gas(gas_limit)?;
 
 		let exec_result = api
-			.call(&at, origin, dest, value, gas_limit, input_data.to_vec())
+			.call(&at, origin, dest, value, gas_limit, storage_deposit_limit, input_data.to_vec())
 			.map_err(runtime_error_into_rpc_err)?;
 
 		Ok(exec_result)
@@ -195,26 +230,66 @@ where
 		&self,
 		instantiate_request: InstantiateRequest<AccountId, Hash>,
 		at: Option<<Block as BlockT>::Hash>,
-	) -> Result<ContractInstantiateResult<AccountId>> {
+	) -> Result<ContractInstantiateResult<AccountId, Balance>> {
 		let api = self.client.runtime_api();
 		let at = BlockId::hash(at.unwrap_or_else(||
 			// If the block hash is not supplied assume the best block.
 			self.client.info().best_hash));
 
-		let InstantiateRequest { origin, endowment, gas_limit, code, data, salt } =
-			instantiate_request;
+		let InstantiateRequest {
+			origin,
+			endowment,
+			gas_limit,
+			storage_deposit_limit,
+			code,
+			data,
+			salt,
+		} = instantiate_request;
 
 		let endowment: Balance = decode_hex(endowment, "balance")?;
 		let gas_limit: Weight = decode_hex(gas_limit, "weight")?;
+		let storage_deposit_limit: Option<Balance> =
+			storage_deposit_limit.map(|l| decode_hex(l, "balance")).transpose()?;
 		limit_gas(gas_limit)?;
 
 		let exec_result = api
-			.instantiate(&at, origin, endowment, gas_limit, code, data.to_vec(), salt.to_vec())
+			.instantiate(
+				&at,
+				origin,
+				endowment,
+				gas_limit,
+				storage_deposit_limit,
+				code,
+				data.to_vec(),
+				salt.to_vec(),
+			)
 			.map_err(runtime_error_into_rpc_err)?;
 
 		Ok(exec_result)
 	}
 
+	fn upload_code(
+		&self,
+		upload_request: CodeUploadRequest<AccountId>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<CodeUploadResult<Hash, Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let CodeUploadRequest { origin, code, storage_deposit_limit } = upload_request;
+
+		let storage_deposit_limit: Option<Balance> =
+			storage_deposit_limit.map(|l| decode_hex(l, "balance")).transpose()?;
+
+		let result = api
+			.upload_code(&at, origin, code.to_vec(), storage_deposit_limit)
+			.map_err(runtime_error_into_rpc_err)?;
+
+		Ok(result)
+	}
+
 	fn get_storage(
 		&self,
 		address: AccountId,
@@ -236,6 +311,141 @@ where
 	}
 }
 
+/// Contracts RPC methods that replay blocks which are already part of the chain.
+#[rpc]
+pub trait ContractsTraceApi<BlockHash, AccountId, Balance, Hash> {
+	/// RPC Metadata
+	type Metadata;
+
+	/// Replay the specified block and trace the contract calls and instantiations dispatched
+	/// by its extrinsics with debug message recording enabled.
+	///
+	/// One notification is sent for every traced call. It contains the debug message, the
+	/// events emitted by contracts and the gas consumed during its execution. Calls nested in
+	/// other dispatchables like `utility::batch` are traced as well. The subscription produces
+	/// no further notifications after the last traced call.
+	///
+	/// This is an unsafe RPC because replaying a whole block is expensive.
+	#[pubsub(
+		subscription = "contracts_blockTrace",
+		subscribe,
+		name = "contracts_subscribeBlockTrace"
+	)]
+	fn subscribe_block_trace(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<ContractCallTrace<AccountId, Balance, Hash>>,
+		block: BlockHash,
+	);
+
+	/// Unsubscribe from a block trace.
+	#[pubsub(
+		subscription = "contracts_blockTrace",
+		unsubscribe,
+		name = "contracts_unsubscribeBlockTrace"
+	)]
+	fn unsubscribe_block_trace(
+		&self,
+		metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool>;
+}
+
+/// An implementation of the contracts RPC methods that replay blocks.
+pub struct ContractsTrace<C, B> {
+	client: Arc<C>,
+	manager: SubscriptionManager,
+	deny_unsafe: DenyUnsafe,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> ContractsTrace<C, B> {
+	/// Create new `ContractsTrace` which drives its subscriptions on the given `executor`.
+	pub fn new<E>(client: Arc<C>, executor: E, deny_unsafe: DenyUnsafe) -> Self
+	where
+		E: Spawn + Send + Sync + 'static,
+	{
+		ContractsTrace {
+			client,
+			manager: SubscriptionManager::new(Arc::new(executor)),
+			deny_unsafe,
+			_marker: Default::default(),
+		}
+	}
+}
+
+impl<C, Block, AccountId, Balance, Hash>
+	ContractsTraceApi<<Block as BlockT>::Hash, AccountId, Balance, Hash> for ContractsTrace<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + BlockBackend<Block>,
+	C::Api: ContractsRuntimeApi<
+		Block,
+		AccountId,
+		Balance,
+		<<Block as BlockT>::Header as HeaderT>::Number,
+		Hash,
+	>,
+	AccountId: Codec + Send + 'static,
+	Balance: Codec + Send + 'static,
+	Hash: Codec + Send + 'static,
+{
+	type Metadata = sc_rpc_api::Metadata;
+
+	fn subscribe_block_trace(
+		&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<ContractCallTrace<AccountId, Balance, Hash>>,
+		block: <Block as BlockT>::Hash,
+	) {
+		let block = self
+			.deny_unsafe
+			.check_if_safe()
+			.map_err(Error::from)
+			.and_then(|()| {
+				self.client.block(&BlockId::hash(block)).map_err(runtime_error_into_rpc_err)
+			})
+			.and_then(|block| {
+				block.map(|signed| signed.block).ok_or_else(|| Error {
+					code: ErrorCode::ServerError(BLOCK_NOT_FOUND),
+					message: "The specified block doesn't exist.".into(),
+					data: None,
+				})
+			});
+		let block = match block {
+			Ok(block) => block,
+			Err(err) => {
+				let _ = subscriber.reject(err);
+				return
+			},
+		};
+
+		// The block is replayed on top of the state of its parent.
+		let parent = BlockId::hash(*block.header().parent_hash());
+		let client = self.client.clone();
+		self.manager.add(subscriber, move |sink| async move {
+			let traces = match client.runtime_api().trace_block(&parent, block) {
+				Ok(traces) => traces,
+				Err(err) => {
+					log::warn!("Failed to trace contract calls of block: {:?}", err);
+					return
+				},
+			};
+			let _ = stream::iter(traces.into_iter().map(|trace| Ok(Ok::<_, Error>(trace))))
+				.forward(sink.sink_map_err(|e| log::warn!("Error sending notifications: {:?}", e)))
+				.await;
+		});
+	}
+
+	fn unsubscribe_block_trace(
+		&self,
+		_metadata: Option<Self::Metadata>,
+		id: SubscriptionId,
+	) -> Result<bool> {
+		Ok(self.manager.cancel(id))
+	}
+}
+
 /// Converts a runtime trap into an RPC error.
 fn runtime_error_into_rpc_err(err: impl std::fmt::Debug) -> Error {
 	Error {
@@ -271,7 +481,148 @@ fn limit_gas(gas_limit: Weight) -> Result<()> {
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use futures::executor::{self, ThreadPool};
+	use pallet_contracts_rpc_runtime_api::ContractsApi;
+	use sp_api::ApiRef;
 	use sp_core::U256;
+	use sp_runtime::{
+		generic::SignedBlock,
+		testing::{Block as RawBlock, ExtrinsicWrapper, Header},
+		DispatchError, Justifications,
+	};
+
+	type Block = RawBlock<ExtrinsicWrapper<u64>>;
+	type TestTrace = ContractsTrace<TestClient, Block>;
+	type TraceSubscriber = Subscriber<ContractCallTrace<u64, u64, H256>>;
+
+	/// A chain made of a single block whose extrinsics are the accounts that call a contract.
+	#[derive(Clone)]
+	struct TestClient {
+		block: Block,
+	}
+
+	struct RuntimeApi;
+
+	impl ProvideRuntimeApi<Block> for TestClient {
+		type Api = RuntimeApi;
+
+		fn runtime_api<'a>(&'a self) -> ApiRef<'a, Self::Api> {
+			RuntimeApi.into()
+		}
+	}
+
+	impl BlockBackend<Block> for TestClient {
+		fn block_body(
+			&self,
+			_id: &BlockId<Block>,
+		) -> sp_blockchain::Result<Option<Vec<<Block as BlockT>::Extrinsic>>> {
+			unimplemented!()
+		}
+
+		fn block_indexed_body(
+			&self,
+			_id: &BlockId<Block>,
+		) -> sp_blockchain::Result<Option<Vec<Vec<u8>>>> {
+			unimplemented!()
+		}
+
+		fn block(&self, id: &BlockId<Block>) -> sp_blockchain::Result<Option<SignedBlock<Block>>> {
+			Ok((*id == BlockId::hash(self.block.hash()))
+				.then(|| SignedBlock { block: self.block.clone(), justifications: None }))
+		}
+
+		fn block_status(
+			&self,
+			_id: &BlockId<Block>,
+		) -> sp_blockchain::Result<sp_consensus::BlockStatus> {
+			unimplemented!()
+		}
+
+		fn justifications(
+			&self,
+			_id: &BlockId<Block>,
+		) -> sp_blockchain::Result<Option<Justifications>> {
+			unimplemented!()
+		}
+
+		fn block_hash(&self, _number: u64) -> sp_blockchain::Result<Option<H256>> {
+			unimplemented!()
+		}
+
+		fn indexed_transaction(&self, _hash: &H256) -> sp_blockchain::Result<Option<Vec<u8>>> {
+			unimplemented!()
+		}
+	}
+
+	sp_api::mock_impl_runtime_apis! {
+		impl ContractsApi<Block, u64, u64, u64, H256> for RuntimeApi {
+			fn call(
+				_origin: u64,
+				_dest: u64,
+				_value: u64,
+				_gas_limit: u64,
+				_storage_deposit_limit: Option<u64>,
+				_input_data: Vec<u8>,
+			) -> ContractExecResult<u64> {
+				unimplemented!()
+			}
+
+			fn instantiate(
+				_origin: u64,
+				_endowment: u64,
+				_gas_limit: u64,
+				_storage_deposit_limit: Option<u64>,
+				_code: Code<H256>,
+				_data: Vec<u8>,
+				_salt: Vec<u8>,
+			) -> ContractInstantiateResult<u64, u64> {
+				unimplemented!()
+			}
+
+			fn upload_code(
+				_origin: u64,
+				_code: Vec<u8>,
+				_storage_deposit_limit: Option<u64>,
+			) -> CodeUploadResult<H256, u64> {
+				unimplemented!()
+			}
+
+			fn get_storage(
+				_address: u64,
+				_key: [u8; 32],
+			) -> pallet_contracts_primitives::GetStorageResult {
+				unimplemented!()
+			}
+
+			fn trace_block(block: Block) -> Vec<ContractCallTrace<u64, u64, H256>> {
+				block
+					.extrinsics
+					.iter()
+					.enumerate()
+					.map(|(index, origin)| ContractCallTrace {
+						extrinsic_index: index as u32,
+						origin: **origin,
+						result: ContractExecResult {
+							gas_consumed: 0,
+							gas_required: 0,
+							storage_deposit: Default::default(),
+							debug_message: b"Hello World!".to_vec(),
+							result: Err(DispatchError::BadOrigin),
+						},
+						events: vec![],
+					})
+					.collect()
+			}
+		}
+	}
+
+	fn test_client() -> Arc<TestClient> {
+		let block = Block {
+			header: Header::new_from_number(1),
+			extrinsics: vec![ExtrinsicWrapper::from(7), ExtrinsicWrapper::from(9)],
+		};
+		Arc::new(TestClient { block })
+	}
 
 	fn trim(json: &str) -> String {
 		json.chars().filter(|c| !c.is_whitespace()).collect()
@@ -287,12 +638,14 @@ mod tests {
 			"dest": "5DRakbLVnjVrW6niwLfHGW24EeCEvDAFGEXrtaYS5M4ynoom",
 			"value": "0x112210f4B16c1cb1",
 			"gasLimit": 1000000000000,
+			"storageDepositLimit": 5000,
 			"inputData": "0x8c97db39"
 		}
 		"#,
 		)
 		.unwrap();
 		assert_eq!(req.gas_limit.into_u256(), U256::from(0xe8d4a51000u64));
+		assert_eq!(req.storage_deposit_limit.map(|l| l.into_u256()), Some(5000.into()));
 		assert_eq!(req.value.into_u256(), U256::from(1234567890987654321u128));
 	}
 
@@ -316,6 +669,7 @@ mod tests {
 		assert_eq!(req.origin, "5CiPPseXPECbkjWCa6MnjNokrgYjMqmKndv2rSnekmSK2DjL");
 		assert_eq!(req.endowment.into_u256(), 0x88.into());
 		assert_eq!(req.gas_limit.into_u256(), 42.into());
+		assert!(req.storage_deposit_limit.is_none());
 		assert_eq!(&*req.data, [0x42, 0x99].as_ref());
 		assert_eq!(&*req.salt, [0x99, 0x88].as_ref());
 		let code = match req.code {
@@ -325,10 +679,28 @@ mod tests {
 		assert_eq!(&code, "0x1122");
 	}
 
+	#[test]
+	fn code_upload_request_should_serialize_deserialize_properly() {
+		type Req = CodeUploadRequest<String>;
+		let req: Req = serde_json::from_str(
+			r#"
+		{
+			"origin": "5CiPPseXPECbkjWCa6MnjNokrgYjMqmKndv2rSnekmSK2DjL",
+			"code": "0x8c97db39",
+			"storageDepositLimit": 5000
+		}
+		"#,
+		)
+		.unwrap();
+		assert_eq!(req.origin, "5CiPPseXPECbkjWCa6MnjNokrgYjMqmKndv2rSnekmSK2DjL");
+		assert_eq!(&*req.code, [0x8c, 0x97, 0xdb, 0x39].as_ref());
+		assert_eq!(req.storage_deposit_limit.map(|l| l.into_u256()), Some(5000.into()));
+	}
+
 	#[test]
 	fn call_result_should_serialize_deserialize_properly() {
 		fn test(expected: &str) {
-			let res: ContractExecResult = serde_json::from_str(expected).unwrap();
+			let res: ContractExecResult<u32> = serde_json::from_str(expected).unwrap();
 			let actual = serde_json::to_string(&res).unwrap();
 			assert_eq!(actual, trim(expected).as_str());
 		}
@@ -336,6 +708,7 @@ mod tests {
 			r#"{
 			"gasConsumed": 5000,
 			"gasRequired": 8000,
+			"storageDeposit": {"charge": 42000},
 			"debugMessage": "HelloWorld",
 			"result": {
 			  "Ok": {
@@ -349,6 +722,7 @@ mod tests {
 			r#"{
 			"gasConsumed": 3400,
 			"gasRequired": 5200,
+			"storageDeposit": {"refund": 4200},
 			"debugMessage": "HelloWorld",
 			"result": {
 			  "Err": "BadOrigin"
@@ -360,7 +734,8 @@ mod tests {
 	#[test]
 	fn instantiate_result_should_serialize_deserialize_properly() {
 		fn test(expected: &str) {
-			let res: ContractInstantiateResult<String> = serde_json::from_str(expected).unwrap();
+			let res: ContractInstantiateResult<String, u64> =
+				serde_json::from_str(expected).unwrap();
 			let actual = serde_json::to_string(&res).unwrap();
 			assert_eq!(actual, trim(expected).as_str());
 		}
@@ -368,6 +743,7 @@ mod tests {
 			r#"{
 			"gasConsumed": 5000,
 			"gasRequired": 8000,
+			"storageDeposit": {"charge": 42000},
 			"debugMessage": "HelloWorld",
 			"result": {
 			   "Ok": {
@@ -384,6 +760,7 @@ mod tests {
 			r#"{
 			"gasConsumed": 3400,
 			"gasRequired": 5200,
+			"storageDeposit": {"refund": 4200},
 			"debugMessage": "HelloWorld",
 			"result": {
 			  "Err": "BadOrigin"
@@ -391,4 +768,65 @@ mod tests {
 		}"#,
 		);
 	}
+
+	#[test]
+	fn code_upload_result_should_serialize_deserialize_properly() {
+		fn test(expected: &str) {
+			let res: CodeUploadResult<String, u64> = serde_json::from_str(expected).unwrap();
+			let actual = serde_json::to_string(&res).unwrap();
+			assert_eq!(actual, trim(expected).as_str());
+		}
+		test(
+			r#"{
+			"Ok": {
+				"codeHash": "0x1122",
+				"deposit": 16
+			}
+		}"#,
+		);
+		test(
+			r#"{
+			"Err": "BadOrigin"
+		}"#,
+		);
+	}
+
+	#[test]
+	fn block_trace_notifies_every_traced_call() {
+		let client = test_client();
+		let api = TestTrace::new(client.clone(), ThreadPool::new().unwrap(), DenyUnsafe::No);
+		let (subscriber, id, transport) = TraceSubscriber::new_test("test");
+
+		api.subscribe_block_trace(Default::default(), subscriber, client.block.hash());
+
+		assert!(matches!(executor::block_on(id), Ok(Ok(SubscriptionId::String(_)))));
+		let notifications = executor::block_on(transport.take(2).collect::<Vec<_>>());
+		assert!(notifications[0].contains(r#""extrinsicIndex":0,"origin":7"#));
+		assert!(notifications[1].contains(r#""extrinsicIndex":1,"origin":9"#));
+		assert!(notifications.iter().all(|n| n.contains(r#""debugMessage":"Hello World!""#)));
+	}
+
+	#[test]
+	fn block_trace_is_unsafe() {
+		let client = test_client();
+		let api = TestTrace::new(client.clone(), ThreadPool::new().unwrap(), DenyUnsafe::Yes);
+		let (subscriber, id, _transport) = TraceSubscriber::new_test("test");
+
+		api.subscribe_block_trace(Default::default(), subscriber, client.block.hash());
+
+		assert!(matches!(executor::block_on(id), Ok(Err(_))));
+	}
+
+	#[test]
+	fn block_trace_rejects_unknown_blocks() {
+		let api = TestTrace::new(test_client(), ThreadPool::new().unwrap(), DenyUnsafe::No);
+		let (subscriber, id, _transport) = TraceSubscriber::new_test("test");
+
+		api.subscribe_block_trace(Default::default(), subscriber, Default::default());
+
+		assert!(matches!(
+			executor::block_on(id),
+			Ok(Err(Error { code: ErrorCode::ServerError(BLOCK_NOT_FOUND), .. }))
+		));
+	}
 }
@@ -16,22 +16,29 @@
 // limitations under the License.
 
 use crate::{
-	gas::GasMeter, storage::Storage, AccountCounter, BalanceOf, CodeHash, Config, ContractInfo,
-	ContractInfoOf, Error, Event, Pallet as Contracts, Schedule,
+	gas::GasMeter,
+	storage::{
+		meter::{DepositOf, Meter as StorageMeter},
+		Storage,
+	},
+	trace, AccountCounter, BalanceOf, CodeHash, Config, ContractInfo, ContractInfoOf, Error, Event,
+	Pallet as Contracts, Schedule,
 };
 use frame_support::{
 	dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo, Dispatchable},
-	ensure,
 	storage::{with_transaction, TransactionOutcome},
-	traits::{Contains, Currency, ExistenceRequirement, Get, OriginTrait, Randomness, Time},
+	traits::{
+		Contains, Currency, ExistenceRequirement, Get, OriginTrait, Randomness, ReservableCurrency,
+		Time,
+	},
 	weights::Weight,
 };
 use frame_system::RawOrigin;
-use pallet_contracts_primitives::ExecReturnValue;
+use pallet_contracts_primitives::{ExecReturnValue, StorageDeposit};
 use smallvec::{Array, SmallVec};
 use sp_core::crypto::UncheckedFrom;
 use sp_io::crypto::secp256k1_ecdsa_recover_compressed;
-use sp_runtime::traits::{Convert, Saturating};
+use sp_runtime::traits::Convert;
 use sp_std::{marker::PhantomData, mem, prelude::*};
 
 /// When fields are added to the [`ContractInfo`] that can change during execution this
@@ -168,7 +175,9 @@ pub trait Ext: sealing::Sealed {
 	/// Returns the minimum balance that is required for creating an account.
 	fn minimum_balance(&self) -> BalanceOf<Self::T>;
 
-	/// Returns the deposit required to instantiate a contract.
+	/// Returns the storage deposit charged for the data structure of a contract.
+	///
+	/// This is in addition to the deposit for the storage items of a contract.
 	fn contract_deposit(&self) -> BalanceOf<Self::T>;
 
 	/// Returns a random number for the current block with the given subject.
@@ -209,6 +218,12 @@ pub trait Ext: sealing::Sealed {
 
 	/// Recovers ECDSA compressed public key based on signature and message hash.
 	fn ecdsa_recover(&self, signature: &[u8; 65], message_hash: &[u8; 32]) -> Result<[u8; 33], ()>;
+
+	/// Replace the code of the executing contract with the code stored under `hash`.
+	///
+	/// The refcount of the new code is incremented and the one of the previous code is
+	/// decremented. Fails if there is no code stored under `hash`.
+	fn set_code_hash(&mut self, hash: CodeHash<Self::T>) -> Result<(), DispatchError>;
 }
 
 /// Describes the different functions that can be exported by an [`Executable`].
@@ -255,7 +270,7 @@ pub trait Executable<T: Config>: Sized {
 	/// Charges weight proportional to the code size from the gas meter.
 	fn add_user(code_hash: CodeHash<T>, gas_meter: &mut GasMeter<T>) -> Result<(), DispatchError>;
 
-	/// Decrement the refcount by one and remove the code when it drops to zero.
+	/// Decrement the refcount by one if the code exists.
 	///
 	/// Returns the size of the original code.
 	///
@@ -291,9 +306,6 @@ pub trait Executable<T: Config>: Sized {
 
 	/// Sum of instrumented and pristine code len.
 	fn aggregate_code_len(&self) -> u32;
-
-	// The number of contracts using this executable.
-	fn refcount(&self) -> u32;
 }
 
 /// The complete call stack of a contract execution.
@@ -314,6 +326,8 @@ pub struct Stack<'a, T: Config, E> {
 	schedule: &'a Schedule<T>,
 	/// The gas meter where costs are charged to.
 	gas_meter: &'a mut GasMeter<T>,
+	/// The storage meter that settles the storage deposits with the origin.
+	storage_meter: &'a mut StorageMeter<T>,
 	/// The timestamp at the point of call stack instantiation.
 	timestamp: MomentOf<T>,
 	/// The block number at the time of call stack instantiation.
@@ -355,6 +369,8 @@ pub struct Frame<T: Config> {
 	entry_point: ExportedFunction,
 	/// The gas meter capped to the supplied gas limit.
 	nested_meter: GasMeter<T>,
+	/// The storage deposit caused by this frame which is not yet settled with the origin.
+	storage_deposit: DepositOf<T>,
 	/// If `false` the contract enabled its defense against reentrance attacks.
 	allows_reentry: bool,
 }
@@ -476,6 +492,7 @@ where
 		origin: T::AccountId,
 		dest: T::AccountId,
 		gas_meter: &'a mut GasMeter<T>,
+		storage_meter: &'a mut StorageMeter<T>,
 		schedule: &'a Schedule<T>,
 		value: BalanceOf<T>,
 		input_data: Vec<u8>,
@@ -485,6 +502,7 @@ where
 			FrameArgs::Call { dest, cached_info: None },
 			origin,
 			gas_meter,
+			storage_meter,
 			schedule,
 			value,
 			debug_message,
@@ -506,6 +524,7 @@ where
 		origin: T::AccountId,
 		executable: E,
 		gas_meter: &'a mut GasMeter<T>,
+		storage_meter: &'a mut StorageMeter<T>,
 		schedule: &'a Schedule<T>,
 		value: BalanceOf<T>,
 		input_data: Vec<u8>,
@@ -521,6 +540,7 @@ where
 			},
 			origin,
 			gas_meter,
+			storage_meter,
 			schedule,
 			value,
 			debug_message,
@@ -534,6 +554,7 @@ where
 		args: FrameArgs<T, E>,
 		origin: T::AccountId,
 		gas_meter: &'a mut GasMeter<T>,
+		storage_meter: &'a mut StorageMeter<T>,
 		schedule: &'a Schedule<T>,
 		value: BalanceOf<T>,
 		debug_message: Option<&'a mut Vec<u8>>,
@@ -544,6 +565,7 @@ where
 			origin,
 			schedule,
 			gas_meter,
+			storage_meter,
 			timestamp: T::Time::now(),
 			block_number: <frame_system::Pallet<T>>::block_number(),
 			account_counter,
@@ -593,12 +615,21 @@ where
 			}
 		};
 
+		// A new contract pays the deposit for its own `ContractInfo`.
+		let storage_deposit = match entry_point {
+			ExportedFunction::Constructor => {
+				StorageDeposit::Charge(Contracts::<T>::contract_deposit())
+			}
+			ExportedFunction::Call => Default::default(),
+		};
+
 		let frame = Frame {
 			value_transferred,
 			contract_info: CachedContract::Cached(contract_info),
 			account_id,
 			entry_point,
 			nested_meter: gas_meter.nested(gas_limit)?,
+			storage_deposit,
 			allows_reentry: true,
 		};
 
@@ -642,6 +673,8 @@ where
 	/// This can be either a call or an instantiate.
 	fn run(&mut self, executable: E, input_data: Vec<u8>) -> Result<ExecReturnValue, ExecError> {
 		let entry_point = self.top_frame().entry_point;
+		let deposit_before = self.storage_meter.total_deposit();
+		let events_before = trace::event_count();
 		let do_transaction = || {
 			// Every call or instantiate also optionally transferres balance.
 			self.initial_transfer()?;
@@ -668,6 +701,12 @@ where
 				);
 			}
 
+			// Settle the storage deposit while still inside the transaction so that
+			// the balance transfers are reverted together with the storage changes.
+			if output.is_success() {
+				self.settle_storage_deposit()?;
+			}
+
 			Ok(output)
 		};
 
@@ -681,10 +720,29 @@ where
 				_ => TransactionOutcome::Rollback((false, output)),
 			}
 		});
+		// The deposit of a reverted frame was never actually charged or refunded.
+		if !success {
+			self.storage_meter.revert_to(deposit_before);
+			trace::truncate_events(events_before);
+		}
 		self.pop_frame(success);
 		output
 	}
 
+	/// Settle the storage deposit accumulated by the current (top) frame with the origin.
+	///
+	/// Nothing needs to be settled for a terminated contract: Its whole balance including
+	/// the reserved deposit was already transferred to the beneficiary.
+	fn settle_storage_deposit(&mut self) -> DispatchResult {
+		let frame = self.frames.last_mut().unwrap_or(&mut self.first_frame);
+		let deposit = mem::take(&mut frame.storage_deposit);
+		if let CachedContract::Terminated = frame.contract_info {
+			return Ok(());
+		}
+		let info = frame.contract_info.get(&frame.account_id);
+		self.storage_meter.settle(&frame.account_id, info, deposit)
+	}
+
 	/// Remove the current (top) frame from the stack.
 	///
 	/// This is called after running the current frame. It commits cached values to storage
@@ -755,10 +813,9 @@ where
 	///
 	/// We only allow allow for draining all funds of the sender if `allow_death` is
 	/// is specified as `true`. Otherwise, any transfer that would bring the sender below the
-	/// subsistence threshold (for contracts) or the existential deposit (for plain accounts)
-	/// results in an error.
+	/// existential deposit results in an error. This applies to contracts and plain accounts
+	/// alike. The reserved storage deposit of a contract can never be transferred.
 	fn transfer(
-		sender_is_contract: bool,
 		allow_death: bool,
 		from: &T::AccountId,
 		to: &T::AccountId,
@@ -768,17 +825,10 @@ where
 			return Ok(());
 		}
 
-		let existence_requirement = match (allow_death, sender_is_contract) {
-			(true, _) => ExistenceRequirement::AllowDeath,
-			(false, true) => {
-				ensure!(
-					T::Currency::total_balance(from).saturating_sub(value)
-						>= Contracts::<T>::subsistence_threshold(),
-					Error::<T>::BelowSubsistenceThreshold,
-				);
-				ExistenceRequirement::KeepAlive
-			}
-			(false, false) => ExistenceRequirement::KeepAlive,
+		let existence_requirement = if allow_death {
+			ExistenceRequirement::AllowDeath
+		} else {
+			ExistenceRequirement::KeepAlive
 		};
 
 		T::Currency::transfer(from, to, value, existence_requirement)
@@ -791,21 +841,17 @@ where
 	fn initial_transfer(&self) -> DispatchResult {
 		let frame = self.top_frame();
 		let value = frame.value_transferred;
-		let subsistence_threshold = <Contracts<T>>::subsistence_threshold();
 
-		// If the value transferred to a new contract is less than the subsistence threshold
+		// If the value transferred to a new contract is less than the existential deposit
 		// we can error out early. This avoids executing the constructor in cases where
 		// we already know that the contract has too little balance.
-		if frame.entry_point == ExportedFunction::Constructor && value < subsistence_threshold {
+		if frame.entry_point == ExportedFunction::Constructor &&
+			value < T::Currency::minimum_balance()
+		{
 			return Err(<Error<T>>::NewContractNotFunded.into());
 		}
 
-		Self::transfer(self.caller_is_origin(), false, self.caller(), &frame.account_id, value)
-	}
-
-	/// Wether the caller is the initiator of the call stack.
-	fn caller_is_origin(&self) -> bool {
-		!self.frames.is_empty()
+		Self::transfer(false, self.caller(), &frame.account_id, value)
 	}
 
 	/// Reference to the current (top) frame.
@@ -938,8 +984,10 @@ where
 		let frame = self.top_frame_mut();
 		let info = frame.terminate();
 		Storage::<T>::queue_trie_for_deletion(&info)?;
+		// The storage is removed together with the contract. Therefore the deposit that was
+		// reserved for it is released and transferred alongside the rest of the balance.
+		T::Currency::unreserve(&frame.account_id, info.storage_deposit);
 		<Stack<'a, T, E>>::transfer(
-			true,
 			true,
 			&frame.account_id,
 			beneficiary,
@@ -955,7 +1003,7 @@ where
 	}
 
 	fn transfer(&mut self, to: &T::AccountId, value: BalanceOf<T>) -> DispatchResult {
-		Self::transfer(true, false, &self.top_frame().account_id, to, value)
+		Self::transfer(false, &self.top_frame().account_id, to, value)
 	}
 
 	fn get_storage(&mut self, key: &StorageKey) -> Option<Vec<u8>> {
@@ -964,7 +1012,9 @@ where
 
 	fn set_storage(&mut self, key: StorageKey, value: Option<Vec<u8>>) -> DispatchResult {
 		let frame = self.top_frame_mut();
-		Storage::<T>::write(frame.contract_info(), &key, value)
+		let deposit = Storage::<T>::write(frame.contract_info(), &key, value)?;
+		frame.storage_deposit = frame.storage_deposit.saturating_add(&deposit);
+		Ok(())
 	}
 
 	fn address(&self) -> &T::AccountId {
@@ -996,10 +1046,11 @@ where
 	}
 
 	fn contract_deposit(&self) -> BalanceOf<T> {
-		T::ContractDeposit::get()
+		Contracts::<T>::contract_deposit()
 	}
 
 	fn deposit_event(&mut self, topics: Vec<T::Hash>, data: Vec<u8>) {
+		trace::record_event::<T>(&self.top_frame().account_id, &topics, &data);
 		deposit_event::<Self::T>(
 			topics,
 			Event::ContractEmitted { contract: self.top_frame().account_id.clone(), data },
@@ -1046,6 +1097,20 @@ where
 	fn ecdsa_recover(&self, signature: &[u8; 65], message_hash: &[u8; 32]) -> Result<[u8; 33], ()> {
 		secp256k1_ecdsa_recover_compressed(&signature, &message_hash).map_err(|_| ())
 	}
+
+	fn set_code_hash(&mut self, hash: CodeHash<Self::T>) -> Result<(), DispatchError> {
+		let frame = self.top_frame_mut();
+		E::add_user(hash, &mut frame.nested_meter)?;
+		let prev_hash = frame.contract_info().code_hash;
+		E::remove_user(prev_hash, &mut frame.nested_meter)?;
+		frame.contract_info().code_hash = hash;
+		Contracts::<T>::deposit_event(Event::ContractCodeUpdated {
+			contract: frame.account_id.clone(),
+			new_code_hash: hash,
+			old_code_hash: prev_hash,
+		});
+		Ok(())
+	}
 }
 
 fn deposit_event<T: Config>(topics: Vec<T::Hash>, event: Event<T>) {
@@ -1250,10 +1315,6 @@ mod tests {
 		fn aggregate_code_len(&self) -> u32 {
 			0
 		}
-
-		fn refcount(&self) -> u32 {
-			self.refcount as u32
-		}
 	}
 
 	fn exec_success() -> ExecResult {
@@ -1282,7 +1343,16 @@ mod tests {
 			place_contract(&BOB, exec_ch);
 
 			assert_matches!(
-				MockStack::run_call(ALICE, BOB, &mut gas_meter, &schedule, value, vec![], None,),
+				MockStack::run_call(
+					ALICE,
+					BOB,
+					&mut gas_meter,
+					&mut StorageMeter::new(ALICE, None),
+					&schedule,
+					value,
+					vec![],
+					None,
+				),
 				Ok(_)
 			);
 		});
@@ -1301,7 +1371,7 @@ mod tests {
 			set_balance(&origin, 100);
 			set_balance(&dest, 0);
 
-			MockStack::transfer(true, false, &origin, &dest, 55).unwrap();
+			MockStack::transfer(false, &origin, &dest, 55).unwrap();
 
 			assert_eq!(get_balance(&origin), 45);
 			assert_eq!(get_balance(&dest), 55);
@@ -1329,6 +1399,7 @@ mod tests {
 				origin.clone(),
 				dest.clone(),
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(origin.clone(), None),
 				&schedule,
 				55,
 				vec![],
@@ -1352,7 +1423,7 @@ mod tests {
 		ExtBuilder::default().build().execute_with(|| {
 			set_balance(&origin, 0);
 
-			let result = MockStack::transfer(false, false, &origin, &dest, 100);
+			let result = MockStack::transfer(false, &origin, &dest, 100);
 
 			assert_eq!(result, Err(Error::<Test>::TransferFailed.into()));
 			assert_eq!(get_balance(&origin), 0);
@@ -1375,9 +1446,10 @@ mod tests {
 			place_contract(&BOB, return_ch);
 
 			let result = MockStack::run_call(
-				origin,
+				origin.clone(),
 				dest,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(origin, None),
 				&schedule,
 				0,
 				vec![],
@@ -1405,9 +1477,10 @@ mod tests {
 			place_contract(&BOB, return_ch);
 
 			let result = MockStack::run_call(
-				origin,
+				origin.clone(),
 				dest,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(origin, None),
 				&schedule,
 				0,
 				vec![],
@@ -1436,6 +1509,7 @@ mod tests {
 				ALICE,
 				BOB,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(ALICE, None),
 				&schedule,
 				0,
 				vec![1, 2, 3, 4],
@@ -1455,19 +1529,20 @@ mod tests {
 		// This one tests passing the input data into a contract via instantiate.
 		ExtBuilder::default().build().execute_with(|| {
 			let schedule = <Test as Config>::Schedule::get();
-			let subsistence = Contracts::<Test>::subsistence_threshold();
+			let min_balance = <Test as Config>::Currency::minimum_balance();
 			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
 			let executable =
 				MockExecutable::from_storage(input_data_ch, &schedule, &mut gas_meter).unwrap();
 
-			set_balance(&ALICE, subsistence * 10);
+			set_balance(&ALICE, min_balance * 1000);
 
 			let result = MockStack::run_instantiate(
 				ALICE,
 				executable,
 				&mut gas_meter,
+				&mut StorageMeter::new(ALICE, None),
 				&schedule,
-				subsistence * 3,
+				min_balance * 3,
 				vec![1, 2, 3, 4],
 				&[],
 				None,
@@ -1513,6 +1588,7 @@ mod tests {
 				ALICE,
 				BOB,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(ALICE, None),
 				&schedule,
 				value,
 				vec![],
@@ -1558,6 +1634,7 @@ mod tests {
 				origin.clone(),
 				dest.clone(),
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(origin.clone(), None),
 				&schedule,
 				0,
 				vec![],
@@ -1595,6 +1672,7 @@ mod tests {
 				ALICE,
 				BOB,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(ALICE, None),
 				&schedule,
 				0,
 				vec![],
@@ -1620,6 +1698,7 @@ mod tests {
 					ALICE,
 					executable,
 					&mut gas_meter,
+					&mut StorageMeter::new(ALICE, None),
 					&schedule,
 					0, // <- zero endowment
 					vec![],
@@ -1649,6 +1728,7 @@ mod tests {
 					ALICE,
 					executable,
 					&mut gas_meter,
+					&mut StorageMeter::new(ALICE, None),
 					&schedule,
 					100,
 					vec![],
@@ -1689,6 +1769,7 @@ mod tests {
 					ALICE,
 					executable,
 					&mut gas_meter,
+					&mut StorageMeter::new(ALICE, None),
 					&schedule,
 					100,
 					vec![],
@@ -1718,7 +1799,7 @@ mod tests {
 					.instantiate(
 						0,
 						dummy_ch,
-						Contracts::<Test>::subsistence_threshold() * 3,
+						<Test as Config>::Currency::minimum_balance() * 3,
 						vec![],
 						&[48, 49, 50],
 					)
@@ -1731,7 +1812,7 @@ mod tests {
 
 		ExtBuilder::default().existential_deposit(15).build().execute_with(|| {
 			let schedule = <Test as Config>::Schedule::get();
-			set_balance(&ALICE, Contracts::<Test>::subsistence_threshold() * 100);
+			set_balance(&ALICE, <Test as Config>::Currency::minimum_balance() * 100);
 			place_contract(&BOB, instantiator_ch);
 
 			assert_matches!(
@@ -1739,6 +1820,7 @@ mod tests {
 					ALICE,
 					BOB,
 					&mut GasMeter::<Test>::new(GAS_LIMIT),
+					&mut StorageMeter::new(ALICE, None),
 					&schedule,
 					20,
 					vec![],
@@ -1774,7 +1856,7 @@ mod tests {
 					ctx.ext.instantiate(
 						0,
 						dummy_ch,
-						Contracts::<Test>::subsistence_threshold(),
+						<Test as Config>::Currency::minimum_balance(),
 						vec![],
 						&[],
 					),
@@ -1799,6 +1881,7 @@ mod tests {
 					ALICE,
 					BOB,
 					&mut GasMeter::<Test>::new(GAS_LIMIT),
+					&mut StorageMeter::new(ALICE, None),
 					&schedule,
 					20,
 					vec![],
@@ -1832,6 +1915,7 @@ mod tests {
 					ALICE,
 					executable,
 					&mut gas_meter,
+					&mut StorageMeter::new(ALICE, None),
 					&schedule,
 					100,
 					vec![],
@@ -1882,6 +1966,7 @@ mod tests {
 				ALICE,
 				BOB,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(ALICE, None),
 				&schedule,
 				0,
 				vec![0],
@@ -1904,18 +1989,19 @@ mod tests {
 		// This one tests passing the input data into a contract via instantiate.
 		ExtBuilder::default().build().execute_with(|| {
 			let schedule = <Test as Config>::Schedule::get();
-			let subsistence = Contracts::<Test>::subsistence_threshold();
+			let min_balance = <Test as Config>::Currency::minimum_balance();
 			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
 			let executable = MockExecutable::from_storage(code, &schedule, &mut gas_meter).unwrap();
 
-			set_balance(&ALICE, subsistence * 10);
+			set_balance(&ALICE, min_balance * 1000);
 
 			let result = MockStack::run_instantiate(
 				ALICE,
 				executable,
 				&mut gas_meter,
+				&mut StorageMeter::new(ALICE, None),
 				&schedule,
-				subsistence * 3,
+				min_balance * 3,
 				vec![],
 				&[],
 				None,
@@ -1935,15 +2021,16 @@ mod tests {
 		let mut debug_buffer = Vec::new();
 
 		ExtBuilder::default().build().execute_with(|| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
+			let min_balance = <Test as Config>::Currency::minimum_balance();
 			let schedule = <Test as Config>::Schedule::get();
 			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
-			set_balance(&ALICE, subsistence * 10);
+			set_balance(&ALICE, min_balance * 1000);
 			place_contract(&BOB, code_hash);
 			MockStack::run_call(
 				ALICE,
 				BOB,
 				&mut gas_meter,
+				&mut StorageMeter::new(ALICE, None),
 				&schedule,
 				0,
 				vec![],
@@ -1966,15 +2053,16 @@ mod tests {
 		let mut debug_buffer = Vec::new();
 
 		ExtBuilder::default().build().execute_with(|| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
+			let min_balance = <Test as Config>::Currency::minimum_balance();
 			let schedule = <Test as Config>::Schedule::get();
 			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
-			set_balance(&ALICE, subsistence * 10);
+			set_balance(&ALICE, min_balance * 1000);
 			place_contract(&BOB, code_hash);
 			let result = MockStack::run_call(
 				ALICE,
 				BOB,
 				&mut gas_meter,
+				&mut StorageMeter::new(ALICE, None),
 				&schedule,
 				0,
 				vec![],
@@ -2006,6 +2094,7 @@ mod tests {
 				ALICE,
 				BOB,
 				&mut GasMeter::<Test>::new(GAS_LIMIT),
+				&mut StorageMeter::new(ALICE, None),
 				&schedule,
 				0,
 				CHARLIE.encode(),
@@ -2018,6 +2107,7 @@ mod tests {
 					ALICE,
 					BOB,
 					&mut GasMeter::<Test>::new(GAS_LIMIT),
+					&mut StorageMeter::new(ALICE, None),
 					&schedule,
 					0,
 					BOB.encode(),
@@ -2054,6 +2144,7 @@ mod tests {
 					ALICE,
 					BOB,
 					&mut GasMeter::<Test>::new(GAS_LIMIT),
+					&mut StorageMeter::new(ALICE, None),
 					&schedule,
 					0,
 					vec![0],
@@ -2076,13 +2167,23 @@ mod tests {
 		});
 
 		ExtBuilder::default().build().execute_with(|| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
+			let min_balance = <Test as Config>::Currency::minimum_balance();
 			let schedule = <Test as Config>::Schedule::get();
 			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
-			set_balance(&ALICE, subsistence * 10);
+			set_balance(&ALICE, min_balance * 1000);
 			place_contract(&BOB, code_hash);
 			System::reset_events();
-			MockStack::run_call(ALICE, BOB, &mut gas_meter, &schedule, 0, vec![], None).unwrap();
+			MockStack::run_call(
+				ALICE,
+				BOB,
+				&mut gas_meter,
+				&mut StorageMeter::new(ALICE, None),
+				&schedule,
+				0,
+				vec![],
+				None,
+			)
+			.unwrap();
 
 			let remark_hash = <Test as frame_system::Config>::Hashing::hash(b"Hello World");
 			assert_eq!(
@@ -2133,13 +2234,23 @@ mod tests {
 		});
 
 		ExtBuilder::default().build().execute_with(|| {
-			let subsistence = Contracts::<Test>::subsistence_threshold();
+			let min_balance = <Test as Config>::Currency::minimum_balance();
 			let schedule = <Test as Config>::Schedule::get();
 			let mut gas_meter = GasMeter::<Test>::new(GAS_LIMIT);
-			set_balance(&ALICE, subsistence * 10);
+			set_balance(&ALICE, min_balance * 1000);
 			place_contract(&BOB, code_hash);
 			System::reset_events();
-			MockStack::run_call(ALICE, BOB, &mut gas_meter, &schedule, 0, vec![], None).unwrap();
+			MockStack::run_call(
+				ALICE,
+				BOB,
+				&mut gas_meter,
+				&mut StorageMeter::new(ALICE, None),
+				&schedule,
+				0,
+				vec![],
+				None,
+			)
+			.unwrap();
 
 			let remark_hash = <Test as frame_system::Config>::Hashing::hash(b"Hello");
 			assert_eq!(
@@ -2208,6 +2319,7 @@ mod tests {
 				ALICE,
 				fail_executable,
 				&mut gas_meter,
+				&mut StorageMeter::new(ALICE, None),
 				&schedule,
 				min_balance * 100,
 				vec![],
@@ -2221,6 +2333,7 @@ mod tests {
 				ALICE,
 				success_executable,
 				&mut gas_meter,
+				&mut StorageMeter::new(ALICE, None),
 				&schedule,
 				min_balance * 100,
 				vec![],
@@ -2233,6 +2346,7 @@ mod tests {
 				ALICE,
 				succ_fail_executable,
 				&mut gas_meter,
+				&mut StorageMeter::new(ALICE, None),
 				&schedule,
 				min_balance * 200,
 				vec![],
@@ -2245,6 +2359,7 @@ mod tests {
 				ALICE,
 				succ_succ_executable,
 				&mut gas_meter,
+				&mut StorageMeter::new(ALICE, None),
 				&schedule,
 				min_balance * 200,
 				vec![],
@@ -90,6 +90,7 @@ mod exec;
 mod migration;
 mod schedule;
 mod storage;
+mod trace;
 mod wasm;
 
 pub mod chain_extension;
@@ -106,23 +107,24 @@ pub use crate::{
 use crate::{
 	exec::{AccountIdOf, ExecError, Executable, Stack as ExecStack},
 	gas::GasMeter,
-	storage::{ContractInfo, DeletedContract, Storage},
-	wasm::PrefabWasmModule,
+	storage::{meter::Meter as StorageMeter, ContractInfo, DeletedContract, Storage},
+	wasm::{OwnerInfo, PrefabWasmModule},
 	weights::WeightInfo,
 };
 use frame_support::{
 	dispatch::Dispatchable,
 	ensure,
-	traits::{Contains, Currency, Get, Randomness, StorageVersion, Time},
+	traits::{Contains, Currency, Get, Randomness, ReservableCurrency, StorageVersion, Time},
 	weights::{GetDispatchInfo, PostDispatchInfo, Weight},
 };
 use frame_system::Pallet as System;
 use pallet_contracts_primitives::{
-	Code, ContractAccessError, ContractExecResult, ContractInstantiateResult, ExecReturnValue,
-	GetStorageResult, InstantiateReturnValue,
+	Code, CodeUploadResult, CodeUploadReturnValue, ContractAccessError, ContractCallTrace,
+	ContractExecResult, ContractInstantiateResult, ExecReturnValue, GetStorageResult,
+	InstantiateReturnValue, StorageDeposit,
 };
 use sp_core::{crypto::UncheckedFrom, Bytes};
-use sp_runtime::traits::{Convert, Hash, Saturating, StaticLookup};
+use sp_runtime::traits::{CheckedSub, Convert, Hash, StaticLookup};
 use sp_std::prelude::*;
 
 type CodeHash<T> = <T as frame_system::Config>::Hash;
@@ -131,7 +133,7 @@ type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 /// The current storage version.
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(7);
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -148,7 +150,9 @@ pub mod pallet {
 		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
 
 		/// The currency in which fees are paid and contract balances are held.
-		type Currency: Currency<Self::AccountId>;
+		///
+		/// Storage deposits are reserved on the contract's account using this currency.
+		type Currency: ReservableCurrency<Self::AccountId>;
 
 		/// The overarching event type.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
@@ -165,11 +169,11 @@ pub mod pallet {
 		/// This is applied in **addition** to [`frame_system::Config::BaseCallFilter`].
 		/// It is recommended to treat this as a whitelist.
 		///
-		/// # Subsistence Threshold
+		/// # Storage Deposit
 		///
-		/// The runtime **must** make sure that any allowed dispatchable makes sure that the
-		/// `total_balance` of the contract stays above [`Pallet::subsistence_threshold()`].
-		/// Otherwise users could clutter the storage with contracts.
+		/// The runtime **must** make sure that no allowed dispatchable is able to access
+		/// the reserved balance of a contract. It holds the deposit for the contract's storage
+		/// and must only be released by the contracts pallet itself.
 		///
 		/// # Stability
 		///
@@ -201,12 +205,21 @@ pub mod pallet {
 		#[pallet::constant]
 		type Schedule: Get<Schedule<Self>>;
 
-		/// The deposit that must be placed into the contract's account to instantiate it.
-		/// This is in **addition** to the [`Currency::minimum_balance`].
-		/// The minimum balance for a contract's account can be queried using
-		/// [`Pallet::subsistence_threshold`].
+		/// The amount of balance a caller has to pay for each byte of storage.
+		///
+		/// # Note
+		///
+		/// Changing this value for an existing chain might need a storage migration.
+		#[pallet::constant]
+		type DepositPerByte: Get<BalanceOf<Self>>;
+
+		/// The amount of balance a caller has to pay for each storage item.
+		///
+		/// # Note
+		///
+		/// Changing this value for an existing chain might need a storage migration.
 		#[pallet::constant]
-		type ContractDeposit: Get<BalanceOf<Self>>;
+		type DepositPerItem: Get<BalanceOf<Self>>;
 
 		/// The type of the call stack determines the maximum nesting depth of contract calls.
 		///
@@ -263,29 +276,51 @@ pub mod pallet {
 		/// * If the account is a regular account, any value will be transferred.
 		/// * If no account exists and the call value is not less than `existential_deposit`,
 		/// a regular account will be created and any value will be transferred.
+		///
+		/// # Parameters
+		///
+		/// * `storage_deposit_limit`: The maximum amount of balance that can be charged from the
+		///   caller to pay for the storage consumed. `None` means that the caller is only limited
+		///   by its free balance.
 		#[pallet::weight(T::WeightInfo::call().saturating_add(*gas_limit))]
 		pub fn call(
 			origin: OriginFor<T>,
 			dest: <T::Lookup as StaticLookup>::Source,
 			#[pallet::compact] value: BalanceOf<T>,
 			#[pallet::compact] gas_limit: Weight,
+			storage_deposit_limit: Option<<BalanceOf<T> as codec::HasCompact>::Type>,
 			data: Vec<u8>,
 		) -> DispatchResultWithPostInfo {
 			let origin = ensure_signed(origin)?;
 			let dest = T::Lookup::lookup(dest)?;
-			let output = Self::internal_call(origin, dest, value, gas_limit, data, None);
+			let mut debug_message = trace::is_recording().then(Vec::new);
+			let output = Self::internal_call(
+				origin.clone(),
+				dest,
+				value,
+				gas_limit,
+				storage_deposit_limit.map(Into::into),
+				data,
+				debug_message.as_mut(),
+			);
+			trace::record_call(&origin, &output, |result| result, debug_message);
 			output.gas_meter.into_dispatch_result(output.result, T::WeightInfo::call())
 		}
 
 		/// Instantiates a new contract from the supplied `code` optionally transferring
 		/// some balance.
 		///
-		/// This is the only function that can deploy new code to the chain.
+		/// This dispatchable has the same effect as calling [`Self::upload_code`] +
+		/// [`Self::instantiate`]. Bundling them together provides efficiency gains. Please
+		/// also check the documentation of [`Self::upload_code`].
 		///
 		/// # Parameters
 		///
 		/// * `endowment`: The balance to transfer from the `origin` to the newly created contract.
 		/// * `gas_limit`: The gas limit enforced when executing the constructor.
+		/// * `storage_deposit_limit`: The maximum amount of balance that can be charged from the
+		///   caller to pay for the storage consumed. `None` means that the caller is only limited
+		///   by its free balance.
 		/// * `code`: The contract code to deploy in raw bytes.
 		/// * `data`: The input data to pass to the contract constructor.
 		/// * `salt`: Used for the address derivation. See [`Pallet::contract_address`].
@@ -310,6 +345,7 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			#[pallet::compact] endowment: BalanceOf<T>,
 			#[pallet::compact] gas_limit: Weight,
+			storage_deposit_limit: Option<<BalanceOf<T> as codec::HasCompact>::Type>,
 			code: Vec<u8>,
 			data: Vec<u8>,
 			salt: Vec<u8>,
@@ -317,15 +353,18 @@ pub mod pallet {
 			let origin = ensure_signed(origin)?;
 			let code_len = code.len() as u32;
 			let salt_len = salt.len() as u32;
+			let mut debug_message = trace::is_recording().then(Vec::new);
 			let output = Self::internal_instantiate(
-				origin,
+				origin.clone(),
 				endowment,
 				gas_limit,
+				storage_deposit_limit.map(Into::into),
 				Code::Upload(Bytes(code)),
 				data,
 				salt,
-				None,
+				debug_message.as_mut(),
 			);
+			trace::record_call(&origin, &output, |(_address, result)| result, debug_message);
 			output.gas_meter.into_dispatch_result(
 				output.result.map(|(_address, result)| result),
 				T::WeightInfo::instantiate_with_code(code_len / 1024, salt_len / 1024),
@@ -344,26 +383,70 @@ pub mod pallet {
 			origin: OriginFor<T>,
 			#[pallet::compact] endowment: BalanceOf<T>,
 			#[pallet::compact] gas_limit: Weight,
+			storage_deposit_limit: Option<<BalanceOf<T> as codec::HasCompact>::Type>,
 			code_hash: CodeHash<T>,
 			data: Vec<u8>,
 			salt: Vec<u8>,
 		) -> DispatchResultWithPostInfo {
 			let origin = ensure_signed(origin)?;
 			let salt_len = salt.len() as u32;
+			let mut debug_message = trace::is_recording().then(Vec::new);
 			let output = Self::internal_instantiate(
-				origin,
+				origin.clone(),
 				endowment,
 				gas_limit,
+				storage_deposit_limit.map(Into::into),
 				Code::Existing(code_hash),
 				data,
 				salt,
-				None,
+				debug_message.as_mut(),
 			);
+			trace::record_call(&origin, &output, |(_address, result)| result, debug_message);
 			output.gas_meter.into_dispatch_result(
 				output.result.map(|(_address, output)| output),
 				T::WeightInfo::instantiate(salt_len / 1024),
 			)
 		}
+
+		/// Upload new `code` without instantiating a contract from it.
+		///
+		/// If the code does not already exist a deposit is reserved from the caller
+		/// and unreserved only when [`Self::remove_code`] is called. The size of the reserve
+		/// depends on the instrumented size of the supplied `code`.
+		///
+		/// If the code already exists in storage it will still return `Ok` without reserving
+		/// any additional deposit.
+		///
+		/// # Note
+		///
+		/// Anyone can instantiate a contract from any uploaded code and thus prevent its removal.
+		/// To avoid this situation a constructor could employ access control so that it can
+		/// only be instantiated by permissioned entities. The same is true when uploading
+		/// through [`Self::instantiate_with_code`].
+		#[pallet::weight(T::WeightInfo::upload_code(code.len() as u32 / 1024))]
+		pub fn upload_code(
+			origin: OriginFor<T>,
+			code: Vec<u8>,
+			storage_deposit_limit: Option<<BalanceOf<T> as codec::HasCompact>::Type>,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			Self::bare_upload_code(origin, code, storage_deposit_limit.map(Into::into)).map(|_| ())
+		}
+
+		/// Remove the code stored under `code_hash` and refund the deposit to its owner.
+		///
+		/// A code can only be removed by its original uploader (its owner) and only if it is
+		/// not used by any contract.
+		#[pallet::weight(T::WeightInfo::remove_code())]
+		pub fn remove_code(
+			origin: OriginFor<T>,
+			code_hash: CodeHash<T>,
+		) -> DispatchResultWithPostInfo {
+			let origin = ensure_signed(origin)?;
+			<PrefabWasmModule<T>>::remove(&origin, code_hash)?;
+			// we waive the fee because removing unused code is beneficial
+			Ok(Pays::No.into())
+		}
 	}
 
 	#[pallet::event]
@@ -404,9 +487,17 @@ pub mod pallet {
 		},
 
 		/// A code with the specified hash was removed.
-		///
-		/// This happens when the last contract that uses this code hash was removed.
 		CodeRemoved { code_hash: T::Hash },
+
+		/// A contract's code was updated.
+		ContractCodeUpdated {
+			/// The contract that has been updated.
+			contract: T::AccountId,
+			/// New code hash that was set for the contract.
+			new_code_hash: T::Hash,
+			/// Previous code hash of the contract.
+			old_code_hash: T::Hash,
+		},
 	}
 
 	#[pallet::error]
@@ -417,12 +508,8 @@ pub mod pallet {
 		OutOfGas,
 		/// The output buffer supplied to a contract API call was too small.
 		OutputBufferTooSmall,
-		/// Performing the requested transfer would have brought the contract below
-		/// the subsistence threshold. No transfer is allowed to do this. Use `seal_terminate`
-		/// to recover a deposit.
-		BelowSubsistenceThreshold,
-		/// The newly created contract is below the subsistence threshold after executing
-		/// its contructor. No contracts are allowed to exist below that threshold.
+		/// The endowment supplied to a new contract is below the existential deposit.
+		/// No contracts are allowed to exist below that threshold.
 		NewContractNotFunded,
 		/// Performing the requested transfer failed for a reason originating in the
 		/// chosen currency implementation of the runtime. Most probably the balance is
@@ -482,6 +569,12 @@ pub mod pallet {
 		DebugMessageInvalidUTF8,
 		/// A call tried to invoke a contract that is flagged as non-reentrant.
 		ReentranceDenied,
+		/// More storage was created than allowed by the storage deposit limit.
+		StorageDepositLimitExhausted,
+		/// Origin doesn't have enough balance to pay the required storage deposits.
+		StorageDepositNotEnoughFunds,
+		/// Code removal was denied because the code is still in use by at least one contract.
+		CodeInUse,
 	}
 
 	/// A mapping from an original code hash to the original code, untouched by instrumentation.
@@ -493,6 +586,10 @@ pub mod pallet {
 	pub(crate) type CodeStorage<T: Config> =
 		StorageMap<_, Identity, CodeHash<T>, PrefabWasmModule<T>>;
 
+	/// A mapping between an original code hash and its owner information.
+	#[pallet::storage]
+	pub(crate) type OwnerInfoOf<T: Config> = StorageMap<_, Identity, CodeHash<T>, OwnerInfo<T>>;
+
 	/// The subtrie counter.
 	#[pallet::storage]
 	pub(crate) type AccountCounter<T: Config> = StorageValue<_, u64, ValueQuery>;
@@ -522,6 +619,8 @@ type InternalInstantiateOutput<T> = InternalOutput<T, (AccountIdOf<T>, ExecRetur
 struct InternalOutput<T: Config, O> {
 	/// The gas meter that was used to execute the call.
 	gas_meter: GasMeter<T>,
+	/// The storage deposit that was charged or refunded by the call.
+	storage_deposit: StorageDeposit<BalanceOf<T>>,
 	/// The result of the call.
 	result: Result<O, ExecError>,
 }
@@ -547,16 +646,25 @@ where
 		dest: T::AccountId,
 		value: BalanceOf<T>,
 		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
 		data: Vec<u8>,
 		debug: bool,
-	) -> ContractExecResult {
+	) -> ContractExecResult<BalanceOf<T>> {
 		let mut debug_message = if debug { Some(Vec::new()) } else { None };
-		let output =
-			Self::internal_call(origin, dest, value, gas_limit, data, debug_message.as_mut());
+		let output = Self::internal_call(
+			origin,
+			dest,
+			value,
+			gas_limit,
+			storage_deposit_limit,
+			data,
+			debug_message.as_mut(),
+		);
 		ContractExecResult {
 			result: output.result.map_err(|r| r.error),
 			gas_consumed: output.gas_meter.gas_consumed(),
 			gas_required: output.gas_meter.gas_required(),
+			storage_deposit: output.storage_deposit,
 			debug_message: debug_message.unwrap_or_default(),
 		}
 	}
@@ -578,16 +686,18 @@ where
 		origin: T::AccountId,
 		endowment: BalanceOf<T>,
 		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
 		code: Code<CodeHash<T>>,
 		data: Vec<u8>,
 		salt: Vec<u8>,
 		debug: bool,
-	) -> ContractInstantiateResult<T::AccountId> {
+	) -> ContractInstantiateResult<T::AccountId, BalanceOf<T>> {
 		let mut debug_message = if debug { Some(Vec::new()) } else { None };
 		let output = Self::internal_instantiate(
 			origin,
 			endowment,
 			gas_limit,
+			storage_deposit_limit,
 			code,
 			data,
 			salt,
@@ -600,10 +710,53 @@ where
 				.map_err(|e| e.error),
 			gas_consumed: output.gas_meter.gas_consumed(),
 			gas_required: output.gas_meter.gas_required(),
+			storage_deposit: output.storage_deposit,
 			debug_message: debug_message.unwrap_or_default(),
 		}
 	}
 
+	/// Upload new code without instantiating a contract from it.
+	///
+	/// This function is similar to [`Self::upload_code`], but doesn't perform any address lookups
+	/// and better suitable for calling directly from Rust.
+	pub fn bare_upload_code(
+		origin: T::AccountId,
+		code: Vec<u8>,
+		storage_deposit_limit: Option<BalanceOf<T>>,
+	) -> CodeUploadResult<CodeHash<T>, BalanceOf<T>> {
+		let schedule = T::Schedule::get();
+		ensure!(code.len() as u32 <= schedule.limits.code_len, <Error<T>>::CodeTooLarge);
+		let module = PrefabWasmModule::from_code(code, &schedule, origin)?;
+		ensure!(module.code_len() <= schedule.limits.code_len, <Error<T>>::CodeTooLarge);
+		let deposit = module.open_deposit();
+		if let Some(storage_deposit_limit) = storage_deposit_limit {
+			ensure!(storage_deposit_limit >= deposit, <Error<T>>::StorageDepositLimitExhausted);
+		}
+		let result = CodeUploadReturnValue { code_hash: *module.code_hash(), deposit };
+		module.store()?;
+		Ok(result)
+	}
+
+	/// Apply an extrinsic by calling `apply` and trace the contract calls it dispatches.
+	///
+	/// Every call and instantiation that goes through a dispatchable of this pallet is
+	/// recorded, including those nested in other dispatchables like `utility::batch`. Calls
+	/// made by contracts to other contracts are part of the trace of the outermost call.
+	/// Debug message recording is enabled for the traced calls. Events emitted by a frame
+	/// that was reverted are dropped from the trace. A call is recorded as it returned, even
+	/// if a dispatchable enclosing it reverts its changes later on.
+	///
+	/// # Note
+	///
+	/// This is meant to be used by RPCs that replay already included blocks. For the same
+	/// reasons as laid out in [`Self::bare_call`] it must never be used on-chain.
+	pub fn trace<R>(
+		extrinsic_index: u32,
+		apply: impl FnOnce() -> R,
+	) -> (R, Vec<ContractCallTrace<T::AccountId, BalanceOf<T>, T::Hash>>) {
+		trace::record::<T, R>(extrinsic_index, apply)
+	}
+
 	/// Query storage of a specified contract under a specified key.
 	pub fn get_storage(address: T::AccountId, key: [u8; 32]) -> GetStorageResult {
 		let contract_info =
@@ -636,14 +789,12 @@ where
 		UncheckedFrom::unchecked_from(T::Hashing::hash(&buf))
 	}
 
-	/// Subsistence threshold is the extension of the minimum balance (aka existential deposit)
-	/// by the contract deposit. It is the minimum balance any contract must hold.
+	/// The storage deposit that is charged for the data structure associated with each contract.
 	///
-	/// Any contract initiated balance transfer mechanism cannot make the balance lower
-	/// than the subsistence threshold. The only way to recover the balance is to remove
-	/// contract using `seal_terminate`.
-	pub fn subsistence_threshold() -> BalanceOf<T> {
-		T::Currency::minimum_balance().saturating_add(T::ContractDeposit::get())
+	/// It is charged from the origin when a contract is instantiated and is reserved on the
+	/// contract's account in addition to the deposit for its storage items.
+	pub fn contract_deposit() -> BalanceOf<T> {
+		storage::meter::item_deposit::<T>(None, Some(Self::contract_info_size())).charge_or_zero()
 	}
 
 	/// The in-memory size in bytes of the data structure associated with each contract.
@@ -662,9 +813,12 @@ where
 
 	/// Store code for benchmarks which does not check nor instrument the code.
 	#[cfg(feature = "runtime-benchmarks")]
-	fn store_code_raw(code: Vec<u8>) -> frame_support::dispatch::DispatchResult {
+	fn store_code_raw(
+		code: Vec<u8>,
+		owner: T::AccountId,
+	) -> frame_support::dispatch::DispatchResult {
 		let schedule = T::Schedule::get();
-		PrefabWasmModule::store_code_unchecked(code, &schedule)?;
+		PrefabWasmModule::store_code_unchecked(code, &schedule, owner)?;
 		Ok(())
 	}
 
@@ -685,21 +839,24 @@ where
 		dest: T::AccountId,
 		value: BalanceOf<T>,
 		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
 		data: Vec<u8>,
 		debug_message: Option<&mut Vec<u8>>,
 	) -> InternalCallOutput<T> {
 		let mut gas_meter = GasMeter::new(gas_limit);
+		let mut storage_meter = StorageMeter::new(origin.clone(), storage_deposit_limit);
 		let schedule = T::Schedule::get();
 		let result = ExecStack::<T, PrefabWasmModule<T>>::run_call(
 			origin,
 			dest,
 			&mut gas_meter,
+			&mut storage_meter,
 			&schedule,
 			value,
 			data,
 			debug_message,
 		);
-		InternalCallOutput { gas_meter, result }
+		InternalCallOutput { gas_meter, storage_deposit: storage_meter.total_deposit(), result }
 	}
 
 	/// Internal function that does the actual instantiation.
@@ -709,42 +866,63 @@ where
 		origin: T::AccountId,
 		endowment: BalanceOf<T>,
 		gas_limit: Weight,
+		storage_deposit_limit: Option<BalanceOf<T>>,
 		code: Code<CodeHash<T>>,
 		data: Vec<u8>,
 		salt: Vec<u8>,
 		debug_message: Option<&mut Vec<u8>>,
 	) -> InternalInstantiateOutput<T> {
 		let mut gas_meter = GasMeter::new(gas_limit);
+		let mut storage_deposit = Default::default();
 		let schedule = T::Schedule::get();
 		let try_exec = || {
-			let executable = match code {
+			let (extra_deposit, executable) = match code {
 				Code::Upload(Bytes(binary)) => {
 					ensure!(
 						binary.len() as u32 <= schedule.limits.code_len,
 						<Error<T>>::CodeTooLarge
 					);
-					let executable = PrefabWasmModule::from_code(binary, &schedule)?;
+					let executable =
+						PrefabWasmModule::from_code(binary, &schedule, origin.clone())?;
 					ensure!(
 						executable.code_len() <= schedule.limits.code_len,
 						<Error<T>>::CodeTooLarge
 					);
-					executable
-				}
-				Code::Existing(hash) => {
-					PrefabWasmModule::from_storage(hash, &schedule, &mut gas_meter)?
+					// The open deposit will be reserved from the origin when the uploaded
+					// module is stored during execution. It is not part of the storage meter
+					// because it is not transferred to the contract.
+					(executable.open_deposit(), executable)
 				}
+				Code::Existing(hash) => (
+					Default::default(),
+					PrefabWasmModule::from_storage(hash, &schedule, &mut gas_meter)?,
+				),
 			};
-			ExecStack::<T, PrefabWasmModule<T>>::run_instantiate(
+			let storage_deposit_limit = storage_deposit_limit
+				.map(|limit| {
+					limit
+						.checked_sub(&extra_deposit)
+						.ok_or(<Error<T>>::StorageDepositLimitExhausted)
+				})
+				.transpose()?;
+			let mut storage_meter = StorageMeter::new(origin.clone(), storage_deposit_limit);
+			let result = ExecStack::<T, PrefabWasmModule<T>>::run_instantiate(
 				origin,
 				executable,
 				&mut gas_meter,
+				&mut storage_meter,
 				&schedule,
 				endowment,
 				data,
 				&salt,
 				debug_message,
-			)
+			);
+			storage_deposit = storage_meter
+				.total_deposit()
+				.saturating_add(&StorageDeposit::Charge(extra_deposit));
+			result
 		};
-		InternalInstantiateOutput { result: try_exec(), gas_meter }
+		let result = try_exec();
+		InternalInstantiateOutput { result, gas_meter, storage_deposit }
 	}
 }
@@ -38,6 +38,16 @@ pub fn migrate<T: Config>() -> Weight {
 		StorageVersion::new(5).put::<Pallet<T>>();
 	}
 
+	if version < 6 {
+		weight = weight.saturating_add(v6::migrate::<T>());
+		StorageVersion::new(6).put::<Pallet<T>>();
+	}
+
+	if version < 7 {
+		weight = weight.saturating_add(v7::migrate::<T>());
+		StorageVersion::new(7).put::<Pallet<T>>();
+	}
+
 	weight
 }
 
@@ -54,10 +64,9 @@ mod v4 {
 /// V5: State rent is removed which obsoletes some fields in `ContractInfo`.
 mod v5 {
 	use super::*;
-	use crate::{
-		BalanceOf, CodeHash, ContractInfo, ContractInfoOf, DeletedContract, DeletionQueue, TrieId,
-	};
-	use codec::Decode;
+	use crate::{BalanceOf, CodeHash, DeletedContract, DeletionQueue, TrieId};
+	use codec::{Decode, Encode};
+	use frame_support::{generate_storage_alias, Twox64Concat};
 	use sp_std::marker::PhantomData;
 
 	type AliveContractInfo<T> =
@@ -89,12 +98,24 @@ mod v5 {
 	#[derive(Decode)]
 	struct RawTombstoneContractInfo<H, Hasher>(H, PhantomData<Hasher>);
 
+	#[derive(Encode, Decode)]
+	pub struct ContractInfo<T: Config> {
+		pub trie_id: TrieId,
+		pub code_hash: CodeHash<T>,
+		pub _reserved: Option<()>,
+	}
+
 	#[derive(Decode)]
 	struct OldDeletedContract {
 		_pair_count: u32,
 		trie_id: TrieId,
 	}
 
+	generate_storage_alias!(
+		Contracts,
+		ContractInfoOf<T: Config> => Map<(Twox64Concat, T::AccountId), ContractInfo<T>>
+	);
+
 	pub fn migrate<T: Config>() -> Weight {
 		let mut weight: Weight = 0;
 
@@ -119,3 +140,126 @@ mod v5 {
 		weight
 	}
 }
+
+/// V6: Added storage deposits
+mod v6 {
+	use super::*;
+	use crate::{storage::RawContractInfo, BalanceOf, CodeHash, TrieId};
+	use codec::{Decode, Encode};
+	use frame_support::{generate_storage_alias, Twox64Concat};
+	use sp_runtime::traits::Zero;
+
+	#[derive(Encode, Decode)]
+	struct OldContractInfo<T: Config> {
+		trie_id: TrieId,
+		code_hash: CodeHash<T>,
+		_reserved: Option<()>,
+	}
+
+	type ContractInfo<T> = RawContractInfo<CodeHash<T>, BalanceOf<T>>;
+
+	generate_storage_alias!(
+		Contracts,
+		ContractInfoOf<T: Config> => Map<(Twox64Concat, T::AccountId), ContractInfo<T>>
+	);
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut weight: Weight = 0;
+
+		<ContractInfoOf<T>>::translate(|_key, old: OldContractInfo<T>| {
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+			Some(ContractInfo::<T> {
+				trie_id: old.trie_id,
+				code_hash: old.code_hash,
+				storage_deposit: Zero::zero(),
+				_reserved: old._reserved,
+			})
+		});
+
+		weight
+	}
+}
+
+/// V7: The code refcount is moved to `OwnerInfoOf` which also tracks the code owner and deposit.
+mod v7 {
+	use super::*;
+	use crate::{BalanceOf, CodeHash};
+	use codec::{Decode, Encode};
+	use frame_support::{generate_storage_alias, Identity};
+	use sp_runtime::traits::Zero;
+
+	#[derive(Decode)]
+	struct OldPrefabWasmModule {
+		#[codec(compact)]
+		instruction_weights_version: u32,
+		#[codec(compact)]
+		initial: u32,
+		#[codec(compact)]
+		maximum: u32,
+		#[codec(compact)]
+		refcount: u64,
+		_reserved: Option<()>,
+		code: Vec<u8>,
+		original_code_len: u32,
+	}
+
+	#[derive(Encode, Decode)]
+	struct PrefabWasmModule {
+		#[codec(compact)]
+		instruction_weights_version: u32,
+		#[codec(compact)]
+		initial: u32,
+		#[codec(compact)]
+		maximum: u32,
+		_reserved: Option<()>,
+		code: Vec<u8>,
+		original_code_len: u32,
+	}
+
+	#[derive(Encode, Decode)]
+	struct OwnerInfo<T: Config> {
+		owner: T::AccountId,
+		#[codec(compact)]
+		deposit: BalanceOf<T>,
+		#[codec(compact)]
+		refcount: u64,
+	}
+
+	generate_storage_alias!(
+		Contracts,
+		CodeStorage<T: Config> => Map<(Identity, CodeHash<T>), PrefabWasmModule>
+	);
+
+	generate_storage_alias!(
+		Contracts,
+		OwnerInfoOf<T: Config> => Map<(Identity, CodeHash<T>), OwnerInfo<T>>
+	);
+
+	pub fn migrate<T: Config>() -> Weight {
+		let mut weight: Weight = 0;
+
+		<CodeStorage<T>>::translate(|key, old: OldPrefabWasmModule| {
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 2));
+			// Code that was uploaded before this migration has no known owner and no deposit.
+			// It can therefore never be removed by a `remove_code` transaction.
+			<OwnerInfoOf<T>>::insert(
+				key,
+				OwnerInfo::<T> {
+					owner: Default::default(),
+					deposit: Zero::zero(),
+					refcount: old.refcount,
+				},
+			);
+			Some(PrefabWasmModule {
+				instruction_weights_version: old.instruction_weights_version,
+				initial: old.initial,
+				maximum: old.maximum,
+				_reserved: old._reserved,
+				code: old.code,
+				original_code_len: old.original_code_len,
+			})
+		});
+
+		weight
+	}
+}
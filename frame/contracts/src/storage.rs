@@ -17,10 +17,13 @@
 
 //! This module contains routines for accessing and altering a contract related state.
 
+pub mod meter;
+
 use crate::{
 	exec::{AccountIdOf, StorageKey},
+	storage::meter::{item_deposit, DepositOf},
 	weights::WeightInfo,
-	CodeHash, Config, ContractInfoOf, DeletionQueue, Error, TrieId,
+	BalanceOf, CodeHash, Config, ContractInfoOf, DeletionQueue, Error, TrieId,
 };
 use codec::{Decode, Encode};
 use frame_support::{
@@ -32,24 +35,30 @@ use frame_support::{
 use scale_info::TypeInfo;
 use sp_core::crypto::UncheckedFrom;
 use sp_io::hashing::blake2_256;
-use sp_runtime::{traits::Hash, RuntimeDebug};
+use sp_runtime::{
+	traits::{Hash, Zero},
+	RuntimeDebug,
+};
 use sp_std::{marker::PhantomData, prelude::*};
 
-pub type ContractInfo<T> = RawContractInfo<CodeHash<T>>;
+pub type ContractInfo<T> = RawContractInfo<CodeHash<T>, BalanceOf<T>>;
 
 /// Information for managing an account and its sub trie abstraction.
 /// This is the required info to cache for an account.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
-pub struct RawContractInfo<CodeHash> {
+pub struct RawContractInfo<CodeHash, Balance> {
 	/// Unique ID for the subtree encoded as a bytes vector.
 	pub trie_id: TrieId,
 	/// The code associated with a given account.
 	pub code_hash: CodeHash,
+	/// The amount of balance that is currently reserved on the contract's account to pay
+	/// for its storage.
+	pub storage_deposit: Balance,
 	/// This field is reserved for future evolution of format.
 	pub _reserved: Option<()>,
 }
 
-impl<CodeHash> RawContractInfo<CodeHash> {
+impl<CodeHash, Balance> RawContractInfo<CodeHash, Balance> {
 	/// Associated child trie unique id is built from the hash part of the trie id.
 	#[cfg(test)]
 	pub fn child_trie_info(&self) -> ChildInfo {
@@ -86,23 +95,24 @@ where
 	///
 	/// If the `opt_new_value` is `None` then the kv pair is removed.
 	///
-	/// This function also updates the bookkeeping info such as: number of total non-empty pairs a
-	/// contract owns, the last block the storage was written to, etc. That's why, in contrast to
-	/// `read`, this function also requires the `account` ID.
+	/// Returns the storage deposit that is due because of this change. It is a charge when
+	/// the storage grew and a refund when it shrank. The caller is responsible for settling it.
 	pub fn write(
 		new_info: &mut ContractInfo<T>,
 		key: &StorageKey,
 		opt_new_value: Option<Vec<u8>>,
-	) -> DispatchResult {
+	) -> Result<DepositOf<T>, DispatchError> {
 		let hashed_key = blake2_256(key);
 		let child_trie_info = &child_trie_info(&new_info.trie_id);
+		let old_len = child::len(&child_trie_info, &hashed_key);
+		let new_len = opt_new_value.as_ref().map(|new_value| new_value.len() as u32);
 
 		match opt_new_value {
 			Some(new_value) => child::put_raw(&child_trie_info, &hashed_key, &new_value[..]),
 			None => child::kill(&child_trie_info, &hashed_key),
 		}
 
-		Ok(())
+		Ok(item_deposit::<T>(old_len, new_len))
 	}
 
 	/// Creates a new contract descriptor in the storage with the given code hash at the given
@@ -118,7 +128,12 @@ where
 			return Err(Error::<T>::DuplicateContract.into());
 		}
 
-		let contract = ContractInfo::<T> { code_hash: ch, trie_id, _reserved: None };
+		let contract = ContractInfo::<T> {
+			code_hash: ch,
+			trie_id,
+			storage_deposit: Zero::zero(),
+			_reserved: None,
+		};
 
 		Ok(contract)
 	}
@@ -140,10 +155,10 @@ where
 	/// and weight limit.
 	pub fn deletion_budget(queue_len: usize, weight_limit: Weight) -> (u64, u32) {
 		let base_weight = T::WeightInfo::on_initialize();
-		let weight_per_queue_item = T::WeightInfo::on_initialize_per_queue_item(1)
-			- T::WeightInfo::on_initialize_per_queue_item(0);
-		let weight_per_key = T::WeightInfo::on_initialize_per_trie_key(1)
-			- T::WeightInfo::on_initialize_per_trie_key(0);
+		let weight_per_queue_item = T::WeightInfo::on_initialize_per_queue_item(1) -
+			T::WeightInfo::on_initialize_per_queue_item(0);
+		let weight_per_key = T::WeightInfo::on_initialize_per_trie_key(1) -
+			T::WeightInfo::on_initialize_per_trie_key(0);
 		let decoding_weight = weight_per_queue_item.saturating_mul(queue_len as Weight);
 
 		// `weight_per_key` being zero makes no sense and would constitute a failure to
@@ -0,0 +1,144 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains functions to meter the storage deposit.
+
+use crate::{storage::ContractInfo, BalanceOf, Config, Error};
+use frame_support::{
+	dispatch::DispatchResult,
+	ensure,
+	traits::{Currency, ExistenceRequirement, Get, ReservableCurrency},
+};
+use pallet_contracts_primitives::StorageDeposit;
+use sp_runtime::traits::{Saturating, Zero};
+
+/// Deposit that uses the native currency's balance type.
+pub type DepositOf<T> = StorageDeposit<BalanceOf<T>>;
+
+/// Calculates the deposit that is due when an item of `old_len` bytes is replaced by one of
+/// `new_len` bytes.
+///
+/// `None` means that the item does not exist (yet or anymore).
+pub fn item_deposit<T: Config>(old_len: Option<u32>, new_len: Option<u32>) -> DepositOf<T> {
+	let deposit =
+		|len: Option<u32>| len.map(|len| deposit_for::<T>(1, len)).unwrap_or_else(Zero::zero);
+	let (old, new) = (deposit(old_len), deposit(new_len));
+	if new >= old {
+		StorageDeposit::Charge(new.saturating_sub(old))
+	} else {
+		StorageDeposit::Refund(old.saturating_sub(new))
+	}
+}
+
+/// Calculates the deposit that is due for `items` storage items holding `bytes` bytes in total.
+pub fn deposit_for<T: Config>(items: u32, bytes: u32) -> BalanceOf<T> {
+	T::DepositPerByte::get()
+		.saturating_mul(bytes.into())
+		.saturating_add(T::DepositPerItem::get().saturating_mul(items.into()))
+}
+
+/// Keeps track of the storage deposit of a whole call stack.
+///
+/// Every frame of the call stack accumulates the deposit that is due for the storage
+/// changes of its contract. When the frame finished executing successfully its deposit is
+/// settled with the origin of the call stack using [`Self::settle`]. Settling happens while
+/// still inside the storage transaction of the frame. A frame that is reverted later on
+/// therefore also reverts all the balance transfers that were made when settling it.
+pub struct Meter<T: Config> {
+	/// The account that pays for the storage deposit and receives the refunds.
+	origin: T::AccountId,
+	/// The maximum amount of balance the origin is willing to pay for storage.
+	///
+	/// `None` means that the deposit is only limited by the origin's free balance.
+	limit: Option<BalanceOf<T>>,
+	/// The deposit that was settled so far.
+	total_deposit: DepositOf<T>,
+}
+
+impl<T: Config> Meter<T> {
+	/// Create a new meter for a call stack initiated by `origin`.
+	pub fn new(origin: T::AccountId, limit: Option<BalanceOf<T>>) -> Self {
+		Self { origin, limit, total_deposit: Default::default() }
+	}
+
+	/// The deposit that was charged or refunded so far.
+	pub fn total_deposit(&self) -> DepositOf<T> {
+		self.total_deposit.clone()
+	}
+
+	/// Reset the meter to a value previously returned by [`Self::total_deposit`].
+	///
+	/// This is used to discard the deposit of frames whose changes are rolled back.
+	pub fn revert_to(&mut self, total_deposit: DepositOf<T>) {
+		self.total_deposit = total_deposit;
+	}
+
+	/// Settle the `deposit` accumulated by a frame of `contract`.
+	///
+	/// A charge is transferred from the origin to the contract where it is reserved. A refund
+	/// is unreserved and transferred back to the origin. A refund can never be larger than
+	/// what was deposited for the contract in the first place.
+	pub fn settle(
+		&mut self,
+		contract: &T::AccountId,
+		info: &mut ContractInfo<T>,
+		deposit: DepositOf<T>,
+	) -> DispatchResult {
+		match deposit {
+			StorageDeposit::Charge(amount) => {
+				if amount.is_zero() {
+					return Ok(());
+				}
+				let total_deposit = self.total_deposit.saturating_add(&deposit);
+				if let Some(limit) = self.limit {
+					ensure!(
+						total_deposit.charge_or_zero() <= limit,
+						<Error<T>>::StorageDepositLimitExhausted,
+					);
+				}
+				T::Currency::transfer(
+					&self.origin,
+					contract,
+					amount,
+					ExistenceRequirement::KeepAlive,
+				)
+				.and_then(|_| T::Currency::reserve(contract, amount))
+				.map_err(|_| <Error<T>>::StorageDepositNotEnoughFunds)?;
+				info.storage_deposit = info.storage_deposit.saturating_add(amount);
+				self.total_deposit = total_deposit;
+			},
+			StorageDeposit::Refund(amount) => {
+				let amount = amount.min(info.storage_deposit);
+				if amount.is_zero() {
+					return Ok(());
+				}
+				let amount = amount.saturating_sub(T::Currency::unreserve(contract, amount));
+				T::Currency::transfer(
+					contract,
+					&self.origin,
+					amount,
+					ExistenceRequirement::AllowDeath,
+				)
+				.map_err(|_| <Error<T>>::TransferFailed)?;
+				info.storage_deposit = info.storage_deposit.saturating_sub(amount);
+				self.total_deposit =
+					self.total_deposit.saturating_add(&StorageDeposit::Refund(amount));
+			},
+		}
+		Ok(())
+	}
+}
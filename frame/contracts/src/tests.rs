@@ -24,12 +24,12 @@ use crate::{
 	storage::{RawContractInfo, Storage},
 	wasm::{PrefabWasmModule, ReturnCode as RuntimeReturnCode},
 	weights::WeightInfo,
-	BalanceOf, Config, ContractInfoOf, Error, Pallet, Schedule,
+	BalanceOf, Config, ContractInfoOf, Error, Pallet, PristineCode, Schedule,
 };
 use assert_matches::assert_matches;
 use codec::Encode;
 use frame_support::{
-	assert_err, assert_err_ignore_postinfo, assert_ok,
+	assert_err, assert_err_ignore_postinfo, assert_noop, assert_ok,
 	dispatch::DispatchErrorWithPostInfo,
 	parameter_types,
 	storage::child,
@@ -37,12 +37,13 @@ use frame_support::{
 	weights::{constants::WEIGHT_PER_SECOND, DispatchClass, PostDispatchInfo, Weight},
 };
 use frame_system::{self as system, EventRecord, Phase};
+use pallet_contracts_primitives::{Code, ContractEvent, StorageDeposit};
 use pretty_assertions::assert_eq;
 use sp_core::Bytes;
 use sp_io::hashing::blake2_256;
 use sp_runtime::{
 	testing::{Header, H256},
-	traits::{BlakeTwo256, Convert, Hash, IdentityLookup},
+	traits::{BadOrigin, BlakeTwo256, Convert, Hash, IdentityLookup},
 	AccountId32,
 };
 use std::cell::RefCell;
@@ -73,7 +74,7 @@ pub mod test_utils {
 	use crate::{
 		exec::{AccountIdOf, StorageKey},
 		storage::Storage,
-		AccountCounter, CodeHash, ContractInfoOf, Pallet as Contracts, TrieId,
+		AccountCounter, CodeHash, Config, ContractInfoOf, TrieId,
 	};
 	use frame_support::traits::Currency;
 
@@ -94,7 +95,7 @@ pub mod test_utils {
 	}
 	pub fn place_contract(address: &AccountIdOf<Test>, code_hash: CodeHash<Test>) {
 		let trie_id = generate_trie_id(address);
-		set_balance(address, Contracts::<Test>::subsistence_threshold() * 10);
+		set_balance(address, <Test as Config>::Currency::minimum_balance() * 10);
 		let contract = Storage::<Test>::new_contract(&address, trie_id, code_hash).unwrap();
 		<ContractInfoOf<Test>>::insert(address, contract);
 	}
@@ -113,7 +114,7 @@ pub mod test_utils {
 	}
 	macro_rules! assert_refcount {
 		( $code_hash:expr , $should:expr $(,)? ) => {{
-			let is = crate::CodeStorage::<Test>::get($code_hash).map(|m| m.refcount()).unwrap_or(0);
+			let is = crate::OwnerInfoOf::<Test>::get($code_hash).map(|m| m.refcount()).unwrap_or(0);
 			assert_eq!(is, $should);
 		}};
 	}
@@ -248,7 +249,8 @@ impl pallet_utility::Config for Test {
 	type WeightInfo = ();
 }
 parameter_types! {
-	pub const ContractDeposit: u64 = 16;
+	pub const DepositPerByte: BalanceOf<Test> = 1;
+	pub const DepositPerItem: BalanceOf<Test> = 2;
 	pub const MaxValueSize: u32 = 16_384;
 	pub const DeletionQueueDepth: u32 = 1024;
 	pub const DeletionWeightLimit: Weight = 500_000_000_000;
@@ -289,7 +291,6 @@ impl Config for Test {
 	type Event = Event;
 	type Call = Call;
 	type CallFilter = TestFilter;
-	type ContractDeposit = ContractDeposit;
 	type CallStack = [Frame<Self>; 31];
 	type WeightPrice = Self;
 	type WeightInfo = ();
@@ -297,6 +298,8 @@ impl Config for Test {
 	type DeletionQueueDepth = DeletionQueueDepth;
 	type DeletionWeightLimit = DeletionWeightLimit;
 	type Schedule = MySchedule;
+	type DepositPerByte = DepositPerByte;
+	type DepositPerItem = DepositPerItem;
 }
 
 pub const ALICE: AccountId32 = AccountId32::new([1u8; 32]);
@@ -358,7 +361,7 @@ fn calling_plain_account_fails() {
 		let base_cost = <<Test as Config>::WeightInfo as WeightInfo>::call();
 
 		assert_eq!(
-			Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, Vec::new()),
+			Contracts::call(Origin::signed(ALICE), BOB, 0, GAS_LIMIT, None, Vec::new()),
 			Err(DispatchErrorWithPostInfo {
 				error: Error::<Test>::ContractNotFound.into(),
 				post_info: PostDispatchInfo {
@@ -385,6 +388,7 @@ fn account_removal_does_not_remove_storage() {
 			let alice_contract_info = RawContractInfo {
 				trie_id: trie_id1.clone(),
 				code_hash: H256::repeat_byte(1),
+				storage_deposit: 0,
 				_reserved: None,
 			};
 			let _ = Balances::deposit_creating(&ALICE, 110);
@@ -395,6 +399,7 @@ fn account_removal_does_not_remove_storage() {
 			let bob_contract_info = RawContractInfo {
 				trie_id: trie_id2.clone(),
 				code_hash: H256::repeat_byte(2),
+				storage_deposit: 0,
 				_reserved: None,
 			};
 			let _ = Balances::deposit_creating(&BOB, 110);
@@ -429,18 +434,21 @@ fn instantiate_and_call_and_deposit_event() {
 
 	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
 		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-		let subsistence = Pallet::<Test>::subsistence_threshold();
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let info_deposit = <Pallet<Test>>::contract_deposit();
 
 		// Check at the end to get hash on error easily
 		let creation = Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			wasm,
 			vec![],
 			vec![],
 		);
 		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
+		let code_deposit = Balances::reserved_balance(&ALICE);
 
 		assert_eq!(
 			System::events(),
@@ -475,7 +483,7 @@ fn instantiate_and_call_and_deposit_event() {
 					phase: Phase::Initialization,
 					event: Event::Balances(pallet_balances::Event::Endowed {
 						account: addr.clone(),
-						free_balance: subsistence * 100
+						free_balance: min_balance * 100
 					}),
 					topics: vec![],
 				},
@@ -484,7 +492,15 @@ fn instantiate_and_call_and_deposit_event() {
 					event: Event::Balances(pallet_balances::Event::Transfer {
 						from: ALICE,
 						to: addr.clone(),
-						amount: subsistence * 100
+						amount: min_balance * 100
+					}),
+					topics: vec![],
+				},
+				EventRecord {
+					phase: Phase::Initialization,
+					event: Event::Balances(pallet_balances::Event::Reserved {
+						who: ALICE,
+						amount: code_deposit,
 					}),
 					topics: vec![],
 				},
@@ -511,6 +527,23 @@ fn instantiate_and_call_and_deposit_event() {
 					}),
 					topics: vec![],
 				},
+				EventRecord {
+					phase: Phase::Initialization,
+					event: Event::Balances(pallet_balances::Event::Transfer {
+						from: ALICE,
+						to: addr.clone(),
+						amount: info_deposit,
+					}),
+					topics: vec![],
+				},
+				EventRecord {
+					phase: Phase::Initialization,
+					event: Event::Balances(pallet_balances::Event::Reserved {
+						who: addr.clone(),
+						amount: info_deposit,
+					}),
+					topics: vec![],
+				},
 			]
 		);
 
@@ -530,6 +563,7 @@ fn deposit_event_max_value_limit() {
 			Origin::signed(ALICE),
 			30_000,
 			GAS_LIMIT,
+			None,
 			wasm,
 			vec![],
 			vec![],
@@ -542,6 +576,7 @@ fn deposit_event_max_value_limit() {
 			addr.clone(),
 			0,
 			GAS_LIMIT * 2, // we are copying a huge buffer,
+			None,
 			<Test as Config>::Schedule::get().limits.payload_len.encode(),
 		));
 
@@ -552,6 +587,7 @@ fn deposit_event_max_value_limit() {
 				addr,
 				0,
 				GAS_LIMIT,
+				None,
 				(<Test as Config>::Schedule::get().limits.payload_len + 1).encode(),
 			),
 			Error::<Test>::ValueTooLarge,
@@ -562,15 +598,16 @@ fn deposit_event_max_value_limit() {
 #[test]
 fn run_out_of_gas() {
 	let (wasm, code_hash) = compile_module::<Test>("run_out_of_gas").unwrap();
-	let subsistence = Pallet::<Test>::subsistence_threshold();
+	let min_balance = <Test as Config>::Currency::minimum_balance();
 
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
 		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
 
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			100 * subsistence,
+			100 * min_balance,
 			GAS_LIMIT,
+			None,
 			wasm,
 			vec![],
 			vec![],
@@ -585,6 +622,7 @@ fn run_out_of_gas() {
 				addr, // newly created account
 				0,
 				1_000_000_000_000,
+				None,
 				vec![],
 			),
 			Error::<Test>::OutOfGas,
@@ -607,6 +645,7 @@ fn storage_max_value_limit() {
 			Origin::signed(ALICE),
 			30_000,
 			GAS_LIMIT,
+			None,
 			wasm,
 			vec![],
 			vec![],
@@ -620,6 +659,7 @@ fn storage_max_value_limit() {
 			addr.clone(),
 			0,
 			GAS_LIMIT * 2, // we are copying a huge buffer
+			None,
 			<Test as Config>::Schedule::get().limits.payload_len.encode(),
 		));
 
@@ -630,6 +670,7 @@ fn storage_max_value_limit() {
 				addr,
 				0,
 				GAS_LIMIT,
+				None,
 				(<Test as Config>::Schedule::get().limits.payload_len + 1).encode(),
 			),
 			Error::<Test>::ValueTooLarge,
@@ -649,6 +690,7 @@ fn deploy_and_call_other_contract() {
 			Origin::signed(ALICE),
 			100_000,
 			GAS_LIMIT,
+			None,
 			caller_wasm,
 			vec![],
 			vec![],
@@ -657,6 +699,7 @@ fn deploy_and_call_other_contract() {
 			Origin::signed(ALICE),
 			100_000,
 			GAS_LIMIT,
+			None,
 			callee_wasm,
 			0u32.to_le_bytes().encode(),
 			vec![42],
@@ -669,6 +712,7 @@ fn deploy_and_call_other_contract() {
 			Contracts::contract_address(&ALICE, &caller_code_hash, &[]),
 			0,
 			GAS_LIMIT,
+			None,
 			callee_code_hash.as_ref().to_vec(),
 		));
 	});
@@ -685,6 +729,7 @@ fn cannot_self_destruct_through_draning() {
 			Origin::signed(ALICE),
 			100_000,
 			GAS_LIMIT,
+			None,
 			wasm,
 			vec![],
 			vec![],
@@ -696,7 +741,7 @@ fn cannot_self_destruct_through_draning() {
 
 		// Call BOB which makes it send all funds to the zero address
 		// The contract code asserts that the correct error value is returned.
-		assert_ok!(Contracts::call(Origin::signed(ALICE), addr, 0, GAS_LIMIT, vec![]));
+		assert_ok!(Contracts::call(Origin::signed(ALICE), addr, 0, GAS_LIMIT, None, vec![]));
 	});
 }
 
@@ -711,6 +756,7 @@ fn cannot_self_destruct_while_live() {
 			Origin::signed(ALICE),
 			100_000,
 			GAS_LIMIT,
+			None,
 			wasm,
 			vec![],
 			vec![],
@@ -723,7 +769,7 @@ fn cannot_self_destruct_while_live() {
 		// Call BOB with input data, forcing it make a recursive call to itself to
 		// self-destruct, resulting in a trap.
 		assert_err_ignore_postinfo!(
-			Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, vec![0],),
+			Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, None, vec![0],),
 			Error::<Test>::ContractTrapped,
 		);
 
@@ -738,12 +784,14 @@ fn self_destruct_works() {
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
 		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
 		let _ = Balances::deposit_creating(&DJANGO, 1_000_000);
+		let info_deposit = <Pallet<Test>>::contract_deposit();
 
 		// Instantiate the BOB contract.
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
 			100_000,
 			GAS_LIMIT,
+			None,
 			wasm,
 			vec![],
 			vec![],
@@ -758,13 +806,21 @@ fn self_destruct_works() {
 
 		// Call BOB without input data which triggers termination.
 		assert_matches!(
-			Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, vec![],),
+			Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, None, vec![],),
 			Ok(_)
 		);
 
 		pretty_assertions::assert_eq!(
 			System::events(),
 			vec![
+				EventRecord {
+					phase: Phase::Initialization,
+					event: Event::Balances(pallet_balances::Event::Unreserved {
+						who: addr.clone(),
+						amount: info_deposit,
+					}),
+					topics: vec![],
+				},
 				EventRecord {
 					phase: Phase::Initialization,
 					event: Event::System(frame_system::Event::KilledAccount(addr.clone())),
@@ -775,15 +831,10 @@ fn self_destruct_works() {
 					event: Event::Balances(pallet_balances::Event::Transfer {
 						from: addr.clone(),
 						to: DJANGO,
-						amount: 100_000,
+						amount: 100_000 + info_deposit,
 					}),
 					topics: vec![],
 				},
-				EventRecord {
-					phase: Phase::Initialization,
-					event: Event::Contracts(crate::Event::CodeRemoved { code_hash }),
-					topics: vec![],
-				},
 				EventRecord {
 					phase: Phase::Initialization,
 					event: Event::Contracts(crate::Event::Terminated {
@@ -799,8 +850,8 @@ fn self_destruct_works() {
 		assert!(ContractInfoOf::<Test>::get(&addr).is_none());
 
 		// check that the beneficiary (django) got remaining balance
-		// some rent was deducted before termination
-		assert_eq!(Balances::free_balance(DJANGO), 1_000_000 + 100_000);
+		// including the storage deposit that was released
+		assert_eq!(Balances::free_balance(DJANGO), 1_000_000 + 100_000 + info_deposit);
 	});
 }
 
@@ -818,6 +869,7 @@ fn destroy_contract_and_transfer_funds() {
 			Origin::signed(ALICE),
 			200_000,
 			GAS_LIMIT,
+			None,
 			callee_wasm,
 			vec![],
 			vec![42]
@@ -829,6 +881,7 @@ fn destroy_contract_and_transfer_funds() {
 			Origin::signed(ALICE),
 			200_000,
 			GAS_LIMIT,
+			None,
 			caller_wasm,
 			callee_code_hash.as_ref().to_vec(),
 			vec![],
@@ -845,6 +898,7 @@ fn destroy_contract_and_transfer_funds() {
 			addr_bob,
 			0,
 			GAS_LIMIT,
+			None,
 			addr_charlie.encode(),
 		));
 
@@ -865,6 +919,7 @@ fn cannot_self_destruct_in_constructor() {
 				Origin::signed(ALICE),
 				100_000,
 				GAS_LIMIT,
+				None,
 				wasm,
 				vec![],
 				vec![],
@@ -886,6 +941,7 @@ fn crypto_hashes() {
 			Origin::signed(ALICE),
 			100_000,
 			GAS_LIMIT,
+			None,
 			wasm,
 			vec![],
 			vec![],
@@ -913,7 +969,7 @@ fn crypto_hashes() {
 			let mut params = vec![(n + 1) as u8];
 			params.extend_from_slice(input);
 			let result =
-				<Pallet<Test>>::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, params, false)
+				<Pallet<Test>>::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, None, params, false)
 					.result
 					.unwrap();
 			assert!(result.is_success());
@@ -927,32 +983,35 @@ fn crypto_hashes() {
 fn transfer_return_code() {
 	let (wasm, code_hash) = compile_module::<Test>("transfer_return_code").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
 
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			wasm,
 			vec![],
 			vec![],
 		),);
 		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
 
-		// Contract has only the minimal balance so any transfer will return BelowSubsistence.
-		Balances::make_free_balance_be(&addr, subsistence);
-		let result = Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, vec![], false)
+		// Contract has only the minimal balance so any transfer will fail.
+		Balances::make_free_balance_be(&addr, min_balance);
+		let result = Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, None, vec![], false)
 			.result
 			.unwrap();
-		assert_return_code!(result, RuntimeReturnCode::BelowSubsistenceThreshold);
+		assert_return_code!(result, RuntimeReturnCode::TransferFailed);
 
-		// Contract has enough total balance in order to not go below the subsistence
+		// Contract has enough total balance in order to not go below the min balance
 		// threshold when transfering 100 balance but this balance is reserved so
-		// the transfer still fails but with another return code.
-		Balances::make_free_balance_be(&addr, subsistence + 100);
-		Balances::reserve(&addr, subsistence + 100).unwrap();
-		let result = Contracts::bare_call(ALICE, addr, 0, GAS_LIMIT, vec![], false).result.unwrap();
+		// the transfer still fails.
+		Balances::make_free_balance_be(&addr, min_balance + 100);
+		Balances::reserve(&addr, min_balance + 100).unwrap();
+		let result = Contracts::bare_call(ALICE, addr, 0, GAS_LIMIT, None, vec![], false)
+			.result
+			.unwrap();
 		assert_return_code!(result, RuntimeReturnCode::TransferFailed);
 	});
 }
@@ -962,20 +1021,21 @@ fn call_return_code() {
 	let (caller_code, caller_hash) = compile_module::<Test>("call_return_code").unwrap();
 	let (callee_code, callee_hash) = compile_module::<Test>("ok_trap_revert").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
-		let _ = Balances::deposit_creating(&CHARLIE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
+		let _ = Balances::deposit_creating(&CHARLIE, 1000 * min_balance);
 
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			caller_code,
 			vec![0],
 			vec![],
 		),);
 		let addr_bob = Contracts::contract_address(&ALICE, &caller_hash, &[]);
-		Balances::make_free_balance_be(&addr_bob, subsistence);
+		Balances::make_free_balance_be(&addr_bob, min_balance);
 
 		// Contract calls into Django which is no valid contract
 		let result = Contracts::bare_call(
@@ -983,6 +1043,7 @@ fn call_return_code() {
 			addr_bob.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			AsRef::<[u8]>::as_ref(&DJANGO).to_vec(),
 			false,
 		)
@@ -992,21 +1053,23 @@ fn call_return_code() {
 
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(CHARLIE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			callee_code,
 			vec![0],
 			vec![],
 		),);
 		let addr_django = Contracts::contract_address(&CHARLIE, &callee_hash, &[]);
-		Balances::make_free_balance_be(&addr_django, subsistence);
+		Balances::make_free_balance_be(&addr_django, min_balance);
 
-		// Contract has only the minimal balance so any transfer will return BelowSubsistence.
+		// Contract has only the minimal balance so any transfer will fail.
 		let result = Contracts::bare_call(
 			ALICE,
 			addr_bob.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			AsRef::<[u8]>::as_ref(&addr_django)
 				.iter()
 				.chain(&0u32.to_le_bytes())
@@ -1016,18 +1079,19 @@ fn call_return_code() {
 		)
 		.result
 		.unwrap();
-		assert_return_code!(result, RuntimeReturnCode::BelowSubsistenceThreshold);
+		assert_return_code!(result, RuntimeReturnCode::TransferFailed);
 
-		// Contract has enough total balance in order to not go below the subsistence
+		// Contract has enough total balance in order to not go below the min balance
 		// threshold when transfering 100 balance but this balance is reserved so
-		// the transfer still fails but with another return code.
-		Balances::make_free_balance_be(&addr_bob, subsistence + 100);
-		Balances::reserve(&addr_bob, subsistence + 100).unwrap();
+		// the transfer still fails.
+		Balances::make_free_balance_be(&addr_bob, min_balance + 100);
+		Balances::reserve(&addr_bob, min_balance + 100).unwrap();
 		let result = Contracts::bare_call(
 			ALICE,
 			addr_bob.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			AsRef::<[u8]>::as_ref(&addr_django)
 				.iter()
 				.chain(&0u32.to_le_bytes())
@@ -1040,12 +1104,13 @@ fn call_return_code() {
 		assert_return_code!(result, RuntimeReturnCode::TransferFailed);
 
 		// Contract has enough balance but callee reverts because "1" is passed.
-		Balances::make_free_balance_be(&addr_bob, subsistence + 1000);
+		Balances::make_free_balance_be(&addr_bob, min_balance + 1000);
 		let result = Contracts::bare_call(
 			ALICE,
 			addr_bob.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			AsRef::<[u8]>::as_ref(&addr_django)
 				.iter()
 				.chain(&1u32.to_le_bytes())
@@ -1063,6 +1128,7 @@ fn call_return_code() {
 			addr_bob,
 			0,
 			GAS_LIMIT,
+			None,
 			AsRef::<[u8]>::as_ref(&addr_django)
 				.iter()
 				.chain(&2u32.to_le_bytes())
@@ -1081,15 +1147,16 @@ fn instantiate_return_code() {
 	let (caller_code, caller_hash) = compile_module::<Test>("instantiate_return_code").unwrap();
 	let (callee_code, callee_hash) = compile_module::<Test>("ok_trap_revert").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
-		let _ = Balances::deposit_creating(&CHARLIE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
+		let _ = Balances::deposit_creating(&CHARLIE, 1000 * min_balance);
 		let callee_hash = callee_hash.as_ref().to_vec();
 
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			callee_code,
 			vec![],
 			vec![],
@@ -1097,38 +1164,54 @@ fn instantiate_return_code() {
 
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			caller_code,
 			vec![],
 			vec![],
 		),);
 		let addr = Contracts::contract_address(&ALICE, &caller_hash, &[]);
 
-		// Contract has only the minimal balance so any transfer will return BelowSubsistence.
-		Balances::make_free_balance_be(&addr, subsistence);
-		let result =
-			Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, callee_hash.clone(), false)
-				.result
-				.unwrap();
-		assert_return_code!(result, RuntimeReturnCode::BelowSubsistenceThreshold);
+		// Contract has only the minimal balance so any transfer will fail.
+		Balances::make_free_balance_be(&addr, min_balance);
+		let result = Contracts::bare_call(
+			ALICE,
+			addr.clone(),
+			0,
+			GAS_LIMIT,
+			None,
+			callee_hash.clone(),
+			false,
+		)
+		.result
+		.unwrap();
+		assert_return_code!(result, RuntimeReturnCode::TransferFailed);
 
-		// Contract has enough total balance in order to not go below the subsistence
+		// Contract has enough total balance in order to not go below the min balance
 		// threshold when transfering the balance but this balance is reserved so
-		// the transfer still fails but with another return code.
-		Balances::make_free_balance_be(&addr, subsistence + 10_000);
-		Balances::reserve(&addr, subsistence + 10_000).unwrap();
-		let result =
-			Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, callee_hash.clone(), false)
-				.result
-				.unwrap();
+		// the transfer still fails.
+		Balances::make_free_balance_be(&addr, min_balance + 10_000);
+		Balances::reserve(&addr, min_balance + 10_000).unwrap();
+		let result = Contracts::bare_call(
+			ALICE,
+			addr.clone(),
+			0,
+			GAS_LIMIT,
+			None,
+			callee_hash.clone(),
+			false,
+		)
+		.result
+		.unwrap();
 		assert_return_code!(result, RuntimeReturnCode::TransferFailed);
 
 		// Contract has enough balance but the passed code hash is invalid
-		Balances::make_free_balance_be(&addr, subsistence + 10_000);
-		let result = Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, vec![0; 33], false)
-			.result
-			.unwrap();
+		Balances::make_free_balance_be(&addr, min_balance + 10_000);
+		let result =
+			Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, None, vec![0; 33], false)
+				.result
+				.unwrap();
 		assert_return_code!(result, RuntimeReturnCode::CodeNotFound);
 
 		// Contract has enough balance but callee reverts because "1" is passed.
@@ -1137,6 +1220,7 @@ fn instantiate_return_code() {
 			addr.clone(),
 			0,
 			GAS_LIMIT,
+			None,
 			callee_hash.iter().chain(&1u32.to_le_bytes()).cloned().collect(),
 			false,
 		)
@@ -1150,6 +1234,7 @@ fn instantiate_return_code() {
 			addr,
 			0,
 			GAS_LIMIT,
+			None,
 			callee_hash.iter().chain(&2u32.to_le_bytes()).cloned().collect(),
 			false,
 		)
@@ -1163,14 +1248,15 @@ fn instantiate_return_code() {
 fn disabled_chain_extension_wont_deploy() {
 	let (code, _hash) = compile_module::<Test>("chain_extension").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
 		TestExtension::disable();
 		assert_err_ignore_postinfo!(
 			Contracts::instantiate_with_code(
 				Origin::signed(ALICE),
-				3 * subsistence,
+				3 * min_balance,
 				GAS_LIMIT,
+				None,
 				code,
 				vec![],
 				vec![],
@@ -1184,12 +1270,13 @@ fn disabled_chain_extension_wont_deploy() {
 fn disabled_chain_extension_errors_on_call() {
 	let (code, hash) = compile_module::<Test>("chain_extension").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			code,
 			vec![],
 			vec![],
@@ -1197,7 +1284,7 @@ fn disabled_chain_extension_errors_on_call() {
 		let addr = Contracts::contract_address(&ALICE, &hash, &[]);
 		TestExtension::disable();
 		assert_err_ignore_postinfo!(
-			Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, vec![],),
+			Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, None, vec![],),
 			Error::<Test>::NoChainExtension,
 		);
 	});
@@ -1207,12 +1294,13 @@ fn disabled_chain_extension_errors_on_call() {
 fn chain_extension_works() {
 	let (code, hash) = compile_module::<Test>("chain_extension").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			code,
 			vec![],
 			vec![],
@@ -1224,25 +1312,27 @@ fn chain_extension_works() {
 		// func_id.
 
 		// 0 = read input buffer and pass it through as output
-		let result = Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, vec![0, 99], false);
+		let result =
+			Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, None, vec![0, 99], false);
 		let gas_consumed = result.gas_consumed;
 		assert_eq!(TestExtension::last_seen_buffer(), vec![0, 99]);
 		assert_eq!(result.result.unwrap().data, Bytes(vec![0, 99]));
 
 		// 1 = treat inputs as integer primitives and store the supplied integers
-		Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, vec![1], false)
+		Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, None, vec![1], false)
 			.result
 			.unwrap();
 		// those values passed in the fixture
 		assert_eq!(TestExtension::last_seen_inputs(), (4, 1, 16, 12));
 
 		// 2 = charge some extra weight (amount supplied in second byte)
-		let result = Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, vec![2, 42], false);
+		let result =
+			Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, None, vec![2, 42], false);
 		assert_ok!(result.result);
 		assert_eq!(result.gas_consumed, gas_consumed + 42);
 
 		// 3 = diverging chain extension call that sets flags to 0x1 and returns a fixed buffer
-		let result = Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, vec![3], false)
+		let result = Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, None, vec![3], false)
 			.result
 			.unwrap();
 		assert_eq!(result.flags, ReturnFlags::REVERT);
@@ -1254,13 +1344,14 @@ fn chain_extension_works() {
 fn lazy_removal_works() {
 	let (code, hash) = compile_module::<Test>("self_destruct").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
 
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			code,
 			vec![],
 			vec![],
@@ -1274,7 +1365,14 @@ fn lazy_removal_works() {
 		child::put(trie, &[99], &42);
 
 		// Terminate the contract
-		assert_ok!(Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, vec![]));
+		assert_ok!(Contracts::call(
+			Origin::signed(ALICE),
+			addr.clone(),
+			0,
+			GAS_LIMIT,
+			None,
+			vec![]
+		));
 
 		// Contract info should be gone
 		assert!(!<ContractInfoOf::<Test>>::contains_key(&addr));
@@ -1305,13 +1403,14 @@ fn lazy_removal_partial_remove_works() {
 	let mut ext = ExtBuilder::default().existential_deposit(50).build();
 
 	let trie = ext.execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
 
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			code,
 			vec![],
 			vec![],
@@ -1327,7 +1426,14 @@ fn lazy_removal_partial_remove_works() {
 		<ContractInfoOf<Test>>::insert(&addr, info.clone());
 
 		// Terminate the contract
-		assert_ok!(Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, vec![]));
+		assert_ok!(Contracts::call(
+			Origin::signed(ALICE),
+			addr.clone(),
+			0,
+			GAS_LIMIT,
+			None,
+			vec![]
+		));
 
 		// Contract info should be gone
 		assert!(!<ContractInfoOf::<Test>>::contains_key(&addr));
@@ -1375,13 +1481,14 @@ fn lazy_removal_partial_remove_works() {
 fn lazy_removal_does_no_run_on_full_block() {
 	let (code, hash) = compile_module::<Test>("self_destruct").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
 
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			code,
 			vec![],
 			vec![],
@@ -1403,7 +1510,14 @@ fn lazy_removal_does_no_run_on_full_block() {
 		<ContractInfoOf<Test>>::insert(&addr, info.clone());
 
 		// Terminate the contract
-		assert_ok!(Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, vec![]));
+		assert_ok!(Contracts::call(
+			Origin::signed(ALICE),
+			addr.clone(),
+			0,
+			GAS_LIMIT,
+			None,
+			vec![]
+		));
 
 		// Contract info should be gone
 		assert!(!<ContractInfoOf::<Test>>::contains_key(&addr));
@@ -1450,13 +1564,14 @@ fn lazy_removal_does_not_use_all_weight() {
 	let mut ext = ExtBuilder::default().existential_deposit(50).build();
 
 	let (trie, vals, weight_per_key) = ext.execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
 
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			code,
 			vec![],
 			vec![],
@@ -1478,7 +1593,14 @@ fn lazy_removal_does_not_use_all_weight() {
 		<ContractInfoOf<Test>>::insert(&addr, info.clone());
 
 		// Terminate the contract
-		assert_ok!(Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, vec![]));
+		assert_ok!(Contracts::call(
+			Origin::signed(ALICE),
+			addr.clone(),
+			0,
+			GAS_LIMIT,
+			None,
+			vec![]
+		));
 
 		// Contract info should be gone
 		assert!(!<ContractInfoOf::<Test>>::contains_key(&addr));
@@ -1515,13 +1637,14 @@ fn lazy_removal_does_not_use_all_weight() {
 fn deletion_queue_full() {
 	let (code, hash) = compile_module::<Test>("self_destruct").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
 
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			code,
 			vec![],
 			vec![],
@@ -1534,7 +1657,7 @@ fn deletion_queue_full() {
 
 		// Terminate the contract should fail
 		assert_err_ignore_postinfo!(
-			Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, vec![],),
+			Contracts::call(Origin::signed(ALICE), addr.clone(), 0, GAS_LIMIT, None, vec![],),
 			Error::<Test>::DeletionQueueFull,
 		);
 
@@ -1548,21 +1671,23 @@ fn refcounter() {
 	let (wasm, code_hash) = compile_module::<Test>("self_destruct").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
 		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-		let subsistence = Pallet::<Test>::subsistence_threshold();
+		let min_balance = <Test as Config>::Currency::minimum_balance();
 
 		// Create two contracts with the same code and check that they do in fact share it.
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			wasm.clone(),
 			vec![],
 			vec![0],
 		));
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			wasm.clone(),
 			vec![],
 			vec![1],
@@ -1572,8 +1697,9 @@ fn refcounter() {
 		// Sharing should also work with the usual instantiate call
 		assert_ok!(Contracts::instantiate(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			code_hash,
 			vec![],
 			vec![2],
@@ -1586,23 +1712,29 @@ fn refcounter() {
 		let addr2 = Contracts::contract_address(&ALICE, &code_hash, &[2]);
 
 		// Terminating one contract should decrement the refcount
-		assert_ok!(Contracts::call(Origin::signed(ALICE), addr0, 0, GAS_LIMIT, vec![]));
+		assert_ok!(Contracts::call(Origin::signed(ALICE), addr0, 0, GAS_LIMIT, None, vec![]));
 		assert_refcount!(code_hash, 2);
 
 		// remove another one
-		assert_ok!(Contracts::call(Origin::signed(ALICE), addr1, 0, GAS_LIMIT, vec![]));
+		assert_ok!(Contracts::call(Origin::signed(ALICE), addr1, 0, GAS_LIMIT, None, vec![]));
 		assert_refcount!(code_hash, 1);
 
 		// Pristine code should still be there
 		crate::PristineCode::<Test>::get(code_hash).unwrap();
 
 		// remove the last contract
-		assert_ok!(Contracts::call(Origin::signed(ALICE), addr2, 0, GAS_LIMIT, vec![]));
+		assert_ok!(Contracts::call(Origin::signed(ALICE), addr2, 0, GAS_LIMIT, None, vec![]));
 		assert_refcount!(code_hash, 0);
 
-		// all code should be gone
+		// refcount is `0` but code should still exists because it needs to be removed manually
+		assert!(crate::PristineCode::<Test>::contains_key(&code_hash));
+		assert!(crate::CodeStorage::<Test>::contains_key(&code_hash));
+
+		// all code should be gone once it is removed by its owner
+		assert_ok!(Contracts::remove_code(Origin::signed(ALICE), code_hash));
 		assert_matches!(crate::PristineCode::<Test>::get(code_hash), None);
 		assert_matches!(crate::CodeStorage::<Test>::get(code_hash), None);
+		assert_matches!(crate::OwnerInfoOf::<Test>::get(code_hash), None);
 	});
 }
 
@@ -1611,14 +1743,15 @@ fn reinstrument_does_charge() {
 	let (wasm, code_hash) = compile_module::<Test>("return_with_data").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
 		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
-		let subsistence = Pallet::<Test>::subsistence_threshold();
+		let min_balance = <Test as Config>::Currency::minimum_balance();
 		let zero = 0u32.to_le_bytes().encode();
 		let code_len = wasm.len() as u32;
 
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			wasm,
 			zero.clone(),
 			vec![],
@@ -1628,10 +1761,12 @@ fn reinstrument_does_charge() {
 
 		// Call the contract two times without reinstrument
 
-		let result0 = Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, zero.clone(), false);
+		let result0 =
+			Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, None, zero.clone(), false);
 		assert!(result0.result.unwrap().is_success());
 
-		let result1 = Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, zero.clone(), false);
+		let result1 =
+			Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, None, zero.clone(), false);
 		assert!(result1.result.unwrap().is_success());
 
 		// They should match because both where called with the same schedule.
@@ -1644,7 +1779,8 @@ fn reinstrument_does_charge() {
 		});
 
 		// This call should trigger reinstrumentation
-		let result2 = Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, zero.clone(), false);
+		let result2 =
+			Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, None, zero.clone(), false);
 		assert!(result2.result.unwrap().is_success());
 		assert!(result2.gas_consumed > result1.gas_consumed);
 		assert_eq!(
@@ -1664,18 +1800,140 @@ fn debug_message_works() {
 			Origin::signed(ALICE),
 			30_000,
 			GAS_LIMIT,
+			None,
 			wasm,
 			vec![],
 			vec![],
 		),);
 		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-		let result = Contracts::bare_call(ALICE, addr, 0, GAS_LIMIT, vec![], true);
+		let result = Contracts::bare_call(ALICE, addr, 0, GAS_LIMIT, None, vec![], true);
 
 		assert_matches!(result.result, Ok(_));
 		assert_eq!(std::str::from_utf8(&result.debug_message).unwrap(), "Hello World!");
 	});
 }
 
+#[test]
+fn trace_call_records_debug_message() {
+	let (wasm, code_hash) = compile_module::<Test>("debug_message_works").unwrap();
+
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+		assert_ok!(Contracts::instantiate_with_code(
+			Origin::signed(ALICE),
+			30_000,
+			GAS_LIMIT,
+			None,
+			wasm,
+			vec![],
+			vec![],
+		));
+		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
+
+		let (result, traces) = Contracts::trace(3, || {
+			Contracts::call(Origin::signed(ALICE), addr, 0, GAS_LIMIT, None, vec![])
+		});
+
+		assert_ok!(result);
+		assert_eq!(traces.len(), 1);
+		assert_eq!(traces[0].extrinsic_index, 3);
+		assert_eq!(traces[0].origin, ALICE);
+		assert_matches!(traces[0].result.result, Ok(_));
+		assert!(traces[0].result.gas_consumed > 0);
+		assert_eq!(std::str::from_utf8(&traces[0].result.debug_message).unwrap(), "Hello World!");
+		assert_eq!(traces[0].events, vec![]);
+	});
+}
+
+#[test]
+fn trace_instantiate_records_events() {
+	let (wasm, code_hash) = compile_module::<Test>("return_from_start_fn").unwrap();
+
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
+
+		let (result, traces) = Contracts::trace(0, || {
+			Contracts::instantiate_with_code(
+				Origin::signed(ALICE),
+				30_000,
+				GAS_LIMIT,
+				None,
+				wasm,
+				vec![],
+				vec![],
+			)
+		});
+
+		assert_ok!(result);
+		assert_eq!(traces.len(), 1);
+		assert_matches!(traces[0].result.result, Ok(_));
+		assert_eq!(
+			traces[0].events,
+			vec![ContractEvent {
+				contract: addr.clone(),
+				topics: vec![],
+				data: Bytes(vec![1, 2, 3, 4]),
+			}]
+		);
+
+		// The traced dispatch is applied like any other.
+		assert!(<ContractInfoOf<Test>>::contains_key(&addr));
+	});
+}
+
+#[test]
+fn trace_covers_calls_nested_in_other_dispatchables() {
+	let (wasm, code_hash) = compile_module::<Test>("debug_message_works").unwrap();
+
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+		assert_ok!(Contracts::instantiate_with_code(
+			Origin::signed(ALICE),
+			30_000,
+			GAS_LIMIT,
+			None,
+			wasm,
+			vec![],
+			vec![],
+		));
+		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
+		let call = Call::Contracts(crate::Call::call {
+			dest: addr,
+			value: 0,
+			gas_limit: GAS_LIMIT,
+			storage_deposit_limit: None,
+			data: vec![],
+		});
+
+		let (result, traces) =
+			Contracts::trace(1, || Utility::batch(Origin::signed(ALICE), vec![call.clone(), call]));
+
+		assert_ok!(result);
+		assert_eq!(traces.len(), 2);
+		for trace in traces {
+			assert_eq!(trace.extrinsic_index, 1);
+			assert_eq!(trace.origin, ALICE);
+			assert_eq!(std::str::from_utf8(&trace.result.debug_message).unwrap(), "Hello World!");
+		}
+	});
+}
+
+#[test]
+fn trace_ignores_calls_without_contract_execution() {
+	let (wasm, _code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+		let (result, traces) =
+			Contracts::trace(0, || Contracts::upload_code(Origin::signed(ALICE), wasm, None));
+
+		assert_ok!(result);
+		assert_eq!(traces, vec![]);
+	});
+}
+
 #[test]
 fn debug_message_logging_disabled() {
 	let (wasm, code_hash) = compile_module::<Test>("debug_message_logging_disabled").unwrap();
@@ -1686,16 +1944,17 @@ fn debug_message_logging_disabled() {
 			Origin::signed(ALICE),
 			30_000,
 			GAS_LIMIT,
+			None,
 			wasm,
 			vec![],
 			vec![],
 		),);
 		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
 		// disable logging by passing `false`
-		let result = Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, vec![], false);
+		let result = Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, None, vec![], false);
 		assert_matches!(result.result, Ok(_));
 		// the dispatchables always run without debugging
-		assert_ok!(Contracts::call(Origin::signed(ALICE), addr, 0, GAS_LIMIT, vec![]));
+		assert_ok!(Contracts::call(Origin::signed(ALICE), addr, 0, GAS_LIMIT, None, vec![]));
 		assert!(result.debug_message.is_empty());
 	});
 }
@@ -1710,12 +1969,13 @@ fn debug_message_invalid_utf8() {
 			Origin::signed(ALICE),
 			30_000,
 			GAS_LIMIT,
+			None,
 			wasm,
 			vec![],
 			vec![],
 		),);
 		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
-		let result = Contracts::bare_call(ALICE, addr, 0, GAS_LIMIT, vec![], true);
+		let result = Contracts::bare_call(ALICE, addr, 0, GAS_LIMIT, None, vec![], true);
 		assert_err!(result.result, <Error<Test>>::DebugMessageInvalidUTF8);
 	});
 }
@@ -1725,14 +1985,15 @@ fn gas_estimation_nested_call_fixed_limit() {
 	let (caller_code, caller_hash) = compile_module::<Test>("call_with_limit").unwrap();
 	let (callee_code, callee_hash) = compile_module::<Test>("dummy").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
-		let _ = Balances::deposit_creating(&CHARLIE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
+		let _ = Balances::deposit_creating(&CHARLIE, 1000 * min_balance);
 
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			caller_code,
 			vec![],
 			vec![0],
@@ -1741,8 +2002,9 @@ fn gas_estimation_nested_call_fixed_limit() {
 
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			callee_code,
 			vec![],
 			vec![1],
@@ -1756,15 +2018,23 @@ fn gas_estimation_nested_call_fixed_limit() {
 			.collect();
 
 		// Call in order to determine the gas that is required for this call
-		let result =
-			Contracts::bare_call(ALICE, addr_caller.clone(), 0, GAS_LIMIT, input.clone(), false);
+		let result = Contracts::bare_call(
+			ALICE,
+			addr_caller.clone(),
+			0,
+			GAS_LIMIT,
+			None,
+			input.clone(),
+			false,
+		);
 		assert_ok!(&result.result);
 
 		assert!(result.gas_required > result.gas_consumed);
 
 		// Make the same call using the estimated gas. Should succeed.
 		assert_ok!(
-			Contracts::bare_call(ALICE, addr_caller, 0, result.gas_required, input, false,).result
+			Contracts::bare_call(ALICE, addr_caller, 0, result.gas_required, None, input, false,)
+				.result
 		);
 	});
 }
@@ -1775,14 +2045,15 @@ fn gas_estimation_call_runtime() {
 	let (caller_code, caller_hash) = compile_module::<Test>("call_runtime").unwrap();
 	let (callee_code, callee_hash) = compile_module::<Test>("dummy").unwrap();
 	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
-		let subsistence = Pallet::<Test>::subsistence_threshold();
-		let _ = Balances::deposit_creating(&ALICE, 1000 * subsistence);
-		let _ = Balances::deposit_creating(&CHARLIE, 1000 * subsistence);
+		let min_balance = <Test as Config>::Currency::minimum_balance();
+		let _ = Balances::deposit_creating(&ALICE, 1000 * min_balance);
+		let _ = Balances::deposit_creating(&CHARLIE, 1000 * min_balance);
 
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			caller_code,
 			vec![],
 			vec![0],
@@ -1791,8 +2062,9 @@ fn gas_estimation_call_runtime() {
 
 		assert_ok!(Contracts::instantiate_with_code(
 			Origin::signed(ALICE),
-			subsistence * 100,
+			min_balance * 100,
 			GAS_LIMIT,
+			None,
 			callee_code,
 			vec![],
 			vec![1],
@@ -1807,16 +2079,31 @@ fn gas_estimation_call_runtime() {
 			gas_limit: GAS_LIMIT / 3,
 			data: vec![],
 		});
-		let result =
-			Contracts::bare_call(ALICE, addr_caller.clone(), 0, GAS_LIMIT, call.encode(), false);
+		let result = Contracts::bare_call(
+			ALICE,
+			addr_caller.clone(),
+			0,
+			GAS_LIMIT,
+			None,
+			call.encode(),
+			false,
+		);
 		assert_ok!(&result.result);
 
 		assert!(result.gas_required > result.gas_consumed);
 
 		// Make the same call using the required gas. Should succeed.
 		assert_ok!(
-			Contracts::bare_call(ALICE, addr_caller, 0, result.gas_required, call.encode(), false,)
-				.result
+			Contracts::bare_call(
+				ALICE,
+				addr_caller,
+				0,
+				result.gas_required,
+				None,
+				call.encode(),
+				false,
+			)
+			.result
 		);
 	});
 }
@@ -1834,6 +2121,7 @@ fn ecdsa_recover() {
 			Origin::signed(ALICE),
 			100_000,
 			GAS_LIMIT,
+			None,
 			wasm,
 			vec![],
 			vec![],
@@ -1863,10 +2151,390 @@ fn ecdsa_recover() {
 		params.extend_from_slice(&signature);
 		params.extend_from_slice(&message_hash);
 		assert!(params.len() == 65 + 32);
-		let result = <Pallet<Test>>::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, params, false)
-			.result
-			.unwrap();
+		let result =
+			<Pallet<Test>>::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, None, params, false)
+				.result
+				.unwrap();
 		assert!(result.is_success());
 		assert_eq!(result.data.as_ref(), &EXPECTED_COMPRESSED_PUBLIC_KEY);
 	})
 }
+
+#[test]
+#[cfg(feature = "unstable-interface")]
+fn set_code_hash() {
+	let (wasm, code_hash) = compile_module::<Test>("set_code_hash").unwrap();
+	let (new_wasm, new_code_hash) = compile_module::<Test>("new_set_code_hash_contract").unwrap();
+
+	let contract_addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+		// Instantiate the 'caller'
+		assert_ok!(Contracts::instantiate_with_code(
+			Origin::signed(ALICE),
+			300_000,
+			GAS_LIMIT,
+			None,
+			wasm,
+			vec![],
+			vec![],
+		));
+		// upload new code
+		assert_ok!(Contracts::upload_code(Origin::signed(ALICE), new_wasm, None));
+		assert_refcount!(&code_hash, 1);
+		assert_refcount!(&new_code_hash, 0);
+
+		// Drop previous events
+		initialize_block(2);
+
+		// First call sets new code_hash and returns 1
+		let result = Contracts::bare_call(
+			ALICE,
+			contract_addr.clone(),
+			0,
+			GAS_LIMIT,
+			None,
+			new_code_hash.as_ref().to_vec(),
+			true,
+		)
+		.result
+		.unwrap();
+		assert_return_code!(result, 1);
+		assert_refcount!(&code_hash, 0);
+		assert_refcount!(&new_code_hash, 1);
+
+		// Second calls new contract code that returns 2
+		let result =
+			Contracts::bare_call(ALICE, contract_addr.clone(), 0, GAS_LIMIT, None, vec![], true)
+				.result
+				.unwrap();
+		assert_return_code!(result, 2);
+
+		// Checking for the last event only
+		assert_eq!(
+			System::events().pop().unwrap(),
+			EventRecord {
+				phase: Phase::Initialization,
+				event: Event::Contracts(crate::Event::ContractCodeUpdated {
+					contract: contract_addr.clone(),
+					new_code_hash: new_code_hash.clone(),
+					old_code_hash: code_hash.clone(),
+				}),
+				topics: vec![],
+			},
+		);
+	});
+}
+
+#[test]
+fn storage_deposit_works() {
+	let (wasm, code_hash) = compile_module::<Test>("storage_size").unwrap();
+
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+		let info_deposit = <Pallet<Test>>::contract_deposit();
+
+		// The origin pays the deposit for the contract info and the code on instantiation.
+		let result = Contracts::bare_instantiate(
+			ALICE,
+			30_000,
+			GAS_LIMIT,
+			None,
+			Code::Upload(Bytes(wasm)),
+			vec![],
+			vec![],
+			false,
+		);
+		assert_ok!(result.result);
+		let code_deposit = Balances::reserved_balance(&ALICE);
+		assert_eq!(result.storage_deposit, StorageDeposit::Charge(info_deposit + code_deposit));
+		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
+		assert_eq!(ContractInfoOf::<Test>::get(&addr).unwrap().storage_deposit, info_deposit);
+		assert_eq!(Balances::reserved_balance(&addr), info_deposit);
+		assert_eq!(Balances::free_balance(&addr), 30_000);
+
+		// Creating a new item of 100 bytes charges for the item and its bytes.
+		let result =
+			Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, None, 100u32.encode(), false);
+		assert_ok!(result.result);
+		assert_eq!(result.storage_deposit, StorageDeposit::Charge(100 + 2));
+		assert_eq!(Balances::reserved_balance(&addr), info_deposit + 102);
+
+		// Shrinking the item refunds the bytes that are no longer used.
+		let result =
+			Contracts::bare_call(ALICE, addr.clone(), 0, GAS_LIMIT, None, 40u32.encode(), false);
+		assert_ok!(result.result);
+		assert_eq!(result.storage_deposit, StorageDeposit::Refund(60));
+		assert_eq!(Balances::reserved_balance(&addr), info_deposit + 42);
+		assert_eq!(ContractInfoOf::<Test>::get(&addr).unwrap().storage_deposit, info_deposit + 42);
+
+		// The contract's free balance is untouched while the origin paid the net deposit.
+		assert_eq!(Balances::free_balance(&addr), 30_000);
+		assert_eq!(
+			Balances::free_balance(&ALICE),
+			1_000_000 - 30_000 - info_deposit - 42 - code_deposit,
+		);
+	});
+}
+
+#[test]
+fn storage_deposit_limit_is_enforced() {
+	let (wasm, code_hash) = compile_module::<Test>("storage_size").unwrap();
+
+	ExtBuilder::default().existential_deposit(50).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+		let info_deposit = <Pallet<Test>>::contract_deposit();
+
+		// Upload the code separately so that its deposit is not part of the limit.
+		assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm, None));
+
+		// Instantiation fails when the limit does not cover the contract info.
+		assert_err_ignore_postinfo!(
+			Contracts::instantiate(
+				Origin::signed(ALICE),
+				30_000,
+				GAS_LIMIT,
+				Some(codec::Compact(info_deposit - 1)),
+				code_hash,
+				vec![],
+				vec![],
+			),
+			Error::<Test>::StorageDepositLimitExhausted,
+		);
+		assert_ok!(Contracts::instantiate(
+			Origin::signed(ALICE),
+			30_000,
+			GAS_LIMIT,
+			Some(codec::Compact(info_deposit)),
+			code_hash,
+			vec![],
+			vec![],
+		));
+		let addr = Contracts::contract_address(&ALICE, &code_hash, &[]);
+
+		// Storing 100 bytes requires a deposit of 102.
+		assert_err_ignore_postinfo!(
+			Contracts::call(
+				Origin::signed(ALICE),
+				addr.clone(),
+				0,
+				GAS_LIMIT,
+				Some(codec::Compact(101)),
+				100u32.encode(),
+			),
+			Error::<Test>::StorageDepositLimitExhausted,
+		);
+		assert_eq!(Balances::reserved_balance(&addr), info_deposit);
+		assert_ok!(Contracts::call(
+			Origin::signed(ALICE),
+			addr.clone(),
+			0,
+			GAS_LIMIT,
+			Some(codec::Compact(102)),
+			100u32.encode(),
+		));
+		assert_eq!(Balances::reserved_balance(&addr), info_deposit + 102);
+
+		// The origin cannot pay more than its free balance.
+		Balances::make_free_balance_be(&ALICE, 100);
+		assert_err_ignore_postinfo!(
+			Contracts::call(Origin::signed(ALICE), addr, 0, GAS_LIMIT, None, 200u32.encode()),
+			Error::<Test>::StorageDepositNotEnoughFunds,
+		);
+	});
+}
+
+#[test]
+fn upload_code_works() {
+	let (wasm, code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+		// Drop previous events
+		initialize_block(2);
+
+		assert!(!<PristineCode<Test>>::contains_key(&code_hash));
+		assert_ok!(Contracts::upload_code(
+			Origin::signed(ALICE),
+			wasm,
+			Some(codec::Compact(1_000))
+		));
+		assert!(<PristineCode<Test>>::contains_key(&code_hash));
+		let deposit = Balances::reserved_balance(&ALICE);
+		assert!(deposit > 0);
+		assert_refcount!(code_hash, 0);
+
+		assert_eq!(
+			System::events(),
+			vec![
+				EventRecord {
+					phase: Phase::Initialization,
+					event: Event::Balances(pallet_balances::Event::Reserved {
+						who: ALICE,
+						amount: deposit,
+					}),
+					topics: vec![],
+				},
+				EventRecord {
+					phase: Phase::Initialization,
+					event: Event::Contracts(crate::Event::CodeStored { code_hash }),
+					topics: vec![],
+				},
+			]
+		);
+
+		// Uploading the same code again succeeds without reserving another deposit.
+		let result =
+			Contracts::bare_upload_code(ALICE, compile_module::<Test>("dummy").unwrap().0, None)
+				.unwrap();
+		assert_eq!(result.code_hash, code_hash);
+		assert_eq!(result.deposit, 0);
+		assert_eq!(Balances::reserved_balance(&ALICE), deposit);
+	});
+}
+
+#[test]
+fn upload_code_limit_too_low() {
+	let (wasm, _code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+		// Drop previous events
+		initialize_block(2);
+
+		assert_noop!(
+			Contracts::upload_code(Origin::signed(ALICE), wasm, Some(codec::Compact(100))),
+			<Error<Test>>::StorageDepositLimitExhausted,
+		);
+
+		assert_eq!(System::events(), vec![]);
+	});
+}
+
+#[test]
+fn upload_code_not_enough_balance() {
+	let (wasm, _code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 150);
+
+		// Drop previous events
+		initialize_block(2);
+
+		assert_noop!(
+			Contracts::upload_code(Origin::signed(ALICE), wasm, Some(codec::Compact(1_000))),
+			<Error<Test>>::StorageDepositNotEnoughFunds,
+		);
+
+		assert_eq!(System::events(), vec![]);
+	});
+}
+
+#[test]
+fn remove_code_works() {
+	let (wasm, code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+		assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm, None));
+		let deposit = Balances::reserved_balance(&ALICE);
+
+		// Drop previous events
+		initialize_block(2);
+
+		assert_ok!(Contracts::remove_code(Origin::signed(ALICE), code_hash));
+		assert!(!<PristineCode<Test>>::contains_key(&code_hash));
+		assert_eq!(Balances::reserved_balance(&ALICE), 0);
+
+		assert_eq!(
+			System::events(),
+			vec![
+				EventRecord {
+					phase: Phase::Initialization,
+					event: Event::Balances(pallet_balances::Event::Unreserved {
+						who: ALICE,
+						amount: deposit,
+					}),
+					topics: vec![],
+				},
+				EventRecord {
+					phase: Phase::Initialization,
+					event: Event::Contracts(crate::Event::CodeRemoved { code_hash }),
+					topics: vec![],
+				},
+			]
+		);
+	});
+}
+
+#[test]
+fn remove_code_wrong_origin() {
+	let (wasm, code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+		assert_ok!(Contracts::upload_code(Origin::signed(ALICE), wasm, None));
+
+		// Drop previous events
+		initialize_block(2);
+
+		assert_noop!(Contracts::remove_code(Origin::signed(BOB), code_hash), BadOrigin);
+		assert!(<PristineCode<Test>>::contains_key(&code_hash));
+
+		assert_eq!(System::events(), vec![]);
+	});
+}
+
+#[test]
+fn remove_code_in_use() {
+	let (wasm, code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+		assert_ok!(Contracts::instantiate_with_code(
+			Origin::signed(ALICE),
+			1_000,
+			GAS_LIMIT,
+			None,
+			wasm,
+			vec![],
+			vec![],
+		));
+
+		// Drop previous events
+		initialize_block(2);
+
+		assert_noop!(
+			Contracts::remove_code(Origin::signed(ALICE), code_hash),
+			<Error<Test>>::CodeInUse,
+		);
+
+		assert_eq!(System::events(), vec![]);
+	});
+}
+
+#[test]
+fn remove_code_not_found() {
+	let (_wasm, code_hash) = compile_module::<Test>("dummy").unwrap();
+
+	ExtBuilder::default().existential_deposit(100).build().execute_with(|| {
+		let _ = Balances::deposit_creating(&ALICE, 1_000_000);
+
+		// Drop previous events
+		initialize_block(2);
+
+		assert_noop!(
+			Contracts::remove_code(Origin::signed(ALICE), code_hash),
+			<Error<Test>>::CodeNotFound,
+		);
+
+		assert_eq!(System::events(), vec![]);
+	});
+}
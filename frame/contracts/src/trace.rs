@@ -0,0 +1,124 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Recording of the contract calls that are dispatched while tracing.
+//!
+//! Tracing is switched on for the duration of [`record`]. While it is on the dispatchables of
+//! this pallet execute with debug message recording enabled and report their results here.
+//! Nothing is recorded and no additional allocations are made while tracing is off.
+
+use crate::{exec::AccountIdOf, BalanceOf, Config, InternalOutput};
+use codec::{Decode, Encode};
+use pallet_contracts_primitives::{
+	ContractCallTrace, ContractEvent, ContractExecResult, ExecReturnValue,
+};
+use sp_core::Bytes;
+use sp_std::{mem, prelude::*};
+
+/// Everything that was recorded so far.
+///
+/// The values are kept SCALE encoded because an environmental cannot be generic over the
+/// runtime.
+#[derive(Default)]
+struct Recorder {
+	/// The index of the extrinsic that is being traced.
+	extrinsic_index: u32,
+	/// Events emitted by contracts since the last call was recorded.
+	events: Vec<Vec<u8>>,
+	/// The origin and result of each recorded call together with the events it emitted.
+	calls: Vec<(Vec<u8>, Vec<Vec<u8>>)>,
+}
+
+environmental::environmental!(recorder: Recorder);
+
+/// Run `f` with tracing switched on and return the contract calls it dispatched.
+pub fn record<T: Config, R>(
+	extrinsic_index: u32,
+	f: impl FnOnce() -> R,
+) -> (R, Vec<ContractCallTrace<AccountIdOf<T>, BalanceOf<T>, T::Hash>>) {
+	let mut recorder = Recorder { extrinsic_index, ..Default::default() };
+	let result = recorder::using(&mut recorder, f);
+	let traces = recorder
+		.calls
+		.into_iter()
+		.filter_map(|(call, events)| {
+			let (origin, result) = Decode::decode(&mut &call[..]).ok()?;
+			let events = events
+				.into_iter()
+				.filter_map(|event| Decode::decode(&mut &event[..]).ok())
+				.collect();
+			Some(ContractCallTrace { extrinsic_index, origin, result, events })
+		})
+		.collect();
+	(result, traces)
+}
+
+/// Whether tracing is switched on.
+pub fn is_recording() -> bool {
+	recorder::with(|_| ()).is_some()
+}
+
+/// The number of events recorded since the last call was recorded.
+pub fn event_count() -> usize {
+	recorder::with(|recorder| recorder.events.len()).unwrap_or_default()
+}
+
+/// Forget the events that were recorded after the first `len` ones.
+///
+/// Used when a frame is reverted together with the events it emitted.
+pub fn truncate_events(len: usize) {
+	recorder::with(|recorder| recorder.events.truncate(len));
+}
+
+/// Record an event emitted by `contract`.
+pub fn record_event<T: Config>(contract: &AccountIdOf<T>, topics: &[T::Hash], data: &[u8]) {
+	recorder::with(|recorder| {
+		let event = ContractEvent {
+			contract: contract.clone(),
+			topics: topics.to_vec(),
+			data: Bytes(data.to_vec()),
+		};
+		recorder.events.push(event.encode());
+	});
+}
+
+/// Record a call or instantiation dispatched by `origin` together with the events recorded
+/// since the previous one.
+pub fn record_call<T: Config, O>(
+	origin: &AccountIdOf<T>,
+	output: &InternalOutput<T, O>,
+	return_value: impl FnOnce(&O) -> &ExecReturnValue,
+	debug_message: Option<Vec<u8>>,
+) {
+	recorder::with(|recorder| {
+		let result = ContractExecResult::<BalanceOf<T>> {
+			result: match &output.result {
+				Ok(output) => {
+					let value = return_value(output);
+					Ok(ExecReturnValue { flags: value.flags, data: value.data.clone() })
+				}
+				Err(err) => Err(err.error),
+			},
+			gas_consumed: output.gas_meter.gas_consumed(),
+			gas_required: output.gas_meter.gas_required(),
+			storage_deposit: output.storage_deposit.clone(),
+			debug_message: debug_message.unwrap_or_default(),
+		};
+		let events = mem::take(&mut recorder.events);
+		recorder.calls.push(((origin, result).encode(), events));
+	});
+}
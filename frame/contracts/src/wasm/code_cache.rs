@@ -34,34 +34,51 @@ use crate::{
 	gas::{GasMeter, Token},
 	wasm::{prepare, PrefabWasmModule},
 	weights::WeightInfo,
-	CodeHash, CodeStorage, Config, Error, Event, Pallet as Contracts, PristineCode, Schedule,
-	Weight,
+	CodeHash, CodeStorage, Config, Error, Event, OwnerInfoOf, Pallet as Contracts, PristineCode,
+	Schedule, Weight,
+};
+use frame_support::{
+	dispatch::{DispatchError, DispatchResult},
+	ensure,
+	traits::ReservableCurrency,
 };
-use frame_support::dispatch::DispatchError;
 use sp_core::crypto::UncheckedFrom;
+use sp_runtime::traits::BadOrigin;
 
 /// Put the instrumented module in storage.
 ///
 /// Increments the refcount of the in-storage `prefab_module` if it already exists in storage
-/// under the specified `code_hash`.
-pub fn store<T: Config>(mut prefab_module: PrefabWasmModule<T>)
+/// under the specified `code_hash`. Otherwise the deposit for storing the code is reserved
+/// from its owner.
+pub fn store<T: Config>(mut module: PrefabWasmModule<T>, instantiated: bool) -> DispatchResult
 where
 	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
 {
-	let code_hash = sp_std::mem::take(&mut prefab_module.code_hash);
-
-	// original_code is only `Some` if the contract was instantiated from a new code
-	// but `None` if it was loaded from storage.
-	if let Some(code) = prefab_module.original_code.take() {
-		<PristineCode<T>>::insert(&code_hash, code);
-	}
-	<CodeStorage<T>>::mutate(&code_hash, |existing| match existing {
-		Some(module) => increment_64(&mut module.refcount),
+	let code_hash = sp_std::mem::take(&mut module.code_hash);
+	<OwnerInfoOf<T>>::try_mutate(&code_hash, |existing| match existing {
+		Some(existing) => {
+			if instantiated {
+				increment_64(&mut existing.refcount);
+			}
+			Ok(())
+		}
 		None => {
-			*existing = Some(prefab_module);
-			Contracts::<T>::deposit_event(Event::CodeStored { code_hash })
+			// This `None` case happens only in freshly uploaded modules. This means that
+			// the `owner_info` and `original_code` are always `Some` here.
+			let original_code =
+				module.original_code.take().expect("Freshly uploaded code always has it; qed");
+			let mut owner_info =
+				module.owner_info.take().expect("Freshly uploaded code always has it; qed");
+			owner_info.refcount = if instantiated { 1 } else { 0 };
+			T::Currency::reserve(&owner_info.owner, owner_info.deposit)
+				.map_err(|_| <Error<T>>::StorageDepositNotEnoughFunds)?;
+			<PristineCode<T>>::insert(&code_hash, original_code);
+			<CodeStorage<T>>::insert(&code_hash, module);
+			*existing = Some(owner_info);
+			Contracts::<T>::deposit_event(Event::CodeStored { code_hash });
+			Ok(())
 		}
-	});
+	})
 }
 
 /// Increment the refcount of a code in-storage by one.
@@ -73,9 +90,9 @@ where
 	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
 {
 	gas_meter.charge(CodeToken::UpdateRefcount(estimate_code_size::<T>(&code_hash)?))?;
-	<CodeStorage<T>>::mutate(code_hash, |existing| {
-		if let Some(module) = existing {
-			increment_64(&mut module.refcount);
+	<OwnerInfoOf<T>>::mutate(code_hash, |existing| {
+		if let Some(info) = existing {
+			increment_64(&mut info.refcount);
 			Ok(())
 		} else {
 			Err(Error::<T>::CodeNotFound.into())
@@ -83,7 +100,12 @@ where
 	})
 }
 
-/// Decrement the refcount of a code in-storage by one and remove the code when it drops to zero.
+/// Decrement the refcount of a code in-storage by one.
+///
+/// # Note
+///
+/// A contract whose refcount dropped to zero isn't automatically removed. A `remove_code`
+/// transaction must be submitted by the original uploader to do so.
 pub fn decrement_refcount<T: Config>(
 	code_hash: CodeHash<T>,
 	gas_meter: &mut GasMeter<T>,
@@ -94,18 +116,37 @@ where
 	if let Ok(len) = estimate_code_size::<T>(&code_hash) {
 		gas_meter.charge(CodeToken::UpdateRefcount(len))?;
 	}
-	<CodeStorage<T>>::mutate_exists(code_hash, |existing| {
-		if let Some(module) = existing {
-			module.refcount = module.refcount.saturating_sub(1);
-			if module.refcount == 0 {
-				*existing = None;
-				finish_removal::<T>(code_hash);
-			}
+	<OwnerInfoOf<T>>::mutate(code_hash, |existing| {
+		if let Some(info) = existing {
+			info.refcount = info.refcount.saturating_sub(1);
 		}
 	});
 	Ok(())
 }
 
+/// Try to remove code together with all associated information.
+///
+/// Fails if the code is still in use or `origin` is not its owner.
+pub fn try_remove<T: Config>(origin: &T::AccountId, code_hash: CodeHash<T>) -> DispatchResult
+where
+	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
+{
+	<OwnerInfoOf<T>>::try_mutate_exists(&code_hash, |existing| {
+		if let Some(owner_info) = existing {
+			ensure!(owner_info.refcount == 0, <Error<T>>::CodeInUse);
+			ensure!(&owner_info.owner == origin, BadOrigin);
+			T::Currency::unreserve(&owner_info.owner, owner_info.deposit);
+			*existing = None;
+			<PristineCode<T>>::remove(&code_hash);
+			<CodeStorage<T>>::remove(&code_hash);
+			Contracts::<T>::deposit_event(Event::CodeRemoved { code_hash });
+			Ok(())
+		} else {
+			Err(<Error<T>>::CodeNotFound.into())
+		}
+	})
+}
+
 /// Load code with the given code hash.
 ///
 /// If the module was instrumented with a lower version of schedule than
@@ -164,15 +205,6 @@ mod private {
 	}
 }
 
-/// Finish removal of a code by deleting the pristine code and emitting an event.
-fn finish_removal<T: Config>(code_hash: CodeHash<T>)
-where
-	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
-{
-	<PristineCode<T>>::remove(code_hash);
-	Contracts::<T>::deposit_event(Event::CodeRemoved { code_hash })
-}
-
 /// Increment the refcount panicking if it should ever overflow (which will not happen).
 ///
 /// We try hard to be infallible here because otherwise more storage transactions would be
@@ -28,14 +28,15 @@ mod runtime;
 pub use self::code_cache::reinstrument;
 pub use self::runtime::{ReturnCode, Runtime, RuntimeCosts};
 use crate::{
-	exec::{ExecResult, Executable, ExportedFunction, Ext},
+	exec::{AccountIdOf, ExecResult, Executable, ExportedFunction, Ext},
 	gas::GasMeter,
 	wasm::env_def::FunctionImplProvider,
-	CodeHash, Config, Schedule,
+	BalanceOf, CodeHash, Config, OwnerInfoOf, Schedule,
 };
-use codec::{Decode, Encode};
-use frame_support::dispatch::DispatchError;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::dispatch::{DispatchError, DispatchResult};
 use sp_core::crypto::UncheckedFrom;
+use sp_runtime::traits::Zero;
 use sp_sandbox::{SandboxEnvironmentBuilder, SandboxInstance, SandboxMemory};
 use sp_std::prelude::*;
 #[cfg(test)]
@@ -46,8 +47,7 @@ pub use tests::MockExt;
 /// # Note
 ///
 /// This data structure is mostly immutable once created and stored. The exceptions that
-/// can be changed by calling a contract are `refcount`, `instruction_weights_version` and `code`.
-/// `refcount` can change when a contract instantiates a new contract or self terminates.
+/// can be changed by calling a contract are `instruction_weights_version` and `code`.
 /// `instruction_weights_version` and `code` when a contract with an outdated instrumention is
 /// called. Therefore one must be careful when holding any in-memory representation of this
 /// type while calling into a contract as those fields can get out of date.
@@ -63,11 +63,6 @@ pub struct PrefabWasmModule<T: Config> {
 	/// The maximum memory size of a contract's sandbox.
 	#[codec(compact)]
 	maximum: u32,
-	/// The number of contracts that use this as their contract code.
-	///
-	/// If this number drops to zero this module is removed from storage.
-	#[codec(compact)]
-	refcount: u64,
 	/// This field is reserved for future evolution of format.
 	///
 	/// For now this field is serialized as `None`. In the future we are able to change the
@@ -96,6 +91,27 @@ pub struct PrefabWasmModule<T: Config> {
 	/// when loading the module from storage.
 	#[codec(skip)]
 	code_hash: CodeHash<T>,
+	/// The owner of the code.
+	///
+	/// It is stored in its own storage item. The value is only `Some` when this module
+	/// was created from an `original_code` and `None` if it was loaded from storage.
+	#[codec(skip)]
+	owner_info: Option<OwnerInfo<T>>,
+}
+
+/// Information about the owner of a contract code.
+#[derive(Clone, Encode, Decode, scale_info::TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct OwnerInfo<T: Config> {
+	/// The account that has uploaded the code and hence is allowed to remove it.
+	owner: AccountIdOf<T>,
+	/// The amount of balance that was reserved from the owner in order to store the code.
+	#[codec(compact)]
+	deposit: BalanceOf<T>,
+	/// The number of contracts that use this as their code.
+	#[codec(compact)]
+	refcount: u64,
 }
 
 impl ExportedFunction {
@@ -108,16 +124,53 @@ impl ExportedFunction {
 	}
 }
 
+impl<T: Config> OwnerInfo<T> {
+	/// Return the refcount of the module.
+	#[cfg(test)]
+	pub fn refcount(&self) -> u64 {
+		self.refcount
+	}
+}
+
 impl<T: Config> PrefabWasmModule<T>
 where
 	T::AccountId: UncheckedFrom<T::Hash> + AsRef<[u8]>,
 {
 	/// Create the module by checking and instrumenting `original_code`.
+	///
+	/// The `owner` is the account that pays the deposit for storing the code and is the only
+	/// one that is allowed to remove it again.
 	pub fn from_code(
 		original_code: Vec<u8>,
 		schedule: &Schedule<T>,
+		owner: AccountIdOf<T>,
 	) -> Result<Self, DispatchError> {
-		prepare::prepare_contract(original_code, schedule).map_err(Into::into)
+		prepare::prepare_contract(original_code, schedule, owner).map_err(Into::into)
+	}
+
+	/// Store the code without instantiating it.
+	///
+	/// Otherwise the code is stored when [`<Self as Executable>::execute`] is called.
+	pub fn store(self) -> DispatchResult {
+		code_cache::store(self, false)
+	}
+
+	/// Remove the code from storage and refund the deposit to its owner.
+	///
+	/// Applies all necessary checks before removing the code.
+	pub fn remove(origin: &T::AccountId, code_hash: CodeHash<T>) -> DispatchResult {
+		code_cache::try_remove::<T>(origin, code_hash)
+	}
+
+	/// Returns the deposit that is reserved from the owner when storing this module.
+	///
+	/// This is zero when a module with the same code hash is already stored.
+	pub fn open_deposit(&self) -> BalanceOf<T> {
+		if <OwnerInfoOf<T>>::contains_key(&self.code_hash) {
+			Zero::zero()
+		} else {
+			self.owner_info.as_ref().map(|i| i.deposit).unwrap_or_else(Zero::zero)
+		}
 	}
 
 	/// Create and store the module without checking nor instrumenting the passed code.
@@ -130,17 +183,11 @@ where
 	pub fn store_code_unchecked(
 		original_code: Vec<u8>,
 		schedule: &Schedule<T>,
-	) -> Result<(), DispatchError> {
-		let executable = prepare::benchmarking::prepare_contract(original_code, schedule)
+		owner: T::AccountId,
+	) -> DispatchResult {
+		let executable = prepare::benchmarking::prepare_contract(original_code, schedule, owner)
 			.map_err::<DispatchError, _>(Into::into)?;
-		code_cache::store(executable);
-		Ok(())
-	}
-
-	/// Return the refcount of the module.
-	#[cfg(test)]
-	pub fn refcount(&self) -> u64 {
-		self.refcount
+		code_cache::store(executable, false)
 	}
 
 	/// Decrement instruction_weights_version by 1. Panics if it is already 0.
@@ -205,7 +252,7 @@ where
 		// We store before executing so that the code hash is available in the constructor.
 		let code = self.code.clone();
 		if let &ExportedFunction::Constructor = function {
-			code_cache::store(self)
+			code_cache::store(self, true)?;
 		}
 
 		// Instantiate the instance from the instrumented module code and invoke the contract
@@ -227,10 +274,6 @@ where
 	fn aggregate_code_len(&self) -> u32 {
 		self.original_code_len.saturating_add(self.code_len())
 	}
-
-	fn refcount(&self) -> u32 {
-		self.refcount as u32
-	}
 }
 
 #[cfg(test)]
@@ -298,6 +341,7 @@ mod tests {
 		gas_meter: GasMeter<Test>,
 		debug_buffer: Vec<u8>,
 		ecdsa_recover: RefCell<Vec<([u8; 65], [u8; 32])>>,
+		code_hashes: Vec<CodeHash<Test>>,
 	}
 
 	/// The call is mocked and just returns this hardcoded value.
@@ -319,6 +363,7 @@ mod tests {
 				gas_meter: GasMeter::new(10_000_000_000),
 				debug_buffer: Default::default(),
 				ecdsa_recover: Default::default(),
+				code_hashes: Default::default(),
 			}
 		}
 	}
@@ -431,13 +476,17 @@ mod tests {
 			self.ecdsa_recover.borrow_mut().push((signature.clone(), message_hash.clone()));
 			Ok([3; 33])
 		}
+		fn set_code_hash(&mut self, hash: CodeHash<Self::T>) -> Result<(), DispatchError> {
+			self.code_hashes.push(hash);
+			Ok(())
+		}
 	}
 
 	fn execute<E: BorrowMut<MockExt>>(wat: &str, input_data: Vec<u8>, mut ext: E) -> ExecResult {
 		let wasm = wat::parse_str(wat).unwrap();
 		let schedule = crate::Schedule::default();
 		let executable =
-			PrefabWasmModule::<<MockExt as Ext>::T>::from_code(wasm, &schedule).unwrap();
+			PrefabWasmModule::<<MockExt as Ext>::T>::from_code(wasm, &schedule, ALICE).unwrap();
 		executable.execute(ext.borrow_mut(), &ExportedFunction::Call, input_data)
 	}
 
@@ -905,6 +954,47 @@ mod tests {
 		assert_eq!(mock_ext.ecdsa_recover.into_inner(), [([1; 65], [1; 32])]);
 	}
 
+	#[cfg(feature = "unstable-interface")]
+	const CODE_SET_CODE_HASH: &str = r#"
+(module
+	;; seal_set_code_hash(code_hash_ptr: u32) -> u32
+	(import "__unstable__" "seal_set_code_hash" (func $seal_set_code_hash (param i32) (result i32)))
+	(import "env" "memory" (memory 1 1))
+	(func $assert (param i32)
+		(block $ok
+			(br_if $ok
+				(get_local 0)
+			)
+			(unreachable)
+		)
+	)
+	(func (export "call")
+		(local $exit_code i32)
+		(set_local $exit_code
+			(call $seal_set_code_hash (i32.const 0))
+		)
+		(call $assert
+			(i32.eq (get_local $exit_code) (i32.const 0)) ;; ReturnCode::Success
+		)
+	)
+	(func (export "deploy"))
+
+	;; Hash of code.
+	(data (i32.const 0)
+		"\11\11\11\11\11\11\11\11\11\11\11\11\11\11\11\11"
+		"\11\11\11\11\11\11\11\11\11\11\11\11\11\11\11\11"
+	)
+)
+"#;
+
+	#[test]
+	#[cfg(feature = "unstable-interface")]
+	fn contract_set_code_hash() {
+		let mut mock_ext = MockExt::default();
+		assert_ok!(execute(CODE_SET_CODE_HASH, vec![], &mut mock_ext));
+		assert_eq!(mock_ext.code_hashes.pop().unwrap(), H256::from_slice(&[17u8; 32]));
+	}
+
 	const CODE_GET_STORAGE: &str = r#"
 (module
 	(import "seal0" "seal_get_storage" (func $seal_get_storage (param i32 i32 i32) (result i32)))
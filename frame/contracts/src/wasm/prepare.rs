@@ -21,9 +21,12 @@
 
 use crate::{
 	chain_extension::ChainExtension,
-	wasm::{env_def::ImportSatisfyCheck, PrefabWasmModule},
+	exec::AccountIdOf,
+	storage::meter::deposit_for,
+	wasm::{env_def::ImportSatisfyCheck, OwnerInfo, PrefabWasmModule},
 	Config, Schedule,
 };
+use codec::{Encode, MaxEncodedLen};
 use pwasm_utils::parity_wasm::elements::{self, External, Internal, MemoryType, Type, ValueType};
 use sp_runtime::traits::Hash;
 use sp_std::prelude::*;
@@ -400,20 +403,39 @@ fn check_and_instrument<C: ImportSatisfyCheck, T: Config>(
 fn do_preparation<C: ImportSatisfyCheck, T: Config>(
 	original_code: Vec<u8>,
 	schedule: &Schedule<T>,
+	owner: AccountIdOf<T>,
 ) -> Result<PrefabWasmModule<T>, &'static str> {
 	let (code, (initial, maximum)) =
 		check_and_instrument::<C, T>(original_code.as_ref(), schedule)?;
-	Ok(PrefabWasmModule {
+	let module = PrefabWasmModule {
 		instruction_weights_version: schedule.instruction_weights.version,
 		initial,
 		maximum,
 		_reserved: None,
 		code,
 		original_code_len: original_code.len() as u32,
-		refcount: 1,
 		code_hash: T::Hashing::hash(&original_code),
 		original_code: Some(original_code),
-	})
+		owner_info: None,
+	};
+	Ok(with_owner_info(module, owner))
+}
+
+/// Attach the owner info to a freshly created `module`.
+///
+/// The deposit covers the three storage items that are created for every code: The pristine
+/// code, the instrumented module and the owner info itself.
+fn with_owner_info<T: Config>(
+	mut module: PrefabWasmModule<T>,
+	owner: AccountIdOf<T>,
+) -> PrefabWasmModule<T> {
+	let bytes = module
+		.encoded_size()
+		.saturating_add(module.original_code_len as usize)
+		.saturating_add(<OwnerInfo<T>>::max_encoded_len()) as u32;
+	let deposit = deposit_for::<T>(3, bytes);
+	module.owner_info = Some(OwnerInfo { owner, deposit, refcount: 0 });
+	module
 }
 
 /// Loads the given module given in `original_code`, performs some checks on it and
@@ -430,8 +452,9 @@ fn do_preparation<C: ImportSatisfyCheck, T: Config>(
 pub fn prepare_contract<T: Config>(
 	original_code: Vec<u8>,
 	schedule: &Schedule<T>,
+	owner: AccountIdOf<T>,
 ) -> Result<PrefabWasmModule<T>, &'static str> {
-	do_preparation::<super::runtime::Env, T>(original_code, schedule)
+	do_preparation::<super::runtime::Env, T>(original_code, schedule, owner)
 }
 
 /// The same as [`prepare_contract`] but without constructing a new [`PrefabWasmModule`]
@@ -466,27 +489,29 @@ pub mod benchmarking {
 	pub fn prepare_contract<T: Config>(
 		original_code: Vec<u8>,
 		schedule: &Schedule<T>,
+		owner: AccountIdOf<T>,
 	) -> Result<PrefabWasmModule<T>, &'static str> {
 		let contract_module = ContractModule::new(&original_code, schedule)?;
 		let memory_limits = get_memory_limits(contract_module.scan_imports::<()>(&[])?, schedule)?;
-		Ok(PrefabWasmModule {
+		let module = PrefabWasmModule {
 			instruction_weights_version: schedule.instruction_weights.version,
 			initial: memory_limits.0,
 			maximum: memory_limits.1,
 			_reserved: None,
 			code: contract_module.into_wasm_code()?,
 			original_code_len: original_code.len() as u32,
-			refcount: 1,
 			code_hash: T::Hashing::hash(&original_code),
 			original_code: Some(original_code),
-		})
+			owner_info: None,
+		};
+		Ok(with_owner_info(module, owner))
 	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::{exec::Ext, schedule::Limits};
+	use crate::{exec::Ext, schedule::Limits, tests::ALICE};
 	use std::fmt;
 
 	impl fmt::Debug for PrefabWasmModule<crate::tests::Test> {
@@ -531,7 +556,7 @@ mod tests {
 					},
 					.. Default::default()
 				};
-				let r = do_preparation::<env::Test, crate::tests::Test>(wasm, &schedule);
+				let r = do_preparation::<env::Test, crate::tests::Test>(wasm, &schedule, ALICE);
 				assert_matches::assert_matches!(r, $($expected)*);
 			}
 		};
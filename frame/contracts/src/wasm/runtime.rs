@@ -55,14 +55,12 @@ pub enum ReturnCode {
 	CalleeReverted = 2,
 	/// The passed key does not exist in storage.
 	KeyNotFound = 3,
-	/// Transfer failed because it would have brought the sender's total balance below the
-	/// subsistence threshold.
-	BelowSubsistenceThreshold = 4,
+	/// Deprecated and no longer returned: There is only the minimum balance.
+	_BelowSubsistenceThreshold = 4,
 	/// Transfer failed for other reasons. Most probably reserved or locked balance of the
 	/// sender prevents the transfer.
 	TransferFailed = 5,
-	/// The newly created contract is below the subsistence threshold after executing
-	/// its constructor.
+	/// The value transferred to a newly created contract is below the existential deposit.
 	NewContractNotFunded = 6,
 	/// No code could be found at the supplied code hash.
 	CodeNotFound = 7,
@@ -214,6 +212,9 @@ pub enum RuntimeCosts {
 	/// Weight charged for calling into the runtime.
 	#[cfg(feature = "unstable-interface")]
 	CallRuntime(Weight),
+	/// Weight of calling `seal_set_code_hash`.
+	#[cfg(feature = "unstable-interface")]
+	SetCodeHash,
 }
 
 impl RuntimeCosts {
@@ -281,6 +282,8 @@ impl RuntimeCosts {
 			CopyIn(len) => s.return_per_byte.saturating_mul(len.into()),
 			#[cfg(feature = "unstable-interface")]
 			CallRuntime(weight) => weight,
+			#[cfg(feature = "unstable-interface")]
+			SetCodeHash => s.set_code_hash,
 		};
 		RuntimeToken {
 			#[cfg(test)]
@@ -615,14 +618,12 @@ where
 	fn err_into_return_code(from: DispatchError) -> Result<ReturnCode, DispatchError> {
 		use ReturnCode::*;
 
-		let below_sub = Error::<E::T>::BelowSubsistenceThreshold.into();
 		let transfer_failed = Error::<E::T>::TransferFailed.into();
 		let not_funded = Error::<E::T>::NewContractNotFunded.into();
 		let no_code = Error::<E::T>::CodeNotFound.into();
 		let not_found = Error::<E::T>::ContractNotFound.into();
 
 		match from {
-			x if x == below_sub => Ok(BelowSubsistenceThreshold),
 			x if x == transfer_failed => Ok(TransferFailed),
 			x if x == not_funded => Ok(NewContractNotFunded),
 			x if x == no_code => Ok(CodeNotFound),
@@ -841,7 +842,6 @@ define_env!(Env, <E: Ext>,
 	//
 	// # Errors
 	//
-	// `ReturnCode::BelowSubsistenceThreshold`
 	// `ReturnCode::TransferFailed`
 	[seal0] seal_transfer(
 		ctx,
@@ -929,7 +929,6 @@ define_env!(Env, <E: Ext>,
 	//
 	// `ReturnCode::CalleeReverted`: Output buffer is returned.
 	// `ReturnCode::CalleeTrapped`
-	// `ReturnCode::BelowSubsistenceThreshold`
 	// `ReturnCode::TransferFailed`
 	// `ReturnCode::NotCallable`
 	[seal1] seal_call(
@@ -1006,9 +1005,9 @@ define_env!(Env, <E: Ext>,
 	// length to `output_len_ptr`. The copy of the output buffer and address can be skipped by
 	// supplying the sentinel value of `u32::MAX` to `output_ptr` or `address_ptr`.
 	//
-	// After running the constructor it is verified that the contract account holds at
-	// least the subsistence threshold. If that is not the case the instantiation fails and
-	// the contract is not created.
+	// The transferred value must be at least the existential deposit of the currency.
+	// Otherwise the instantiation fails and the contract is not created. The storage
+	// deposit for the new contract is paid by the origin of the call stack.
 	//
 	// # Parameters
 	//
@@ -1037,7 +1036,6 @@ define_env!(Env, <E: Ext>,
 	//
 	// `ReturnCode::CalleeReverted`: Output buffer is returned.
 	// `ReturnCode::CalleeTrapped`
-	// `ReturnCode::BelowSubsistenceThreshold`
 	// `ReturnCode::TransferFailed`
 	// `ReturnCode::NewContractNotFunded`
 	// `ReturnCode::CodeNotFound`
@@ -1351,9 +1349,9 @@ define_env!(Env, <E: Ext>,
 	//
 	// # Note
 	//
-	// The contract deposit is on top of the existential deposit. The sum
-	// is commonly referred as subsistence threshold in code. No contract initiated
-	// balance transfer can go below this threshold.
+	// The contract deposit is the storage deposit that is due for the contract info
+	// of a newly created contract. It is paid by the origin and reserved on the
+	// contract's account.
 	[seal0] seal_contract_deposit(ctx, out_ptr: u32, out_len_ptr: u32) => {
 		ctx.charge_gas(RuntimeCosts::ContractDeposit)?;
 		Ok(ctx.write_sandbox_output(
@@ -1769,4 +1767,47 @@ define_env!(Env, <E: Ext>,
 			Err(_) => Ok(ReturnCode::EcdsaRecoverFailed),
 		}
 	},
+
+	// Replace the contract code at the specified address with new code.
+	//
+	// # Note
+	//
+	// There are a couple of important considerations which must be taken into account when
+	// using this API:
+	//
+	// 1. The storage at the code address will remain untouched. This means that contract
+	// developers must ensure that the storage layout of the new code is compatible with that
+	// of the old code.
+	//
+	// 2. Contracts using this API can't be assumed as having deterministic addresses. Said
+	// another way, when using this API you lose the guarantee that an address always
+	// identifies a specific code hash.
+	//
+	// 3. The currently executing code is not affected by the switch. Only calls which are
+	// made into the contract after this function returned will use the new code.
+	//
+	// # Parameters
+	//
+	// - `code_hash_ptr`: A pointer to the buffer that contains the new code hash.
+	//   Should be decodable as an `T::Hash`. Traps otherwise.
+	//
+	// # Errors
+	//
+	// `ReturnCode::CodeNotFound`
+	//
+	// # Unstable
+	//
+	// This function is unstable and subject to change (or removal) in the future. Do not
+	// deploy a contract using it to a production chain.
+	[__unstable__] seal_set_code_hash(ctx, code_hash_ptr: u32) -> ReturnCode => {
+		ctx.charge_gas(RuntimeCosts::SetCodeHash)?;
+		let code_hash: CodeHash<<E as Ext>::T> = ctx.read_sandbox_memory_as(code_hash_ptr)?;
+		match ctx.ext.set_code_hash(code_hash) {
+			Ok(()) => Ok(ReturnCode::Success),
+			Err(err) => {
+				let code = Runtime::<E>::err_into_return_code(err)?;
+				Ok(code)
+			},
+		}
+	},
 );
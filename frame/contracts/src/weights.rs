@@ -54,6 +54,8 @@ pub trait WeightInfo {
 	fn instantiate_with_code(c: u32, s: u32, ) -> Weight;
 	fn instantiate(s: u32, ) -> Weight;
 	fn call() -> Weight;
+	fn upload_code(c: u32, ) -> Weight;
+	fn remove_code() -> Weight;
 	fn seal_caller(r: u32, ) -> Weight;
 	fn seal_address(r: u32, ) -> Weight;
 	fn seal_gas_left(r: u32, ) -> Weight;
@@ -93,6 +95,7 @@ pub trait WeightInfo {
 	fn seal_hash_blake2_128(r: u32, ) -> Weight;
 	fn seal_hash_blake2_128_per_kb(n: u32, ) -> Weight;
 	fn seal_ecdsa_recover(r: u32, ) -> Weight;
+	fn seal_set_code_hash(r: u32, ) -> Weight;
 	fn instr_i64const(r: u32, ) -> Weight;
 	fn instr_i64load(r: u32, ) -> Weight;
 	fn instr_i64store(r: u32, ) -> Weight;
@@ -231,6 +234,26 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
+	// Storage: Contracts OwnerInfoOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Contracts CodeStorage (r:0 w:1)
+	// Storage: Contracts PristineCode (r:0 w:1)
+	fn upload_code(c: u32, ) -> Weight {
+		(46_741_000 as Weight)
+			// Standard Error: 84_000
+			.saturating_add((72_390_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Contracts OwnerInfoOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Contracts CodeStorage (r:0 w:1)
+	// Storage: Contracts PristineCode (r:0 w:1)
+	fn remove_code() -> Weight {
+		(41_378_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
 	// Storage: Contracts ContractInfoOf (r:1 w:1)
 	// Storage: Contracts CodeStorage (r:1 w:0)
 	// Storage: Timestamp Now (r:1 w:0)
@@ -654,6 +677,19 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	// Storage: Contracts ContractInfoOf (r:1 w:1)
+	// Storage: Contracts CodeStorage (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: Contracts OwnerInfoOf (r:4000 w:4000)
+	fn seal_set_code_hash(r: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 1_714_000
+			.saturating_add((1_090_373_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((200 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((200 as Weight).saturating_mul(r as Weight)))
+	}
 	fn instr_i64const(r: u32, ) -> Weight {
 		(46_003_000 as Weight)
 			// Standard Error: 10_000
@@ -993,6 +1029,26 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
+	// Storage: Contracts OwnerInfoOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Contracts CodeStorage (r:0 w:1)
+	// Storage: Contracts PristineCode (r:0 w:1)
+	fn upload_code(c: u32, ) -> Weight {
+		(46_741_000 as Weight)
+			// Standard Error: 84_000
+			.saturating_add((72_390_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Contracts OwnerInfoOf (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Contracts CodeStorage (r:0 w:1)
+	// Storage: Contracts PristineCode (r:0 w:1)
+	fn remove_code() -> Weight {
+		(41_378_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
 	// Storage: Contracts ContractInfoOf (r:1 w:1)
 	// Storage: Contracts CodeStorage (r:1 w:0)
 	// Storage: Timestamp Now (r:1 w:0)
@@ -1416,6 +1472,19 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	// Storage: Contracts ContractInfoOf (r:1 w:1)
+	// Storage: Contracts CodeStorage (r:1 w:0)
+	// Storage: Timestamp Now (r:1 w:0)
+	// Storage: Contracts OwnerInfoOf (r:4000 w:4000)
+	fn seal_set_code_hash(r: u32, ) -> Weight {
+		(0 as Weight)
+			// Standard Error: 1_714_000
+			.saturating_add((1_090_373_000 as Weight).saturating_mul(r as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((200 as Weight).saturating_mul(r as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((200 as Weight).saturating_mul(r as Weight)))
+	}
 	fn instr_i64const(r: u32, ) -> Weight {
 		(46_003_000 as Weight)
 			// Standard Error: 10_000
@@ -0,0 +1,246 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ConvictionVoting pallet benchmarking.
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks, whitelist_account};
+use frame_support::assert_ok;
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+use crate::Pallet as ConvictionVoting;
+
+const SEED: u32 = 0;
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+	caller
+}
+
+fn account_vote<T: Config>(b: BalanceOf<T>) -> AccountVote<BalanceOf<T>> {
+	let v = Vote { aye: true, conviction: Conviction::Locked1x };
+	AccountVote::Standard { vote: v, balance: b }
+}
+
+/// Create as many ongoing polls as a single account may vote on in the class which allows the
+/// most ongoing polls.
+fn fill_voting<T: Config>() -> (ClassOf<T>, Vec<PollIndexOf<T>>) {
+	let (class, max) = T::Polls::max_ongoing();
+	let polls = (0..max.min(T::MaxVotes::get()))
+		.map(|_| T::Polls::create_ongoing(class.clone()).expect("Must create poll"))
+		.collect();
+	(class, polls)
+}
+
+fn votes_of<T: Config>(who: &T::AccountId, class: &ClassOf<T>) -> usize {
+	match VotingFor::<T>::get(who, class) {
+		Voting::Casting(Casting { votes, .. }) => votes.len(),
+		Voting::Delegating(_) => 0,
+	}
+}
+
+benchmarks! {
+	vote_new {
+		let caller = funded_account::<T>("caller", 0);
+		whitelist_account!(caller);
+		let account_vote = account_vote::<T>(100u32.into());
+
+		let (class, polls) = fill_voting::<T>();
+		// Vote on all polls except for the first.
+		for &i in polls.iter().skip(1) {
+			assert_ok!(ConvictionVoting::<T>::vote(
+				RawOrigin::Signed(caller.clone()).into(),
+				i,
+				account_vote,
+			));
+		}
+		let r = votes_of::<T>(&caller, &class);
+		let index = polls[0];
+	}: vote(RawOrigin::Signed(caller.clone()), index, account_vote)
+	verify {
+		assert_eq!(votes_of::<T>(&caller, &class), r + 1);
+	}
+
+	vote_existing {
+		let caller = funded_account::<T>("caller", 0);
+		whitelist_account!(caller);
+		let old_account_vote = account_vote::<T>(100u32.into());
+
+		let (class, polls) = fill_voting::<T>();
+		for &i in polls.iter() {
+			assert_ok!(ConvictionVoting::<T>::vote(
+				RawOrigin::Signed(caller.clone()).into(),
+				i,
+				old_account_vote,
+			));
+		}
+		let r = votes_of::<T>(&caller, &class);
+
+		let new_account_vote = account_vote::<T>(200u32.into());
+		let index = polls[0];
+	}: vote(RawOrigin::Signed(caller.clone()), index, new_account_vote)
+	verify {
+		assert_eq!(votes_of::<T>(&caller, &class), r);
+	}
+
+	remove_vote {
+		let caller = funded_account::<T>("caller", 0);
+		whitelist_account!(caller);
+		let old_account_vote = account_vote::<T>(100u32.into());
+
+		let (class, polls) = fill_voting::<T>();
+		for &i in polls.iter() {
+			assert_ok!(ConvictionVoting::<T>::vote(
+				RawOrigin::Signed(caller.clone()).into(),
+				i,
+				old_account_vote,
+			));
+		}
+		let r = votes_of::<T>(&caller, &class);
+
+		let index = polls[0];
+	}: _(RawOrigin::Signed(caller.clone()), Some(class.clone()), index)
+	verify {
+		assert_eq!(votes_of::<T>(&caller, &class), r - 1);
+	}
+
+	remove_other_vote {
+		let caller = funded_account::<T>("caller", 0);
+		let voter = funded_account::<T>("caller", 1);
+		whitelist_account!(caller);
+		let old_account_vote = account_vote::<T>(100u32.into());
+
+		let (class, polls) = fill_voting::<T>();
+		for &i in polls.iter() {
+			assert_ok!(ConvictionVoting::<T>::vote(
+				RawOrigin::Signed(voter.clone()).into(),
+				i,
+				old_account_vote,
+			));
+		}
+		let r = votes_of::<T>(&voter, &class);
+
+		let index = polls[0];
+		assert!(T::Polls::end_ongoing(index, false).is_ok());
+	}: _(RawOrigin::Signed(caller.clone()), voter.clone(), class.clone(), index)
+	verify {
+		assert_eq!(votes_of::<T>(&voter, &class), r - 1);
+	}
+
+	delegate {
+		let r in 0 .. T::MaxVotes::get().min(T::Polls::max_ongoing().1);
+
+		let (class, polls) = fill_voting::<T>();
+
+		let voter = funded_account::<T>("voter", 0);
+		let caller = funded_account::<T>("caller", 0);
+		whitelist_account!(caller);
+
+		let delegated_balance: BalanceOf<T> = 1000u32.into();
+		let delegate_vote = account_vote::<T>(delegated_balance);
+
+		// We need to create existing delegations
+		for &i in polls.iter().take(r as usize) {
+			assert_ok!(ConvictionVoting::<T>::vote(
+				RawOrigin::Signed(voter.clone()).into(),
+				i,
+				delegate_vote,
+			));
+		}
+		assert_eq!(votes_of::<T>(&voter, &class), r as usize);
+	}: _(RawOrigin::Signed(caller.clone()), class.clone(), voter, Conviction::Locked1x, delegated_balance)
+	verify {
+		assert!(matches!(VotingFor::<T>::get(&caller, &class), Voting::Delegating(_)));
+	}
+
+	undelegate {
+		let r in 0 .. T::MaxVotes::get().min(T::Polls::max_ongoing().1);
+
+		let (class, polls) = fill_voting::<T>();
+
+		let voter = funded_account::<T>("voter", 0);
+		let caller = funded_account::<T>("caller", 0);
+		whitelist_account!(caller);
+
+		let delegated_balance: BalanceOf<T> = 1000u32.into();
+		let delegate_vote = account_vote::<T>(delegated_balance);
+
+		assert_ok!(ConvictionVoting::<T>::delegate(
+			RawOrigin::Signed(caller.clone()).into(),
+			class.clone(),
+			voter.clone(),
+			Conviction::Locked1x,
+			delegated_balance,
+		));
+
+		// We need to create votes
+		for &i in polls.iter().take(r as usize) {
+			assert_ok!(ConvictionVoting::<T>::vote(
+				RawOrigin::Signed(voter.clone()).into(),
+				i,
+				delegate_vote,
+			));
+		}
+		assert_eq!(votes_of::<T>(&voter, &class), r as usize);
+	}: _(RawOrigin::Signed(caller.clone()), class.clone())
+	verify {
+		assert!(matches!(VotingFor::<T>::get(&caller, &class), Voting::Casting(_)));
+	}
+
+	unlock {
+		let caller = funded_account::<T>("caller", 0);
+		whitelist_account!(caller);
+		let normal_balance = T::Currency::free_balance(&caller) - 100u32.into();
+		let normal_account_vote = account_vote::<T>(normal_balance);
+		let big_account_vote = account_vote::<T>(T::Currency::free_balance(&caller));
+
+		// Fill everything up to the max by filling all classes with votes and voting on them all.
+		let (class, polls) = fill_voting::<T>();
+		for &i in polls.iter().skip(1) {
+			assert_ok!(ConvictionVoting::<T>::vote(
+				RawOrigin::Signed(caller.clone()).into(),
+				i,
+				normal_account_vote,
+			));
+		}
+
+		// Vote big on the first poll and remove that vote again, so the lock needs reducing.
+		let index = polls[0];
+		assert_ok!(ConvictionVoting::<T>::vote(
+			RawOrigin::Signed(caller.clone()).into(),
+			index,
+			big_account_vote,
+		));
+		assert_ok!(ConvictionVoting::<T>::remove_vote(
+			RawOrigin::Signed(caller.clone()).into(),
+			Some(class.clone()),
+			index,
+		));
+	}: _(RawOrigin::Signed(caller.clone()), class, caller.clone())
+	verify {
+		assert!(ClassLocksFor::<T>::get(&caller).iter().all(|(_, amount)| *amount <= normal_balance));
+	}
+
+	impl_benchmark_test_suite!(
+		ConvictionVoting,
+		crate::tests::new_test_ext(),
+		crate::tests::Test
+	);
+}
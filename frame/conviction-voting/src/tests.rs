@@ -0,0 +1,486 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The crate's tests.
+
+use std::collections::BTreeMap;
+
+use super::*;
+use crate as pallet_conviction_voting;
+use frame_support::{
+	assert_noop, assert_ok, parameter_types,
+	traits::{ConstU32, Contains, TotalIssuanceOf},
+};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Voting: pallet_conviction_voting::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+// Test that a filtered call can be dispatched.
+pub struct BaseFilter;
+impl Contains<Call> for BaseFilter {
+	fn contains(call: &Call) -> bool {
+		!matches!(call, &Call::Balances(pallet_balances::Call::set_balance { .. }))
+	}
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+impl frame_system::Config for Test {
+	type BaseCallFilter = BaseFilter;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_types! {
+	pub const ExistentialDeposit: u64 = 1;
+	pub const MaxLocks: u32 = 10;
+}
+impl pallet_balances::Config for Test {
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type MaxLocks = MaxLocks;
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TestPollState {
+	Ongoing(TallyOf<Test>, u8),
+	Completed(u64, bool),
+}
+use TestPollState::*;
+
+parameter_types! {
+	pub const VoteLockingPeriod: u64 = 3;
+	pub static Polls: BTreeMap<u8, TestPollState> = vec![
+		(1, Completed(1, true)),
+		(2, Completed(2, false)),
+		(3, Ongoing(Tally::from_parts(0, 0, 0), 0)),
+	].into_iter().collect();
+}
+
+pub struct TestPolls;
+impl Polling<TallyOf<Test>> for TestPolls {
+	type Index = u8;
+	type Votes = u64;
+	type Moment = u64;
+	type Class = u8;
+
+	fn classes() -> Vec<u8> {
+		vec![0, 1, 2]
+	}
+
+	fn as_ongoing(index: u8) -> Option<(TallyOf<Test>, Self::Class)> {
+		Polls::get().remove(&index).and_then(|x| {
+			if let TestPollState::Ongoing(t, c) = x {
+				Some((t, c))
+			} else {
+				None
+			}
+		})
+	}
+
+	fn access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut TallyOf<Test>, u64, u8>) -> R,
+	) -> R {
+		let mut polls = Polls::get();
+		let entry = polls.get_mut(&index);
+		let r = match entry {
+			Some(Ongoing(ref mut tally_mut_ref, class)) =>
+				f(PollStatus::Ongoing(tally_mut_ref, *class)),
+			Some(Completed(when, succeeded)) => f(PollStatus::Completed(*when, *succeeded)),
+			None => f(PollStatus::None),
+		};
+		Polls::set(polls);
+		r
+	}
+
+	fn try_access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut TallyOf<Test>, u64, u8>) -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError> {
+		let mut polls = Polls::get();
+		let entry = polls.get_mut(&index);
+		let r = match entry {
+			Some(Ongoing(ref mut tally_mut_ref, class)) =>
+				f(PollStatus::Ongoing(tally_mut_ref, *class)),
+			Some(Completed(when, succeeded)) => f(PollStatus::Completed(*when, *succeeded)),
+			None => f(PollStatus::None),
+		}?;
+		Polls::set(polls);
+		Ok(r)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_ongoing(class: Self::Class) -> Result<Self::Index, ()> {
+		let mut polls = Polls::get();
+		let i = polls.keys().rev().next().map_or(0, |x| x + 1);
+		polls.insert(i, Ongoing(Tally::default(), class));
+		Polls::set(polls);
+		Ok(i)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn end_ongoing(index: Self::Index, approved: bool) -> Result<(), ()> {
+		let mut polls = Polls::get();
+		match polls.get(&index) {
+			Some(Ongoing(..)) => {},
+			_ => return Err(()),
+		}
+		let now = frame_system::Pallet::<Test>::block_number();
+		polls.insert(index, Completed(now, approved));
+		Polls::set(polls);
+		Ok(())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn max_ongoing() -> (Self::Class, u32) {
+		(0, 1000)
+	}
+}
+
+impl Config for Test {
+	type Event = Event;
+	type Currency = pallet_balances::Pallet<Self>;
+	type VoteLockingPeriod = VoteLockingPeriod;
+	type MaxVotes = ConstU32<3>;
+	type WeightInfo = ();
+	type MaxTurnout = TotalIssuanceOf<Balances, Self::AccountId>;
+	type Polls = TestPolls;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 10), (2, 20), (3, 30), (4, 40), (5, 50), (6, 60)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+fn aye(amount: u64, conviction: u8) -> AccountVote<u64> {
+	let vote = Vote { aye: true, conviction: conviction.try_into().unwrap() };
+	AccountVote::Standard { vote, balance: amount }
+}
+
+fn nay(amount: u64, conviction: u8) -> AccountVote<u64> {
+	let vote = Vote { aye: false, conviction: conviction.try_into().unwrap() };
+	AccountVote::Standard { vote, balance: amount }
+}
+
+fn split(aye: u64, nay: u64) -> AccountVote<u64> {
+	AccountVote::Split { aye, nay }
+}
+
+fn tally(index: u8) -> TallyOf<Test> {
+	<TestPolls as Polling<TallyOf<Test>>>::as_ongoing(index).expect("No poll").0
+}
+
+fn set_ongoing(index: u8, class: u8) {
+	let mut polls = Polls::get();
+	polls.insert(index, Ongoing(Tally::default(), class));
+	Polls::set(polls);
+}
+
+fn complete(index: u8, when: u64, approved: bool) {
+	let mut polls = Polls::get();
+	polls.insert(index, Completed(when, approved));
+	Polls::set(polls);
+}
+
+#[test]
+fn params_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Balances::free_balance(42), 0);
+		assert_eq!(Balances::total_issuance(), 210);
+	});
+}
+
+#[test]
+fn basic_voting_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Voting::vote(Origin::signed(1), 3, aye(2, 5)));
+		assert_eq!(tally(3), Tally::from_parts(10, 0, 2));
+		assert_ok!(Voting::vote(Origin::signed(1), 3, nay(2, 5)));
+		assert_eq!(tally(3), Tally::from_parts(0, 10, 2));
+		assert_eq!(Balances::usable_balance(1), 8);
+
+		assert_ok!(Voting::vote(Origin::signed(1), 3, aye(5, 1)));
+		assert_eq!(tally(3), Tally::from_parts(5, 0, 5));
+		assert_ok!(Voting::vote(Origin::signed(1), 3, nay(5, 1)));
+		assert_eq!(tally(3), Tally::from_parts(0, 5, 5));
+		assert_eq!(Balances::usable_balance(1), 5);
+
+		assert_ok!(Voting::vote(Origin::signed(1), 3, aye(10, 0)));
+		assert_eq!(tally(3), Tally::from_parts(1, 0, 10));
+		assert_ok!(Voting::vote(Origin::signed(1), 3, nay(10, 0)));
+		assert_eq!(tally(3), Tally::from_parts(0, 1, 10));
+		assert_eq!(Balances::usable_balance(1), 0);
+
+		assert_ok!(Voting::remove_vote(Origin::signed(1), None, 3));
+		assert_eq!(tally(3), Tally::from_parts(0, 0, 0));
+
+		assert_ok!(Voting::unlock(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 10);
+	});
+}
+
+#[test]
+fn voting_balance_gets_locked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Voting::vote(Origin::signed(1), 3, aye(2, 5)));
+		assert_eq!(tally(3), Tally::from_parts(10, 0, 2));
+		assert_ok!(Voting::vote(Origin::signed(1), 3, nay(2, 5)));
+		assert_eq!(tally(3), Tally::from_parts(0, 10, 2));
+		assert_eq!(Balances::usable_balance(1), 8);
+
+		// Removing the vote does not lift the lock until `unlock` is called.
+		assert_ok!(Voting::remove_vote(Origin::signed(1), None, 3));
+		assert_eq!(Balances::usable_balance(1), 8);
+		assert_ok!(Voting::unlock(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 10);
+	});
+}
+
+#[test]
+fn split_voting_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Voting::vote(Origin::signed(1), 3, split(10, 0)));
+		assert_eq!(tally(3), Tally::from_parts(1, 0, 10));
+		assert_ok!(Voting::vote(Origin::signed(1), 3, split(5, 5)));
+		assert_eq!(tally(3), Tally::from_parts(0, 0, 10));
+		assert_eq!(Balances::usable_balance(1), 0);
+
+		assert_ok!(Voting::remove_vote(Origin::signed(1), None, 3));
+		assert_eq!(tally(3), Tally::from_parts(0, 0, 0));
+
+		assert_ok!(Voting::unlock(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 10);
+	});
+}
+
+#[test]
+fn winning_vote_with_conviction_is_locked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Voting::vote(Origin::signed(1), 3, aye(10, 1)));
+		complete(3, 3, true);
+
+		assert_noop!(Voting::remove_vote(Origin::signed(1), None, 3), Error::<Test>::ClassNeeded);
+		assert_noop!(
+			Voting::remove_other_vote(Origin::signed(2), 1, 0, 3),
+			Error::<Test>::NoPermissionYet
+		);
+		// The vote may be removed by its owner, but the lock is held until the lock period is over.
+		assert_ok!(Voting::remove_vote(Origin::signed(1), Some(0), 3));
+		assert_ok!(Voting::unlock(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 0);
+
+		System::set_block_number(5);
+		assert_ok!(Voting::unlock(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 0);
+
+		System::set_block_number(6);
+		assert_ok!(Voting::unlock(Origin::signed(1), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 10);
+	});
+}
+
+#[test]
+fn expired_votes_can_be_removed_by_anyone() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Voting::vote(Origin::signed(1), 3, aye(10, 1)));
+		assert_ok!(Voting::vote(Origin::signed(2), 3, nay(20, 1)));
+		assert_noop!(
+			Voting::remove_other_vote(Origin::signed(3), 1, 0, 3),
+			Error::<Test>::NoPermission
+		);
+		complete(3, 3, true);
+
+		// The losing side is not locked.
+		assert_ok!(Voting::remove_other_vote(Origin::signed(3), 2, 0, 3));
+		assert_ok!(Voting::unlock(Origin::signed(3), 0, 2));
+		assert_eq!(Balances::usable_balance(2), 20);
+
+		// The winning side is locked until the lock period is over.
+		System::set_block_number(6);
+		assert_ok!(Voting::remove_other_vote(Origin::signed(3), 1, 0, 3));
+		assert_ok!(Voting::unlock(Origin::signed(3), 0, 1));
+		assert_eq!(Balances::usable_balance(1), 10);
+
+		assert_noop!(
+			Voting::remove_other_vote(Origin::signed(3), 1, 0, 3),
+			Error::<Test>::NotVoter
+		);
+	});
+}
+
+#[test]
+fn vote_errors_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Voting::vote(Origin::signed(1), 1, aye(10, 0)), Error::<Test>::NotOngoing);
+		assert_noop!(Voting::vote(Origin::signed(1), 4, aye(10, 0)), Error::<Test>::NotOngoing);
+		assert_noop!(
+			Voting::vote(Origin::signed(1), 3, aye(11, 0)),
+			Error::<Test>::InsufficientFunds
+		);
+
+		for i in 4..=6 {
+			set_ongoing(i, 0);
+		}
+		for i in 3..=5 {
+			assert_ok!(Voting::vote(Origin::signed(1), i, aye(10, 0)));
+		}
+		assert_noop!(
+			Voting::vote(Origin::signed(1), 6, aye(10, 0)),
+			Error::<Test>::MaxVotesReached
+		);
+		// Votes in another class are counted separately.
+		set_ongoing(7, 1);
+		assert_ok!(Voting::vote(Origin::signed(1), 7, aye(10, 0)));
+	});
+}
+
+#[test]
+fn classes_are_independent() {
+	new_test_ext().execute_with(|| {
+		set_ongoing(4, 1);
+		assert_ok!(Voting::vote(Origin::signed(1), 3, aye(10, 0)));
+		assert_ok!(Voting::vote(Origin::signed(1), 4, aye(5, 0)));
+		assert_eq!(ClassLocksFor::<Test>::get(1), vec![(0, 10), (1, 5)]);
+
+		assert_ok!(Voting::remove_vote(Origin::signed(1), None, 3));
+		assert_ok!(Voting::unlock(Origin::signed(1), 0, 1));
+		assert_eq!(ClassLocksFor::<Test>::get(1), vec![(1, 5)]);
+		assert_eq!(Balances::usable_balance(1), 5);
+
+		assert_ok!(Voting::remove_vote(Origin::signed(1), None, 4));
+		assert_ok!(Voting::unlock(Origin::signed(1), 1, 1));
+		assert!(ClassLocksFor::<Test>::get(1).is_empty());
+		assert_eq!(Balances::usable_balance(1), 10);
+	});
+}
+
+#[test]
+fn delegation_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Voting::vote(Origin::signed(1), 3, aye(10, 1)));
+		assert_eq!(tally(3), Tally::from_parts(10, 0, 10));
+
+		assert_ok!(Voting::delegate(Origin::signed(2), 0, 1, Conviction::Locked1x, 20));
+		assert_eq!(tally(3), Tally::from_parts(30, 0, 30));
+		assert_eq!(Balances::usable_balance(2), 0);
+
+		// Delegated votes follow the vote of the delegate.
+		assert_ok!(Voting::vote(Origin::signed(1), 3, nay(10, 1)));
+		assert_eq!(tally(3), Tally::from_parts(0, 30, 30));
+
+		assert_ok!(Voting::undelegate(Origin::signed(2), 0));
+		assert_eq!(tally(3), Tally::from_parts(0, 10, 10));
+		System::assert_last_event(Event::Voting(crate::Event::Undelegated { account: 2 }));
+
+		// The delegated balance stays locked for the conviction's lock period.
+		System::set_block_number(3);
+		assert_ok!(Voting::unlock(Origin::signed(2), 0, 2));
+		assert_eq!(Balances::usable_balance(2), 0);
+		System::set_block_number(4);
+		assert_ok!(Voting::unlock(Origin::signed(2), 0, 2));
+		assert_eq!(Balances::usable_balance(2), 20);
+	});
+}
+
+#[test]
+fn delegation_errors_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Voting::delegate(Origin::signed(1), 0, 1, Conviction::None, 10),
+			Error::<Test>::Nonsense
+		);
+		assert_noop!(
+			Voting::delegate(Origin::signed(1), 3, 2, Conviction::None, 10),
+			Error::<Test>::BadClass
+		);
+		assert_noop!(
+			Voting::delegate(Origin::signed(1), 0, 2, Conviction::None, 11),
+			Error::<Test>::InsufficientFunds
+		);
+		assert_noop!(Voting::undelegate(Origin::signed(1), 0), Error::<Test>::NotDelegating);
+
+		assert_ok!(Voting::vote(Origin::signed(1), 3, aye(10, 0)));
+		assert_noop!(
+			Voting::delegate(Origin::signed(1), 0, 2, Conviction::None, 10),
+			Error::<Test>::AlreadyVoting
+		);
+		// Delegating in another class is fine.
+		assert_ok!(Voting::delegate(Origin::signed(1), 1, 2, Conviction::None, 10));
+		assert_noop!(
+			Voting::delegate(Origin::signed(1), 1, 3, Conviction::None, 10),
+			Error::<Test>::AlreadyDelegating
+		);
+
+		set_ongoing(4, 1);
+		assert_noop!(
+			Voting::vote(Origin::signed(1), 4, aye(10, 0)),
+			Error::<Test>::AlreadyDelegating
+		);
+	});
+}
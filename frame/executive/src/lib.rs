@@ -117,6 +117,8 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Codec, Encode};
+#[cfg(feature = "try-runtime")]
+use frame_support::traits::TryState;
 use frame_support::{
 	dispatch::PostDispatchInfo,
 	traits::{
@@ -187,6 +189,7 @@ where
 	}
 }
 
+#[cfg(feature = "try-runtime")]
 impl<
 		System: frame_system::Config + EnsureInherentsAreFirst<Block>,
 		Block: traits::Block<Header = System::Header, Hash = System::Hash>,
@@ -196,7 +199,8 @@ impl<
 			+ OnInitialize<System::BlockNumber>
 			+ OnIdle<System::BlockNumber>
 			+ OnFinalize<System::BlockNumber>
-			+ OffchainWorker<System::BlockNumber>,
+			+ OffchainWorker<System::BlockNumber>
+			+ TryState<System::BlockNumber>,
 		COnRuntimeUpgrade: OnRuntimeUpgrade,
 	> Executive<System, Block, Context, UnsignedValidator, AllPallets, COnRuntimeUpgrade>
 where
@@ -207,23 +211,26 @@ where
 	OriginOf<Block::Extrinsic, Context>: From<Option<System::AccountId>>,
 	UnsignedValidator: ValidateUnsigned<Call = CallOf<Block::Extrinsic, Context>>,
 {
-	/// Execute all `OnRuntimeUpgrade` of this runtime, and return the aggregate weight.
-	pub fn execute_on_runtime_upgrade() -> frame_support::weights::Weight {
-		let mut weight = 0;
-		weight = weight.saturating_add(COnRuntimeUpgrade::on_runtime_upgrade());
-		weight = weight.saturating_add(
-			<frame_system::Pallet<System> as OnRuntimeUpgrade>::on_runtime_upgrade(),
-		);
-		weight = weight.saturating_add(<AllPallets as OnRuntimeUpgrade>::on_runtime_upgrade());
-
-		weight
-	}
-
 	/// Execute given block, but don't do any of the `final_checks`.
 	///
 	/// Should only be used for testing.
-	#[cfg(feature = "try-runtime")]
 	pub fn execute_block_no_check(block: Block) -> frame_support::weights::Weight {
+		Self::try_execute_block(block, false, false).unwrap()
+	}
+
+	/// Execute given block, optionally checking its state root and the `try_state` invariants of
+	/// all pallets at the end of it.
+	///
+	/// If `state_root_check` is not set, only the digest and the extrinsics root are checked,
+	/// which is only sensible where the block is from a different network, yet it has the same
+	/// block format as the runtime. Returns the weight consumed by the block.
+	///
+	/// Should only be used for testing.
+	pub fn try_execute_block(
+		block: Block,
+		state_root_check: bool,
+		try_state: bool,
+	) -> Result<frame_support::weights::Weight, &'static str> {
 		Self::initialize_block(block.header());
 		Self::initial_checks(&block);
 
@@ -231,9 +238,11 @@ where
 
 		Self::execute_extrinsics_with_book_keeping(extrinsics, *header.number());
 
-		// do some of the checks that would normally happen in `final_checks`, but definitely skip
-		// the state root check.
-		{
+		if state_root_check {
+			Self::final_checks(&header);
+		} else {
+			// do some of the checks that would normally happen in `final_checks`, but definitely
+			// skip the state root check.
 			let new_header = <frame_system::Pallet<System>>::finalize();
 			let items_zip = header.digest().logs().iter().zip(new_header.digest().logs().iter());
 			for (header_item, computed_item) in items_zip {
@@ -247,13 +256,24 @@ where
 			);
 		}
 
-		frame_system::Pallet::<System>::block_weight().total()
+		if try_state {
+			Self::try_state(*header.number())?;
+		}
+
+		Ok(frame_system::Pallet::<System>::block_weight().total())
+	}
+
+	/// Execute the `try_state` checks of the system pallet and all other pallets, as of block
+	/// number `n`.
+	///
+	/// Should only be used for testing.
+	pub fn try_state(n: System::BlockNumber) -> Result<(), &'static str> {
+		<(frame_system::Pallet<System>, AllPallets) as TryState<System::BlockNumber>>::try_state(n)
 	}
 
 	/// Execute all `OnRuntimeUpgrade` of this runtime, including the pre and post migration checks.
 	///
 	/// This should only be used for testing.
-	#[cfg(feature = "try-runtime")]
 	pub fn try_runtime_upgrade() -> Result<frame_support::weights::Weight, &'static str> {
 		<
 			(frame_system::Pallet::<System>, COnRuntimeUpgrade, AllPallets)
@@ -271,6 +291,39 @@ where
 
 		Ok(weight)
 	}
+}
+
+impl<
+		System: frame_system::Config + EnsureInherentsAreFirst<Block>,
+		Block: traits::Block<Header = System::Header, Hash = System::Hash>,
+		Context: Default,
+		UnsignedValidator,
+		AllPallets: OnRuntimeUpgrade
+			+ OnInitialize<System::BlockNumber>
+			+ OnIdle<System::BlockNumber>
+			+ OnFinalize<System::BlockNumber>
+			+ OffchainWorker<System::BlockNumber>,
+		COnRuntimeUpgrade: OnRuntimeUpgrade,
+	> Executive<System, Block, Context, UnsignedValidator, AllPallets, COnRuntimeUpgrade>
+where
+	Block::Extrinsic: Checkable<Context> + Codec,
+	CheckedOf<Block::Extrinsic, Context>: Applyable + GetDispatchInfo,
+	CallOf<Block::Extrinsic, Context>:
+		Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	OriginOf<Block::Extrinsic, Context>: From<Option<System::AccountId>>,
+	UnsignedValidator: ValidateUnsigned<Call = CallOf<Block::Extrinsic, Context>>,
+{
+	/// Execute all `OnRuntimeUpgrade` of this runtime, and return the aggregate weight.
+	pub fn execute_on_runtime_upgrade() -> frame_support::weights::Weight {
+		let mut weight = 0;
+		weight = weight.saturating_add(COnRuntimeUpgrade::on_runtime_upgrade());
+		weight = weight.saturating_add(
+			<frame_system::Pallet<System> as OnRuntimeUpgrade>::on_runtime_upgrade(),
+		);
+		weight = weight.saturating_add(<AllPallets as OnRuntimeUpgrade>::on_runtime_upgrade());
+
+		weight
+	}
 
 	/// Start the execution of a particular block.
 	pub fn initialize_block(header: &System::Header) {
@@ -807,6 +860,7 @@ mod tests {
 		type OperationalFeeMultiplier = OperationalFeeMultiplier;
 		type WeightToFee = IdentityFee<Balance>;
 		type FeeMultiplierUpdate = ();
+		type FeeHistoryLength = ();
 	}
 	impl custom::Config for Runtime {}
 
@@ -0,0 +1,372 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks for the Nomination Pools Pallet.
+// This is separated into its own crate because the benchmarks need a concrete staking system
+// behind `StakingInterface`, e.g. to move the era forward and to create validators.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod mock;
+
+use frame_benchmarking::{account, benchmarks, whitelist_account};
+use frame_support::{
+	assert_ok,
+	traits::{Currency, Get},
+};
+use frame_system::RawOrigin;
+use pallet_nomination_pools::{
+	BalanceOf, BondExtra, BondedPools, Call, ConfigOp, LastPoolId, MaxPoolMembers,
+	MaxPoolMembersPerPool, MaxPools, Metadata, MinCreateBond, MinJoinBond, Pallet as Pools,
+	PoolMembers, PoolState, SubPoolsStorage,
+};
+use pallet_staking::{benchmarking::add_slashing_spans, testing_utils::create_validators};
+use sp_runtime::traits::{Bounded, StaticLookup, Zero};
+use sp_staking::StakingInterface;
+use sp_std::prelude::*;
+
+const USER_SEED: u32 = 0;
+const MAX_SPANS: u32 = 100;
+
+type CurrencyOf<T> = <T as pallet_nomination_pools::Config>::Currency;
+
+pub struct Pallet<T: Config>(Pools<T>);
+pub trait Config: pallet_nomination_pools::Config + pallet_staking::Config {}
+
+fn create_funded_user_with_balance<T: Config>(
+	string: &'static str,
+	n: u32,
+	balance: BalanceOf<T>,
+) -> T::AccountId {
+	let user = account(string, n, USER_SEED);
+	CurrencyOf::<T>::make_free_balance_be(&user, balance);
+	user
+}
+
+/// The smallest amount a pool can be created with, which is also enough to nominate.
+fn min_create_bond<T: Config>() -> BalanceOf<T> {
+	Pools::<T>::depositor_min_bond().max(CurrencyOf::<T>::minimum_balance())
+}
+
+/// Create a pool bonding `balance`, with its creator holding all roles. Returns the creator, the
+/// id of the pool and its bonded account.
+fn create_pool<T: Config>(n: u32, balance: BalanceOf<T>) -> (T::AccountId, u32, T::AccountId) {
+	let ed = CurrencyOf::<T>::minimum_balance();
+	let creator: T::AccountId =
+		create_funded_user_with_balance::<T>("pool_creator", n, ed + balance * 2u32.into());
+	let creator_lookup = T::Lookup::unlookup(creator.clone());
+
+	assert_ok!(Pools::<T>::create(
+		RawOrigin::Signed(creator.clone()).into(),
+		balance,
+		creator_lookup.clone(),
+		creator_lookup.clone(),
+		creator_lookup,
+	));
+
+	let pool_id = LastPoolId::<T>::get();
+	(creator, pool_id, Pools::<T>::create_bonded_account(pool_id))
+}
+
+/// Create a funded account and let it join `pool_id` with `amount`.
+fn join_pool<T: Config>(n: u32, pool_id: u32, amount: BalanceOf<T>) -> T::AccountId {
+	let joiner = create_funded_user_with_balance::<T>("joiner", n, amount * 2u32.into());
+	assert_ok!(Pools::<T>::join(RawOrigin::Signed(joiner.clone()).into(), amount, pool_id));
+	joiner
+}
+
+/// Move the staking era forward such that anything unbonded until now can be withdrawn.
+fn pass_bonding_duration<T: Config>() {
+	let current_era = T::StakingInterface::current_era();
+	let bonding_duration = T::StakingInterface::bonding_duration();
+	pallet_staking::CurrentEra::<T>::put(current_era.saturating_add(bonding_duration));
+}
+
+benchmarks! {
+	join {
+		let origin_weight = min_create_bond::<T>() * 2u32.into();
+		let (_, pool_id, pool_account) = create_pool::<T>(0, origin_weight);
+
+		let joiner = create_funded_user_with_balance::<T>("joiner", 0, origin_weight * 2u32.into());
+		whitelist_account!(joiner);
+	}: _(RawOrigin::Signed(joiner.clone()), origin_weight, pool_id)
+	verify {
+		assert_eq!(CurrencyOf::<T>::free_balance(&joiner), origin_weight);
+		assert_eq!(
+			T::StakingInterface::active_stake(&pool_account),
+			Some(origin_weight * 2u32.into())
+		);
+	}
+
+	bond_extra_transfer {
+		let origin_weight = min_create_bond::<T>() * 2u32.into();
+		let (_, pool_id, pool_account) = create_pool::<T>(0, origin_weight);
+		let joiner = join_pool::<T>(0, pool_id, origin_weight);
+		let extra = origin_weight / 2u32.into();
+		whitelist_account!(joiner);
+	}: bond_extra(RawOrigin::Signed(joiner.clone()), BondExtra::FreeBalance(extra))
+	verify {
+		assert_eq!(
+			T::StakingInterface::active_stake(&pool_account),
+			Some(origin_weight * 2u32.into() + extra)
+		);
+	}
+
+	bond_extra_reward {
+		let origin_weight = min_create_bond::<T>() * 2u32.into();
+		let (_, pool_id, pool_account) = create_pool::<T>(0, origin_weight);
+		let joiner = join_pool::<T>(0, pool_id, origin_weight);
+
+		// pay a reward into the pool, half of which is owed to the joiner.
+		let reward_account = Pools::<T>::create_reward_account(pool_id);
+		let ed = CurrencyOf::<T>::minimum_balance();
+		CurrencyOf::<T>::make_free_balance_be(&reward_account, ed + origin_weight);
+		whitelist_account!(joiner);
+	}: bond_extra(RawOrigin::Signed(joiner.clone()), BondExtra::Rewards)
+	verify {
+		assert!(
+			T::StakingInterface::active_stake(&pool_account).unwrap() >
+				origin_weight * 2u32.into()
+		);
+	}
+
+	claim_payout {
+		let origin_weight = min_create_bond::<T>() * 2u32.into();
+		let (_, pool_id, _) = create_pool::<T>(0, origin_weight);
+		let joiner = join_pool::<T>(0, pool_id, origin_weight);
+
+		let reward_account = Pools::<T>::create_reward_account(pool_id);
+		let ed = CurrencyOf::<T>::minimum_balance();
+		CurrencyOf::<T>::make_free_balance_be(&reward_account, ed + origin_weight);
+		let balance_before = CurrencyOf::<T>::free_balance(&joiner);
+		whitelist_account!(joiner);
+	}: _(RawOrigin::Signed(joiner.clone()))
+	verify {
+		assert!(CurrencyOf::<T>::free_balance(&joiner) > balance_before);
+		assert!(Pools::<T>::pending_rewards(joiner).is_zero());
+	}
+
+	unbond {
+		let origin_weight = min_create_bond::<T>() * 2u32.into();
+		let (_, pool_id, pool_account) = create_pool::<T>(0, origin_weight);
+		let joiner = join_pool::<T>(0, pool_id, origin_weight);
+		let points = PoolMembers::<T>::get(&joiner).unwrap().points;
+		whitelist_account!(joiner);
+	}: _(RawOrigin::Signed(joiner.clone()), joiner.clone(), points)
+	verify {
+		let member = PoolMembers::<T>::get(&joiner).unwrap();
+		assert!(member.points.is_zero());
+		assert_eq!(member.unbonding_points(), points);
+		assert_eq!(T::StakingInterface::active_stake(&pool_account), Some(origin_weight));
+		assert!(SubPoolsStorage::<T>::contains_key(pool_id));
+	}
+
+	pool_withdraw_unbonded {
+		let s in 0 .. MAX_SPANS;
+
+		let origin_weight = min_create_bond::<T>() * 2u32.into();
+		let (_, pool_id, pool_account) = create_pool::<T>(0, origin_weight);
+		let joiner = join_pool::<T>(0, pool_id, origin_weight);
+		let points = PoolMembers::<T>::get(&joiner).unwrap().points;
+		assert_ok!(Pools::<T>::unbond(
+			RawOrigin::Signed(joiner.clone()).into(),
+			joiner.clone(),
+			points
+		));
+		add_slashing_spans::<T>(&pool_account, s);
+		pass_bonding_duration::<T>();
+	}: _(RawOrigin::Signed(joiner), pool_id, s)
+	verify {
+		assert_eq!(T::StakingInterface::total_stake(&pool_account), Some(origin_weight));
+	}
+
+	withdraw_unbonded_update {
+		let s in 0 .. MAX_SPANS;
+
+		let origin_weight = min_create_bond::<T>() * 2u32.into();
+		let (_, pool_id, pool_account) = create_pool::<T>(0, origin_weight);
+		let joiner = join_pool::<T>(0, pool_id, origin_weight);
+		let points = PoolMembers::<T>::get(&joiner).unwrap().points;
+		assert_ok!(Pools::<T>::unbond(
+			RawOrigin::Signed(joiner.clone()).into(),
+			joiner.clone(),
+			points
+		));
+		add_slashing_spans::<T>(&pool_account, s);
+		pass_bonding_duration::<T>();
+		whitelist_account!(joiner);
+	}: withdraw_unbonded(RawOrigin::Signed(joiner.clone()), joiner.clone(), s)
+	verify {
+		assert!(!PoolMembers::<T>::contains_key(&joiner));
+		assert_eq!(CurrencyOf::<T>::free_balance(&joiner), origin_weight * 2u32.into());
+		assert_eq!(BondedPools::<T>::get(pool_id).unwrap().member_counter, 1);
+	}
+
+	withdraw_unbonded_kill {
+		let s in 0 .. MAX_SPANS;
+
+		let origin_weight = min_create_bond::<T>();
+		let (depositor, pool_id, pool_account) = create_pool::<T>(0, origin_weight);
+		assert_ok!(Pools::<T>::set_state(
+			RawOrigin::Signed(depositor.clone()).into(),
+			pool_id,
+			PoolState::Destroying
+		));
+		let points = PoolMembers::<T>::get(&depositor).unwrap().points;
+		assert_ok!(Pools::<T>::unbond(
+			RawOrigin::Signed(depositor.clone()).into(),
+			depositor.clone(),
+			points
+		));
+		add_slashing_spans::<T>(&pool_account, s);
+		pass_bonding_duration::<T>();
+		whitelist_account!(depositor);
+	}: withdraw_unbonded(RawOrigin::Signed(depositor.clone()), depositor.clone(), s)
+	verify {
+		assert!(!PoolMembers::<T>::contains_key(&depositor));
+		assert!(!BondedPools::<T>::contains_key(pool_id));
+		assert!(!SubPoolsStorage::<T>::contains_key(pool_id));
+		assert_eq!(T::StakingInterface::total_stake(&pool_account), None);
+	}
+
+	create {
+		let min_create_bond = min_create_bond::<T>();
+		let ed = CurrencyOf::<T>::minimum_balance();
+		let depositor = create_funded_user_with_balance::<T>(
+			"depositor",
+			USER_SEED,
+			ed + min_create_bond * 2u32.into(),
+		);
+		let depositor_lookup = T::Lookup::unlookup(depositor.clone());
+		whitelist_account!(depositor);
+	}: _(
+		RawOrigin::Signed(depositor.clone()),
+		min_create_bond,
+		depositor_lookup.clone(),
+		depositor_lookup.clone(),
+		depositor_lookup
+	)
+	verify {
+		let pool_id = LastPoolId::<T>::get();
+		let pool = BondedPools::<T>::get(pool_id).unwrap();
+		assert_eq!(pool.member_counter, 1);
+		assert_eq!(pool.roles.depositor, depositor);
+		assert_eq!(
+			T::StakingInterface::active_stake(&Pools::<T>::create_bonded_account(pool_id)),
+			Some(min_create_bond)
+		);
+	}
+
+	nominate {
+		let n in 1 .. <T as pallet_staking::Config>::MAX_NOMINATIONS;
+
+		let origin_weight = min_create_bond::<T>() * 2u32.into();
+		let (depositor, pool_id, pool_account) = create_pool::<T>(0, origin_weight);
+		let validators = create_validators::<T>(n, 100)?
+			.into_iter()
+			.map(|v| T::Lookup::lookup(v).map_err(|_| "invalid validator lookup"))
+			.collect::<Result<Vec<_>, _>>()?;
+		whitelist_account!(depositor);
+	}: _(RawOrigin::Signed(depositor), pool_id, validators)
+	verify {
+		assert_eq!(
+			pallet_staking::Nominators::<T>::get(&pool_account).map(|n| n.targets.len() as u32),
+			Some(n)
+		);
+	}
+
+	set_state {
+		let origin_weight = min_create_bond::<T>() * 2u32.into();
+		let (depositor, pool_id, _) = create_pool::<T>(0, origin_weight);
+		whitelist_account!(depositor);
+	}: _(RawOrigin::Signed(depositor), pool_id, PoolState::Blocked)
+	verify {
+		assert_eq!(BondedPools::<T>::get(pool_id).unwrap().state, PoolState::Blocked);
+	}
+
+	set_metadata {
+		let n in 1 .. <T as pallet_nomination_pools::Config>::MaxMetadataLen::get();
+
+		let origin_weight = min_create_bond::<T>() * 2u32.into();
+		let (depositor, pool_id, _) = create_pool::<T>(0, origin_weight);
+		let metadata: Vec<u8> = (0..n).map(|v| (v % 255) as u8).collect();
+		whitelist_account!(depositor);
+	}: _(RawOrigin::Signed(depositor), pool_id, metadata.clone())
+	verify {
+		assert_eq!(Metadata::<T>::get(pool_id), metadata);
+	}
+
+	set_configs {
+	}: _(
+		RawOrigin::Root,
+		ConfigOp::Set(BalanceOf::<T>::max_value()),
+		ConfigOp::Set(BalanceOf::<T>::max_value()),
+		ConfigOp::Set(u32::MAX),
+		ConfigOp::Set(u32::MAX),
+		ConfigOp::Set(u32::MAX)
+	) verify {
+		assert_eq!(MinJoinBond::<T>::get(), BalanceOf::<T>::max_value());
+		assert_eq!(MinCreateBond::<T>::get(), BalanceOf::<T>::max_value());
+		assert_eq!(MaxPools::<T>::get(), Some(u32::MAX));
+		assert_eq!(MaxPoolMembers::<T>::get(), Some(u32::MAX));
+		assert_eq!(MaxPoolMembersPerPool::<T>::get(), Some(u32::MAX));
+	}
+
+	update_roles {
+		let origin_weight = min_create_bond::<T>() * 2u32.into();
+		let (root, pool_id, _) = create_pool::<T>(0, origin_weight);
+		let new_root: T::AccountId = account("new_root", 0, USER_SEED);
+		let new_nominator: T::AccountId = account("new_nominator", 0, USER_SEED);
+		let new_state_toggler: T::AccountId = account("new_state_toggler", 0, USER_SEED);
+		whitelist_account!(root);
+	}: _(
+		RawOrigin::Signed(root),
+		pool_id,
+		ConfigOp::Set(new_root.clone()),
+		ConfigOp::Set(new_nominator.clone()),
+		ConfigOp::Set(new_state_toggler.clone())
+	) verify {
+		let roles = BondedPools::<T>::get(pool_id).unwrap().roles;
+		assert_eq!(roles.root, Some(new_root));
+		assert_eq!(roles.nominator, Some(new_nominator));
+		assert_eq!(roles.state_toggler, Some(new_state_toggler));
+	}
+
+	chill {
+		let origin_weight = min_create_bond::<T>() * 2u32.into();
+		let (depositor, pool_id, pool_account) = create_pool::<T>(0, origin_weight);
+		let validators = create_validators::<T>(
+			<T as pallet_staking::Config>::MAX_NOMINATIONS,
+			100,
+		)?
+		.into_iter()
+		.map(|v| T::Lookup::lookup(v).map_err(|_| "invalid validator lookup"))
+		.collect::<Result<Vec<_>, _>>()?;
+		assert_ok!(Pools::<T>::nominate(
+			RawOrigin::Signed(depositor.clone()).into(),
+			pool_id,
+			validators
+		));
+		assert!(pallet_staking::Nominators::<T>::contains_key(&pool_account));
+		whitelist_account!(depositor);
+	}: _(RawOrigin::Signed(depositor), pool_id)
+	verify {
+		assert!(!pallet_staking::Nominators::<T>::contains_key(&pool_account));
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
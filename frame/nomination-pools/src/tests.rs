@@ -0,0 +1,686 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{mock::*, Event};
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+
+#[test]
+fn test_setup_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_eq!(BondedPools::<Runtime>::count(), 1);
+		assert_eq!(RewardPools::<Runtime>::count(), 1);
+		assert_eq!(SubPoolsStorage::<Runtime>::count(), 0);
+		assert_eq!(PoolMembers::<Runtime>::count(), 1);
+		assert_eq!(StakingMock::bonding_duration(), 3);
+
+		let last_pool = LastPoolId::<Runtime>::get();
+		assert_eq!(
+			BondedPool::<Runtime>::get(last_pool).unwrap(),
+			BondedPool::<Runtime> {
+				id: last_pool,
+				inner: BondedPoolInner {
+					state: PoolState::Open,
+					points: 10,
+					member_counter: 1,
+					roles: PoolRoles {
+						depositor: 10,
+						root: Some(900),
+						nominator: Some(901),
+						state_toggler: Some(902)
+					},
+				}
+			}
+		);
+		assert_eq!(
+			PoolMembers::<Runtime>::get(10).unwrap(),
+			PoolMember::<Runtime> { pool_id: last_pool, points: 10, ..Default::default() }
+		);
+
+		let bonded_account = Pools::create_bonded_account(last_pool);
+		let reward_account = Pools::create_reward_account(last_pool);
+		assert_ne!(bonded_account, reward_account);
+
+		// the bonded balance of the pool is staked, and the reward account holds exactly ED.
+		assert_eq!(StakingMock::active_stake(&bonded_account).unwrap(), 10);
+		assert_eq!(Balances::free_balance(&reward_account), ExistentialDeposit::get());
+		assert_eq!(Balances::free_balance(&10), 35);
+
+		assert_eq!(
+			pool_events_since_last_call(),
+			vec![
+				Event::Created { depositor: 10, pool_id: 1 },
+				Event::Bonded { member: 10, pool_id: 1, bonded: 10, joined: true },
+			]
+		);
+	})
+}
+
+mod join {
+	use super::*;
+
+	#[test]
+	fn join_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			// Given
+			Balances::make_free_balance_be(&11, ExistentialDeposit::get() + 2);
+			assert!(!PoolMembers::<Runtime>::contains_key(&11));
+
+			// When
+			assert_ok!(Pools::join(Origin::signed(11), 2, 1));
+
+			// Then
+			assert_eq!(
+				pool_events_since_last_call(),
+				vec![
+					Event::Created { depositor: 10, pool_id: 1 },
+					Event::Bonded { member: 10, pool_id: 1, bonded: 10, joined: true },
+					Event::Bonded { member: 11, pool_id: 1, bonded: 2, joined: true },
+				]
+			);
+
+			assert_eq!(
+				PoolMembers::<Runtime>::get(&11).unwrap(),
+				PoolMember::<Runtime> { pool_id: 1, points: 2, ..Default::default() }
+			);
+			assert_eq!(BondedPool::<Runtime>::get(1).unwrap().points, 12);
+			assert_eq!(BondedPool::<Runtime>::get(1).unwrap().member_counter, 2);
+			assert_eq!(StakingMock::active_stake(&default_bonded_account()).unwrap(), 12);
+		});
+	}
+
+	#[test]
+	fn join_errors_correctly() {
+		ExtBuilder::default().build_and_execute(|| {
+			// 10 is already part of the default pool created.
+			assert_eq!(PoolMembers::<Runtime>::get(&10).unwrap().pool_id, 1);
+
+			assert_noop!(
+				Pools::join(Origin::signed(10), 420, 123),
+				Error::<Runtime>::AccountBelongsToOtherPool
+			);
+
+			assert_noop!(Pools::join(Origin::signed(11), 420, 123), Error::<Runtime>::PoolNotFound);
+
+			// the bond is below `MinJoinBond`.
+			assert_noop!(
+				Pools::join(Origin::signed(11), 1, 1),
+				Error::<Runtime>::MinimumBondNotMet
+			);
+
+			// Blocked pools cannot be joined.
+			unsafe_set_state(1, PoolState::Blocked).unwrap();
+			assert_noop!(Pools::join(Origin::signed(11), 2, 1), Error::<Runtime>::NotOpen);
+
+			unsafe_set_state(1, PoolState::Destroying).unwrap();
+			assert_noop!(Pools::join(Origin::signed(11), 2, 1), Error::<Runtime>::NotOpen);
+		});
+	}
+
+	#[test]
+	fn join_max_member_limits_are_respected() {
+		ExtBuilder::default()
+			.add_members(vec![(20, 20), (21, 21)])
+			.build_and_execute(|| {
+				// the pool is full with the depositor, 20 and 21.
+				assert_eq!(MaxPoolMembersPerPool::<Runtime>::get(), Some(3));
+				Balances::make_free_balance_be(&22, 100);
+				assert_noop!(
+					Pools::join(Origin::signed(22), 10, 1),
+					Error::<Runtime>::MaxPoolMembers
+				);
+			});
+
+		ExtBuilder::default()
+			.max_members(Some(2))
+			.max_members_per_pool(None)
+			.add_members(vec![(20, 20)])
+			.build_and_execute(|| {
+				// the system is full with the depositor and 20.
+				assert_eq!(PoolMembers::<Runtime>::count(), 2);
+				Balances::make_free_balance_be(&22, 100);
+				assert_noop!(
+					Pools::join(Origin::signed(22), 10, 1),
+					Error::<Runtime>::MaxPoolMembers
+				);
+			});
+	}
+}
+
+mod claim_payout {
+	use super::*;
+
+	#[test]
+	fn claim_payout_works() {
+		ExtBuilder::default().add_members(vec![(40, 40)]).build_and_execute(|| {
+			// Given the pool has earned some rewards.
+			let ed = ExistentialDeposit::get();
+			Balances::make_free_balance_be(&default_reward_account(), ed + 100);
+			assert_eq!(Pools::pending_rewards(10), 20);
+			assert_eq!(Pools::pending_rewards(40), 80);
+
+			// When
+			assert_ok!(Pools::claim_payout(Origin::signed(10)));
+
+			// Then 10 gets its share of 10 / 50 points.
+			assert_eq!(Balances::free_balance(&10), 35 + 20);
+			assert_eq!(Pools::pending_rewards(10), 0);
+
+			// When
+			assert_ok!(Pools::claim_payout(Origin::signed(40)));
+
+			// Then 40 gets its share of 40 / 50 points.
+			assert_eq!(Balances::free_balance(&40), 40 + 80);
+			assert_eq!(Balances::free_balance(&default_reward_account()), ed);
+			assert_eq!(RewardPools::<Runtime>::get(1).unwrap().total_rewards_claimed, 100);
+
+			assert_eq!(
+				pool_events_since_last_call(),
+				vec![
+					Event::Created { depositor: 10, pool_id: 1 },
+					Event::Bonded { member: 10, pool_id: 1, bonded: 10, joined: true },
+					Event::Bonded { member: 40, pool_id: 1, bonded: 40, joined: true },
+					Event::PaidOut { member: 10, pool_id: 1, payout: 20 },
+					Event::PaidOut { member: 40, pool_id: 1, payout: 80 },
+				]
+			);
+
+			// claiming again is a noop.
+			assert_ok!(Pools::claim_payout(Origin::signed(10)));
+			assert_eq!(Balances::free_balance(&10), 35 + 20);
+			assert!(pool_events_since_last_call().is_empty());
+		});
+	}
+
+	#[test]
+	fn claim_payout_errors_correctly() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_noop!(
+				Pools::claim_payout(Origin::signed(11)),
+				Error::<Runtime>::PoolMemberNotFound
+			);
+		});
+	}
+}
+
+mod bond_extra {
+	use super::*;
+
+	#[test]
+	fn bond_extra_from_free_balance_and_rewards() {
+		ExtBuilder::default().build_and_execute(|| {
+			// When
+			assert_ok!(Pools::bond_extra(Origin::signed(10), BondExtra::FreeBalance(10)));
+
+			// Then
+			assert_eq!(Balances::free_balance(&10), 25);
+			assert_eq!(PoolMembers::<Runtime>::get(10).unwrap().points, 20);
+			assert_eq!(BondedPool::<Runtime>::get(1).unwrap().points, 20);
+			assert_eq!(StakingMock::active_stake(&default_bonded_account()).unwrap(), 20);
+
+			// Given the pool has earned some rewards.
+			Balances::make_free_balance_be(
+				&default_reward_account(),
+				ExistentialDeposit::get() + 20,
+			);
+
+			// When
+			assert_ok!(Pools::bond_extra(Origin::signed(10), BondExtra::Rewards));
+
+			// Then the rewards are claimed and immediately bonded again.
+			assert_eq!(Balances::free_balance(&10), 25);
+			assert_eq!(PoolMembers::<Runtime>::get(10).unwrap().points, 40);
+			assert_eq!(BondedPool::<Runtime>::get(1).unwrap().points, 40);
+			assert_eq!(StakingMock::active_stake(&default_bonded_account()).unwrap(), 40);
+
+			assert_eq!(
+				pool_events_since_last_call(),
+				vec![
+					Event::Created { depositor: 10, pool_id: 1 },
+					Event::Bonded { member: 10, pool_id: 1, bonded: 10, joined: true },
+					Event::Bonded { member: 10, pool_id: 1, bonded: 10, joined: false },
+					Event::PaidOut { member: 10, pool_id: 1, payout: 20 },
+					Event::Bonded { member: 10, pool_id: 1, bonded: 20, joined: false },
+				]
+			);
+		})
+	}
+}
+
+mod unbond {
+	use super::*;
+
+	#[test]
+	fn unbond_and_withdraw_works() {
+		ExtBuilder::default().add_members(vec![(40, 40)]).build_and_execute(|| {
+			// When
+			assert_ok!(Pools::unbond(Origin::signed(40), 40, 40));
+
+			// Then
+			let member = PoolMembers::<Runtime>::get(40).unwrap();
+			assert_eq!(member.active_points(), 0);
+			assert_eq!(member.unbonding_points(), 40);
+			assert_eq!(
+				SubPoolsStorage::<Runtime>::get(1).unwrap().with_era.get(&3),
+				Some(&UnbondPool::<Runtime> { points: 40, balance: 40 })
+			);
+			assert_eq!(BondedPool::<Runtime>::get(1).unwrap().points, 10);
+			assert_eq!(StakingMock::active_stake(&default_bonded_account()).unwrap(), 10);
+
+			// Nothing can be withdrawn before the bonding duration has passed.
+			assert_noop!(
+				Pools::withdraw_unbonded(Origin::signed(40), 40, 0),
+				Error::<Runtime>::CannotWithdrawAny
+			);
+
+			// When
+			CurrentEra::set(3);
+			assert_ok!(Pools::withdraw_unbonded(Origin::signed(40), 40, 0));
+
+			// Then
+			assert_eq!(Balances::free_balance(&40), 80);
+			assert!(!PoolMembers::<Runtime>::contains_key(40));
+			assert_eq!(BondedPool::<Runtime>::get(1).unwrap().member_counter, 1);
+			assert!(SubPoolsStorage::<Runtime>::get(1).unwrap().with_era.is_empty());
+
+			assert_eq!(
+				pool_events_since_last_call(),
+				vec![
+					Event::Created { depositor: 10, pool_id: 1 },
+					Event::Bonded { member: 10, pool_id: 1, bonded: 10, joined: true },
+					Event::Bonded { member: 40, pool_id: 1, bonded: 40, joined: true },
+					Event::Unbonded { member: 40, pool_id: 1, balance: 40, points: 40, era: 3 },
+					Event::Withdrawn { member: 40, pool_id: 1, balance: 40, points: 40 },
+					Event::MemberRemoved { pool_id: 1, member: 40 },
+				]
+			);
+		});
+	}
+
+	#[test]
+	fn depositor_cannot_leave_open_pool() {
+		ExtBuilder::default().build_and_execute(|| {
+			// cannot fully unbond while the pool is not destroying.
+			assert_noop!(
+				Pools::unbond(Origin::signed(10), 10, 10),
+				Error::<Runtime>::MinimumBondNotMet
+			);
+
+			// cannot unbond below the depositor minimum bond.
+			assert_noop!(
+				Pools::unbond(Origin::signed(10), 10, 1),
+				Error::<Runtime>::MinimumBondNotMet
+			);
+		});
+	}
+
+	#[test]
+	fn depositor_leaving_destroys_the_pool() {
+		ExtBuilder::default().build_and_execute(|| {
+			// Given
+			assert_ok!(Pools::set_state(Origin::signed(902), 1, PoolState::Destroying));
+
+			// When
+			assert_ok!(Pools::unbond(Origin::signed(10), 10, 10));
+			CurrentEra::set(3);
+			assert_ok!(Pools::withdraw_unbonded(Origin::signed(10), 10, 0));
+
+			// Then the depositor gets back their bond and the reward account deposit.
+			assert_eq!(Balances::free_balance(&10), 50);
+			assert_eq!(BondedPools::<Runtime>::count(), 0);
+			assert_eq!(RewardPools::<Runtime>::count(), 0);
+			assert_eq!(SubPoolsStorage::<Runtime>::count(), 0);
+			assert_eq!(ReversePoolIdLookup::<Runtime>::count(), 0);
+			assert_eq!(PoolMembers::<Runtime>::count(), 0);
+			assert_eq!(Balances::free_balance(&default_reward_account()), 0);
+
+			assert_eq!(
+				pool_events_since_last_call(),
+				vec![
+					Event::Created { depositor: 10, pool_id: 1 },
+					Event::Bonded { member: 10, pool_id: 1, bonded: 10, joined: true },
+					Event::StateChanged { pool_id: 1, new_state: PoolState::Destroying },
+					Event::Unbonded { member: 10, pool_id: 1, balance: 10, points: 10, era: 3 },
+					Event::Withdrawn { member: 10, pool_id: 1, balance: 10, points: 10 },
+					Event::MemberRemoved { pool_id: 1, member: 10 },
+					Event::Destroyed { pool_id: 1 },
+				]
+			);
+		});
+	}
+
+	#[test]
+	fn kicking_members_works() {
+		ExtBuilder::default().add_members(vec![(40, 40)]).build_and_execute(|| {
+			// members of an open pool cannot be kicked.
+			assert_noop!(
+				Pools::unbond(Origin::signed(902), 40, 40),
+				Error::<Runtime>::NotKickerOrDestroying
+			);
+
+			// Given
+			assert_ok!(Pools::set_state(Origin::signed(902), 1, PoolState::Blocked));
+
+			// a kick must always be a full unbond.
+			assert_noop!(
+				Pools::unbond(Origin::signed(902), 40, 20),
+				Error::<Runtime>::PartialUnbondNotAllowedPermissionlessly
+			);
+			// only admins can kick.
+			assert_noop!(
+				Pools::unbond(Origin::signed(123), 40, 40),
+				Error::<Runtime>::NotKickerOrDestroying
+			);
+			// the depositor can never be kicked.
+			assert_noop!(
+				Pools::unbond(Origin::signed(902), 10, 10),
+				Error::<Runtime>::DoesNotHavePermission
+			);
+
+			// When
+			assert_ok!(Pools::unbond(Origin::signed(902), 40, 40));
+			CurrentEra::set(3);
+			assert_ok!(Pools::withdraw_unbonded(Origin::signed(900), 40, 0));
+
+			// Then
+			assert!(!PoolMembers::<Runtime>::contains_key(40));
+			assert_eq!(Balances::free_balance(&40), 80);
+		});
+	}
+
+	#[test]
+	fn slashed_unbonding_pools_are_withdrawn_pro_rata() {
+		ExtBuilder::default().add_members(vec![(40, 40)]).build_and_execute(|| {
+			// Given
+			assert_ok!(Pools::unbond(Origin::signed(40), 40, 40));
+			pool_events_since_last_call();
+
+			// When the staking system reports a slash of the pool.
+			StakingMock::set_bonded_balance(default_bonded_account(), 5);
+			let slashed_unlocking = vec![(3, 20)].into_iter().collect::<BTreeMap<_, _>>();
+			<Pools as OnStakerSlash<_, _>>::on_slash(
+				&default_bonded_account(),
+				5,
+				&slashed_unlocking,
+			);
+
+			// Then
+			assert_eq!(
+				pool_events_since_last_call(),
+				vec![
+					Event::UnbondingPoolSlashed { pool_id: 1, era: 3, balance: 20 },
+					Event::PoolSlashed { pool_id: 1, balance: 5 },
+				]
+			);
+			assert_eq!(
+				SubPoolsStorage::<Runtime>::get(1).unwrap().with_era.get(&3),
+				Some(&UnbondPool::<Runtime> { points: 40, balance: 20 })
+			);
+
+			// When
+			CurrentEra::set(3);
+			assert_ok!(Pools::withdraw_unbonded(Origin::signed(40), 40, 0));
+
+			// Then 40 only gets the slashed amount.
+			assert_eq!(Balances::free_balance(&40), 40 + 20);
+			assert_eq!(
+				pool_events_since_last_call(),
+				vec![
+					Event::Withdrawn { member: 40, pool_id: 1, balance: 20, points: 40 },
+					Event::MemberRemoved { pool_id: 1, member: 40 },
+				]
+			);
+		});
+	}
+}
+
+mod sub_pools {
+	use super::*;
+
+	#[test]
+	fn maybe_merge_pools_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_eq!(TotalUnbondingPools::<Runtime>::get(), 5);
+
+			let with_era: BTreeMap<EraIndex, UnbondPool<Runtime>> = vec![
+				(1, UnbondPool::<Runtime> { points: 10, balance: 10 }),
+				(2, UnbondPool::<Runtime> { points: 20, balance: 20 }),
+				(3, UnbondPool::<Runtime> { points: 30, balance: 30 }),
+			]
+			.into_iter()
+			.collect();
+			let sub_pools = SubPools::<Runtime> {
+				no_era: UnbondPool::<Runtime> { points: 5, balance: 5 },
+				with_era: with_era.try_into().unwrap(),
+			};
+
+			// nothing is merged before `PostUnbondingPoolsWindow` has passed.
+			let sub_pools = sub_pools.maybe_merge_pools(1);
+			assert_eq!(sub_pools.with_era.len(), 3);
+
+			// When
+			let sub_pools = sub_pools.maybe_merge_pools(4);
+
+			// Then eras 1 and 2 are merged into the era-less pool.
+			assert_eq!(sub_pools.no_era, UnbondPool::<Runtime> { points: 35, balance: 35 });
+			assert_eq!(sub_pools.with_era.keys().cloned().collect::<Vec<_>>(), vec![3]);
+		});
+	}
+}
+
+mod admin {
+	use super::*;
+
+	#[test]
+	fn create_works_and_errors_correctly() {
+		ExtBuilder::default().build_and_execute(|| {
+			Balances::make_free_balance_be(&11, 100);
+			Balances::make_free_balance_be(&12, 100);
+
+			assert_noop!(
+				Pools::create(Origin::signed(11), 9, 123, 123, 123),
+				Error::<Runtime>::MinimumBondNotMet
+			);
+			assert_noop!(
+				Pools::create(Origin::signed(10), 10, 123, 123, 123),
+				Error::<Runtime>::AccountBelongsToOtherPool
+			);
+
+			// When
+			assert_ok!(Pools::create(Origin::signed(11), 10, 123, 124, 125));
+
+			// Then
+			assert_eq!(LastPoolId::<Runtime>::get(), 2);
+			assert_eq!(PoolMembers::<Runtime>::get(11).unwrap().pool_id, 2);
+			assert_eq!(
+				ReversePoolIdLookup::<Runtime>::get(Pools::create_bonded_account(2)),
+				Some(2)
+			);
+			assert_eq!(Balances::free_balance(&11), 100 - 10 - ExistentialDeposit::get());
+
+			// the maximum amount of pools is reached.
+			assert_noop!(
+				Pools::create(Origin::signed(12), 10, 123, 123, 123),
+				Error::<Runtime>::MaxPools
+			);
+		});
+	}
+
+	#[test]
+	fn nominate_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_noop!(
+				Pools::nominate(RawOrigin::Signed(902).into(), 1, vec![21]),
+				Error::<Runtime>::NotNominator
+			);
+			assert_noop!(
+				Pools::nominate(RawOrigin::Signed(901).into(), 123, vec![21]),
+				Error::<Runtime>::PoolNotFound
+			);
+
+			assert_ok!(Pools::nominate(RawOrigin::Signed(901).into(), 1, vec![21]));
+			assert_eq!(Nominations::get(), Some(vec![21]));
+
+			// root can also nominate.
+			assert_ok!(Pools::nominate(RawOrigin::Signed(900).into(), 1, vec![22]));
+			assert_eq!(Nominations::get(), Some(vec![22]));
+		});
+	}
+
+	#[test]
+	fn set_state_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_noop!(
+				Pools::set_state(Origin::signed(123), 1, PoolState::Blocked),
+				Error::<Runtime>::CanNotChangeState
+			);
+			assert_noop!(
+				Pools::set_state(Origin::signed(900), 123, PoolState::Blocked),
+				Error::<Runtime>::PoolNotFound
+			);
+
+			assert_ok!(Pools::set_state(Origin::signed(900), 1, PoolState::Blocked));
+			assert_eq!(BondedPool::<Runtime>::get(1).unwrap().state, PoolState::Blocked);
+
+			assert_ok!(Pools::set_state(Origin::signed(902), 1, PoolState::Destroying));
+			assert_eq!(BondedPool::<Runtime>::get(1).unwrap().state, PoolState::Destroying);
+
+			// a destroying pool can never be re-opened.
+			assert_noop!(
+				Pools::set_state(Origin::signed(900), 1, PoolState::Open),
+				Error::<Runtime>::CanNotChangeState
+			);
+		});
+	}
+
+	#[test]
+	fn set_metadata_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_ok!(Pools::set_metadata(Origin::signed(900), 1, vec![1, 1]));
+			assert_eq!(Metadata::<Runtime>::get(1), vec![1, 1]);
+
+			assert_ok!(Pools::set_metadata(Origin::signed(902), 1, vec![2, 2]));
+			assert_eq!(Metadata::<Runtime>::get(1), vec![2, 2]);
+
+			assert_noop!(
+				Pools::set_metadata(Origin::signed(901), 1, vec![1, 1]),
+				Error::<Runtime>::DoesNotHavePermission
+			);
+			assert_noop!(
+				Pools::set_metadata(Origin::signed(900), 1, vec![1, 1, 1]),
+				Error::<Runtime>::MetadataExceedsMaxLen
+			);
+		});
+	}
+
+	#[test]
+	fn set_configs_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_noop!(
+				Pools::set_configs(
+					Origin::signed(10),
+					ConfigOp::Set(1),
+					ConfigOp::Noop,
+					ConfigOp::Noop,
+					ConfigOp::Noop,
+					ConfigOp::Noop,
+				),
+				sp_runtime::traits::BadOrigin
+			);
+
+			assert_ok!(Pools::set_configs(
+				Origin::root(),
+				ConfigOp::Set(1),
+				ConfigOp::Set(2),
+				ConfigOp::Set(3),
+				ConfigOp::Set(4),
+				ConfigOp::Set(5),
+			));
+			assert_eq!(MinJoinBond::<Runtime>::get(), 1);
+			assert_eq!(MinCreateBond::<Runtime>::get(), 2);
+			assert_eq!(MaxPools::<Runtime>::get(), Some(3));
+			assert_eq!(MaxPoolMembers::<Runtime>::get(), Some(4));
+			assert_eq!(MaxPoolMembersPerPool::<Runtime>::get(), Some(5));
+
+			assert_ok!(Pools::set_configs(
+				Origin::root(),
+				ConfigOp::Noop,
+				ConfigOp::Noop,
+				ConfigOp::Remove,
+				ConfigOp::Remove,
+				ConfigOp::Remove,
+			));
+			assert_eq!(MinJoinBond::<Runtime>::get(), 1);
+			assert_eq!(MaxPools::<Runtime>::get(), None);
+			assert_eq!(MaxPoolMembers::<Runtime>::get(), None);
+			assert_eq!(MaxPoolMembersPerPool::<Runtime>::get(), None);
+		});
+	}
+
+	#[test]
+	fn update_roles_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_noop!(
+				Pools::update_roles(
+					Origin::signed(901),
+					1,
+					ConfigOp::Set(5),
+					ConfigOp::Noop,
+					ConfigOp::Noop
+				),
+				Error::<Runtime>::DoesNotHavePermission
+			);
+
+			assert_ok!(Pools::update_roles(
+				Origin::signed(900),
+				1,
+				ConfigOp::Set(5),
+				ConfigOp::Set(6),
+				ConfigOp::Remove
+			));
+			assert_eq!(
+				BondedPool::<Runtime>::get(1).unwrap().roles,
+				PoolRoles { depositor: 10, root: Some(5), nominator: Some(6), state_toggler: None }
+			);
+
+			// root origin can always update the roles.
+			assert_ok!(Pools::update_roles(
+				Origin::root(),
+				1,
+				ConfigOp::Noop,
+				ConfigOp::Noop,
+				ConfigOp::Set(7)
+			));
+
+			assert_eq!(
+				pool_events_since_last_call()
+					.into_iter()
+					.filter(|e| matches!(e, Event::RolesUpdated { .. }))
+					.collect::<Vec<_>>(),
+				vec![
+					Event::RolesUpdated { root: Some(5), state_toggler: None, nominator: Some(6) },
+					Event::RolesUpdated {
+						root: Some(5),
+						state_toggler: Some(7),
+						nominator: Some(6)
+					},
+				]
+			);
+		});
+	}
+}
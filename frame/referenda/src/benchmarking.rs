@@ -0,0 +1,158 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Referenda pallet benchmarking.
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelist_account};
+use frame_support::{assert_ok, traits::EnsureOrigin};
+use frame_system::RawOrigin;
+use sp_runtime::traits::{Bounded, Hash};
+use sp_std::vec;
+
+use crate::Pallet as Referenda;
+
+const SEED: u32 = 0;
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let caller: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+	caller
+}
+
+fn create_referendum<T: Config>() -> (T::AccountId, ReferendumIndex) {
+	let caller = funded_account::<T>("caller", 0);
+	whitelist_account!(caller);
+	let index = ReferendumCount::<T>::get();
+	let proposal_hash = T::Hashing::hash_of(&index);
+	assert_ok!(Referenda::<T>::submit(
+		RawOrigin::Signed(caller.clone()).into(),
+		Box::new(RawOrigin::Root.into()),
+		proposal_hash,
+		DispatchTime::After(0u32.into()),
+	));
+	(caller, index)
+}
+
+fn place_deposit<T: Config>(index: ReferendumIndex) {
+	let caller = funded_account::<T>("caller", 0);
+	whitelist_account!(caller);
+	assert_ok!(Referenda::<T>::place_decision_deposit(RawOrigin::Signed(caller).into(), index));
+}
+
+fn info<T: Config>(index: ReferendumIndex) -> &'static TrackInfoOf<T> {
+	let status = Referenda::<T>::ensure_ongoing(index).unwrap();
+	T::Tracks::info(status.track).expect("Id value returned from T::Tracks")
+}
+
+fn skip_prepare_period<T: Config>(index: ReferendumIndex) {
+	let status = Referenda::<T>::ensure_ongoing(index).unwrap();
+	let prepare_period_over = status.submitted + info::<T>(index).prepare_period;
+	frame_system::Pallet::<T>::set_block_number(prepare_period_over);
+}
+
+fn is_deciding<T: Config>(index: ReferendumIndex) -> bool {
+	Referenda::<T>::ensure_ongoing(index).unwrap().deciding.is_some()
+}
+
+benchmarks! {
+	submit {
+		let caller = funded_account::<T>("caller", 0);
+		whitelist_account!(caller);
+	}: _(
+		RawOrigin::Signed(caller),
+		Box::new(RawOrigin::Root.into()),
+		T::Hashing::hash_of(&0),
+		DispatchTime::After(0u32.into())
+	) verify {
+		let index = ReferendumCount::<T>::get().checked_sub(1).unwrap();
+		assert!(Referenda::<T>::ensure_ongoing(index).is_ok());
+	}
+
+	place_decision_deposit {
+		let (caller, index) = create_referendum::<T>();
+	}: _(RawOrigin::Signed(caller), index)
+	verify {
+		let status = Referenda::<T>::ensure_ongoing(index).unwrap();
+		assert!(status.decision_deposit.is_some());
+	}
+
+	refund_decision_deposit {
+		let (caller, index) = create_referendum::<T>();
+		place_deposit::<T>(index);
+		assert_ok!(Referenda::<T>::cancel(T::CancelOrigin::successful_origin(), index));
+	}: _(RawOrigin::Signed(caller), index)
+	verify {
+		assert!(matches!(ReferendumInfoFor::<T>::get(index), Some(ReferendumInfo::Cancelled(_, _, None))));
+	}
+
+	cancel {
+		let (_caller, index) = create_referendum::<T>();
+		place_deposit::<T>(index);
+		let origin = T::CancelOrigin::successful_origin();
+	}: _<T::Origin>(origin, index)
+	verify {
+		assert!(matches!(ReferendumInfoFor::<T>::get(index), Some(ReferendumInfo::Cancelled(..))));
+	}
+
+	kill {
+		let (_caller, index) = create_referendum::<T>();
+		place_deposit::<T>(index);
+		let origin = T::KillOrigin::successful_origin();
+	}: _<T::Origin>(origin, index)
+	verify {
+		assert!(matches!(ReferendumInfoFor::<T>::get(index), Some(ReferendumInfo::Killed(..))));
+	}
+
+	// Worst case: a referendum is being decided and the next one is taken from a full queue.
+	one_fewer_deciding {
+		let (_caller, index) = create_referendum::<T>();
+		let track = Referenda::<T>::ensure_ongoing(index).unwrap().track;
+		let max_deciding = info::<T>(index).max_deciding;
+		let mut indices = vec![index];
+		for _ in 1..(max_deciding + T::MaxQueued::get()) {
+			indices.push(create_referendum::<T>().1);
+		}
+		skip_prepare_period::<T>(index);
+		for &i in indices.iter() {
+			place_deposit::<T>(i);
+		}
+		assert_eq!(DecidingCount::<T>::get(track), max_deciding);
+		assert_eq!(TrackQueue::<T>::get(track).len() as u32, T::MaxQueued::get());
+		// End one of the referenda being decided without touching the deciding count.
+		let mut status = Referenda::<T>::ensure_ongoing(index).unwrap();
+		Referenda::<T>::ensure_no_alarm(&mut status);
+		ReferendumInfoFor::<T>::insert(index, ReferendumInfo::Killed(status.submitted));
+	}: _(RawOrigin::Root, track)
+	verify {
+		assert_eq!(DecidingCount::<T>::get(track), max_deciding);
+		assert_eq!(TrackQueue::<T>::get(track).len() as u32, T::MaxQueued::get() - 1);
+	}
+
+	// Worst case: the referendum begins being decided.
+	nudge_referendum {
+		let (_caller, index) = create_referendum::<T>();
+		place_deposit::<T>(index);
+		skip_prepare_period::<T>(index);
+		assert!(!is_deciding::<T>(index));
+	}: _(RawOrigin::Root, index)
+	verify {
+		assert!(is_deciding::<T>(index));
+	}
+
+	impl_benchmark_test_suite!(Referenda, crate::mock::new_test_ext(), crate::mock::Test);
+}
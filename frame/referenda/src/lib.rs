@@ -0,0 +1,1049 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Referenda Pallet
+//!
+//! - [`Config`]
+//! - [`Call`]
+//!
+//! ## Overview
+//!
+//! A pallet for executing referenda. No voting logic is present here, and the `Polling` and
+//! `PollStatus` traits are used to allow the voting logic (likely in a pallet) to be utilized.
+//!
+//! A referendum is a vote on whether a proposal should be dispatched from a particular origin. The
+//! origin is used to determine which one of several _tracks_ that a referendum happens under.
+//! Tracks each have their own configuration which governs the voting process and parameters.
+//!
+//! A referendum's lifecycle has three main stages: Preparation, deciding and conclusion.
+//! Referenda are considered "ongoing" immediately after submission until their eventual
+//! conclusion, and votes may be cast throughout.
+//!
+//! In order to progress from preparation to being decided, three things must be in place:
+//! - There must have been a *Decision Deposit* placed, an amount determined by the track. Anyone
+//! may place this deposit.
+//! - A period must have elapsed since submission of the referendum. This period is known as the
+//! *Preparation Period* and is determined by the track.
+//! - The track must not already be at capacity with referenda being decided. The maximum number of
+//! referenda which may be being decided simultaneously is determined by the track.
+//!
+//! In order to become concluded, one of three things must happen:
+//! - The referendum should remain in an unbroken _Passing_ state for a period of time. This
+//! is known as the _Confirmation Period_ and is determined by the track. A referendum is considered
+//! _Passing_ when there is a sufficiently high turnout and approval, given the amount of time it
+//! has been being decided. Generally the threshold for what counts as being "sufficiently high"
+//! will reduce over time. The curves setting these thresholds are determined by the track. In this
+//! case, the referendum is considered _Approved_ and the proposal is scheduled for dispatch.
+//! - The referendum reaches the end of its deciding phase while not _Passing_. It ends in
+//! _Rejection_ and the proposal is not dispatched.
+//! - The referendum is cancelled.
+//!
+//! A general time-out is also in place and referenda which exist in preparation for too long may
+//! conclude without ever entering into a deciding stage.
+//!
+//! Once a referendum is concluded, the decision deposit may be refunded.
+
+#![recursion_limit = "256"]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Codec, Encode};
+use frame_support::{
+	ensure,
+	traits::{
+		schedule::{
+			v2::{Anon as ScheduleAnon, Named as ScheduleNamed},
+			DispatchTime, MaybeHashed,
+		},
+		Currency, Get, LockIdentifier, OnUnbalanced, PollStatus, Polling, ReservableCurrency,
+		VoteTally,
+	},
+	BoundedVec,
+};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, Dispatchable, One, Saturating, Zero},
+	DispatchError, DispatchResult, Perbill,
+};
+use sp_std::{fmt::Debug, prelude::*};
+
+mod types;
+pub mod weights;
+pub use pallet::*;
+pub use types::{
+	BalanceOf, CallOf, Curve, DecidingStatus, DecidingStatusOf, Deposit, InsertSorted,
+	NegativeImbalanceOf, PalletsOriginOf, ReferendumIndex, ReferendumInfo, ReferendumInfoOf,
+	ReferendumStatus, ReferendumStatusOf, ScheduleAddressOf, TallyOf, TrackIdOf, TrackInfo,
+	TrackInfoOf, TracksInfo, VotesOf,
+};
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+
+const ASSEMBLY_ID: LockIdentifier = *b"assembly";
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, traits::EnsureOrigin, Parameter};
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + Sized {
+		// System level stuff.
+		type Call: Parameter + Dispatchable<Origin = Self::Origin> + From<Call<Self>>;
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The caller origin, overarching type of all pallets origins.
+		type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>
+			+ Codec
+			+ Clone
+			+ Eq
+			+ TypeInfo
+			+ Debug;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+		/// The Scheduler.
+		type Scheduler: ScheduleAnon<Self::BlockNumber, CallOf<Self>, PalletsOriginOf<Self>, Hash = Self::Hash>
+			+ ScheduleNamed<Self::BlockNumber, CallOf<Self>, PalletsOriginOf<Self>, Hash = Self::Hash>;
+		/// Currency type for this pallet.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// Origin from which any vote may be cancelled.
+		type CancelOrigin: EnsureOrigin<Self::Origin>;
+		/// Origin from which any vote may be killed.
+		type KillOrigin: EnsureOrigin<Self::Origin>;
+		/// Handler for the unbalanced reduction when slashing a deposit.
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+		/// The counting type for votes. Usually just balance.
+		type Votes: AtLeast32BitUnsigned + Copy + Parameter + Member;
+		/// The tallying type.
+		type Tally: VoteTally<Self::Votes> + Default + Clone + Codec + Eq + Debug + TypeInfo;
+
+		// Constants
+		/// The minimum amount to be used as a deposit for a public referendum proposal.
+		#[pallet::constant]
+		type SubmissionDeposit: Get<BalanceOf<Self>>;
+
+		/// Maximum size of the referendum queue for a single track.
+		#[pallet::constant]
+		type MaxQueued: Get<u32>;
+
+		/// The number of blocks after submission that a referendum must begin being decided by.
+		/// Once this passes, then anyone may cancel the referendum.
+		#[pallet::constant]
+		type UndecidingTimeout: Get<Self::BlockNumber>;
+
+		/// Quantization level for the referendum wakeup scheduler. A higher number will result in
+		/// fewer storage reads/writes needed for smaller voters, but also result in delays to the
+		/// automatic referendum status changes. Explicit servicing instructions are unaffected.
+		#[pallet::constant]
+		type AlarmInterval: Get<Self::BlockNumber>;
+
+		// The other stuff.
+		/// Information concerning the different referendum tracks.
+		type Tracks: TracksInfo<BalanceOf<Self>, Self::BlockNumber, Origin = Self::PalletsOrigin>;
+	}
+
+	/// The next free referendum index, aka the number of referenda started so far.
+	#[pallet::storage]
+	pub type ReferendumCount<T> = StorageValue<_, ReferendumIndex, ValueQuery>;
+
+	/// Information concerning any given referendum.
+	#[pallet::storage]
+	pub type ReferendumInfoFor<T: Config> =
+		StorageMap<_, Blake2_128Concat, ReferendumIndex, ReferendumInfoOf<T>>;
+
+	/// The sorted list of referenda ready to be decided but not yet being decided, ordered by
+	/// conviction-weighted approvals.
+	///
+	/// This should be empty if `DecidingCount` is less than `TrackInfo::max_deciding`.
+	#[pallet::storage]
+	pub type TrackQueue<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		TrackIdOf<T>,
+		BoundedVec<(ReferendumIndex, T::Votes), T::MaxQueued>,
+		ValueQuery,
+	>;
+
+	/// The number of referenda being decided currently.
+	#[pallet::storage]
+	pub type DecidingCount<T: Config> = StorageMap<_, Twox64Concat, TrackIdOf<T>, u32, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A referendum has been submitted.
+		Submitted {
+			/// Index of the referendum.
+			index: ReferendumIndex,
+			/// The track (and by extension proposal dispatch origin) of this referendum.
+			track: TrackIdOf<T>,
+			/// The hash of the proposal up for referendum.
+			proposal_hash: T::Hash,
+		},
+		/// The decision deposit has been placed.
+		DecisionDepositPlaced {
+			/// Index of the referendum.
+			index: ReferendumIndex,
+			/// The account who placed the deposit.
+			who: T::AccountId,
+			/// The amount placed by the account.
+			amount: BalanceOf<T>,
+		},
+		/// The decision deposit has been refunded.
+		DecisionDepositRefunded {
+			/// Index of the referendum.
+			index: ReferendumIndex,
+			/// The account who placed the deposit.
+			who: T::AccountId,
+			/// The amount placed by the account.
+			amount: BalanceOf<T>,
+		},
+		/// A deposit has been slashed.
+		DepositSlashed {
+			/// The account who placed the deposit.
+			who: T::AccountId,
+			/// The amount placed by the account.
+			amount: BalanceOf<T>,
+		},
+		/// A referendum has moved into the deciding phase.
+		DecisionStarted {
+			/// Index of the referendum.
+			index: ReferendumIndex,
+			/// The track (and by extension proposal dispatch origin) of this referendum.
+			track: TrackIdOf<T>,
+			/// The hash of the proposal up for referendum.
+			proposal_hash: T::Hash,
+			/// The current tally of votes in this referendum.
+			tally: T::Tally,
+		},
+		/// A referendum has entered its confirmation period.
+		ConfirmStarted {
+			/// Index of the referendum.
+			index: ReferendumIndex,
+		},
+		/// A referendum has stopped passing and so left its confirmation period.
+		ConfirmAborted {
+			/// Index of the referendum.
+			index: ReferendumIndex,
+		},
+		/// A referendum has ended its confirmation phase and is ready for approval.
+		Confirmed {
+			/// Index of the referendum.
+			index: ReferendumIndex,
+			/// The final tally of votes in this referendum.
+			tally: T::Tally,
+		},
+		/// A referendum has been approved and its proposal has been scheduled.
+		Approved {
+			/// Index of the referendum.
+			index: ReferendumIndex,
+		},
+		/// A proposal has been rejected by referendum.
+		Rejected {
+			/// Index of the referendum.
+			index: ReferendumIndex,
+			/// The final tally of votes in this referendum.
+			tally: T::Tally,
+		},
+		/// A referendum has been timed out without being decided.
+		TimedOut {
+			/// Index of the referendum.
+			index: ReferendumIndex,
+			/// The final tally of votes in this referendum.
+			tally: T::Tally,
+		},
+		/// A referendum has been cancelled.
+		Cancelled {
+			/// Index of the referendum.
+			index: ReferendumIndex,
+			/// The final tally of votes in this referendum.
+			tally: T::Tally,
+		},
+		/// A referendum has been killed.
+		Killed {
+			/// Index of the referendum.
+			index: ReferendumIndex,
+			/// The final tally of votes in this referendum.
+			tally: T::Tally,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Referendum is not ongoing.
+		NotOngoing,
+		/// Referendum's decision deposit is already paid.
+		HasDeposit,
+		/// The track identifier given was invalid.
+		BadTrack,
+		/// There are already a full complement of referendums in progress for this track.
+		Full,
+		/// The queue of the track is empty.
+		QueueEmpty,
+		/// The referendum index provided is invalid in this context.
+		BadReferendum,
+		/// There was nothing to do in the advancement.
+		NothingToDo,
+		/// No track exists for the proposal origin.
+		NoTrack,
+		/// Any deposit cannot be refunded until after the decision is over.
+		Unfinished,
+		/// The deposit refunder is not the depositor.
+		NoPermission,
+		/// The deposit cannot be refunded since none was made.
+		NoDeposit,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Propose a referendum on a privileged action.
+		///
+		/// - `origin`: must be `Signed` and the account must have `SubmissionDeposit` funds
+		///   available.
+		/// - `proposal_origin`: The origin from which the proposal should be executed.
+		/// - `proposal_hash`: The hash of the proposal preimage.
+		/// - `enactment_moment`: The moment that the proposal should be enacted.
+		///
+		/// Emits `Submitted`.
+		#[pallet::weight(T::WeightInfo::submit())]
+		pub fn submit(
+			origin: OriginFor<T>,
+			proposal_origin: Box<PalletsOriginOf<T>>,
+			proposal_hash: T::Hash,
+			enactment_moment: DispatchTime<T::BlockNumber>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let track = T::Tracks::track_for(&proposal_origin).map_err(|_| Error::<T>::NoTrack)?;
+			let submission_deposit = Self::take_deposit(who, T::SubmissionDeposit::get())?;
+			let index = ReferendumCount::<T>::mutate(|x| {
+				let r = *x;
+				*x += 1;
+				r
+			});
+			let now = frame_system::Pallet::<T>::block_number();
+			let nudge_call = Call::nudge_referendum { index };
+			let status = ReferendumStatus {
+				track,
+				origin: *proposal_origin,
+				proposal_hash,
+				enactment: enactment_moment,
+				submitted: now,
+				submission_deposit,
+				decision_deposit: None,
+				deciding: None,
+				tally: Default::default(),
+				in_queue: false,
+				alarm: Self::set_alarm(nudge_call, now.saturating_add(T::UndecidingTimeout::get())),
+			};
+			ReferendumInfoFor::<T>::insert(index, ReferendumInfo::Ongoing(status));
+
+			Self::deposit_event(Event::<T>::Submitted { index, track, proposal_hash });
+			Ok(())
+		}
+
+		/// Post the Decision Deposit for a referendum.
+		///
+		/// - `origin`: must be `Signed` and the account must have funds available for the
+		///   referendum's track's Decision Deposit.
+		/// - `index`: The index of the submitted referendum whose Decision Deposit is yet to be
+		///   posted.
+		///
+		/// Emits `DecisionDepositPlaced`.
+		#[pallet::weight(T::WeightInfo::place_decision_deposit())]
+		pub fn place_decision_deposit(
+			origin: OriginFor<T>,
+			index: ReferendumIndex,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut status = Self::ensure_ongoing(index)?;
+			ensure!(status.decision_deposit.is_none(), Error::<T>::HasDeposit);
+			let track = T::Tracks::info(status.track).ok_or(Error::<T>::NoTrack)?;
+			status.decision_deposit =
+				Some(Self::take_deposit(who.clone(), track.decision_deposit)?);
+			let now = frame_system::Pallet::<T>::block_number();
+			let (info, _) = Self::service_referendum(now, index, status);
+			ReferendumInfoFor::<T>::insert(index, info);
+			let e =
+				Event::<T>::DecisionDepositPlaced { index, who, amount: track.decision_deposit };
+			Self::deposit_event(e);
+			Ok(())
+		}
+
+		/// Refund the Decision Deposit for a closed referendum back to the depositor.
+		///
+		/// - `origin`: must be `Signed`.
+		/// - `index`: The index of a closed referendum whose Decision Deposit has not yet been
+		///   refunded.
+		///
+		/// Emits `DecisionDepositRefunded`.
+		#[pallet::weight(T::WeightInfo::refund_decision_deposit())]
+		pub fn refund_decision_deposit(
+			origin: OriginFor<T>,
+			index: ReferendumIndex,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let mut info = ReferendumInfoFor::<T>::get(index).ok_or(Error::<T>::BadReferendum)?;
+			let deposit = info
+				.take_decision_deposit()
+				.map_err(|_| Error::<T>::Unfinished)?
+				.ok_or(Error::<T>::NoDeposit)?;
+			Self::refund_deposit(Some(deposit.clone()));
+			ReferendumInfoFor::<T>::insert(index, info);
+			let e = Event::<T>::DecisionDepositRefunded {
+				index,
+				who: deposit.who,
+				amount: deposit.amount,
+			};
+			Self::deposit_event(e);
+			Ok(())
+		}
+
+		/// Cancel an ongoing referendum.
+		///
+		/// - `origin`: must be the `CancelOrigin`.
+		/// - `index`: The index of the referendum to be cancelled.
+		///
+		/// Emits `Cancelled`.
+		#[pallet::weight(T::WeightInfo::cancel())]
+		pub fn cancel(origin: OriginFor<T>, index: ReferendumIndex) -> DispatchResult {
+			T::CancelOrigin::ensure_origin(origin)?;
+			let mut status = Self::ensure_ongoing(index)?;
+			Self::ensure_no_alarm(&mut status);
+			Self::note_concluded(index, &status);
+			Self::refund_deposit(Some(status.submission_deposit.clone()));
+			Self::deposit_event(Event::<T>::Cancelled { index, tally: status.tally });
+			let info = ReferendumInfo::Cancelled(
+				frame_system::Pallet::<T>::block_number(),
+				status.submission_deposit,
+				status.decision_deposit,
+			);
+			ReferendumInfoFor::<T>::insert(index, info);
+			Ok(())
+		}
+
+		/// Cancel an ongoing referendum and slash the deposits.
+		///
+		/// - `origin`: must be the `KillOrigin`.
+		/// - `index`: The index of the referendum to be cancelled.
+		///
+		/// Emits `Killed` and `DepositSlashed`.
+		#[pallet::weight(T::WeightInfo::kill())]
+		pub fn kill(origin: OriginFor<T>, index: ReferendumIndex) -> DispatchResult {
+			T::KillOrigin::ensure_origin(origin)?;
+			let mut status = Self::ensure_ongoing(index)?;
+			Self::ensure_no_alarm(&mut status);
+			Self::note_concluded(index, &status);
+			Self::deposit_event(Event::<T>::Killed { index, tally: status.tally });
+			Self::slash_deposit(Some(status.submission_deposit));
+			Self::slash_deposit(status.decision_deposit);
+			let info = ReferendumInfo::Killed(frame_system::Pallet::<T>::block_number());
+			ReferendumInfoFor::<T>::insert(index, info);
+			Ok(())
+		}
+
+		/// Advance a referendum onto its next logical state. Only used internally.
+		///
+		/// - `origin`: must be `Root`.
+		/// - `index`: the referendum to be advanced.
+		#[pallet::weight(T::WeightInfo::nudge_referendum())]
+		pub fn nudge_referendum(origin: OriginFor<T>, index: ReferendumIndex) -> DispatchResult {
+			ensure_root(origin)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			let mut status = Self::ensure_ongoing(index)?;
+			// This is our wake-up, so we can disregard the alarm.
+			status.alarm = None;
+			let (info, _) = Self::service_referendum(now, index, status);
+			ReferendumInfoFor::<T>::insert(index, info);
+			Ok(())
+		}
+
+		/// Advance a track onto its next logical state. Only used internally.
+		///
+		/// - `origin`: must be `Root`.
+		/// - `track`: the track to be advanced.
+		///
+		/// Action item for when there is now one fewer referendum in the deciding phase and the
+		/// `DecidingCount` is not yet updated. This means that we should either:
+		/// - begin deciding another referendum (and leave `DecidingCount` alone); or
+		/// - decrement `DecidingCount`.
+		#[pallet::weight(T::WeightInfo::one_fewer_deciding())]
+		pub fn one_fewer_deciding(origin: OriginFor<T>, track: TrackIdOf<T>) -> DispatchResult {
+			ensure_root(origin)?;
+			let track_info = T::Tracks::info(track).ok_or(Error::<T>::BadTrack)?;
+			Self::begin_deciding_next(track, track_info);
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Polling<T::Tally> for Pallet<T> {
+	type Index = ReferendumIndex;
+	type Votes = VotesOf<T>;
+	type Moment = T::BlockNumber;
+	type Class = TrackIdOf<T>;
+
+	fn classes() -> Vec<Self::Class> {
+		T::Tracks::tracks().iter().map(|x| x.0).collect()
+	}
+
+	fn access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut T::Tally, T::BlockNumber, TrackIdOf<T>>) -> R,
+	) -> R {
+		match ReferendumInfoFor::<T>::get(index) {
+			Some(ReferendumInfo::Ongoing(mut status)) => {
+				let result = f(PollStatus::Ongoing(&mut status.tally, status.track));
+				let now = frame_system::Pallet::<T>::block_number();
+				let (info, _) = Self::service_referendum(now, index, status);
+				ReferendumInfoFor::<T>::insert(index, info);
+				result
+			},
+			Some(ReferendumInfo::Approved(end, ..)) => f(PollStatus::Completed(end, true)),
+			Some(ReferendumInfo::Rejected(end, ..)) => f(PollStatus::Completed(end, false)),
+			_ => f(PollStatus::None),
+		}
+	}
+
+	fn try_access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(
+			PollStatus<&mut T::Tally, T::BlockNumber, TrackIdOf<T>>,
+		) -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError> {
+		match ReferendumInfoFor::<T>::get(index) {
+			Some(ReferendumInfo::Ongoing(mut status)) => {
+				let result = f(PollStatus::Ongoing(&mut status.tally, status.track))?;
+				let now = frame_system::Pallet::<T>::block_number();
+				let (info, _) = Self::service_referendum(now, index, status);
+				ReferendumInfoFor::<T>::insert(index, info);
+				Ok(result)
+			},
+			Some(ReferendumInfo::Approved(end, ..)) => f(PollStatus::Completed(end, true)),
+			Some(ReferendumInfo::Rejected(end, ..)) => f(PollStatus::Completed(end, false)),
+			_ => f(PollStatus::None),
+		}
+	}
+
+	fn as_ongoing(index: Self::Index) -> Option<(T::Tally, TrackIdOf<T>)> {
+		Self::ensure_ongoing(index).ok().map(|x| (x.tally, x.track))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_ongoing(class: Self::Class) -> Result<Self::Index, ()> {
+		let index = ReferendumCount::<T>::mutate(|x| {
+			let r = *x;
+			*x += 1;
+			r
+		});
+		let now = frame_system::Pallet::<T>::block_number();
+		let dummy_account_id =
+			codec::Decode::decode(&mut sp_runtime::traits::TrailingZeroInput::new(&b"dummy"[..]))
+				.expect("infinite length input; no invalid inputs for type; qed");
+		let mut status = ReferendumStatusOf::<T> {
+			track: class,
+			origin: frame_system::RawOrigin::Root.into(),
+			proposal_hash: <T::Hashing as sp_runtime::traits::Hash>::hash_of(&index),
+			enactment: DispatchTime::After(Zero::zero()),
+			submitted: now,
+			submission_deposit: Deposit { who: dummy_account_id, amount: Zero::zero() },
+			decision_deposit: None,
+			deciding: None,
+			tally: Default::default(),
+			in_queue: false,
+			alarm: None,
+		};
+		Self::ensure_alarm_at(&mut status, index, sp_runtime::traits::Bounded::max_value());
+		ReferendumInfoFor::<T>::insert(index, ReferendumInfo::Ongoing(status));
+		Ok(index)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn end_ongoing(index: Self::Index, approved: bool) -> Result<(), ()> {
+		let mut status = Self::ensure_ongoing(index).map_err(|_| ())?;
+		Self::ensure_no_alarm(&mut status);
+		Self::note_concluded(index, &status);
+		let now = frame_system::Pallet::<T>::block_number();
+		let info = if approved {
+			ReferendumInfo::Approved(now, status.submission_deposit, status.decision_deposit)
+		} else {
+			ReferendumInfo::Rejected(now, status.submission_deposit, status.decision_deposit)
+		};
+		ReferendumInfoFor::<T>::insert(index, info);
+		Ok(())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn max_ongoing() -> (Self::Class, u32) {
+		let r = T::Tracks::tracks()
+			.iter()
+			.max_by_key(|(_, info)| info.max_deciding)
+			.expect("Always one class");
+		(r.0.clone(), r.1.max_deciding)
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Check that referendum `index` is in the `Ongoing` state and return the `ReferendumStatus`
+	/// value, or `Err` otherwise.
+	pub fn ensure_ongoing(index: ReferendumIndex) -> Result<ReferendumStatusOf<T>, DispatchError> {
+		match ReferendumInfoFor::<T>::get(index) {
+			Some(ReferendumInfo::Ongoing(status)) => Ok(status),
+			_ => Err(Error::<T>::NotOngoing.into()),
+		}
+	}
+
+	/// Schedule the enactment of the approved proposal `call_hash` of referendum `index`.
+	fn schedule_enactment(
+		index: ReferendumIndex,
+		track: &TrackInfoOf<T>,
+		desired: DispatchTime<T::BlockNumber>,
+		origin: PalletsOriginOf<T>,
+		call_hash: T::Hash,
+	) {
+		let now = frame_system::Pallet::<T>::block_number();
+		let earliest_allowed = now.saturating_add(track.min_enactment_period);
+		let desired = desired.evaluate(now);
+		let ok = T::Scheduler::schedule_named(
+			(ASSEMBLY_ID, "enactment", index).encode(),
+			DispatchTime::At(desired.max(earliest_allowed)),
+			None,
+			63,
+			origin,
+			MaybeHashed::Hash(call_hash),
+		)
+		.is_ok();
+		debug_assert!(ok, "LOGIC ERROR: bake_referendum/schedule_named failed");
+	}
+
+	/// Set an alarm to dispatch `call` at block number `when`.
+	fn set_alarm(
+		call: impl Into<CallOf<T>>,
+		when: T::BlockNumber,
+	) -> Option<(T::BlockNumber, ScheduleAddressOf<T>)> {
+		let alarm_interval = T::AlarmInterval::get().max(One::one());
+		let when = (when.saturating_add(alarm_interval.saturating_sub(One::one())) /
+			alarm_interval)
+			.saturating_mul(alarm_interval);
+		// The scheduler refuses anything which is not strictly in the future.
+		let when = when.max(frame_system::Pallet::<T>::block_number().saturating_add(One::one()));
+		T::Scheduler::schedule(
+			DispatchTime::At(when),
+			None,
+			128u8,
+			frame_system::RawOrigin::Root.into(),
+			MaybeHashed::Value(call.into()),
+		)
+		.ok()
+		.map(|x| (when, x))
+	}
+
+	/// Mutate a referendum's `status` into the correct deciding state.
+	///
+	/// - `now` is the current block number.
+	/// - `track` is the track info for the referendum.
+	///
+	/// This will properly set up the `confirming` item and return the block number at which the
+	/// referendum should next be serviced.
+	fn begin_deciding(
+		status: &mut ReferendumStatusOf<T>,
+		index: ReferendumIndex,
+		now: T::BlockNumber,
+		track: &TrackInfoOf<T>,
+	) -> T::BlockNumber {
+		let is_passing = Self::is_passing(
+			&status.tally,
+			Zero::zero(),
+			track.decision_period,
+			&track.min_turnout,
+			&track.min_approval,
+		);
+		status.in_queue = false;
+		Self::deposit_event(Event::<T>::DecisionStarted {
+			index,
+			tally: status.tally.clone(),
+			proposal_hash: status.proposal_hash.clone(),
+			track: status.track.clone(),
+		});
+		let confirming = if is_passing {
+			Self::deposit_event(Event::<T>::ConfirmStarted { index });
+			Some(now.saturating_add(track.confirm_period))
+		} else {
+			None
+		};
+		let deciding_status = DecidingStatus { since: now, confirming };
+		let alarm = Self::decision_time(&deciding_status, &status.tally, track);
+		status.deciding = Some(deciding_status);
+		alarm
+	}
+
+	/// If it returns `Some`, deciding has begun and it needs waking at the given block number.
+	///
+	/// If `None`, then it is either queued and will be nudged automatically as the queue gets
+	/// drained, or the queue was full of better-supported referenda and it was left out of it.
+	fn ready_for_deciding(
+		now: T::BlockNumber,
+		track: &TrackInfoOf<T>,
+		index: ReferendumIndex,
+		status: &mut ReferendumStatusOf<T>,
+	) -> Option<T::BlockNumber> {
+		let deciding_count = DecidingCount::<T>::get(status.track);
+		if deciding_count < track.max_deciding {
+			// Begin deciding.
+			DecidingCount::<T>::insert(status.track, deciding_count.saturating_add(1));
+			Some(Self::begin_deciding(status, index, now, track))
+		} else {
+			// Add to queue.
+			let item = (index, status.tally.ayes());
+			status.in_queue = TrackQueue::<T>::mutate(status.track, |q| {
+				match q.insert_sorted_by_key(item, |x| x.1) {
+					Ok(maybe_dropped) => {
+						if let Some((dropped, _)) = maybe_dropped {
+							Self::note_dequeued(dropped);
+						}
+						true
+					},
+					Err(_) => false,
+				}
+			});
+			None
+		}
+	}
+
+	/// Mark the referendum `index`, which has just been dropped from its track's queue to make way
+	/// for a better-supported one, as no longer queued.
+	fn note_dequeued(index: ReferendumIndex) {
+		if let Ok(mut status) = Self::ensure_ongoing(index) {
+			status.in_queue = false;
+			let timeout = status.submitted.saturating_add(T::UndecidingTimeout::get());
+			Self::ensure_alarm_at(&mut status, index, timeout);
+			ReferendumInfoFor::<T>::insert(index, ReferendumInfo::Ongoing(status));
+		}
+	}
+
+	/// Grab the index and status for the referendum which is the highest priority of those for the
+	/// given track which are ready for being decided.
+	fn next_for_deciding(
+		track_queue: &mut BoundedVec<(u32, VotesOf<T>), T::MaxQueued>,
+	) -> Option<(ReferendumIndex, ReferendumStatusOf<T>)> {
+		loop {
+			let (index, _) = track_queue.pop()?;
+			match Self::ensure_ongoing(index) {
+				Ok(s) => return Some((index, s)),
+				Err(_) => {}, // referendum already timedout or was cancelled.
+			}
+		}
+	}
+
+	/// Begin deciding the best queued referendum of `track`, if there is one. Otherwise decrement
+	/// the number of referenda being decided on the track.
+	fn begin_deciding_next(track: TrackIdOf<T>, track_info: &TrackInfoOf<T>) {
+		let mut track_queue = TrackQueue::<T>::get(track);
+		if let Some((index, mut status)) = Self::next_for_deciding(&mut track_queue) {
+			let now = frame_system::Pallet::<T>::block_number();
+			let alarm = Self::begin_deciding(&mut status, index, now, track_info);
+			Self::ensure_alarm_at(&mut status, index, alarm);
+			ReferendumInfoFor::<T>::insert(index, ReferendumInfo::Ongoing(status));
+		} else {
+			DecidingCount::<T>::mutate(track, |x| x.saturating_dec());
+		}
+		TrackQueue::<T>::insert(track, track_queue);
+	}
+
+	/// Schedule a call to the `one_fewer_deciding` dispatchable for the next alarm. We could
+	/// theoretically call it immediately (and it would be overall more efficient), however the
+	/// weights become rather less easy to measure.
+	fn note_one_fewer_deciding(track: TrackIdOf<T>) {
+		// Set an alarm call for the next block to nudge the track along.
+		let next_block = frame_system::Pallet::<T>::block_number().saturating_add(One::one());
+		if Self::set_alarm(Call::one_fewer_deciding { track }, next_block).is_none() {
+			// We cannot defer it, so we do it now.
+			if let Some(track_info) = T::Tracks::info(track) {
+				Self::begin_deciding_next(track, track_info);
+			}
+		}
+	}
+
+	/// Clean up the bookkeeping of a referendum which is about to be concluded by something other
+	/// than its own servicing (i.e. cancelled or killed): it no longer occupies a deciding slot
+	/// nor a place in the queue of its track.
+	fn note_concluded(index: ReferendumIndex, status: &ReferendumStatusOf<T>) {
+		if status.deciding.is_some() {
+			Self::note_one_fewer_deciding(status.track);
+		}
+		if status.in_queue {
+			TrackQueue::<T>::mutate(status.track, |q| q.retain(|(i, _)| *i != index));
+		}
+	}
+
+	/// Ensure that a `service_referendum` alarm happens for the referendum `index` at `alarm`.
+	///
+	/// This will do nothing if the alarm is already set.
+	///
+	/// Returns `false` if nothing changed.
+	fn ensure_alarm_at(
+		status: &mut ReferendumStatusOf<T>,
+		index: ReferendumIndex,
+		alarm: T::BlockNumber,
+	) -> bool {
+		if status.alarm.as_ref().map_or(true, |&(when, _)| when != alarm) {
+			// Either no alarm or one that was different
+			Self::ensure_no_alarm(status);
+			status.alarm = Self::set_alarm(Call::nudge_referendum { index }, alarm);
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Advance the state of a referendum, which comes down to:
+	/// - If it's ready to be decided, start deciding;
+	/// - If it's not ready to be decided and non-deciding timeout has passed, fail;
+	/// - If it's ongoing and passing, ensure confirming; if at end of confirmation period, pass.
+	/// - If it's ongoing and not passing, stop confirming; if it has reached end time, fail.
+	///
+	/// Weight will be a bit different depending on what it does, but it's designed so as not to
+	/// differ dramatically, especially if `MaxQueue` is kept small. In particular _there are no
+	/// balance operations in here_.
+	///
+	/// In terms of storage, at most a single referendum's status is written (besides any queue
+	/// entry for its track and the deciding count of its track), and at most one referendum's
+	/// status is read.
+	///
+	/// Returns the new status of the referendum and whether it changed; this is not stored: the
+	/// caller is responsible for doing so.
+	fn service_referendum(
+		now: T::BlockNumber,
+		index: ReferendumIndex,
+		mut status: ReferendumStatusOf<T>,
+	) -> (ReferendumInfoOf<T>, bool) {
+		let mut dirty = false;
+		// Should it begin being decided?
+		let track = match T::Tracks::info(status.track) {
+			Some(x) => x,
+			None => return (ReferendumInfo::Ongoing(status), false),
+		};
+		let timeout = status.submitted.saturating_add(T::UndecidingTimeout::get());
+		// Default the alarm to the end of the world.
+		let mut alarm = None;
+		match status.deciding.clone() {
+			None => {
+				// Are we already queued for deciding?
+				if status.in_queue {
+					// Does our position in the queue need updating?
+					let ayes = status.tally.ayes();
+					let mut queue = TrackQueue::<T>::get(status.track);
+					if let Some(old_pos) = queue.iter().position(|(x, _)| *x == index) {
+						if queue[old_pos].1 != ayes {
+							queue.remove(old_pos);
+							// We just made room, so this can never drop anything.
+							let _ = queue.insert_sorted_by_key((index, ayes), |x| x.1);
+							TrackQueue::<T>::insert(status.track, queue);
+						}
+					} else {
+						// We must have been dropped from the queue at some point.
+						status.in_queue = false;
+						dirty = true;
+					}
+				}
+				if !status.in_queue && status.decision_deposit.is_some() {
+					// Are we ready for deciding?
+					let prepare_end = status.submitted.saturating_add(track.prepare_period);
+					if now >= prepare_end {
+						alarm = Self::ready_for_deciding(now, track, index, &mut status);
+						dirty = true;
+					} else {
+						alarm = Some(prepare_end);
+					}
+				}
+				// If we didn't move into being decided, then check the timeout.
+				if status.deciding.is_none() && !status.in_queue {
+					if now >= timeout {
+						// Too long without being decided - end it.
+						Self::ensure_no_alarm(&mut status);
+						Self::deposit_event(Event::<T>::TimedOut { index, tally: status.tally });
+						Self::refund_deposit(Some(status.submission_deposit.clone()));
+						return (
+							ReferendumInfo::TimedOut(
+								now,
+								status.submission_deposit,
+								status.decision_deposit,
+							),
+							true,
+						)
+					}
+					alarm = Some(alarm.map_or(timeout, |a| a.min(timeout)));
+				}
+			},
+			Some(mut deciding) => {
+				let is_passing = Self::is_passing(
+					&status.tally,
+					now.saturating_sub(deciding.since),
+					track.decision_period,
+					&track.min_turnout,
+					&track.min_approval,
+				);
+				if is_passing {
+					if deciding.confirming.map_or(false, |c| now >= c) {
+						// Passed!
+						Self::ensure_no_alarm(&mut status);
+						Self::note_one_fewer_deciding(status.track);
+						let (desired, call_hash) = (status.enactment, status.proposal_hash);
+						Self::schedule_enactment(
+							index,
+							track,
+							desired,
+							status.origin.clone(),
+							call_hash,
+						);
+						Self::refund_deposit(Some(status.submission_deposit.clone()));
+						Self::deposit_event(Event::<T>::Confirmed { index, tally: status.tally });
+						Self::deposit_event(Event::<T>::Approved { index });
+						return (
+							ReferendumInfo::Approved(
+								now,
+								status.submission_deposit,
+								status.decision_deposit,
+							),
+							true,
+						)
+					}
+					let confirmation = match deciding.confirming {
+						Some(c) => c,
+						None => {
+							// Start confirming.
+							dirty = true;
+							Self::deposit_event(Event::<T>::ConfirmStarted { index });
+							now.saturating_add(track.confirm_period)
+						},
+					};
+					deciding.confirming = Some(confirmation);
+					alarm = Some(confirmation);
+				} else {
+					if now >= deciding.since.saturating_add(track.decision_period) {
+						// Failed!
+						Self::ensure_no_alarm(&mut status);
+						Self::note_one_fewer_deciding(status.track);
+						Self::refund_deposit(Some(status.submission_deposit.clone()));
+						Self::deposit_event(Event::<T>::Rejected { index, tally: status.tally });
+						return (
+							ReferendumInfo::Rejected(
+								now,
+								status.submission_deposit,
+								status.decision_deposit,
+							),
+							true,
+						)
+					}
+					if deciding.confirming.is_some() {
+						// Stop confirming.
+						dirty = true;
+						deciding.confirming = None;
+						Self::deposit_event(Event::<T>::ConfirmAborted { index });
+					}
+					alarm = Some(Self::decision_time(&deciding, &status.tally, track));
+				}
+				status.deciding = Some(deciding);
+			},
+		}
+
+		let dirty_alarm = match alarm {
+			Some(alarm) => Self::ensure_alarm_at(&mut status, index, alarm),
+			None => Self::ensure_no_alarm(&mut status),
+		};
+		(ReferendumInfo::Ongoing(status), dirty || dirty_alarm)
+	}
+
+	/// Determine the point at which a referendum will be accepted, move into confirmation with the
+	/// given `tally` or end with rejection (whichever happens sooner).
+	fn decision_time(
+		deciding: &DecidingStatusOf<T>,
+		tally: &T::Tally,
+		track: &TrackInfoOf<T>,
+	) -> T::BlockNumber {
+		deciding.confirming.unwrap_or_else(|| {
+			// Set alarm to the point where the current voting would make it pass.
+			let approval = tally.approval();
+			let turnout = tally.turnout();
+			let until_approval = track.min_approval.delay(approval);
+			let until_turnout = track.min_turnout.delay(turnout);
+			let offset = until_turnout.max(until_approval);
+			deciding.since.saturating_add(offset * track.decision_period)
+		})
+	}
+
+	/// Cancel the alarm in `status`, if one exists.
+	///
+	/// Returns `true` if there was an alarm to cancel.
+	fn ensure_no_alarm(status: &mut ReferendumStatusOf<T>) -> bool {
+		if let Some((_, last_alarm)) = status.alarm.take() {
+			// Incorrect alarm - cancel it.
+			let _ = T::Scheduler::cancel(last_alarm);
+			true
+		} else {
+			false
+		}
+	}
+
+	/// Reserve a deposit and return the `Deposit` instance.
+	fn take_deposit(
+		who: T::AccountId,
+		amount: BalanceOf<T>,
+	) -> Result<Deposit<T::AccountId, BalanceOf<T>>, DispatchError> {
+		T::Currency::reserve(&who, amount)?;
+		Ok(Deposit { who, amount })
+	}
+
+	/// Return a deposit, if `Some`.
+	fn refund_deposit(deposit: Option<Deposit<T::AccountId, BalanceOf<T>>>) {
+		if let Some(Deposit { who, amount }) = deposit {
+			T::Currency::unreserve(&who, amount);
+		}
+	}
+
+	/// Slash a deposit, if `Some`.
+	fn slash_deposit(deposit: Option<Deposit<T::AccountId, BalanceOf<T>>>) {
+		if let Some(Deposit { who, amount }) = deposit {
+			T::Slash::on_unbalanced(T::Currency::slash_reserved(&who, amount).0);
+			Self::deposit_event(Event::<T>::DepositSlashed { who, amount });
+		}
+	}
+
+	/// Determine whether the given `tally` would result in a referendum passing at `elapsed` blocks
+	/// into a total decision `period`, given the two curves for `turnout_needed` and
+	/// `approval_needed`.
+	fn is_passing(
+		tally: &T::Tally,
+		elapsed: T::BlockNumber,
+		period: T::BlockNumber,
+		turnout_needed: &Curve,
+		approval_needed: &Curve,
+	) -> bool {
+		let x = Perbill::from_rational(elapsed.min(period), period);
+		turnout_needed.passing(x, tally.turnout()) && approval_needed.passing(x, tally.approval())
+	}
+}
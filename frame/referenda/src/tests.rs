@@ -0,0 +1,498 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The crate's tests.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok, traits::Currency};
+use frame_system::RawOrigin;
+use pallet_balances::Error as BalancesError;
+use sp_runtime::traits::BadOrigin;
+
+fn submit_root(who: u64, value: u64) -> ReferendumIndex {
+	assert_ok!(Referenda::submit(
+		Origin::signed(who),
+		Box::new(RawOrigin::Root.into()),
+		set_balance_proposal_hash(value),
+		DispatchTime::At(10),
+	));
+	ReferendumCount::<Test>::get() - 1
+}
+
+#[test]
+fn params_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ReferendumCount::<Test>::get(), 0);
+		assert_eq!(Balances::free_balance(42), 0);
+		assert_eq!(Balances::total_issuance(), 600);
+	});
+}
+
+#[test]
+fn basic_happy_path_works() {
+	new_test_ext().execute_with(|| {
+		// #1: submit
+		let i = submit_root(1, 1);
+		assert_eq!(Balances::reserved_balance(&1), 2);
+		assert_eq!(ReferendumCount::<Test>::get(), 1);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), i));
+		assert_eq!(Balances::reserved_balance(&2), 10);
+		run_to(4);
+		assert_eq!(DecidingCount::<Test>::get(0), 0);
+		assert_eq!(waiting_since(i), 1);
+		run_to(5);
+		// #5: Deciding starts once the prepare period is over.
+		assert_eq!(DecidingCount::<Test>::get(0), 1);
+		assert_eq!(deciding_and_failing_since(i), 5);
+		run_to(6);
+		// #6: Lots of ayes. Should now be confirming.
+		set_tally(i, 100, 0);
+		assert_eq!(confirming_until(i), 8);
+		run_to(7);
+		assert_eq!(confirming_until(i), 8);
+		run_to(8);
+		// #8: Should be confirmed & ended.
+		assert_eq!(approved_since(i), 8);
+		// The submission deposit is returned upon conclusion.
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		run_to(9);
+		// #9: The deciding slot has been freed up.
+		assert_eq!(DecidingCount::<Test>::get(0), 0);
+		run_to(11);
+		// #11: Enactment is delayed until the track's minimum enactment period has passed.
+		assert_eq!(Balances::free_balance(42), 0);
+		run_to(12);
+		// #12: Proposal should be executed.
+		assert_eq!(Balances::free_balance(42), 1);
+		// The decision deposit may now be refunded.
+		assert_ok!(Referenda::refund_decision_deposit(Origin::signed(3), i));
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		assert_noop!(
+			Referenda::refund_decision_deposit(Origin::signed(3), i),
+			Error::<Test>::NoDeposit
+		);
+	});
+}
+
+#[test]
+fn insta_confirm_works() {
+	new_test_ext().execute_with(|| {
+		let i = submit_root(1, 1);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), i));
+		set_tally(i, 100, 0);
+		run_to(5);
+		// A referendum which is passing when it begins being decided starts confirming at once.
+		assert_eq!(deciding_since(i), 5);
+		assert_eq!(confirming_until(i), 7);
+		run_to(7);
+		assert_eq!(approved_since(i), 7);
+	});
+}
+
+#[test]
+fn confirm_then_reconfirm_with_elapsed_trigger_works() {
+	new_test_ext().execute_with(|| {
+		let i = submit_root(1, 1);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), i));
+		run_to(6);
+		set_tally(i, 100, 0);
+		assert_eq!(confirming_until(i), 8);
+		run_to(7);
+		// #7: Votes change; confirmation is aborted.
+		set_tally(i, 100, 99);
+		assert_eq!(deciding_and_failing_since(i), 5);
+		// A tally of 100/199 has 100% turnout and 50.25% approval, which is only passing
+		// at the very end of the decision period.
+		run_to(8);
+		assert_eq!(deciding_and_failing_since(i), 5);
+		run_to(9);
+		assert_eq!(confirming_until(i), 11);
+		run_to(11);
+		// Approval is achieved even though the confirmation ends past the decision period.
+		assert_eq!(approved_since(i), 11);
+	});
+}
+
+#[test]
+fn confirm_aborted_and_rejected_works() {
+	new_test_ext().execute_with(|| {
+		let i = submit_root(1, 1);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), i));
+		run_to(6);
+		set_tally(i, 100, 0);
+		assert_eq!(confirming_until(i), 8);
+		run_to(7);
+		set_tally(i, 0, 100);
+		assert_eq!(deciding_and_failing_since(i), 5);
+		System::assert_has_event(mock::Event::Referenda(crate::Event::ConfirmAborted { index: i }));
+		run_to(8);
+		assert_eq!(deciding_and_failing_since(i), 5);
+		run_to(9);
+		// #9: Decision period is over and it was never confirmed.
+		assert_eq!(rejected_since(i), 9);
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_ok!(Referenda::refund_decision_deposit(Origin::signed(1), i));
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		run_to(20);
+		assert_eq!(Balances::free_balance(42), 0);
+	});
+}
+
+#[test]
+fn queueing_works() {
+	new_test_ext().execute_with(|| {
+		// Submit a proposal into a track with a deciding capacity of 1.
+		let first = submit_root(5, 0);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(5), first));
+
+		run_to(2);
+
+		// Submit 3 more proposals into the same track.
+		for i in 1..=3 {
+			let index = submit_root(i, i);
+			assert_ok!(Referenda::place_decision_deposit(Origin::signed(6), index));
+			set_tally(index, i as u32, 0);
+		}
+
+		run_to(5);
+		// #5: The first proposal begins being decided.
+		assert_eq!(deciding_since(first), 5);
+
+		run_to(6);
+		// #6: The other three are queued, ordered by their ayes.
+		assert_eq!(DecidingCount::<Test>::get(0), 1);
+		let queue = TrackQueue::<Test>::get(0);
+		assert_eq!(queue.into_inner(), vec![(1, 1), (2, 2), (3, 3)]);
+
+		// Votes change the ordering of the queue.
+		set_tally(1, 10, 0);
+		let queue = TrackQueue::<Test>::get(0);
+		assert_eq!(queue.into_inner(), vec![(2, 2), (3, 3), (1, 10)]);
+
+		// Killing the first referendum frees up its deciding slot.
+		assert_ok!(Referenda::kill(Origin::root(), first));
+		run_to(7);
+		// #7: The best-supported queued proposal begins being decided.
+		assert_eq!(deciding_since(1), 7);
+		assert_eq!(DecidingCount::<Test>::get(0), 1);
+		let queue = TrackQueue::<Test>::get(0);
+		assert_eq!(queue.into_inner(), vec![(2, 2), (3, 3)]);
+
+		// Cancelling a queued proposal removes it from the queue.
+		assert_ok!(Referenda::cancel(Origin::signed(4), 3));
+		let queue = TrackQueue::<Test>::get(0);
+		assert_eq!(queue.into_inner(), vec![(2, 2)]);
+
+		// Cancelling the proposal being decided lets the last queued one in.
+		assert_ok!(Referenda::cancel(Origin::signed(4), 1));
+		run_to(8);
+		assert_eq!(deciding_since(2), 8);
+		assert!(TrackQueue::<Test>::get(0).is_empty());
+
+		// Once nothing is queued, ending the decision frees the slot entirely.
+		assert_ok!(Referenda::cancel(Origin::signed(4), 2));
+		run_to(9);
+		assert_eq!(DecidingCount::<Test>::get(0), 0);
+	});
+}
+
+#[test]
+fn full_queue_drops_least_supported() {
+	new_test_ext().execute_with(|| {
+		let first = submit_root(5, 0);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(5), first));
+		run_to(5);
+		assert_eq!(deciding_since(first), 5);
+
+		// Fill the queue (which holds 3) with four proposals of increasing support.
+		for i in 1..=4 {
+			let index = submit_root(i, i);
+			assert_ok!(Referenda::place_decision_deposit(Origin::signed(6), index));
+			set_tally(index, 10 * i as u32, 0);
+		}
+		let alarm_of = |i: ReferendumIndex| match ReferendumInfoFor::<Test>::get(i).unwrap() {
+			ReferendumInfo::Ongoing(status) => status.alarm.map(|x| x.0),
+			_ => panic!("Not ongoing"),
+		};
+		run_to(9);
+		// #9: The least supported proposal did not fit into the queue and waits for its timeout.
+		let queue = TrackQueue::<Test>::get(0);
+		assert_eq!(queue.into_inner(), vec![(2, 20), (3, 30), (4, 40)]);
+		assert_eq!(waiting_since(1), 5);
+		assert_eq!(alarm_of(1), Some(25));
+
+		// More support moves it into the queue, dropping the least supported one.
+		set_tally(1, 50, 0);
+		let queue = TrackQueue::<Test>::get(0);
+		assert_eq!(queue.into_inner(), vec![(3, 30), (4, 40), (1, 50)]);
+		assert_eq!(alarm_of(1), None);
+		assert_eq!(waiting_since(2), 5);
+		assert_eq!(alarm_of(2), Some(25));
+	});
+}
+
+#[test]
+fn auto_timeout_should_happen_with_nothing_but_submit() {
+	new_test_ext().execute_with(|| {
+		// #1: submit
+		let i = submit_root(1, 1);
+		run_to(20);
+		assert_eq!(waiting_since(i), 1);
+		run_to(21);
+		// #21: Timed out.
+		assert_eq!(timed_out_since(i), 21);
+		System::assert_has_event(mock::Event::Referenda(crate::Event::TimedOut {
+			index: i,
+			tally: Tally { ayes: 0, nays: 0 },
+		}));
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_noop!(
+			Referenda::refund_decision_deposit(Origin::signed(1), i),
+			Error::<Test>::NoDeposit
+		);
+	});
+}
+
+#[test]
+fn tracks_are_distinguished() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Referenda::submit(
+			Origin::signed(1),
+			Box::new(RawOrigin::Root.into()),
+			set_balance_proposal_hash(1),
+			DispatchTime::At(10),
+		));
+		assert_ok!(Referenda::submit(
+			Origin::signed(2),
+			Box::new(RawOrigin::None.into()),
+			set_balance_proposal_hash(2),
+			DispatchTime::At(20),
+		));
+
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(3), 0));
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(4), 1));
+
+		let mut i = ReferendumInfoFor::<Test>::iter().collect::<Vec<_>>();
+		i.sort_by_key(|x| x.0);
+		assert_eq!(
+			i,
+			vec![
+				(
+					0,
+					ReferendumInfo::Ongoing(ReferendumStatus {
+						track: 0,
+						origin: OriginCaller::system(RawOrigin::Root),
+						proposal_hash: set_balance_proposal_hash(1),
+						enactment: DispatchTime::At(10),
+						submitted: 1,
+						submission_deposit: Deposit { who: 1, amount: 2 },
+						decision_deposit: Some(Deposit { who: 3, amount: 10 }),
+						deciding: None,
+						tally: Tally { ayes: 0, nays: 0 },
+						in_queue: false,
+						alarm: Some((5, (5, 0))),
+					})
+				),
+				(
+					1,
+					ReferendumInfo::Ongoing(ReferendumStatus {
+						track: 1,
+						origin: OriginCaller::system(RawOrigin::None),
+						proposal_hash: set_balance_proposal_hash(2),
+						enactment: DispatchTime::At(20),
+						submitted: 1,
+						submission_deposit: Deposit { who: 2, amount: 2 },
+						decision_deposit: Some(Deposit { who: 4, amount: 1 }),
+						deciding: None,
+						tally: Tally { ayes: 0, nays: 0 },
+						in_queue: false,
+						alarm: Some((3, (3, 0))),
+					})
+				),
+			]
+		);
+	});
+}
+
+#[test]
+fn submit_errors_work() {
+	new_test_ext().execute_with(|| {
+		let h = set_balance_proposal_hash(1);
+		// No track for a signed origin.
+		assert_noop!(
+			Referenda::submit(
+				Origin::signed(1),
+				Box::new(RawOrigin::Signed(2).into()),
+				h,
+				DispatchTime::At(10),
+			),
+			Error::<Test>::NoTrack
+		);
+
+		// No funds for deposit
+		assert_noop!(
+			Referenda::submit(
+				Origin::signed(10),
+				Box::new(RawOrigin::Root.into()),
+				h,
+				DispatchTime::At(10),
+			),
+			BalancesError::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn decision_deposit_errors_work() {
+	new_test_ext().execute_with(|| {
+		let e = Error::<Test>::NotOngoing;
+		assert_noop!(Referenda::place_decision_deposit(Origin::signed(2), 0), e);
+
+		let i = submit_root(1, 1);
+		let e = BalancesError::<Test>::InsufficientBalance;
+		assert_noop!(Referenda::place_decision_deposit(Origin::signed(10), i), e);
+
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), i));
+		let e = Error::<Test>::HasDeposit;
+		assert_noop!(Referenda::place_decision_deposit(Origin::signed(2), i), e);
+	});
+}
+
+#[test]
+fn refund_deposit_works() {
+	new_test_ext().execute_with(|| {
+		let e = Error::<Test>::BadReferendum;
+		assert_noop!(Referenda::refund_decision_deposit(Origin::signed(1), 0), e);
+
+		let i = submit_root(1, 1);
+		let e = Error::<Test>::NoDeposit;
+		assert_noop!(Referenda::refund_decision_deposit(Origin::signed(2), i), e);
+
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), i));
+		let e = Error::<Test>::Unfinished;
+		assert_noop!(Referenda::refund_decision_deposit(Origin::signed(3), i), e);
+
+		run_to(11);
+		assert_eq!(rejected_since(i), 9);
+		assert_ok!(Referenda::refund_decision_deposit(Origin::signed(3), i));
+		assert_eq!(Balances::free_balance(2), 100);
+	});
+}
+
+#[test]
+fn cancel_works() {
+	new_test_ext().execute_with(|| {
+		let i = submit_root(1, 1);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), i));
+
+		run_to(8);
+		assert_ok!(Referenda::cancel(Origin::signed(4), i));
+		assert_ok!(Referenda::refund_decision_deposit(Origin::signed(3), i));
+		assert_eq!(cancelled_since(i), 8);
+		assert_eq!(Balances::reserved_balance(&1), 0);
+		assert_eq!(Balances::reserved_balance(&2), 0);
+		run_to(9);
+		assert_eq!(DecidingCount::<Test>::get(0), 0);
+	});
+}
+
+#[test]
+fn cancel_errors_works() {
+	new_test_ext().execute_with(|| {
+		let i = submit_root(1, 1);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), i));
+		assert_noop!(Referenda::cancel(Origin::signed(1), i), BadOrigin);
+
+		run_to(11);
+		assert_noop!(Referenda::cancel(Origin::signed(4), i), Error::<Test>::NotOngoing);
+	});
+}
+
+#[test]
+fn kill_works() {
+	new_test_ext().execute_with(|| {
+		let i = submit_root(1, 1);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), i));
+
+		run_to(8);
+		let issuance = Balances::total_issuance();
+		assert_ok!(Referenda::kill(Origin::root(), i));
+		let e = Error::<Test>::Unfinished;
+		assert_noop!(Referenda::refund_decision_deposit(Origin::signed(3), i), e);
+		assert_eq!(killed_since(i), 8);
+		assert_eq!(Balances::total_issuance(), issuance - 12);
+		assert_eq!(Balances::free_balance(1), 98);
+		assert_eq!(Balances::free_balance(2), 90);
+		run_to(20);
+		assert_eq!(Balances::free_balance(42), 0);
+	});
+}
+
+#[test]
+fn kill_errors_works() {
+	new_test_ext().execute_with(|| {
+		let i = submit_root(1, 1);
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), i));
+		assert_noop!(Referenda::kill(Origin::signed(4), i), BadOrigin);
+
+		run_to(11);
+		assert_noop!(Referenda::kill(Origin::root(), i), Error::<Test>::NotOngoing);
+	});
+}
+
+#[test]
+fn internal_calls_require_root() {
+	new_test_ext().execute_with(|| {
+		let i = submit_root(1, 1);
+		assert_noop!(Referenda::nudge_referendum(Origin::signed(1), i), BadOrigin);
+		assert_noop!(Referenda::one_fewer_deciding(Origin::signed(1), 0), BadOrigin);
+		assert_noop!(Referenda::one_fewer_deciding(Origin::root(), 2), Error::<Test>::BadTrack);
+		assert_noop!(Referenda::nudge_referendum(Origin::root(), 1), Error::<Test>::NotOngoing);
+	});
+}
+
+#[test]
+fn alarm_interval_works() {
+	new_test_ext().execute_with(|| {
+		AlarmInterval::set(4);
+		// #1: The timeout alarm at #21 is rounded up to the next multiple of 4.
+		let i = submit_root(1, 1);
+		run_to(21);
+		assert_eq!(waiting_since(i), 1);
+		run_to(24);
+		assert_eq!(timed_out_since(i), 24);
+	});
+}
+
+#[test]
+fn polling_reports_status() {
+	new_test_ext().execute_with(|| {
+		let i = submit_root(1, 1);
+		assert_eq!(<Referenda as Polling<Tally>>::as_ongoing(i), Some((Tally::default(), 0)));
+		assert_ok!(Referenda::place_decision_deposit(Origin::signed(2), i));
+		set_tally(i, 100, 0);
+		run_to(7);
+		assert_eq!(<Referenda as Polling<Tally>>::as_ongoing(i), None);
+		<Referenda as Polling<Tally>>::access_poll(i, |status| {
+			assert!(matches!(status, PollStatus::Completed(7, true)));
+		});
+		<Referenda as Polling<Tally>>::access_poll(1, |status| {
+			assert!(matches!(status, PollStatus::None));
+		});
+	});
+}
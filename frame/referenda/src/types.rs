@@ -0,0 +1,333 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Miscellaneous additional datatypes.
+
+use super::*;
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::{schedule::Anon, Currency},
+	BoundedVec, Parameter,
+};
+use scale_info::TypeInfo;
+use sp_arithmetic::{traits::Zero, Perbill};
+use sp_runtime::RuntimeDebug;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+pub type CallOf<T> = <T as Config>::Call;
+pub type VotesOf<T> = <T as Config>::Votes;
+pub type TallyOf<T> = <T as Config>::Tally;
+pub type PalletsOriginOf<T> = <T as Config>::PalletsOrigin;
+pub type ReferendumInfoOf<T> = ReferendumInfo<
+	TrackIdOf<T>,
+	PalletsOriginOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+	<T as frame_system::Config>::Hash,
+	BalanceOf<T>,
+	TallyOf<T>,
+	<T as frame_system::Config>::AccountId,
+	ScheduleAddressOf<T>,
+>;
+pub type ReferendumStatusOf<T> = ReferendumStatus<
+	TrackIdOf<T>,
+	PalletsOriginOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+	<T as frame_system::Config>::Hash,
+	BalanceOf<T>,
+	TallyOf<T>,
+	<T as frame_system::Config>::AccountId,
+	ScheduleAddressOf<T>,
+>;
+pub type DecidingStatusOf<T> = DecidingStatus<<T as frame_system::Config>::BlockNumber>;
+pub type TrackInfoOf<T> = TrackInfo<BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+pub type TrackIdOf<T> = <<T as Config>::Tracks as TracksInfo<
+	BalanceOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+>>::Id;
+pub type ScheduleAddressOf<T> = <<T as Config>::Scheduler as Anon<
+	<T as frame_system::Config>::BlockNumber,
+	CallOf<T>,
+	PalletsOriginOf<T>,
+>>::Address;
+
+/// A referendum index.
+pub type ReferendumIndex = u32;
+
+pub trait InsertSorted<T> {
+	/// Inserts an item into a slice already sorted by `f`, such that the slice remains sorted.
+	///
+	/// If the slice is already full, then the lowest item is dropped to make room, unless `t`
+	/// would itself be the lowest item, in which case `Err(t)` is returned. Otherwise the dropped
+	/// item, if any, is returned.
+	fn insert_sorted_by_key<F: FnMut(&T) -> K, K: PartialOrd<K> + Ord>(
+		&mut self,
+		t: T,
+		f: F,
+	) -> Result<Option<T>, T>;
+}
+
+impl<T: Ord, S: Get<u32>> InsertSorted<T> for BoundedVec<T, S> {
+	fn insert_sorted_by_key<F: FnMut(&T) -> K, K: PartialOrd<K> + Ord>(
+		&mut self,
+		t: T,
+		mut f: F,
+	) -> Result<Option<T>, T> {
+		let key = f(&t);
+		let index = self.binary_search_by_key::<K, F>(&key, f).unwrap_or_else(|x| x);
+		if self.len() < S::get() as usize {
+			let _ = self.try_insert(index, t);
+			Ok(None)
+		} else if index == 0 {
+			Err(t)
+		} else {
+			let dropped = self.remove(0);
+			let _ = self.try_insert(index - 1, t);
+			Ok(Some(dropped))
+		}
+	}
+}
+
+/// Details of a deposit which has been placed with a referendum.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Deposit<AccountId, Balance> {
+	/// The account which placed the deposit.
+	pub who: AccountId,
+	/// The amount of the deposit.
+	pub amount: Balance,
+}
+
+/// Details of a referendum which is being decided.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct DecidingStatus<BlockNumber> {
+	/// When this referendum began being "decided". If confirming, then the end will actually be
+	/// delayed until the end of the confirmation period.
+	pub since: BlockNumber,
+	/// If `Some`, then the referendum has entered confirmation stage and will end at
+	/// the block number as long as it doesn't lose its approval in the meantime.
+	pub confirming: Option<BlockNumber>,
+}
+
+/// Parameters which govern the referenda of a single track.
+#[derive(Clone, RuntimeDebug)]
+pub struct TrackInfo<Balance, Moment> {
+	/// Name of this track.
+	pub name: &'static str,
+	/// A limit for the number of referenda on this track that can be being decided at once.
+	/// For Root origin this should generally be just one.
+	pub max_deciding: u32,
+	/// Amount that must be placed on deposit before a decision can be made.
+	pub decision_deposit: Balance,
+	/// Amount of time this must be submitted for before a decision can be made.
+	pub prepare_period: Moment,
+	/// Amount of time that a decision may take to be approved prior to cancellation.
+	pub decision_period: Moment,
+	/// Amount of time that the approval criteria must hold before it can be approved.
+	pub confirm_period: Moment,
+	/// Minimum amount of time that an approved proposal must be in the dispatch queue.
+	pub min_enactment_period: Moment,
+	/// Minimum aye votes as percentage of overall conviction-weighted votes needed for
+	/// approval as a function of time into decision period.
+	pub min_approval: Curve,
+	/// Minimum turnout as percentage of overall population that is needed for
+	/// approval as a function of time into decision period.
+	pub min_turnout: Curve,
+}
+
+/// Information on the voting tracks.
+pub trait TracksInfo<Balance, Moment> {
+	/// The identifier for a track.
+	type Id: Copy + Parameter + Ord + PartialOrd + Send + Sync + 'static;
+
+	/// The origin type from which a track is implied.
+	type Origin;
+
+	/// Return the array of known tracks and their information.
+	fn tracks() -> &'static [(Self::Id, TrackInfo<Balance, Moment>)];
+
+	/// Determine the voting track for the given `origin`.
+	fn track_for(origin: &Self::Origin) -> Result<Self::Id, ()>;
+
+	/// Return the track info for track `id`, by default this just looks it up in `Self::tracks()`.
+	fn info(id: Self::Id) -> Option<&'static TrackInfo<Balance, Moment>> {
+		Self::tracks().iter().find(|x| x.0 == id).map(|x| &x.1)
+	}
+}
+
+/// Info regarding an ongoing referendum.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct ReferendumStatus<
+	TrackId,
+	Origin,
+	Moment,
+	Hash,
+	Balance,
+	Tally,
+	AccountId,
+	ScheduleAddress,
+> {
+	/// The track of this referendum.
+	pub(crate) track: TrackId,
+	/// The origin for this referendum.
+	pub(crate) origin: Origin,
+	/// The hash of the proposal up for referendum.
+	pub(crate) proposal_hash: Hash,
+	/// The time the proposal should be scheduled for enactment.
+	pub(crate) enactment: DispatchTime<Moment>,
+	/// The time of submission. Once `UndecidingTimeout` passes, it may be closed by anyone if
+	/// `deciding` is `None`.
+	pub(crate) submitted: Moment,
+	/// The deposit reserved for the submission of this referendum.
+	pub(crate) submission_deposit: Deposit<AccountId, Balance>,
+	/// The deposit reserved for this referendum to be decided.
+	pub(crate) decision_deposit: Option<Deposit<AccountId, Balance>>,
+	/// The status of a decision being made. If `None`, it has not entered the deciding period.
+	pub(crate) deciding: Option<DecidingStatus<Moment>>,
+	/// The current tally of votes in this referendum.
+	pub(crate) tally: Tally,
+	/// Whether we have been placed in the queue for being decided or not.
+	pub(crate) in_queue: bool,
+	/// The next scheduled wake-up, if `Some`.
+	pub(crate) alarm: Option<(Moment, ScheduleAddress)>,
+}
+
+/// Info regarding a referendum, present or past.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum ReferendumInfo<TrackId, Origin, Moment, Hash, Balance, Tally, AccountId, ScheduleAddress> {
+	/// Referendum has been submitted and is being voted on.
+	Ongoing(
+		ReferendumStatus<TrackId, Origin, Moment, Hash, Balance, Tally, AccountId, ScheduleAddress>,
+	),
+	/// Referendum finished with approval. Decision deposit is held until refunded.
+	Approved(Moment, Deposit<AccountId, Balance>, Option<Deposit<AccountId, Balance>>),
+	/// Referendum finished with rejection. Decision deposit is held until refunded.
+	Rejected(Moment, Deposit<AccountId, Balance>, Option<Deposit<AccountId, Balance>>),
+	/// Referendum finished with cancellation. Decision deposit is held until refunded.
+	Cancelled(Moment, Deposit<AccountId, Balance>, Option<Deposit<AccountId, Balance>>),
+	/// Referendum finished and was never decided. Decision deposit is held until refunded.
+	TimedOut(Moment, Deposit<AccountId, Balance>, Option<Deposit<AccountId, Balance>>),
+	/// Referendum finished with a kill. All deposits were slashed.
+	Killed(Moment),
+}
+
+impl<TrackId, Origin, Moment, Hash, Balance, Tally, AccountId, ScheduleAddress>
+	ReferendumInfo<TrackId, Origin, Moment, Hash, Balance, Tally, AccountId, ScheduleAddress>
+{
+	/// Take the Decision Deposit from `self`, if there is one. Returns an `Err` if `self` is not
+	/// in a valid state for the Decision Deposit to be refunded.
+	pub fn take_decision_deposit(&mut self) -> Result<Option<Deposit<AccountId, Balance>>, ()> {
+		use ReferendumInfo::*;
+		match self {
+			Ongoing(x) if x.decision_deposit.is_none() => Ok(None),
+			// Cannot refund while ongoing.
+			Ongoing(_) => Err(()),
+			Approved(_, _, d) | Rejected(_, _, d) | TimedOut(_, _, d) | Cancelled(_, _, d) =>
+				Ok(d.take()),
+			// Cannot refund since it was slashed.
+			Killed(..) => Err(()),
+		}
+	}
+}
+
+/// Type for describing a curve over the 2-dimensional space of axes between 0-1, as represented
+/// by `(Perbill, Perbill)`.
+#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+pub enum Curve {
+	/// Linear curve starting at `(0, begin)`, ending at `(period, begin - delta)`.
+	LinearDecreasing { begin: Perbill, delta: Perbill },
+}
+
+impl Curve {
+	/// Determine the `y` value for the given `x` value.
+	pub fn threshold(&self, x: Perbill) -> Perbill {
+		match self {
+			Self::LinearDecreasing { begin, delta } => *begin - *delta * x,
+		}
+	}
+
+	/// Determine the smallest `x` value such that `passing` returns `true` when passed along with
+	/// the given `y` value.
+	///
+	/// ```nocompile
+	/// let c = Curve::LinearDecreasing { begin: Perbill::one(), delta: Perbill::one() };
+	/// //      ^^^ Can be any curve.
+	/// let y = Perbill::from_percent(50);
+	/// //      ^^^ Can be any value.
+	/// let x = c.delay(y);
+	/// assert!(c.passing(x, y));
+	/// ```
+	pub fn delay(&self, y: Perbill) -> Perbill {
+		match self {
+			Self::LinearDecreasing { begin, delta } =>
+				if delta.is_zero() {
+					*delta
+				} else {
+					(*begin - y.min(*begin)).min(*delta) / *delta
+				},
+		}
+	}
+
+	/// Return `true` iff the `y` value is greater than the curve at the `x`.
+	pub fn passing(&self, x: Perbill, y: Perbill) -> bool {
+		y >= self.threshold(x)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::traits::ConstU32;
+
+	#[test]
+	fn insert_sorted_works() {
+		let mut b: BoundedVec<u32, ConstU32<6>> = vec![20, 30, 40].try_into().unwrap();
+		assert_eq!(b.insert_sorted_by_key(10, |&x| x), Ok(None));
+		assert_eq!(&b[..], &[10, 20, 30, 40][..]);
+
+		assert_eq!(b.insert_sorted_by_key(60, |&x| x), Ok(None));
+		assert_eq!(&b[..], &[10, 20, 30, 40, 60][..]);
+
+		assert_eq!(b.insert_sorted_by_key(50, |&x| x), Ok(None));
+		assert_eq!(&b[..], &[10, 20, 30, 40, 50, 60][..]);
+
+		assert_eq!(b.insert_sorted_by_key(9, |&x| x), Err(9));
+		assert_eq!(&b[..], &[10, 20, 30, 40, 50, 60][..]);
+
+		assert_eq!(b.insert_sorted_by_key(11, |&x| x), Ok(Some(10)));
+		assert_eq!(&b[..], &[11, 20, 30, 40, 50, 60][..]);
+
+		assert_eq!(b.insert_sorted_by_key(21, |&x| x), Ok(Some(11)));
+		assert_eq!(&b[..], &[20, 21, 30, 40, 50, 60][..]);
+
+		assert_eq!(b.insert_sorted_by_key(61, |&x| x), Ok(Some(20)));
+		assert_eq!(&b[..], &[21, 30, 40, 50, 60, 61][..]);
+	}
+
+	#[test]
+	fn curve_delay_works() {
+		let c = Curve::LinearDecreasing { begin: Perbill::one(), delta: Perbill::one() };
+		for i in 0..=100 {
+			let y = Perbill::from_percent(i);
+			assert!(c.passing(c.delay(y), y));
+		}
+		assert_eq!(c.delay(Perbill::one()), Perbill::zero());
+		assert_eq!(c.delay(Perbill::zero()), Perbill::one());
+	}
+}
@@ -46,6 +46,9 @@
 //! * `schedule_named` - augments the `schedule` interface with an additional `Vec<u8>` parameter
 //!   that can be used for identification.
 //! * `cancel_named` - the named complement to the cancel function.
+//!
+//! Calls may be scheduled either as the call itself or by the hash of their encoding, in which
+//! case the preimage is requested from `Config::PreimageProvider` and looked up at dispatch time.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -57,8 +60,8 @@ use codec::{Codec, Decode, Encode};
 use frame_support::{
 	dispatch::{DispatchError, DispatchResult, Dispatchable, Parameter},
 	traits::{
-		schedule::{self, DispatchTime},
-		EnsureOrigin, Get, IsType, OriginTrait, PrivilegeCmp,
+		schedule::{self, DispatchTime, LookupError, MaybeHashed},
+		EnsureOrigin, Get, IsType, OriginTrait, PreimageProvider, PrivilegeCmp,
 	},
 	weights::{GetDispatchInfo, Weight},
 };
@@ -86,15 +89,17 @@ struct ScheduledV1<Call, BlockNumber> {
 	maybe_periodic: Option<schedule::Period<BlockNumber>>,
 }
 
+pub type CallOrHashOf<T> = MaybeHashed<<T as Config>::Call, <T as frame_system::Config>::Hash>;
+
 /// Information regarding an item to be executed in the future.
 #[cfg_attr(any(feature = "std", test), derive(PartialEq, Eq))]
 #[derive(Clone, RuntimeDebug, Encode, Decode, TypeInfo)]
-pub struct ScheduledV2<Call, BlockNumber, PalletsOrigin, AccountId> {
+pub struct ScheduledV3<Call, BlockNumber, PalletsOrigin, AccountId> {
 	/// The unique identity for this task, if there is one.
 	maybe_id: Option<Vec<u8>>,
 	/// This task's priority.
 	priority: schedule::Priority,
-	/// The call to be dispatched.
+	/// The call to be dispatched, or the hash of its encoding.
 	call: Call,
 	/// If the call is periodic, then this points to the information concerning that.
 	maybe_periodic: Option<schedule::Period<BlockNumber>>,
@@ -103,9 +108,30 @@ pub struct ScheduledV2<Call, BlockNumber, PalletsOrigin, AccountId> {
 	_phantom: PhantomData<AccountId>,
 }
 
+/// Information regarding an item to be executed in the future, as stored before calls could be
+/// given by hash. The layout is identical to `ScheduledV3`, only the call is always a value.
+pub type ScheduledV2<Call, BlockNumber, PalletsOrigin, AccountId> =
+	ScheduledV3<Call, BlockNumber, PalletsOrigin, AccountId>;
+
+pub type ScheduledV2Of<T> = ScheduledV2<
+	<T as Config>::Call,
+	<T as frame_system::Config>::BlockNumber,
+	<T as Config>::PalletsOrigin,
+	<T as frame_system::Config>::AccountId,
+>;
+
+pub type ScheduledV3Of<T> = ScheduledV3<
+	CallOrHashOf<T>,
+	<T as frame_system::Config>::BlockNumber,
+	<T as Config>::PalletsOrigin,
+	<T as frame_system::Config>::AccountId,
+>;
+
+pub type ScheduledOf<T> = ScheduledV3Of<T>;
+
 /// The current version of Scheduled struct.
 pub type Scheduled<Call, BlockNumber, PalletsOrigin, AccountId> =
-	ScheduledV2<Call, BlockNumber, PalletsOrigin, AccountId>;
+	ScheduledV3<Call, BlockNumber, PalletsOrigin, AccountId>;
 
 // A value placed in storage that represents the current version of the Scheduler storage.
 // This value is used by the `on_runtime_upgrade` logic to determine whether we run
@@ -114,6 +140,7 @@ pub type Scheduled<Call, BlockNumber, PalletsOrigin, AccountId> =
 enum Releases {
 	V1,
 	V2,
+	V3,
 }
 
 impl Default for Releases {
@@ -176,17 +203,19 @@ pub mod pallet {
 
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
+
+		/// The preimage provider with which we look up call hashes to get the call.
+		type PreimageProvider: PreimageProvider<Self::Hash>;
+
+		/// If `Some` then the number of blocks to postpone execution for when the item is delayed
+		/// because its preimage is not (yet) available.
+		type NoPreimagePostponement: Get<Option<Self::BlockNumber>>;
 	}
 
 	/// Items to be executed, indexed by the block number that they should be executed on.
 	#[pallet::storage]
-	pub type Agenda<T: Config> = StorageMap<
-		_,
-		Twox64Concat,
-		T::BlockNumber,
-		Vec<Option<Scheduled<<T as Config>::Call, T::BlockNumber, T::PalletsOrigin, T::AccountId>>>,
-		ValueQuery,
-	>;
+	pub type Agenda<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, Vec<Option<ScheduledV3Of<T>>>, ValueQuery>;
 
 	/// Lookup from identity to the block number and index of the task.
 	#[pallet::storage]
@@ -209,6 +238,9 @@ pub mod pallet {
 		Canceled(T::BlockNumber, u32),
 		/// Dispatched some task. \[task, id, result\]
 		Dispatched(TaskAddress<T::BlockNumber>, Option<Vec<u8>>, DispatchResult),
+		/// The call for the provided hash was not found so the task has been aborted or
+		/// postponed. \[task, id, error\]
+		CallLookupFailed(TaskAddress<T::BlockNumber>, Option<Vec<u8>>, LookupError),
 	}
 
 	#[pallet::error]
@@ -236,7 +268,7 @@ pub mod pallet {
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig {
 		fn build(&self) {
-			StorageVersion::<T>::put(Releases::V2);
+			StorageVersion::<T>::put(Releases::V3);
 		}
 	}
 
@@ -258,82 +290,119 @@ pub mod pallet {
 				);
 			}
 			queued.sort_by_key(|(_, s)| s.priority);
-			let base_weight: Weight = T::DbWeight::get().reads_writes(1, 2); // Agenda + Agenda(next)
+			let next = now + One::one();
+			let mut cumulative_weight: Weight = T::DbWeight::get().reads_writes(1, 2); // Agenda + Agenda(next)
 			let mut total_weight: Weight = 0;
-			queued
-				.into_iter()
-				.enumerate()
-				.scan(base_weight, |cumulative_weight, (order, (index, s))| {
-					*cumulative_weight =
-						cumulative_weight.saturating_add(s.call.get_dispatch_info().weight);
-
-					let origin =
-						<<T as Config>::Origin as From<T::PalletsOrigin>>::from(s.origin.clone())
-							.into();
-
-					if ensure_signed(origin).is_ok() {
-						// AccountData for inner call origin accountdata.
-						*cumulative_weight =
-							cumulative_weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
-					}
-
-					if s.maybe_id.is_some() {
-						// Remove/Modify Lookup
-						*cumulative_weight =
-							cumulative_weight.saturating_add(T::DbWeight::get().writes(1));
-					}
-					if s.maybe_periodic.is_some() {
-						// Read/Write Agenda for future block
-						*cumulative_weight =
-							cumulative_weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
-					}
+			for (order, (index, mut s)) in queued.into_iter().enumerate() {
+				let (call, maybe_completed) = s.call.resolved::<T::PreimageProvider>();
+				s.call = call;
+
+				if let Some(ref completed) = maybe_completed {
+					// The call is now held by value, so the preimage is no longer needed.
+					T::PreimageProvider::unrequest_preimage(completed);
+					// Preimage lookup + preimage request status.
+					cumulative_weight.saturating_accrue(T::DbWeight::get().reads_writes(2, 2));
+				}
 
-					Some((order, index, *cumulative_weight, s))
-				})
-				.filter_map(|(order, index, cumulative_weight, mut s)| {
-					// We allow a scheduled call if any is true:
-					// - It's priority is `HARD_DEADLINE`
-					// - It does not push the weight past the limit.
-					// - It is the first item in the schedule
-					if s.priority <= schedule::HARD_DEADLINE
-						|| cumulative_weight <= limit
-						|| order == 0
-					{
-						let r = s.call.clone().dispatch(s.origin.clone().into());
-						let maybe_id = s.maybe_id.clone();
-						if let &Some((period, count)) = &s.maybe_periodic {
-							if count > 1 {
-								s.maybe_periodic = Some((period, count - 1));
-							} else {
-								s.maybe_periodic = None;
-							}
-							let next = now + period;
-							// If scheduled is named, place it's information in `Lookup`
+				let call = match s.call.as_value().cloned() {
+					Some(c) => c,
+					None => {
+						// The preimage is not available; we only pay for the failed lookup and
+						// for the postponement of the task.
+						cumulative_weight.saturating_accrue(T::DbWeight::get().reads(2));
+						let error = match s.call.as_hash() {
+							Some(hash) if T::PreimageProvider::have_preimage(hash) =>
+								LookupError::BadFormat,
+							_ => LookupError::Unknown,
+						};
+						Self::deposit_event(Event::CallLookupFailed(
+							(now, index),
+							s.maybe_id.clone(),
+							error,
+						));
+						if let Some(delay) = T::NoPreimagePostponement::get() {
+							let until = now.saturating_add(delay);
 							if let Some(ref id) = s.maybe_id {
-								let next_index = Agenda::<T>::decode_len(now + period).unwrap_or(0);
-								Lookup::<T>::insert(id, (next, next_index as u32));
+								let index = Agenda::<T>::decode_len(until).unwrap_or(0);
+								Lookup::<T>::insert(id, (until, index as u32));
+								cumulative_weight.saturating_accrue(T::DbWeight::get().writes(1));
 							}
-							Agenda::<T>::append(next, Some(s));
+							cumulative_weight
+								.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+							Agenda::<T>::append(until, Some(s));
 						} else {
+							s.call.ensure_unrequested::<T::PreimageProvider>();
 							if let Some(ref id) = s.maybe_id {
 								Lookup::<T>::remove(id);
+								cumulative_weight.saturating_accrue(T::DbWeight::get().writes(1));
 							}
 						}
-						Self::deposit_event(Event::Dispatched(
-							(now, index),
-							maybe_id,
-							r.map(|_| ()).map_err(|e| e.error),
-						));
 						total_weight = cumulative_weight;
-						None
+						continue
+					},
+				};
+
+				cumulative_weight.saturating_accrue(call.get_dispatch_info().weight);
+
+				let origin =
+					<<T as Config>::Origin as From<T::PalletsOrigin>>::from(s.origin.clone())
+						.into();
+
+				if ensure_signed(origin).is_ok() {
+					// AccountData for inner call origin accountdata.
+					cumulative_weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+				}
+
+				if s.maybe_id.is_some() {
+					// Remove/Modify Lookup
+					cumulative_weight.saturating_accrue(T::DbWeight::get().writes(1));
+				}
+				if s.maybe_periodic.is_some() {
+					// Read/Write Agenda for future block
+					cumulative_weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+				}
+
+				// We allow a scheduled call if any is true:
+				// - It's priority is `HARD_DEADLINE`
+				// - It does not push the weight past the limit.
+				// - It is the first item in the schedule
+				if s.priority <= schedule::HARD_DEADLINE || cumulative_weight <= limit || order == 0
+				{
+					let r = call.dispatch(s.origin.clone().into());
+					let maybe_id = s.maybe_id.clone();
+					if let &Some((period, count)) = &s.maybe_periodic {
+						if count > 1 {
+							s.maybe_periodic = Some((period, count - 1));
+						} else {
+							s.maybe_periodic = None;
+						}
+						let wake = now + period;
+						// If scheduled is named, place it's information in `Lookup`
+						if let Some(ref id) = s.maybe_id {
+							let wake_index = Agenda::<T>::decode_len(wake).unwrap_or(0);
+							Lookup::<T>::insert(id, (wake, wake_index as u32));
+						}
+						Agenda::<T>::append(wake, Some(s));
 					} else {
-						Some(Some(s))
+						if let Some(ref id) = s.maybe_id {
+							Lookup::<T>::remove(id);
+						}
 					}
-				})
-				.for_each(|unused| {
-					let next = now + One::one();
-					Agenda::<T>::append(next, unused);
-				});
+					Self::deposit_event(Event::Dispatched(
+						(now, index),
+						maybe_id,
+						r.map(|_| ()).map_err(|e| e.error),
+					));
+					total_weight = cumulative_weight;
+				} else {
+					// Cannot be dispatched this block - postpone until next.
+					if let Some(ref id) = s.maybe_id {
+						let next_index = Agenda::<T>::decode_len(next).unwrap_or(0);
+						Lookup::<T>::insert(id, (next, next_index as u32));
+					}
+					Agenda::<T>::append(next, Some(s));
+				}
+			}
 
 			total_weight
 		}
@@ -348,7 +417,7 @@ pub mod pallet {
 			when: T::BlockNumber,
 			maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 			priority: schedule::Priority,
-			call: Box<<T as Config>::Call>,
+			call: Box<CallOrHashOf<T>>,
 		) -> DispatchResult {
 			T::ScheduleOrigin::ensure_origin(origin.clone())?;
 			let origin = <T as Config>::Origin::from(origin);
@@ -379,7 +448,7 @@ pub mod pallet {
 			when: T::BlockNumber,
 			maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 			priority: schedule::Priority,
-			call: Box<<T as Config>::Call>,
+			call: Box<CallOrHashOf<T>>,
 		) -> DispatchResult {
 			T::ScheduleOrigin::ensure_origin(origin.clone())?;
 			let origin = <T as Config>::Origin::from(origin);
@@ -414,7 +483,7 @@ pub mod pallet {
 			after: T::BlockNumber,
 			maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 			priority: schedule::Priority,
-			call: Box<<T as Config>::Call>,
+			call: Box<CallOrHashOf<T>>,
 		) -> DispatchResult {
 			T::ScheduleOrigin::ensure_origin(origin.clone())?;
 			let origin = <T as Config>::Origin::from(origin);
@@ -440,7 +509,7 @@ pub mod pallet {
 			after: T::BlockNumber,
 			maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 			priority: schedule::Priority,
-			call: Box<<T as Config>::Call>,
+			call: Box<CallOrHashOf<T>>,
 		) -> DispatchResult {
 			T::ScheduleOrigin::ensure_origin(origin.clone())?;
 			let origin = <T as Config>::Origin::from(origin);
@@ -458,24 +527,24 @@ pub mod pallet {
 }
 
 impl<T: Config> Pallet<T> {
-	/// Migrate storage format from V1 to V2.
-	/// Return true if migration is performed.
-	pub fn migrate_v1_to_t2() -> bool {
-		if StorageVersion::<T>::get() == Releases::V1 {
-			StorageVersion::<T>::put(Releases::V2);
-
-			Agenda::<T>::translate::<
-				Vec<Option<ScheduledV1<<T as Config>::Call, T::BlockNumber>>>,
-				_,
-			>(|_, agenda| {
+	/// Migrate storage format from V1 to V3.
+	///
+	/// Returns the weight consumed by this migration.
+	pub fn migrate_v1_to_v3() -> Weight {
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+		Agenda::<T>::translate::<Vec<Option<ScheduledV1<<T as Config>::Call, T::BlockNumber>>>, _>(
+			|_, agenda| {
 				Some(
 					agenda
 						.into_iter()
 						.map(|schedule| {
-							schedule.map(|schedule| ScheduledV2 {
+							weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+
+							schedule.map(|schedule| ScheduledV3 {
 								maybe_id: schedule.maybe_id,
 								priority: schedule.priority,
-								call: schedule.call,
+								call: schedule.call.into(),
 								maybe_periodic: schedule.maybe_periodic,
 								origin: system::RawOrigin::Root.into(),
 								_phantom: Default::default(),
@@ -483,18 +552,74 @@ impl<T: Config> Pallet<T> {
 						})
 						.collect::<Vec<_>>(),
 				)
-			});
+			},
+		);
 
-			true
-		} else {
-			false
+		StorageVersion::<T>::put(Releases::V3);
+
+		weight
+	}
+
+	/// Migrate storage format from V2 to V3.
+	///
+	/// Returns the weight consumed by this migration.
+	pub fn migrate_v2_to_v3() -> Weight {
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+		Agenda::<T>::translate::<Vec<Option<ScheduledV2Of<T>>>, _>(|_, agenda| {
+			Some(
+				agenda
+					.into_iter()
+					.map(|schedule| {
+						weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+						schedule.map(|schedule| ScheduledV3 {
+							maybe_id: schedule.maybe_id,
+							priority: schedule.priority,
+							call: schedule.call.into(),
+							maybe_periodic: schedule.maybe_periodic,
+							origin: schedule.origin,
+							_phantom: Default::default(),
+						})
+					})
+					.collect::<Vec<_>>(),
+			)
+		});
+
+		StorageVersion::<T>::put(Releases::V3);
+
+		weight
+	}
+
+	/// Migrate the storage to V3 from whichever version it is currently in.
+	///
+	/// Returns the weight consumed by this migration.
+	pub fn migrate_to_v3() -> Weight {
+		match StorageVersion::<T>::get() {
+			Releases::V1 => Self::migrate_v1_to_v3(),
+			Releases::V2 => Self::migrate_v2_to_v3(),
+			Releases::V3 => T::DbWeight::get().reads(1),
 		}
 	}
 
+	#[cfg(feature = "try-runtime")]
+	pub fn pre_migrate_to_v3() -> Result<(), &'static str> {
+		assert!(StorageVersion::<T>::get() != Releases::V3);
+		Ok(())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	pub fn post_migrate_to_v3() -> Result<(), &'static str> {
+		assert!(StorageVersion::<T>::get() == Releases::V3);
+		for k in Agenda::<T>::iter_keys() {
+			let _ = Agenda::<T>::try_get(k).map_err(|()| "Invalid item in Agenda")?;
+		}
+		Ok(())
+	}
+
 	/// Helper to migrate scheduler when the pallet origin type has changed.
 	pub fn migrate_origin<OldOrigin: Into<T::PalletsOrigin> + codec::Decode>() {
 		Agenda::<T>::translate::<
-			Vec<Option<Scheduled<<T as Config>::Call, T::BlockNumber, OldOrigin, T::AccountId>>>,
+			Vec<Option<Scheduled<CallOrHashOf<T>, T::BlockNumber, OldOrigin, T::AccountId>>>,
 			_,
 		>(|_, agenda| {
 			Some(
@@ -537,9 +662,10 @@ impl<T: Config> Pallet<T> {
 		maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 		priority: schedule::Priority,
 		origin: T::PalletsOrigin,
-		call: <T as Config>::Call,
+		call: CallOrHashOf<T>,
 	) -> Result<TaskAddress<T::BlockNumber>, DispatchError> {
 		let when = Self::resolve_time(when)?;
+		call.ensure_requested::<T::PreimageProvider>();
 
 		// sanitize maybe_periodic
 		let maybe_periodic = maybe_periodic
@@ -589,6 +715,7 @@ impl<T: Config> Pallet<T> {
 			)
 		})?;
 		if let Some(s) = scheduled {
+			s.call.ensure_unrequested::<T::PreimageProvider>();
 			if let Some(id) = s.maybe_id {
 				Lookup::<T>::remove(id);
 			}
@@ -629,7 +756,7 @@ impl<T: Config> Pallet<T> {
 		maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
 		priority: schedule::Priority,
 		origin: T::PalletsOrigin,
-		call: <T as Config>::Call,
+		call: CallOrHashOf<T>,
 	) -> Result<TaskAddress<T::BlockNumber>, DispatchError> {
 		// ensure id it is unique
 		if Lookup::<T>::contains_key(&id) {
@@ -638,6 +765,8 @@ impl<T: Config> Pallet<T> {
 
 		let when = Self::resolve_time(when)?;
 
+		call.ensure_requested::<T::PreimageProvider>();
+
 		// sanitize maybe_periodic
 		let maybe_periodic = maybe_periodic
 			.filter(|p| p.1 > 1 && !p.0.is_zero())
@@ -682,7 +811,9 @@ impl<T: Config> Pallet<T> {
 								return Err(BadOrigin.into());
 							}
 						}
-						*s = None;
+						if let Some(s) = s.take() {
+							s.call.ensure_unrequested::<T::PreimageProvider>();
+						}
 					}
 					Ok(())
 				})?;
@@ -729,7 +860,7 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
-impl<T: Config> schedule::Anon<T::BlockNumber, <T as Config>::Call, T::PalletsOrigin>
+impl<T: Config> schedule::v1::Anon<T::BlockNumber, <T as Config>::Call, T::PalletsOrigin>
 	for Pallet<T>
 {
 	type Address = TaskAddress<T::BlockNumber>;
@@ -741,7 +872,7 @@ impl<T: Config> schedule::Anon<T::BlockNumber, <T as Config>::Call, T::PalletsOr
 		origin: T::PalletsOrigin,
 		call: <T as Config>::Call,
 	) -> Result<Self::Address, DispatchError> {
-		Self::do_schedule(when, maybe_periodic, priority, origin, call)
+		Self::do_schedule(when, maybe_periodic, priority, origin, call.into())
 	}
 
 	fn cancel((when, index): Self::Address) -> Result<(), ()> {
@@ -760,7 +891,7 @@ impl<T: Config> schedule::Anon<T::BlockNumber, <T as Config>::Call, T::PalletsOr
 	}
 }
 
-impl<T: Config> schedule::Named<T::BlockNumber, <T as Config>::Call, T::PalletsOrigin>
+impl<T: Config> schedule::v1::Named<T::BlockNumber, <T as Config>::Call, T::PalletsOrigin>
 	for Pallet<T>
 {
 	type Address = TaskAddress<T::BlockNumber>;
@@ -772,6 +903,74 @@ impl<T: Config> schedule::Named<T::BlockNumber, <T as Config>::Call, T::PalletsO
 		priority: schedule::Priority,
 		origin: T::PalletsOrigin,
 		call: <T as Config>::Call,
+	) -> Result<Self::Address, ()> {
+		Self::do_schedule_named(id, when, maybe_periodic, priority, origin, call.into())
+			.map_err(|_| ())
+	}
+
+	fn cancel_named(id: Vec<u8>) -> Result<(), ()> {
+		Self::do_cancel_named(None, id).map_err(|_| ())
+	}
+
+	fn reschedule_named(
+		id: Vec<u8>,
+		when: DispatchTime<T::BlockNumber>,
+	) -> Result<Self::Address, DispatchError> {
+		Self::do_reschedule_named(id, when)
+	}
+
+	fn next_dispatch_time(id: Vec<u8>) -> Result<T::BlockNumber, ()> {
+		Lookup::<T>::get(id)
+			.and_then(|(when, index)| Agenda::<T>::get(when).get(index as usize).map(|_| when))
+			.ok_or(())
+	}
+}
+
+impl<T: Config> schedule::v2::Anon<T::BlockNumber, <T as Config>::Call, T::PalletsOrigin>
+	for Pallet<T>
+{
+	type Address = TaskAddress<T::BlockNumber>;
+	type Hash = T::Hash;
+
+	fn schedule(
+		when: DispatchTime<T::BlockNumber>,
+		maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
+		priority: schedule::Priority,
+		origin: T::PalletsOrigin,
+		call: CallOrHashOf<T>,
+	) -> Result<Self::Address, DispatchError> {
+		Self::do_schedule(when, maybe_periodic, priority, origin, call)
+	}
+
+	fn cancel((when, index): Self::Address) -> Result<(), ()> {
+		Self::do_cancel(None, (when, index)).map_err(|_| ())
+	}
+
+	fn reschedule(
+		address: Self::Address,
+		when: DispatchTime<T::BlockNumber>,
+	) -> Result<Self::Address, DispatchError> {
+		Self::do_reschedule(address, when)
+	}
+
+	fn next_dispatch_time((when, index): Self::Address) -> Result<T::BlockNumber, ()> {
+		Agenda::<T>::get(when).get(index as usize).ok_or(()).map(|_| when)
+	}
+}
+
+impl<T: Config> schedule::v2::Named<T::BlockNumber, <T as Config>::Call, T::PalletsOrigin>
+	for Pallet<T>
+{
+	type Address = TaskAddress<T::BlockNumber>;
+	type Hash = T::Hash;
+
+	fn schedule_named(
+		id: Vec<u8>,
+		when: DispatchTime<T::BlockNumber>,
+		maybe_periodic: Option<schedule::Period<T::BlockNumber>>,
+		priority: schedule::Priority,
+		origin: T::PalletsOrigin,
+		call: CallOrHashOf<T>,
 	) -> Result<Self::Address, ()> {
 		Self::do_schedule_named(id, when, maybe_periodic, priority, origin, call).map_err(|_| ())
 	}
@@ -809,7 +1008,7 @@ mod tests {
 	use sp_core::H256;
 	use sp_runtime::{
 		testing::Header,
-		traits::{BlakeTwo256, IdentityLookup},
+		traits::{BlakeTwo256, Hash, IdentityLookup},
 		Perbill,
 	};
 	use substrate_test_utils::assert_eq_uvec;
@@ -888,6 +1087,8 @@ mod tests {
 			System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 			Logger: logger::{Pallet, Call, Event<T>},
 			Scheduler: scheduler::{Pallet, Call, Storage, Event<T>},
+			Balances: pallet_balances::{Pallet, Call, Storage, Event<T>},
+			Preimage: pallet_preimage::{Pallet, Call, Storage, Event<T>},
 		}
 	);
 
@@ -922,7 +1123,7 @@ mod tests {
 		type BlockHashCount = BlockHashCount;
 		type Version = ();
 		type PalletInfo = PalletInfo;
-		type AccountData = ();
+		type AccountData = pallet_balances::AccountData<u64>;
 		type OnNewAccount = ();
 		type OnKilledAccount = ();
 		type SystemWeightInfo = ();
@@ -932,9 +1133,38 @@ mod tests {
 	impl logger::Config for Test {
 		type Event = Event;
 	}
+	parameter_types! {
+		pub const ExistentialDeposit: u64 = 1;
+	}
+	impl pallet_balances::Config for Test {
+		type Balance = u64;
+		type Event = Event;
+		type DustRemoval = ();
+		type ExistentialDeposit = ExistentialDeposit;
+		type AccountStore = System;
+		type WeightInfo = ();
+		type MaxLocks = ();
+		type MaxReserves = ();
+		type ReserveIdentifier = [u8; 8];
+	}
+	parameter_types! {
+		pub const PreimageMaxSize: u32 = 4096 * 1024;
+		pub const PreimageBaseDeposit: u64 = 2;
+		pub const PreimageByteDeposit: u64 = 1;
+	}
+	impl pallet_preimage::Config for Test {
+		type Event = Event;
+		type WeightInfo = ();
+		type Currency = Balances;
+		type ManagerOrigin = EnsureRoot<u64>;
+		type MaxSize = PreimageMaxSize;
+		type BaseDeposit = PreimageBaseDeposit;
+		type ByteDeposit = PreimageByteDeposit;
+	}
 	parameter_types! {
 		pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * BlockWeights::get().max_block;
 		pub const MaxScheduledPerBlock: u32 = 10;
+		pub const NoPreimagePostponement: Option<u64> = Some(2);
 	}
 	ord_parameter_types! {
 		pub const One: u64 = 1;
@@ -950,6 +1180,8 @@ mod tests {
 		type MaxScheduledPerBlock = MaxScheduledPerBlock;
 		type WeightInfo = ();
 		type OriginPrivilegeCmp = EqualPrivilegeOnly;
+		type PreimageProvider = Preimage;
+		type NoPreimagePostponement = NoPreimagePostponement;
 	}
 
 	pub type LoggerCall = logger::Call<Test>;
@@ -976,16 +1208,99 @@ mod tests {
 		new_test_ext().execute_with(|| {
 			let call = Call::Logger(LoggerCall::log { i: 42, weight: 1000 });
 			assert!(!<Test as frame_system::Config>::BaseCallFilter::contains(&call));
-			assert_ok!(Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), call));
+			assert_ok!(Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), call.into()));
+			run_to_block(3);
+			assert!(logger::log().is_empty());
+			run_to_block(4);
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+			run_to_block(100);
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		});
+	}
+
+	#[test]
+	fn scheduling_with_preimages_works() {
+		new_test_ext().execute_with(|| {
+			let call = Call::Logger(LoggerCall::log { i: 42, weight: 1000 });
+			let hash = <Test as frame_system::Config>::Hashing::hash_of(&call);
+			let hashed = MaybeHashed::Hash(hash.clone());
+			assert_ok!(Preimage::note_preimage(Origin::root(), call.encode()));
+			assert_ok!(Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), hashed));
+			assert!(Preimage::preimage_requested(&hash));
 			run_to_block(3);
 			assert!(logger::log().is_empty());
 			run_to_block(4);
+			assert!(!Preimage::have_preimage(&hash));
+			assert!(!Preimage::preimage_requested(&hash));
 			assert_eq!(logger::log(), vec![(root(), 42u32)]);
 			run_to_block(100);
 			assert_eq!(logger::log(), vec![(root(), 42u32)]);
 		});
 	}
 
+	#[test]
+	fn scheduling_with_preimage_postpones_correctly() {
+		new_test_ext().execute_with(|| {
+			let call = Call::Logger(LoggerCall::log { i: 42, weight: 1000 });
+			let hash = <Test as frame_system::Config>::Hashing::hash_of(&call);
+			let hashed = MaybeHashed::Hash(hash.clone());
+
+			assert_ok!(Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), hashed));
+			assert!(Preimage::preimage_requested(&hash));
+
+			run_to_block(4);
+			// #4 empty due to no preimage
+			assert!(logger::log().is_empty());
+			System::assert_last_event(
+				crate::Event::CallLookupFailed((4, 0), None, LookupError::Unknown).into(),
+			);
+
+			// Register preimage.
+			assert_ok!(Preimage::note_preimage(Origin::root(), call.encode()));
+
+			run_to_block(5);
+			// #5 empty since postponement is 2 blocks.
+			assert!(logger::log().is_empty());
+
+			run_to_block(6);
+			// #6 is good.
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+			assert!(!Preimage::have_preimage(&hash));
+			assert!(!Preimage::preimage_requested(&hash));
+
+			run_to_block(100);
+			assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		});
+	}
+
+	#[test]
+	fn cancelling_hashed_call_unrequests_preimage() {
+		new_test_ext().execute_with(|| {
+			let call = Call::Logger(LoggerCall::log { i: 42, weight: 1000 });
+			let hash = <Test as frame_system::Config>::Hashing::hash_of(&call);
+			let hashed = MaybeHashed::Hash(hash.clone());
+
+			assert_ok!(Scheduler::do_schedule_named(
+				1u32.encode(),
+				DispatchTime::At(4),
+				None,
+				127,
+				root(),
+				hashed.clone(),
+			));
+			let i = Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), hashed).unwrap();
+			assert!(Preimage::preimage_requested(&hash));
+
+			assert_ok!(Scheduler::do_cancel_named(None, 1u32.encode()));
+			assert!(Preimage::preimage_requested(&hash));
+			assert_ok!(Scheduler::do_cancel(None, i));
+			assert!(!Preimage::preimage_requested(&hash));
+
+			run_to_block(100);
+			assert!(logger::log().is_empty());
+		});
+	}
+
 	#[test]
 	fn schedule_after_works() {
 		new_test_ext().execute_with(|| {
@@ -993,7 +1308,13 @@ mod tests {
 			let call = Call::Logger(LoggerCall::log { i: 42, weight: 1000 });
 			assert!(!<Test as frame_system::Config>::BaseCallFilter::contains(&call));
 			// This will schedule the call 3 blocks after the next block... so block 3 + 3 = 6
-			assert_ok!(Scheduler::do_schedule(DispatchTime::After(3), None, 127, root(), call));
+			assert_ok!(Scheduler::do_schedule(
+				DispatchTime::After(3),
+				None,
+				127,
+				root(),
+				call.into()
+			));
 			run_to_block(5);
 			assert!(logger::log().is_empty());
 			run_to_block(6);
@@ -1009,7 +1330,13 @@ mod tests {
 			run_to_block(2);
 			let call = Call::Logger(LoggerCall::log { i: 42, weight: 1000 });
 			assert!(!<Test as frame_system::Config>::BaseCallFilter::contains(&call));
-			assert_ok!(Scheduler::do_schedule(DispatchTime::After(0), None, 127, root(), call));
+			assert_ok!(Scheduler::do_schedule(
+				DispatchTime::After(0),
+				None,
+				127,
+				root(),
+				call.into()
+			));
 			// Will trigger on the next block.
 			run_to_block(3);
 			assert_eq!(logger::log(), vec![(root(), 42u32)]);
@@ -1027,7 +1354,7 @@ mod tests {
 				Some((3, 3)),
 				127,
 				root(),
-				Call::Logger(logger::Call::log { i: 42, weight: 1000 })
+				Call::Logger(logger::Call::log { i: 42, weight: 1000 }).into()
 			));
 			run_to_block(3);
 			assert!(logger::log().is_empty());
@@ -1052,7 +1379,8 @@ mod tests {
 			let call = Call::Logger(LoggerCall::log { i: 42, weight: 1000 });
 			assert!(!<Test as frame_system::Config>::BaseCallFilter::contains(&call));
 			assert_eq!(
-				Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), call).unwrap(),
+				Scheduler::do_schedule(DispatchTime::At(4), None, 127, root(), call.into())
+					.unwrap(),
 				(4, 0)
 			);
 
@@ -1089,7 +1417,7 @@ mod tests {
 					None,
 					127,
 					root(),
-					call
+					call.into()
 				)
 				.unwrap(),
 				(4, 0)
@@ -1131,7 +1459,7 @@ mod tests {
 					Some((3, 3)),
 					127,
 					root(),
-					call
+					call.into()
 				)
 				.unwrap(),
 				(4, 0)
@@ -1184,7 +1512,7 @@ mod tests {
 				None,
 				127,
 				root(),
-				Call::Logger(LoggerCall::log { i: 69, weight: 1000 }),
+				Call::Logger(LoggerCall::log { i: 69, weight: 1000 }).into(),
 			)
 			.unwrap();
 			let i = Scheduler::do_schedule(
@@ -1192,7 +1520,7 @@ mod tests {
 				None,
 				127,
 				root(),
-				Call::Logger(LoggerCall::log { i: 42, weight: 1000 }),
+				Call::Logger(LoggerCall::log { i: 42, weight: 1000 }).into(),
 			)
 			.unwrap();
 			run_to_block(3);
@@ -1214,7 +1542,7 @@ mod tests {
 				Some((3, 3)),
 				127,
 				root(),
-				Call::Logger(LoggerCall::log { i: 42, weight: 1000 }),
+				Call::Logger(LoggerCall::log { i: 42, weight: 1000 }).into(),
 			)
 			.unwrap();
 			// same id results in error.
@@ -1224,7 +1552,7 @@ mod tests {
 				None,
 				127,
 				root(),
-				Call::Logger(LoggerCall::log { i: 69, weight: 1000 })
+				Call::Logger(LoggerCall::log { i: 69, weight: 1000 }).into()
 			)
 			.is_err());
 			// different id is ok.
@@ -1234,7 +1562,7 @@ mod tests {
 				None,
 				127,
 				root(),
-				Call::Logger(LoggerCall::log { i: 69, weight: 1000 }),
+				Call::Logger(LoggerCall::log { i: 69, weight: 1000 }).into(),
 			)
 			.unwrap();
 			run_to_block(3);
@@ -1257,6 +1585,7 @@ mod tests {
 				127,
 				root(),
 				Call::Logger(LoggerCall::log { i: 42, weight: MaximumSchedulerWeight::get() / 2 })
+					.into()
 			));
 			assert_ok!(Scheduler::do_schedule(
 				DispatchTime::At(4),
@@ -1264,6 +1593,7 @@ mod tests {
 				127,
 				root(),
 				Call::Logger(LoggerCall::log { i: 69, weight: MaximumSchedulerWeight::get() / 2 })
+					.into()
 			));
 			// 69 and 42 do not fit together
 			run_to_block(4);
@@ -1282,6 +1612,7 @@ mod tests {
 				0,
 				root(),
 				Call::Logger(LoggerCall::log { i: 42, weight: MaximumSchedulerWeight::get() / 2 })
+					.into()
 			));
 			assert_ok!(Scheduler::do_schedule(
 				DispatchTime::At(4),
@@ -1289,6 +1620,7 @@ mod tests {
 				0,
 				root(),
 				Call::Logger(LoggerCall::log { i: 69, weight: MaximumSchedulerWeight::get() / 2 })
+					.into()
 			));
 			// With base weights, 69 and 42 should not fit together, but do because of hard
 			// deadlines
@@ -1306,6 +1638,7 @@ mod tests {
 				1,
 				root(),
 				Call::Logger(LoggerCall::log { i: 42, weight: MaximumSchedulerWeight::get() / 2 })
+					.into()
 			));
 			assert_ok!(Scheduler::do_schedule(
 				DispatchTime::At(4),
@@ -1313,6 +1646,7 @@ mod tests {
 				0,
 				root(),
 				Call::Logger(LoggerCall::log { i: 69, weight: MaximumSchedulerWeight::get() / 2 })
+					.into()
 			));
 			run_to_block(4);
 			assert_eq!(logger::log(), vec![(root(), 69u32), (root(), 42u32)]);
@@ -1328,6 +1662,7 @@ mod tests {
 				255,
 				root(),
 				Call::Logger(LoggerCall::log { i: 42, weight: MaximumSchedulerWeight::get() / 3 })
+					.into()
 			));
 			assert_ok!(Scheduler::do_schedule(
 				DispatchTime::At(4),
@@ -1335,6 +1670,7 @@ mod tests {
 				127,
 				root(),
 				Call::Logger(LoggerCall::log { i: 69, weight: MaximumSchedulerWeight::get() / 2 })
+					.into()
 			));
 			assert_ok!(Scheduler::do_schedule(
 				DispatchTime::At(4),
@@ -1345,6 +1681,7 @@ mod tests {
 					i: 2600,
 					weight: MaximumSchedulerWeight::get() / 2
 				})
+				.into()
 			));
 
 			// 2600 does not fit with 69 or 42, but has higher priority, so will go through
@@ -1374,6 +1711,7 @@ mod tests {
 				255,
 				root(),
 				Call::Logger(LoggerCall::log { i: 3, weight: MaximumSchedulerWeight::get() / 3 })
+					.into()
 			));
 			// Anon Periodic
 			assert_ok!(Scheduler::do_schedule(
@@ -1382,6 +1720,7 @@ mod tests {
 				128,
 				root(),
 				Call::Logger(LoggerCall::log { i: 42, weight: MaximumSchedulerWeight::get() / 3 })
+					.into()
 			));
 			// Anon
 			assert_ok!(Scheduler::do_schedule(
@@ -1390,6 +1729,7 @@ mod tests {
 				127,
 				root(),
 				Call::Logger(LoggerCall::log { i: 69, weight: MaximumSchedulerWeight::get() / 2 })
+					.into()
 			));
 			// Named Periodic
 			assert_ok!(Scheduler::do_schedule_named(
@@ -1402,6 +1742,7 @@ mod tests {
 					i: 2600,
 					weight: MaximumSchedulerWeight::get() / 2
 				})
+				.into()
 			));
 
 			// Will include the named periodic only
@@ -1409,9 +1750,10 @@ mod tests {
 			let call_weight = MaximumSchedulerWeight::get() / 2;
 			assert_eq!(
 				actual_weight,
-				call_weight
-					+ base_weight + base_multiplier
-					+ named_multiplier + periodic_multiplier
+				call_weight +
+					base_weight + base_multiplier +
+					named_multiplier +
+					periodic_multiplier
 			);
 			assert_eq!(logger::log(), vec![(root(), 2600u32)]);
 
@@ -1445,8 +1787,10 @@ mod tests {
 	#[test]
 	fn root_calls_works() {
 		new_test_ext().execute_with(|| {
-			let call = Box::new(Call::Logger(LoggerCall::log { i: 69, weight: 1000 }));
-			let call2 = Box::new(Call::Logger(LoggerCall::log { i: 42, weight: 1000 }));
+			let call =
+				Box::new(MaybeHashed::Value(Call::Logger(LoggerCall::log { i: 69, weight: 1000 })));
+			let call2 =
+				Box::new(MaybeHashed::Value(Call::Logger(LoggerCall::log { i: 42, weight: 1000 })));
 			assert_ok!(Scheduler::schedule_named(
 				Origin::root(),
 				1u32.encode(),
@@ -1473,8 +1817,10 @@ mod tests {
 		new_test_ext().execute_with(|| {
 			run_to_block(3);
 
-			let call = Box::new(Call::Logger(LoggerCall::log { i: 69, weight: 1000 }));
-			let call2 = Box::new(Call::Logger(LoggerCall::log { i: 42, weight: 1000 }));
+			let call =
+				Box::new(MaybeHashed::Value(Call::Logger(LoggerCall::log { i: 69, weight: 1000 })));
+			let call2 =
+				Box::new(MaybeHashed::Value(Call::Logger(LoggerCall::log { i: 42, weight: 1000 })));
 
 			assert_err!(
 				Scheduler::schedule_named(Origin::root(), 1u32.encode(), 2, None, 127, call),
@@ -1496,8 +1842,10 @@ mod tests {
 	#[test]
 	fn should_use_orign() {
 		new_test_ext().execute_with(|| {
-			let call = Box::new(Call::Logger(LoggerCall::log { i: 69, weight: 1000 }));
-			let call2 = Box::new(Call::Logger(LoggerCall::log { i: 42, weight: 1000 }));
+			let call =
+				Box::new(MaybeHashed::Value(Call::Logger(LoggerCall::log { i: 69, weight: 1000 })));
+			let call2 =
+				Box::new(MaybeHashed::Value(Call::Logger(LoggerCall::log { i: 42, weight: 1000 })));
 			assert_ok!(Scheduler::schedule_named(
 				system::RawOrigin::Signed(1).into(),
 				1u32.encode(),
@@ -1528,8 +1876,10 @@ mod tests {
 	#[test]
 	fn should_check_orign() {
 		new_test_ext().execute_with(|| {
-			let call = Box::new(Call::Logger(LoggerCall::log { i: 69, weight: 1000 }));
-			let call2 = Box::new(Call::Logger(LoggerCall::log { i: 42, weight: 1000 }));
+			let call =
+				Box::new(MaybeHashed::Value(Call::Logger(LoggerCall::log { i: 69, weight: 1000 })));
+			let call2 =
+				Box::new(MaybeHashed::Value(Call::Logger(LoggerCall::log { i: 42, weight: 1000 })));
 			assert_noop!(
 				Scheduler::schedule_named(
 					system::RawOrigin::Signed(2).into(),
@@ -1551,10 +1901,15 @@ mod tests {
 	#[test]
 	fn should_check_orign_for_cancel() {
 		new_test_ext().execute_with(|| {
-			let call =
-				Box::new(Call::Logger(LoggerCall::log_without_filter { i: 69, weight: 1000 }));
+			let call = Box::new(MaybeHashed::Value(Call::Logger(LoggerCall::log_without_filter {
+				i: 69,
+				weight: 1000,
+			})));
 			let call2 =
-				Box::new(Call::Logger(LoggerCall::log_without_filter { i: 42, weight: 1000 }));
+				Box::new(MaybeHashed::Value(Call::Logger(LoggerCall::log_without_filter {
+					i: 42,
+					weight: 1000,
+				})));
 			assert_ok!(Scheduler::schedule_named(
 				system::RawOrigin::Signed(1).into(),
 				1u32.encode(),
@@ -1596,7 +1951,7 @@ mod tests {
 	}
 
 	#[test]
-	fn migration_to_v2_works() {
+	fn migration_to_v3_works() {
 		new_test_ext().execute_with(|| {
 			for i in 0..3u64 {
 				let k = i.twox_64_concat();
@@ -1620,7 +1975,7 @@ mod tests {
 
 			assert_eq!(StorageVersion::<Test>::get(), Releases::V1);
 
-			assert!(Scheduler::migrate_v1_to_t2());
+			Scheduler::migrate_v1_to_v3();
 
 			assert_eq_uvec!(
 				Agenda::<Test>::iter().collect::<Vec<_>>(),
@@ -1628,19 +1983,19 @@ mod tests {
 					(
 						0,
 						vec![
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: None,
 								priority: 10,
-								call: Call::Logger(LoggerCall::log { i: 96, weight: 100 }),
+								call: Call::Logger(LoggerCall::log { i: 96, weight: 100 }).into(),
 								maybe_periodic: None,
 								origin: root(),
 								_phantom: PhantomData::<u64>::default(),
 							}),
 							None,
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: Some(b"test".to_vec()),
 								priority: 123,
-								call: Call::Logger(LoggerCall::log { i: 69, weight: 1000 }),
+								call: Call::Logger(LoggerCall::log { i: 69, weight: 1000 }).into(),
 								maybe_periodic: Some((456u64, 10)),
 								origin: root(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1650,19 +2005,19 @@ mod tests {
 					(
 						1,
 						vec![
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: None,
 								priority: 11,
-								call: Call::Logger(LoggerCall::log { i: 96, weight: 100 }),
+								call: Call::Logger(LoggerCall::log { i: 96, weight: 100 }).into(),
 								maybe_periodic: None,
 								origin: root(),
 								_phantom: PhantomData::<u64>::default(),
 							}),
 							None,
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: Some(b"test".to_vec()),
 								priority: 123,
-								call: Call::Logger(LoggerCall::log { i: 69, weight: 1000 }),
+								call: Call::Logger(LoggerCall::log { i: 69, weight: 1000 }).into(),
 								maybe_periodic: Some((456u64, 10)),
 								origin: root(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1672,19 +2027,19 @@ mod tests {
 					(
 						2,
 						vec![
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: None,
 								priority: 12,
-								call: Call::Logger(LoggerCall::log { i: 96, weight: 100 }),
+								call: Call::Logger(LoggerCall::log { i: 96, weight: 100 }).into(),
 								maybe_periodic: None,
 								origin: root(),
 								_phantom: PhantomData::<u64>::default(),
 							}),
 							None,
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: Some(b"test".to_vec()),
 								priority: 123,
-								call: Call::Logger(LoggerCall::log { i: 69, weight: 1000 }),
+								call: Call::Logger(LoggerCall::log { i: 69, weight: 1000 }).into(),
 								maybe_periodic: Some((456u64, 10)),
 								origin: root(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1694,7 +2049,113 @@ mod tests {
 				]
 			);
 
-			assert_eq!(StorageVersion::<Test>::get(), Releases::V2);
+			assert_eq!(StorageVersion::<Test>::get(), Releases::V3);
+		});
+	}
+
+	#[test]
+	fn migration_v2_to_v3_works() {
+		new_test_ext().execute_with(|| {
+			for i in 0..3u64 {
+				let k = i.twox_64_concat();
+				let old = vec![
+					Some(ScheduledV2Of::<Test> {
+						maybe_id: None,
+						priority: i as u8 + 10,
+						call: Call::Logger(LoggerCall::log { i: 96, weight: 100 }),
+						maybe_periodic: None,
+						origin: root(),
+						_phantom: PhantomData::<u64>::default(),
+					}),
+					None,
+					Some(ScheduledV2Of::<Test> {
+						maybe_id: Some(b"test".to_vec()),
+						priority: 123,
+						call: Call::Logger(LoggerCall::log { i: 69, weight: 1000 }),
+						maybe_periodic: Some((456u64, 10)),
+						origin: root(),
+						_phantom: PhantomData::<u64>::default(),
+					}),
+				];
+				frame_support::migration::put_storage_value(b"Scheduler", b"Agenda", &k, old);
+			}
+			StorageVersion::<Test>::put(Releases::V2);
+
+			Scheduler::migrate_v2_to_v3();
+
+			assert_eq_uvec!(
+				Agenda::<Test>::iter().collect::<Vec<_>>(),
+				vec![
+					(
+						0,
+						vec![
+							Some(ScheduledV3 {
+								maybe_id: None,
+								priority: 10,
+								call: Call::Logger(LoggerCall::log { i: 96, weight: 100 }).into(),
+								maybe_periodic: None,
+								origin: root(),
+								_phantom: PhantomData::<u64>::default(),
+							}),
+							None,
+							Some(ScheduledV3 {
+								maybe_id: Some(b"test".to_vec()),
+								priority: 123,
+								call: Call::Logger(LoggerCall::log { i: 69, weight: 1000 }).into(),
+								maybe_periodic: Some((456u64, 10)),
+								origin: root(),
+								_phantom: PhantomData::<u64>::default(),
+							}),
+						]
+					),
+					(
+						1,
+						vec![
+							Some(ScheduledV3 {
+								maybe_id: None,
+								priority: 11,
+								call: Call::Logger(LoggerCall::log { i: 96, weight: 100 }).into(),
+								maybe_periodic: None,
+								origin: root(),
+								_phantom: PhantomData::<u64>::default(),
+							}),
+							None,
+							Some(ScheduledV3 {
+								maybe_id: Some(b"test".to_vec()),
+								priority: 123,
+								call: Call::Logger(LoggerCall::log { i: 69, weight: 1000 }).into(),
+								maybe_periodic: Some((456u64, 10)),
+								origin: root(),
+								_phantom: PhantomData::<u64>::default(),
+							}),
+						]
+					),
+					(
+						2,
+						vec![
+							Some(ScheduledV3 {
+								maybe_id: None,
+								priority: 12,
+								call: Call::Logger(LoggerCall::log { i: 96, weight: 100 }).into(),
+								maybe_periodic: None,
+								origin: root(),
+								_phantom: PhantomData::<u64>::default(),
+							}),
+							None,
+							Some(ScheduledV3 {
+								maybe_id: Some(b"test".to_vec()),
+								priority: 123,
+								call: Call::Logger(LoggerCall::log { i: 69, weight: 1000 }).into(),
+								maybe_periodic: Some((456u64, 10)),
+								origin: root(),
+								_phantom: PhantomData::<u64>::default(),
+							}),
+						]
+					)
+				]
+			);
+
+			assert_eq!(StorageVersion::<Test>::get(), Releases::V3);
 		});
 	}
 
@@ -1703,11 +2164,11 @@ mod tests {
 		new_test_ext().execute_with(|| {
 			for i in 0..3u64 {
 				let k = i.twox_64_concat();
-				let old: Vec<Option<Scheduled<_, _, u32, u64>>> = vec![
+				let old: Vec<Option<Scheduled<CallOrHashOf<Test>, u64, u32, u64>>> = vec![
 					Some(Scheduled {
 						maybe_id: None,
 						priority: i as u8 + 10,
-						call: Call::Logger(LoggerCall::log { i: 96, weight: 100 }),
+						call: Call::Logger(LoggerCall::log { i: 96, weight: 100 }).into(),
 						origin: 3u32,
 						maybe_periodic: None,
 						_phantom: Default::default(),
@@ -1717,7 +2178,7 @@ mod tests {
 						maybe_id: Some(b"test".to_vec()),
 						priority: 123,
 						origin: 2u32,
-						call: Call::Logger(LoggerCall::log { i: 69, weight: 1000 }),
+						call: Call::Logger(LoggerCall::log { i: 69, weight: 1000 }).into(),
 						maybe_periodic: Some((456u64, 10)),
 						_phantom: Default::default(),
 					}),
@@ -1743,19 +2204,19 @@ mod tests {
 					(
 						0,
 						vec![
-							Some(ScheduledV2::<_, _, OriginCaller, u64> {
+							Some(ScheduledV3::<CallOrHashOf<Test>, u64, OriginCaller, u64> {
 								maybe_id: None,
 								priority: 10,
-								call: Call::Logger(LoggerCall::log { i: 96, weight: 100 }),
+								call: Call::Logger(LoggerCall::log { i: 96, weight: 100 }).into(),
 								maybe_periodic: None,
 								origin: system::RawOrigin::Root.into(),
 								_phantom: PhantomData::<u64>::default(),
 							}),
 							None,
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: Some(b"test".to_vec()),
 								priority: 123,
-								call: Call::Logger(LoggerCall::log { i: 69, weight: 1000 }),
+								call: Call::Logger(LoggerCall::log { i: 69, weight: 1000 }).into(),
 								maybe_periodic: Some((456u64, 10)),
 								origin: system::RawOrigin::None.into(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1765,19 +2226,19 @@ mod tests {
 					(
 						1,
 						vec![
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: None,
 								priority: 11,
-								call: Call::Logger(LoggerCall::log { i: 96, weight: 100 }),
+								call: Call::Logger(LoggerCall::log { i: 96, weight: 100 }).into(),
 								maybe_periodic: None,
 								origin: system::RawOrigin::Root.into(),
 								_phantom: PhantomData::<u64>::default(),
 							}),
 							None,
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: Some(b"test".to_vec()),
 								priority: 123,
-								call: Call::Logger(LoggerCall::log { i: 69, weight: 1000 }),
+								call: Call::Logger(LoggerCall::log { i: 69, weight: 1000 }).into(),
 								maybe_periodic: Some((456u64, 10)),
 								origin: system::RawOrigin::None.into(),
 								_phantom: PhantomData::<u64>::default(),
@@ -1787,19 +2248,19 @@ mod tests {
 					(
 						2,
 						vec![
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: None,
 								priority: 12,
-								call: Call::Logger(LoggerCall::log { i: 96, weight: 100 }),
+								call: Call::Logger(LoggerCall::log { i: 96, weight: 100 }).into(),
 								maybe_periodic: None,
 								origin: system::RawOrigin::Root.into(),
 								_phantom: PhantomData::<u64>::default(),
 							}),
 							None,
-							Some(ScheduledV2 {
+							Some(ScheduledV3 {
 								maybe_id: Some(b"test".to_vec()),
 								priority: 123,
-								call: Call::Logger(LoggerCall::log { i: 69, weight: 1000 }),
+								call: Call::Logger(LoggerCall::log { i: 69, weight: 1000 }).into(),
 								maybe_periodic: Some((456u64, 10)),
 								origin: system::RawOrigin::None.into(),
 								_phantom: PhantomData::<u64>::default(),
@@ -524,7 +524,15 @@ where
 	///
 	/// Slashes from `active` funds first, and then `unlocking`, starting with the
 	/// chunks that are closest to unlocking.
-	fn slash(&mut self, mut value: Balance, minimum_balance: Balance) -> Balance {
+	///
+	/// The era and remaining balance of every unlocking chunk touched by the slash are recorded in
+	/// `slashed_unlocking`, such that they can be reported to [`Config::OnStakerSlash`].
+	fn slash(
+		&mut self,
+		mut value: Balance,
+		minimum_balance: Balance,
+		slashed_unlocking: &mut BTreeMap<EraIndex, Balance>,
+	) -> Balance {
 		let pre_total = self.total;
 		let total = &mut self.total;
 		let active = &mut self.active;
@@ -553,7 +561,10 @@ where
 			.unlocking
 			.iter_mut()
 			.map(|chunk| {
-				slash_out_of(total, &mut chunk.value, &mut value);
+				if !value.is_zero() {
+					slash_out_of(total, &mut chunk.value, &mut value);
+					slashed_unlocking.insert(chunk.era, Zero::zero());
+				}
 				chunk.value
 			})
 			.take_while(|value| value.is_zero()) // Take all fully-consumed chunks out.
@@ -562,6 +573,14 @@ where
 		// Kill all drained chunks.
 		let _ = self.unlocking.drain(..i);
 
+		// Report the remaining balance of every era that was touched, summing up all the chunks
+		// that unlock in the same era.
+		for chunk in self.unlocking.iter() {
+			if let Some(remaining) = slashed_unlocking.get_mut(&chunk.era) {
+				*remaining += chunk.value;
+			}
+		}
+
 		pre_total.saturating_sub(*total)
 	}
 }
@@ -275,6 +275,7 @@ impl crate::pallet::pallet::Config for Test {
 	type WeightInfo = ();
 	// NOTE: consider a macro and use `UseNominatorsMap<Self>` as well.
 	type SortedListProvider = BagsList;
+	type OnStakerSlash = ();
 }
 
 impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
@@ -526,38 +527,14 @@ impl ExtBuilder {
 }
 
 fn post_conditions() {
+	assert_eq!(Staking::do_try_state(), Ok(()));
 	check_nominators();
-	check_exposures();
 	check_ledgers();
-	check_count();
-}
-
-fn check_count() {
-	let nominator_count = Nominators::<Test>::iter().count() as u32;
-	let validator_count = Validators::<Test>::iter().count() as u32;
-	assert_eq!(nominator_count, CounterForNominators::<Test>::get());
-	assert_eq!(validator_count, CounterForValidators::<Test>::get());
-
-	// the voters that the `SortedListProvider` list is storing for us.
-	let external_voters = <Test as Config>::SortedListProvider::count();
-	assert_eq!(external_voters, nominator_count);
 }
 
 fn check_ledgers() {
-	// check the ledger of all stakers.
-	Bonded::<Test>::iter().for_each(|(_, ctrl)| assert_ledger_consistent(ctrl))
-}
-
-fn check_exposures() {
-	// a check per validator to ensure the exposure struct is always sane.
-	let era = active_era();
-	ErasStakers::<Test>::iter_prefix_values(era).for_each(|expo| {
-		assert_eq!(
-			expo.total as u128,
-			expo.own as u128 + expo.others.iter().map(|e| e.value as u128).sum::<u128>(),
-			"wrong total exposure.",
-		);
-	})
+	// the consistency of the ledgers themselves is part of `do_try_state`.
+	Bonded::<Test>::iter().for_each(|(_, ctrl)| assert_ledger_above_existential_deposit(ctrl))
 }
 
 fn check_nominators() {
@@ -611,11 +588,8 @@ fn assert_is_stash(acc: AccountId) {
 	assert!(Staking::bonded(&acc).is_some(), "Not a stash.");
 }
 
-fn assert_ledger_consistent(ctrl: AccountId) {
-	// ensures ledger.total == ledger.active + sum(ledger.unlocking).
+fn assert_ledger_above_existential_deposit(ctrl: AccountId) {
 	let ledger = Staking::ledger(ctrl).expect("Not a controller.");
-	let real_total: Balance = ledger.unlocking.iter().fold(ledger.active, |a, c| a + c.value);
-	assert_eq!(real_total, ledger.total);
 	assert!(
 		ledger.active >= Balances::minimum_balance() || ledger.active == 0,
 		"{}: active ledger amount ({}) must be greater than ED {}",
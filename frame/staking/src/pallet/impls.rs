@@ -29,15 +29,15 @@ use frame_support::{
 	},
 	weights::{Weight, WithPostDispatchInfo},
 };
-use frame_system::pallet_prelude::BlockNumberFor;
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
 use pallet_session::historical;
 use sp_runtime::{
-	traits::{Bounded, Convert, SaturatedConversion, Saturating, Zero},
-	Perbill,
+	traits::{Bounded, Convert, SaturatedConversion, Saturating, StaticLookup, Zero},
+	DispatchError, Perbill,
 };
 use sp_staking::{
 	offence::{DisableStrategy, OffenceDetails, OnOffenceHandler},
-	SessionIndex,
+	SessionIndex, StakingInterface,
 };
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
@@ -1320,3 +1320,132 @@ impl<T: Config> SortedListProvider<T::AccountId> for UseNominatorsMap<T> {
 		}
 	}
 }
+
+impl<T: Config> StakingInterface for Pallet<T> {
+	type AccountId = T::AccountId;
+	type Balance = BalanceOf<T>;
+	type LookupSource = <T::Lookup as StaticLookup>::Source;
+
+	fn minimum_bond() -> Self::Balance {
+		MinNominatorBond::<T>::get()
+	}
+
+	fn bonding_duration() -> EraIndex {
+		T::BondingDuration::get()
+	}
+
+	fn current_era() -> EraIndex {
+		Self::current_era().unwrap_or(Zero::zero())
+	}
+
+	fn active_stake(controller: &Self::AccountId) -> Option<Self::Balance> {
+		Self::ledger(controller).map(|l| l.active)
+	}
+
+	fn total_stake(controller: &Self::AccountId) -> Option<Self::Balance> {
+		Self::ledger(controller).map(|l| l.total)
+	}
+
+	fn bond_extra(stash: Self::AccountId, extra: Self::Balance) -> DispatchResult {
+		Self::bond_extra(RawOrigin::Signed(stash).into(), extra)
+	}
+
+	fn unbond(controller: Self::AccountId, value: Self::Balance) -> DispatchResult {
+		Self::unbond(RawOrigin::Signed(controller).into(), value)
+	}
+
+	fn chill(controller: Self::AccountId) -> DispatchResult {
+		Self::chill(RawOrigin::Signed(controller).into())
+	}
+
+	fn withdraw_unbonded(
+		controller: Self::AccountId,
+		num_slashing_spans: u32,
+	) -> Result<bool, DispatchError> {
+		Self::withdraw_unbonded(RawOrigin::Signed(controller.clone()).into(), num_slashing_spans)
+			.map(|_| !Ledger::<T>::contains_key(&controller))
+			.map_err(|with_post| with_post.error)
+	}
+
+	fn bond(
+		stash: Self::AccountId,
+		controller: Self::AccountId,
+		value: Self::Balance,
+		payee: Self::AccountId,
+	) -> DispatchResult {
+		Self::bond(
+			RawOrigin::Signed(stash).into(),
+			T::Lookup::unlookup(controller),
+			value,
+			RewardDestination::Account(payee),
+		)
+	}
+
+	fn nominate(
+		controller: Self::AccountId,
+		targets: Vec<Self::LookupSource>,
+	) -> DispatchResult {
+		Self::nominate(RawOrigin::Signed(controller).into(), targets)
+	}
+}
+
+#[cfg(any(feature = "try-runtime", test))]
+impl<T: Config> Pallet<T> {
+	/// Check the invariants of the staking storage items:
+	///
+	/// * the counters of validators and nominators are in sync with their maps, and the
+	///   `SortedListProvider` tracks exactly the nominators.
+	/// * the ledger of each bonded stash exists, belongs to it, and its total is the sum of its
+	///   active and unlocking chunks.
+	/// * the total of each exposure of the active era is the sum of its own and other stakes.
+	pub(crate) fn do_try_state() -> Result<(), &'static str> {
+		Self::check_count()?;
+		Self::check_ledgers()?;
+		Self::check_exposures()?;
+		T::SortedListProvider::sanity_check()
+	}
+
+	fn check_count() -> Result<(), &'static str> {
+		let nominator_count = Nominators::<T>::iter().count() as u32;
+		let validator_count = Validators::<T>::iter().count() as u32;
+		ensure!(
+			nominator_count == CounterForNominators::<T>::get(),
+			"nominator count does not match CounterForNominators"
+		);
+		ensure!(
+			validator_count == CounterForValidators::<T>::get(),
+			"validator count does not match CounterForValidators"
+		);
+		ensure!(
+			T::SortedListProvider::count() == nominator_count,
+			"the sorted list provider does not track exactly the nominators"
+		);
+		Ok(())
+	}
+
+	fn check_ledgers() -> Result<(), &'static str> {
+		Bonded::<T>::iter().try_for_each(|(stash, ctrl)| {
+			let ledger = Self::ledger(ctrl).ok_or("bonded stash has no ledger")?;
+			ensure!(ledger.stash == stash, "ledger does not belong to its bonded stash");
+			let real_total: BalanceOf<T> =
+				ledger.unlocking.iter().fold(ledger.active, |a, c| a.saturating_add(c.value));
+			ensure!(real_total == ledger.total, "ledger.total != ledger.active + unlocking");
+			Ok(())
+		})
+	}
+
+	fn check_exposures() -> Result<(), &'static str> {
+		let era = match Self::active_era() {
+			Some(era) => era.index,
+			None => return Ok(()),
+		};
+		ErasStakers::<T>::iter_prefix_values(era).try_for_each(|expo| {
+			let others = expo
+				.others
+				.iter()
+				.fold(Zero::zero(), |a: BalanceOf<T>, e| a.saturating_add(e.value));
+			ensure!(expo.own.saturating_add(others) == expo.total, "wrong total exposure");
+			Ok(())
+		})
+	}
+}
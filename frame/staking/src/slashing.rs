@@ -63,8 +63,8 @@ use sp_runtime::{
 	traits::{Saturating, Zero},
 	DispatchResult, RuntimeDebug,
 };
-use sp_staking::offence::DisableStrategy;
-use sp_std::vec::Vec;
+use sp_staking::{offence::DisableStrategy, OnStakerSlash};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
 
 /// The proportion of the slashing reward to be paid out on the first slashing detection.
 /// This is f_1 in the paper.
@@ -609,7 +609,8 @@ pub fn do_slash<T: Config>(
 		None => return, // nothing to do.
 	};
 
-	let value = ledger.slash(value, T::Currency::minimum_balance());
+	let mut slashed_unlocking = BTreeMap::new();
+	let value = ledger.slash(value, T::Currency::minimum_balance(), &mut slashed_unlocking);
 
 	if !value.is_zero() {
 		let (imbalance, missing) = T::Currency::slash(stash, value);
@@ -621,6 +622,7 @@ pub fn do_slash<T: Config>(
 		}
 
 		<Pallet<T>>::update_ledger(&controller, &ledger);
+		T::OnStakerSlash::on_slash(stash, ledger.active, &slashed_unlocking);
 
 		// trigger the event
 		<Pallet<T>>::deposit_event(super::Event::<T>::Slashed(stash.clone(), value));
@@ -4246,6 +4246,28 @@ fn count_check_works() {
 	})
 }
 
+#[test]
+fn try_state_detects_corrupted_counter() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(Staking::do_try_state(), Ok(()));
+
+		CounterForValidators::<Test>::mutate(|x| *x += 1);
+		assert_eq!(
+			Staking::do_try_state(),
+			Err("validator count does not match CounterForValidators")
+		);
+	})
+}
+
+#[test]
+fn try_state_detects_foreign_ledger() {
+	ExtBuilder::default().build().execute_with(|| {
+		// the ledger of controller 10 now claims to belong to stash 21.
+		Ledger::<Test>::mutate(10, |ledger| ledger.as_mut().unwrap().stash = 21);
+		assert_eq!(Staking::do_try_state(), Err("ledger does not belong to its bonded stash"));
+	})
+}
+
 #[test]
 fn min_bond_checks_work() {
 	ExtBuilder::default()
@@ -4522,6 +4544,30 @@ fn capped_stakers_works() {
 	})
 }
 
+#[test]
+fn ledger_slash_reports_remaining_unlocking_per_era() {
+	let mut ledger = StakingLedger::<AccountId, Balance> {
+		stash: 123,
+		total: 40,
+		active: 10,
+		unlocking: vec![
+			UnlockChunk { value: 10, era: 3 },
+			UnlockChunk { value: 10, era: 3 },
+			UnlockChunk { value: 10, era: 4 },
+		],
+		claimed_rewards: vec![],
+	};
+	let mut slashed_unlocking = sp_std::collections::btree_map::BTreeMap::new();
+
+	// the active balance is slashed first, then the chunk that is closest to unlocking.
+	assert_eq!(ledger.slash(15, 0, &mut slashed_unlocking), 15);
+	assert_eq!(ledger.active, 0);
+	assert_eq!(ledger.total, 25);
+
+	// both chunks of era 3 are reported together, era 4 was not touched.
+	assert_eq!(slashed_unlocking.into_iter().collect::<Vec<_>>(), vec![(3, 15)]);
+}
+
 mod sorted_list_provider {
 	use super::*;
 	use frame_election_provider_support::SortedListProvider;
@@ -2020,6 +2020,18 @@ macro_rules! decl_module {
 			$( $offchain )*
 		}
 
+		#[cfg(feature = "try-runtime")]
+		impl<$trait_instance: $system::Config + $trait_name $(<I>, $instance: $instantiable)?>
+			$crate::traits::TryState<<$trait_instance as $system::Config>::BlockNumber>
+			for $mod_type<$trait_instance $(, $instance)?> where $( $other_where_bounds )*
+		{
+			fn try_state(
+				_: <$trait_instance as $system::Config>::BlockNumber,
+			) -> Result<(), &'static str> {
+				Ok(())
+			}
+		}
+
 		$crate::decl_module! {
 			@impl_deposit_event
 			$mod_type<$trait_instance: $trait_name $(<I>, $instance: $instantiable)?>;
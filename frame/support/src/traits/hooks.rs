@@ -210,6 +210,26 @@ impl OnRuntimeUpgrade for Tuple {
 	}
 }
 
+/// Execute some checks to ensure the internal state of a pallet is consistent.
+///
+/// Usually, these checks should check all of the invariants that are expected to be held on all of
+/// the storage items of your pallet.
+#[cfg(feature = "try-runtime")]
+pub trait TryState<BlockNumber> {
+	/// Execute the state checks.
+	fn try_state(_: BlockNumber) -> Result<(), &'static str>;
+}
+
+#[cfg(feature = "try-runtime")]
+#[impl_for_tuples(30)]
+impl<BlockNumber: Clone> TryState<BlockNumber> for Tuple {
+	fn try_state(n: BlockNumber) -> Result<(), &'static str> {
+		let mut result = Ok(());
+		for_tuples!( #( result = result.and(Tuple::try_state(n.clone())); )* );
+		result
+	}
+}
+
 /// The pallet hooks trait. Implementing this lets you express some logic to execute.
 pub trait Hooks<BlockNumber> {
 	/// The block is being finalized. Implement to have something happen.
@@ -268,6 +288,17 @@ pub trait Hooks<BlockNumber> {
 		Ok(())
 	}
 
+	/// Execute the sanity checks of this pallet, per block.
+	///
+	/// It should focus on certain checks to ensure that the state is sensible. This is never
+	/// executed in a consensus code-path, therefore it can consume as much weight as it needs.
+	///
+	/// This hook is never meant to be executed on-chain but is meant to be used by testing tools.
+	#[cfg(feature = "try-runtime")]
+	fn try_state(_n: BlockNumber) -> Result<(), &'static str> {
+		Ok(())
+	}
+
 	/// Implementing this function on a module allows you to perform long-running tasks
 	/// that make (by default) validators generate transactions that feed results
 	/// of those long-running computations back on chain.
@@ -344,6 +375,31 @@ mod tests {
 		assert_eq!(<(Test, Test)>::on_runtime_upgrade(), 40);
 	}
 
+	#[cfg(feature = "try-runtime")]
+	#[test]
+	fn try_state_of_tuple_fails_if_any_fails() {
+		struct Ok1;
+		struct Err1;
+		impl TryState<u8> for Ok1 {
+			fn try_state(_n: u8) -> Result<(), &'static str> {
+				Ok(())
+			}
+		}
+		impl TryState<u8> for Err1 {
+			fn try_state(n: u8) -> Result<(), &'static str> {
+				if n > 1 {
+					Err("too late")
+				} else {
+					Ok(())
+				}
+			}
+		}
+
+		assert_eq!(<(Ok1, Ok1)>::try_state(2), Ok(()));
+		assert_eq!(<(Ok1, Err1, Ok1)>::try_state(1), Ok(()));
+		assert_eq!(<(Ok1, Err1, Ok1)>::try_state(2), Err("too late"));
+	}
+
 	#[test]
 	fn on_idle_round_robin_works() {
 		static mut ON_IDLE_INVOCATION_ORDER: sp_std::vec::Vec<&str> = sp_std::vec::Vec::new();
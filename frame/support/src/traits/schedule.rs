@@ -17,9 +17,10 @@
 
 //! Traits and associated utilities for scheduling dispatchables in FRAME.
 
+use super::PreimageProvider;
 use codec::{Codec, Decode, Encode, EncodeLike};
 use scale_info::TypeInfo;
-use sp_runtime::{DispatchError, RuntimeDebug};
+use sp_runtime::{traits::Saturating, DispatchError, RuntimeDebug};
 use sp_std::{fmt::Debug, prelude::*};
 
 /// Information relating to the period of a scheduled task. First item is the length of the
@@ -40,6 +41,16 @@ pub enum DispatchTime<BlockNumber> {
 	After(BlockNumber),
 }
 
+impl<BlockNumber: Saturating + Copy> DispatchTime<BlockNumber> {
+	/// Resolve this dispatch time into an absolute block number, relative to `since`.
+	pub fn evaluate(&self, since: BlockNumber) -> BlockNumber {
+		match &self {
+			Self::At(m) => *m,
+			Self::After(m) => m.saturating_add(since),
+		}
+	}
+}
+
 /// The highest priority. We invert the value so that normal sorting will place the highest
 /// priority at the beginning of the list.
 pub const HIGHEST_PRIORITY: Priority = 0;
@@ -49,86 +60,265 @@ pub const HARD_DEADLINE: Priority = 63;
 /// The lowest priority. Most stuff should be around here.
 pub const LOWEST_PRIORITY: Priority = 255;
 
-/// A type that can be used as a scheduler.
-pub trait Anon<BlockNumber, Call, Origin> {
-	/// An address which can be used for removing a scheduled task.
-	type Address: Codec + Clone + Eq + EncodeLike + Debug;
+/// Type representing an encodable value or the hash of the encoding of such a value.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum MaybeHashed<T, Hash> {
+	/// The value itself.
+	Value(T),
+	/// The hash of the encoded value which this value represents.
+	Hash(Hash),
+}
 
-	/// Schedule a dispatch to happen at the beginning of some block in the future.
-	///
-	/// This is not named.
-	fn schedule(
-		when: DispatchTime<BlockNumber>,
-		maybe_periodic: Option<Period<BlockNumber>>,
-		priority: Priority,
-		origin: Origin,
-		call: Call,
-	) -> Result<Self::Address, DispatchError>;
-
-	/// Cancel a scheduled task. If periodic, then it will cancel all further instances of that,
-	/// also.
-	///
-	/// Will return an error if the `address` is invalid.
-	///
-	/// NOTE: This guaranteed to work only *before* the point that it is due to be executed.
-	/// If it ends up being delayed beyond the point of execution, then it cannot be cancelled.
-	///
-	/// NOTE2: This will not work to cancel periodic tasks after their initial execution. For
-	/// that, you must name the task explicitly using the `Named` trait.
-	fn cancel(address: Self::Address) -> Result<(), ()>;
-
-	/// Reschedule a task. For one-off tasks, this dispatch is guaranteed to succeed
-	/// only if it is executed *before* the currently scheduled block. For periodic tasks,
-	/// this dispatch is guaranteed to succeed only before the *initial* execution; for
-	/// others, use `reschedule_named`.
-	///
-	/// Will return an error if the `address` is invalid.
-	fn reschedule(
-		address: Self::Address,
-		when: DispatchTime<BlockNumber>,
-	) -> Result<Self::Address, DispatchError>;
+impl<T, H> From<T> for MaybeHashed<T, H> {
+	fn from(t: T) -> Self {
+		MaybeHashed::Value(t)
+	}
+}
 
-	/// Return the next dispatch time for a given task.
-	///
-	/// Will return an error if the `address` is invalid.
-	fn next_dispatch_time(address: Self::Address) -> Result<BlockNumber, ()>;
+/// Reason why a `MaybeHashed` could not be turned into a value.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum LookupError {
+	/// A call of this hash was not known.
+	Unknown,
+	/// The preimage for this hash was known but could not be decoded into a `Call`.
+	BadFormat,
 }
 
-/// A type that can be used as a scheduler.
-pub trait Named<BlockNumber, Call, Origin> {
-	/// An address which can be used for removing a scheduled task.
-	type Address: Codec + Clone + Eq + EncodeLike + sp_std::fmt::Debug;
+impl<T: Decode, H> MaybeHashed<T, H> {
+	/// Returns a reference to the value, if this is not a hash.
+	pub fn as_value(&self) -> Option<&T> {
+		match &self {
+			Self::Value(c) => Some(c),
+			Self::Hash(_) => None,
+		}
+	}
 
-	/// Schedule a dispatch to happen at the beginning of some block in the future.
-	///
-	/// - `id`: The identity of the task. This must be unique and will return an error if not.
-	fn schedule_named(
-		id: Vec<u8>,
-		when: DispatchTime<BlockNumber>,
-		maybe_periodic: Option<Period<BlockNumber>>,
-		priority: Priority,
-		origin: Origin,
-		call: Call,
-	) -> Result<Self::Address, ()>;
-
-	/// Cancel a scheduled, named task. If periodic, then it will cancel all further instances
-	/// of that, also.
-	///
-	/// Will return an error if the `id` is invalid.
-	///
-	/// NOTE: This guaranteed to work only *before* the point that it is due to be executed.
-	/// If it ends up being delayed beyond the point of execution, then it cannot be cancelled.
-	fn cancel_named(id: Vec<u8>) -> Result<(), ()>;
-
-	/// Reschedule a task. For one-off tasks, this dispatch is guaranteed to succeed
-	/// only if it is executed *before* the currently scheduled block.
-	fn reschedule_named(
-		id: Vec<u8>,
-		when: DispatchTime<BlockNumber>,
-	) -> Result<Self::Address, DispatchError>;
-
-	/// Return the next dispatch time for a given task.
+	/// Returns a reference to the hash, if this is a hash.
+	pub fn as_hash(&self) -> Option<&H> {
+		match &self {
+			Self::Value(_) => None,
+			Self::Hash(h) => Some(h),
+		}
+	}
+
+	/// Request the preimage of the hash from `P`, if this is a hash.
+	pub fn ensure_requested<P: PreimageProvider<H>>(&self) {
+		match &self {
+			Self::Value(_) => (),
+			Self::Hash(hash) => P::request_preimage(hash),
+		}
+	}
+
+	/// Withdraw a previous request for the preimage of the hash from `P`, if this is a hash.
+	pub fn ensure_unrequested<P: PreimageProvider<H>>(&self) {
+		match &self {
+			Self::Value(_) => (),
+			Self::Hash(hash) => P::unrequest_preimage(hash),
+		}
+	}
+
+	/// Attempt to turn a hash into a value by looking its preimage up in `P`.
 	///
-	/// Will return an error if the `id` is invalid.
-	fn next_dispatch_time(id: Vec<u8>) -> Result<BlockNumber, ()>;
+	/// Returns the resolved (or unchanged) item, along with the hash if it was resolved by this
+	/// call, such that the caller may release its request for the preimage.
+	pub fn resolved<P: PreimageProvider<H>>(self) -> (Self, Option<H>) {
+		match self {
+			Self::Value(c) => (Self::Value(c), None),
+			Self::Hash(h) => {
+				let data = match P::get_preimage(&h) {
+					Some(p) => p,
+					None => return (Self::Hash(h), None),
+				};
+				match T::decode(&mut &data[..]) {
+					Ok(c) => (Self::Value(c), Some(h)),
+					Err(_) => (Self::Hash(h), None),
+				}
+			},
+		}
+	}
+}
+
+pub mod v1 {
+	use super::*;
+
+	/// A type that can be used as a scheduler.
+	pub trait Anon<BlockNumber, Call, Origin> {
+		/// An address which can be used for removing a scheduled task.
+		type Address: Codec + Clone + Eq + EncodeLike + Debug;
+
+		/// Schedule a dispatch to happen at the beginning of some block in the future.
+		///
+		/// This is not named.
+		fn schedule(
+			when: DispatchTime<BlockNumber>,
+			maybe_periodic: Option<Period<BlockNumber>>,
+			priority: Priority,
+			origin: Origin,
+			call: Call,
+		) -> Result<Self::Address, DispatchError>;
+
+		/// Cancel a scheduled task. If periodic, then it will cancel all further instances of that,
+		/// also.
+		///
+		/// Will return an error if the `address` is invalid.
+		///
+		/// NOTE: This guaranteed to work only *before* the point that it is due to be executed.
+		/// If it ends up being delayed beyond the point of execution, then it cannot be cancelled.
+		///
+		/// NOTE2: This will not work to cancel periodic tasks after their initial execution. For
+		/// that, you must name the task explicitly using the `Named` trait.
+		fn cancel(address: Self::Address) -> Result<(), ()>;
+
+		/// Reschedule a task. For one-off tasks, this dispatch is guaranteed to succeed
+		/// only if it is executed *before* the currently scheduled block. For periodic tasks,
+		/// this dispatch is guaranteed to succeed only before the *initial* execution; for
+		/// others, use `reschedule_named`.
+		///
+		/// Will return an error if the `address` is invalid.
+		fn reschedule(
+			address: Self::Address,
+			when: DispatchTime<BlockNumber>,
+		) -> Result<Self::Address, DispatchError>;
+
+		/// Return the next dispatch time for a given task.
+		///
+		/// Will return an error if the `address` is invalid.
+		fn next_dispatch_time(address: Self::Address) -> Result<BlockNumber, ()>;
+	}
+
+	/// A type that can be used as a scheduler.
+	pub trait Named<BlockNumber, Call, Origin> {
+		/// An address which can be used for removing a scheduled task.
+		type Address: Codec + Clone + Eq + EncodeLike + sp_std::fmt::Debug;
+
+		/// Schedule a dispatch to happen at the beginning of some block in the future.
+		///
+		/// - `id`: The identity of the task. This must be unique and will return an error if not.
+		fn schedule_named(
+			id: Vec<u8>,
+			when: DispatchTime<BlockNumber>,
+			maybe_periodic: Option<Period<BlockNumber>>,
+			priority: Priority,
+			origin: Origin,
+			call: Call,
+		) -> Result<Self::Address, ()>;
+
+		/// Cancel a scheduled, named task. If periodic, then it will cancel all further instances
+		/// of that, also.
+		///
+		/// Will return an error if the `id` is invalid.
+		///
+		/// NOTE: This guaranteed to work only *before* the point that it is due to be executed.
+		/// If it ends up being delayed beyond the point of execution, then it cannot be cancelled.
+		fn cancel_named(id: Vec<u8>) -> Result<(), ()>;
+
+		/// Reschedule a task. For one-off tasks, this dispatch is guaranteed to succeed
+		/// only if it is executed *before* the currently scheduled block.
+		fn reschedule_named(
+			id: Vec<u8>,
+			when: DispatchTime<BlockNumber>,
+		) -> Result<Self::Address, DispatchError>;
+
+		/// Return the next dispatch time for a given task.
+		///
+		/// Will return an error if the `id` is invalid.
+		fn next_dispatch_time(id: Vec<u8>) -> Result<BlockNumber, ()>;
+	}
 }
+
+pub mod v2 {
+	use super::*;
+
+	/// A type that can be used as a scheduler, with calls which may be given by hash.
+	pub type CallOrHashOf<Call, Hash> = MaybeHashed<Call, Hash>;
+
+	/// A type that can be used as a scheduler.
+	pub trait Anon<BlockNumber, Call, Origin> {
+		/// An address which can be used for removing a scheduled task.
+		type Address: Codec + Clone + Eq + EncodeLike + Debug;
+		/// A means of expressing a call by the hash of its encoded data.
+		type Hash;
+
+		/// Schedule a dispatch to happen at the beginning of some block in the future.
+		///
+		/// This is not named.
+		fn schedule(
+			when: DispatchTime<BlockNumber>,
+			maybe_periodic: Option<Period<BlockNumber>>,
+			priority: Priority,
+			origin: Origin,
+			call: CallOrHashOf<Call, Self::Hash>,
+		) -> Result<Self::Address, DispatchError>;
+
+		/// Cancel a scheduled task. If periodic, then it will cancel all further instances of that,
+		/// also.
+		///
+		/// Will return an error if the `address` is invalid.
+		///
+		/// NOTE: This guaranteed to work only *before* the point that it is due to be executed.
+		/// If it ends up being delayed beyond the point of execution, then it cannot be cancelled.
+		///
+		/// NOTE2: This will not work to cancel periodic tasks after their initial execution. For
+		/// that, you must name the task explicitly using the `Named` trait.
+		fn cancel(address: Self::Address) -> Result<(), ()>;
+
+		/// Reschedule a task. For one-off tasks, this dispatch is guaranteed to succeed
+		/// only if it is executed *before* the currently scheduled block. For periodic tasks,
+		/// this dispatch is guaranteed to succeed only before the *initial* execution; for
+		/// others, use `reschedule_named`.
+		///
+		/// Will return an error if the `address` is invalid.
+		fn reschedule(
+			address: Self::Address,
+			when: DispatchTime<BlockNumber>,
+		) -> Result<Self::Address, DispatchError>;
+
+		/// Return the next dispatch time for a given task.
+		///
+		/// Will return an error if the `address` is invalid.
+		fn next_dispatch_time(address: Self::Address) -> Result<BlockNumber, ()>;
+	}
+
+	/// A type that can be used as a scheduler.
+	pub trait Named<BlockNumber, Call, Origin> {
+		/// An address which can be used for removing a scheduled task.
+		type Address: Codec + Clone + Eq + EncodeLike + sp_std::fmt::Debug;
+		/// A means of expressing a call by the hash of its encoded data.
+		type Hash;
+
+		/// Schedule a dispatch to happen at the beginning of some block in the future.
+		///
+		/// - `id`: The identity of the task. This must be unique and will return an error if not.
+		fn schedule_named(
+			id: Vec<u8>,
+			when: DispatchTime<BlockNumber>,
+			maybe_periodic: Option<Period<BlockNumber>>,
+			priority: Priority,
+			origin: Origin,
+			call: CallOrHashOf<Call, Self::Hash>,
+		) -> Result<Self::Address, ()>;
+
+		/// Cancel a scheduled, named task. If periodic, then it will cancel all further instances
+		/// of that, also.
+		///
+		/// Will return an error if the `id` is invalid.
+		///
+		/// NOTE: This guaranteed to work only *before* the point that it is due to be executed.
+		/// If it ends up being delayed beyond the point of execution, then it cannot be cancelled.
+		fn cancel_named(id: Vec<u8>) -> Result<(), ()>;
+
+		/// Reschedule a task. For one-off tasks, this dispatch is guaranteed to succeed
+		/// only if it is executed *before* the currently scheduled block.
+		fn reschedule_named(
+			id: Vec<u8>,
+			when: DispatchTime<BlockNumber>,
+		) -> Result<Self::Address, DispatchError>;
+
+		/// Return the next dispatch time for a given task.
+		///
+		/// Will return an error if the `id` is invalid.
+		fn next_dispatch_time(id: Vec<u8>) -> Result<BlockNumber, ()>;
+	}
+}
+
+pub use v1::*;
@@ -21,10 +21,13 @@ use super::{
 	imbalance::{Imbalance, SignedImbalance},
 	misc::{Balance, ExistenceRequirement, WithdrawReasons},
 };
-use crate::dispatch::{DispatchError, DispatchResult};
+use crate::{
+	dispatch::{DispatchError, DispatchResult},
+	traits::Get,
+};
 use codec::MaxEncodedLen;
 use sp_runtime::traits::MaybeSerializeDeserialize;
-use sp_std::fmt::Debug;
+use sp_std::{fmt::Debug, marker::PhantomData};
 
 mod reservable;
 pub use reservable::{NamedReservableCurrency, ReservableCurrency};
@@ -199,3 +202,12 @@ pub trait Currency<AccountId> {
 		balance: Self::Balance,
 	) -> SignedImbalance<Self::Balance, Self::PositiveImbalance>;
 }
+
+/// A non-const `Get` implementation parameterised by a `Currency` impl which provides the result
+/// of `total_issuance`.
+pub struct TotalIssuanceOf<C: Currency<A>, A>(PhantomData<(C, A)>);
+impl<C: Currency<A>, A> Get<C::Balance> for TotalIssuanceOf<C, A> {
+	fn get() -> C::Balance {
+		C::total_issuance()
+	}
+}
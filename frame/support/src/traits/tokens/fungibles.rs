@@ -66,6 +66,9 @@ pub trait Inspect<AccountId> {
 		who: &AccountId,
 		amount: Self::Balance,
 	) -> WithdrawConsequence<Self::Balance>;
+
+	/// Returns `true` if an `asset` exists.
+	fn asset_exists(asset: Self::AssetId) -> bool;
 }
 
 /// Trait for reading metadata from a fungible asset.
@@ -255,26 +258,35 @@ pub trait Create<AccountId>: Inspect<AccountId> {
 }
 
 /// Trait for providing the ability to destroy existing fungible assets.
+///
+/// Destroying an asset may involve removing an unbounded number of accounts and approvals, so it
+/// happens in several steps: `start_destroy` first stops the asset from being used, then
+/// `destroy_accounts` and `destroy_approvals` remove its items in bounded batches, and finally
+/// `finish_destroy` removes the asset itself.
 pub trait Destroy<AccountId>: Inspect<AccountId> {
-	/// The witness data needed to destroy an asset.
-	type DestroyWitness;
-
-	/// Provide the appropriate witness data needed to destroy an asset.
-	fn get_destroy_witness(id: &Self::AssetId) -> Option<Self::DestroyWitness>;
-
-	/// Destroy an existing fungible asset.
+	/// Start the destruction of an existing fungible asset.
 	/// * `id`: The `AssetId` to be destroyed.
-	/// * `witness`: Any witness data that needs to be provided to complete the operation
-	///   successfully.
 	/// * `maybe_check_owner`: An optional account id that can be used to authorize the destroy
 	///   command. If not provided, we will not do any authorization checks before destroying the
 	///   asset.
+	fn start_destroy(id: Self::AssetId, maybe_check_owner: Option<AccountId>) -> DispatchResult;
+
+	/// Destroy up to `max_items` accounts of an asset whose destruction was started with
+	/// `start_destroy`.
 	///
-	/// If successful, this function will return the actual witness data from the destroyed asset.
-	/// This may be different than the witness data provided, and can be used to refund weight.
-	fn destroy(
-		id: Self::AssetId,
-		witness: Self::DestroyWitness,
-		maybe_check_owner: Option<AccountId>,
-	) -> Result<Self::DestroyWitness, DispatchError>;
+	/// Returns the number of accounts which were actually destroyed. This may need to be called
+	/// several times to destroy all accounts of the asset.
+	fn destroy_accounts(id: Self::AssetId, max_items: u32) -> Result<u32, DispatchError>;
+
+	/// Destroy up to `max_items` approvals of an asset whose destruction was started with
+	/// `start_destroy`.
+	///
+	/// Returns the number of approvals which were actually destroyed. This may need to be called
+	/// several times to destroy all approvals of the asset.
+	fn destroy_approvals(id: Self::AssetId, max_items: u32) -> Result<u32, DispatchError>;
+
+	/// Complete the destruction of an asset whose destruction was started with `start_destroy`.
+	///
+	/// All accounts and approvals of the asset must have been destroyed beforehand.
+	fn finish_destroy(id: Self::AssetId) -> DispatchResult;
 }
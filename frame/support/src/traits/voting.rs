@@ -18,7 +18,14 @@
 //! Traits and associated data structures concerned with voting, and moving between tokens and
 //! votes.
 
-use sp_arithmetic::traits::{SaturatedConversion, UniqueSaturatedFrom, UniqueSaturatedInto};
+use crate::dispatch::{DispatchError, Parameter};
+use codec::HasCompact;
+use sp_arithmetic::{
+	traits::{SaturatedConversion, UniqueSaturatedFrom, UniqueSaturatedInto},
+	Perbill,
+};
+use sp_runtime::traits::Member;
+use sp_std::prelude::*;
 
 /// A trait similar to `Convert` to convert values from `B` an abstract balance type
 /// into u64 and back from u128. (This conversion is used in election and other places where complex
@@ -87,3 +94,93 @@ impl<B: UniqueSaturatedInto<u64> + UniqueSaturatedFrom<u128>> CurrencyToVote<B>
 		B::unique_saturated_from(value)
 	}
 }
+
+/// A tally of votes on a poll, as understood by the governance system that uses it.
+pub trait VoteTally<Votes> {
+	/// The number of aye votes.
+	fn ayes(&self) -> Votes;
+	/// The proportion of the total voting power which has expressed an opinion.
+	fn turnout(&self) -> Perbill;
+	/// The proportion of the opinionated votes which are in favour.
+	fn approval(&self) -> Perbill;
+	/// A tally which would pass any poll.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn unanimity() -> Self;
+	/// A tally which has exactly the given `turnout` and `approval`.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn from_requirements(turnout: Perbill, approval: Perbill) -> Self;
+}
+
+/// The status of a poll.
+pub enum PollStatus<Tally, Moment, Class> {
+	/// The poll does not exist or can no longer be voted on.
+	None,
+	/// The poll is ongoing; it has the given tally and is of the given class.
+	Ongoing(Tally, Class),
+	/// The poll is over. It ended at the given moment and was approved iff the flag is `true`.
+	Completed(Moment, bool),
+}
+
+impl<Tally, Moment, Class> PollStatus<Tally, Moment, Class> {
+	/// Returns the tally and class of the poll, if it is ongoing.
+	pub fn ensure_ongoing(self) -> Option<(Tally, Class)> {
+		match self {
+			Self::Ongoing(t, c) => Some((t, c)),
+			_ => None,
+		}
+	}
+}
+
+/// A system which manages a set of polls, each of which may be voted on with a `Tally`.
+pub trait Polling<Tally> {
+	/// The index by which a poll is identified.
+	type Index: Parameter + Member + Ord + PartialOrd + Copy + HasCompact;
+	/// The type in which votes are counted.
+	type Votes: Parameter + Member + Ord + PartialOrd + Copy + HasCompact;
+	/// The class of a poll. Polls of different classes may be voted on independently.
+	type Class: Parameter + Member + Ord + PartialOrd;
+	/// The type used to express time.
+	type Moment;
+
+	/// Provides a vec of values that `T` may take.
+	fn classes() -> Vec<Self::Class>;
+
+	/// `Some` if the referendum `index` can be voted on, along with the tally and class of
+	/// referendum.
+	///
+	/// Don't use this if you might mutate - use `try_access_poll` instead.
+	fn as_ongoing(index: Self::Index) -> Option<(Tally, Self::Class)>;
+
+	/// Access the status of the poll `index`, giving mutable access to its tally if it is
+	/// ongoing. Any changes to the tally are applied to the poll once `f` returns.
+	fn access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut Tally, Self::Moment, Self::Class>) -> R,
+	) -> R;
+
+	/// As `access_poll`, except that nothing is changed if `f` returns an error.
+	fn try_access_poll<R>(
+		index: Self::Index,
+		f: impl FnOnce(PollStatus<&mut Tally, Self::Moment, Self::Class>) -> Result<R, DispatchError>,
+	) -> Result<R, DispatchError>;
+
+	/// Create an ongoing majority-carries poll of given class lasting given period for the purpose
+	/// of benchmarking.
+	///
+	/// May return `Err` if it is impossible.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn create_ongoing(class: Self::Class) -> Result<Self::Index, ()>;
+
+	/// End the given ongoing poll and return the result.
+	///
+	/// Returns `Err` if `index` is not an ongoing poll.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn end_ongoing(index: Self::Index, approved: bool) -> Result<(), ()>;
+
+	/// The maximum amount of ongoing polls within any single class. By default this is practically
+	/// unlimited (`u32::max_value()`).
+	#[cfg(feature = "runtime-benchmarks")]
+	fn max_ongoing() -> (Self::Class, u32) {
+		(Self::classes().into_iter().next().expect("Always one class"), u32::max_value())
+	}
+}
@@ -27,6 +27,12 @@
 //! amount by converting the fee calculated by [`pallet-transaction-payment`] into the desired
 //! asset.
 //!
+//! The conversion is pluggable through
+//! [`BalanceConversion`](frame_support::traits::tokens::BalanceConversion). `pallet_assets`
+//! provides `BalanceToAssetBalance`, which converts using the ratio between the asset's
+//! `min_balance` and the native existential deposit. When the transaction turns out to use less
+//! weight than declared, the difference is refunded in the same asset.
+//!
 //! ## Integration
 
 //! This pallet wraps FRAME's transaction payment pallet and functions as a replacement. This means
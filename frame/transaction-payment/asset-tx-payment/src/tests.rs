@@ -153,6 +153,7 @@ impl pallet_transaction_payment::Config for Runtime {
 	type TransactionByteFee = TransactionByteFee;
 	type WeightToFee = WeightToFee;
 	type FeeMultiplierUpdate = ();
+	type FeeHistoryLength = ();
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
 }
 
@@ -160,6 +161,7 @@ parameter_types! {
 	pub const AssetDeposit: u64 = 2;
 	pub const MetadataDeposit: u64 = 0;
 	pub const StringLimit: u32 = 20;
+	pub const RemoveItemsLimit: u32 = 1000;
 }
 
 impl pallet_assets::Config for Runtime {
@@ -171,8 +173,10 @@ impl pallet_assets::Config for Runtime {
 	type AssetDeposit = AssetDeposit;
 	type MetadataDepositBase = MetadataDeposit;
 	type MetadataDepositPerByte = MetadataDeposit;
+	type AssetAccountDeposit = MetadataDeposit;
 	type ApprovalDeposit = MetadataDeposit;
 	type StringLimit = StringLimit;
+	type RemoveItemsLimit = RemoveItemsLimit;
 	type Freezer = ();
 	type Extra = ();
 	type WeightInfo = ();
@@ -21,8 +21,11 @@
 
 use codec::Codec;
 use sp_runtime::traits::MaybeDisplay;
+use sp_std::prelude::*;
 
-pub use pallet_transaction_payment::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
+pub use pallet_transaction_payment::{
+	FeeDetails, FeeHistoryItem, InclusionFee, Multiplier, RuntimeDispatchInfo,
+};
 
 sp_api::decl_runtime_apis! {
 	pub trait TransactionPaymentApi<Balance> where
@@ -31,4 +34,24 @@ sp_api::decl_runtime_apis! {
 		fn query_info(uxt: Block::Extrinsic, len: u32) -> RuntimeDispatchInfo<Balance>;
 		fn query_fee_details(uxt: Block::Extrinsic, len: u32) -> FeeDetails<Balance>;
 	}
+
+	pub trait TransactionPaymentCallApi<Balance, Call>
+	where
+		Balance: Codec + MaybeDisplay,
+		Call: Codec,
+	{
+		/// Query information of a dispatch class, weight, and fee of a given encoded `Call`.
+		fn query_call_info(call: Call, len: u32) -> RuntimeDispatchInfo<Balance>;
+
+		/// Query fee details of a given encoded `Call`.
+		fn query_call_fee_details(call: Call, len: u32) -> FeeDetails<Balance>;
+	}
+
+	pub trait TransactionPaymentFeeHistoryApi {
+		/// The normal-class weight and fee multiplier of the most recent blocks, oldest first.
+		fn fee_history() -> Vec<FeeHistoryItem>;
+
+		/// The fee multiplier that applies to the next block.
+		fn next_fee_multiplier() -> Multiplier;
+	}
 }
@@ -0,0 +1,132 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! RPC interface for querying the fees of a call before it is signed.
+
+pub use self::gen_client::Client as TransactionPaymentCallClient;
+use super::{into_rpc_fee_details, Error};
+use codec::{Codec, DecodeAll};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+pub use pallet_transaction_payment_rpc_runtime_api::TransactionPaymentCallApi as TransactionPaymentCallRuntimeApi;
+use pallet_transaction_payment_rpc_runtime_api::{FeeDetails, RuntimeDispatchInfo};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, MaybeDisplay},
+};
+use std::sync::Arc;
+
+#[rpc]
+pub trait TransactionPaymentCallApi<BlockHash, ResponseType> {
+	/// Query the dispatch info of an encoded call, as if it was submitted in a signed extrinsic
+	/// of `len` bytes.
+	#[rpc(name = "payment_queryCallInfo")]
+	fn query_call_info(
+		&self,
+		encoded_call: Bytes,
+		len: u32,
+		at: Option<BlockHash>,
+	) -> Result<ResponseType>;
+	/// Query the fee details of an encoded call, as if it was submitted in a signed extrinsic
+	/// of `len` bytes.
+	#[rpc(name = "payment_queryCallFeeDetails")]
+	fn query_call_fee_details(
+		&self,
+		encoded_call: Bytes,
+		len: u32,
+		at: Option<BlockHash>,
+	) -> Result<FeeDetails<NumberOrHex>>;
+}
+
+/// A struct that implements the [`TransactionPaymentCallApi`].
+///
+/// Unlike [`crate::TransactionPayment`], this needs to know the `Call` type of the runtime, as
+/// the encoded call is not wrapped in an extrinsic.
+pub struct TransactionPaymentCall<C, Block, Call> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<(Block, Call)>,
+}
+
+impl<C, Block, Call> TransactionPaymentCall<C, Block, Call> {
+	/// Create new `TransactionPaymentCall` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, Balance, Call>
+	TransactionPaymentCallApi<<Block as BlockT>::Hash, RuntimeDispatchInfo<Balance>>
+	for TransactionPaymentCall<C, Block, Call>
+where
+	Block: BlockT,
+	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: TransactionPaymentCallRuntimeApi<Block, Balance, Call>,
+	Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex>,
+	Call: Codec + Send + Sync + 'static,
+{
+	fn query_call_info(
+		&self,
+		encoded_call: Bytes,
+		len: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<RuntimeDispatchInfo<Balance>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let call = Call::decode_all(&mut &*encoded_call).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::DecodeError.into()),
+			message: "Unable to query call info.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+		api.query_call_info(&at, call, len).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query call info.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn query_call_fee_details(
+		&self,
+		encoded_call: Bytes,
+		len: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<FeeDetails<NumberOrHex>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		let call = Call::decode_all(&mut &*encoded_call).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::DecodeError.into()),
+			message: "Unable to query call fee details.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+		let fee_details = api.query_call_fee_details(&at, call, len).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query call fee details.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})?;
+
+		into_rpc_fee_details(fee_details)
+	}
+}
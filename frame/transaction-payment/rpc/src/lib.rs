@@ -17,7 +17,13 @@
 
 //! RPC interface for the transaction payment pallet.
 
-pub use self::gen_client::Client as TransactionPaymentClient;
+pub use self::{
+	call::{
+		TransactionPaymentCall, TransactionPaymentCallApi,
+		TransactionPaymentCallClient, TransactionPaymentCallRuntimeApi,
+	},
+	gen_client::Client as TransactionPaymentClient,
+};
 use codec::{Codec, Decode};
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
@@ -33,6 +39,8 @@ use sp_runtime::{
 };
 use std::sync::Arc;
 
+mod call;
+
 #[rpc]
 pub trait TransactionPaymentApi<BlockHash, ResponseType> {
 	#[rpc(name = "payment_queryInfo")]
@@ -130,25 +138,33 @@ where
 			data: Some(format!("{:?}", e).into()),
 		})?;
 
-		let try_into_rpc_balance = |value: Balance| {
-			value.try_into().map_err(|_| RpcError {
-				code: ErrorCode::InvalidParams,
-				message: format!("{} doesn't fit in NumberOrHex representation", value),
-				data: None,
-			})
-		};
-
-		Ok(FeeDetails {
-			inclusion_fee: if let Some(inclusion_fee) = fee_details.inclusion_fee {
-				Some(InclusionFee {
-					base_fee: try_into_rpc_balance(inclusion_fee.base_fee)?,
-					len_fee: try_into_rpc_balance(inclusion_fee.len_fee)?,
-					adjusted_weight_fee: try_into_rpc_balance(inclusion_fee.adjusted_weight_fee)?,
-				})
-			} else {
-				None
-			},
-			tip: Default::default(),
-		})
+		into_rpc_fee_details(fee_details)
 	}
 }
+
+/// Convert the balances of the given fee details into their RPC representation.
+fn into_rpc_fee_details<Balance>(fee_details: FeeDetails<Balance>) -> Result<FeeDetails<NumberOrHex>>
+where
+	Balance: MaybeDisplay + Copy + TryInto<NumberOrHex>,
+{
+	let try_into_rpc_balance = |value: Balance| {
+		value.try_into().map_err(|_| RpcError {
+			code: ErrorCode::InvalidParams,
+			message: format!("{} doesn't fit in NumberOrHex representation", value),
+			data: None,
+		})
+	};
+
+	Ok(FeeDetails {
+		inclusion_fee: if let Some(inclusion_fee) = fee_details.inclusion_fee {
+			Some(InclusionFee {
+				base_fee: try_into_rpc_balance(inclusion_fee.base_fee)?,
+				len_fee: try_into_rpc_balance(inclusion_fee.len_fee)?,
+				adjusted_weight_fee: try_into_rpc_balance(inclusion_fee.adjusted_weight_fee)?,
+			})
+		} else {
+			None
+		},
+		tip: Default::default(),
+	})
+}
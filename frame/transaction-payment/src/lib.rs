@@ -42,7 +42,8 @@
 //!   - The mapping between one unit of weight to one unit of fee via [`Config::WeightToFee`].
 //!   - A means of updating the fee for the next block, via defining a multiplier, based on the
 //!     final state of the chain at the end of the previous block. This can be configured via
-//!     [`Config::FeeMultiplierUpdate`]
+//!     [`Config::FeeMultiplierUpdate`]. [`SmoothedFeeAdjustment`] bases this on the blocks
+//!     recorded in [`FeeHistory`] instead, whose length is set by [`Config::FeeHistoryLength`].
 //!   - How the fees are paid via [`Config::OnChargeTransaction`].
 
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -76,7 +77,7 @@ mod types;
 
 pub use pallet::*;
 pub use payment::*;
-pub use types::{FeeDetails, InclusionFee, RuntimeDispatchInfo};
+pub use types::{FeeDetails, FeeHistoryItem, InclusionFee, RuntimeDispatchInfo};
 
 /// Fee multiplier.
 pub type Multiplier = FixedU128;
@@ -178,50 +179,112 @@ where
 	M: Get<Multiplier>,
 {
 	fn convert(previous: Multiplier) -> Multiplier {
-		// Defensive only. The multiplier in storage should always be at most positive. Nonetheless
-		// we recover here in case of errors, because any value below this would be stale and can
-		// never change.
-		let min_multiplier = M::get();
-		let previous = previous.max(min_multiplier);
-
-		let weights = T::BlockWeights::get();
-		// the computed ratio is only among the normal class.
-		let normal_max_weight = weights
-			.get(DispatchClass::Normal)
-			.max_total
-			.unwrap_or_else(|| weights.max_block);
 		let current_block_weight = <frame_system::Pallet<T>>::block_weight();
-		let normal_block_weight =
-			*current_block_weight.get(DispatchClass::Normal).min(&normal_max_weight);
+		let normal_block_weight = *current_block_weight.get(DispatchClass::Normal);
 
-		let s = S::get();
-		let v = V::get();
-
-		let target_weight = (s * normal_max_weight) as u128;
-		let block_weight = normal_block_weight as u128;
-
-		// determines if the first_term is positive
-		let positive = block_weight >= target_weight;
-		let diff_abs = block_weight.max(target_weight) - block_weight.min(target_weight);
+		targeted_multiplier::<T>(previous, normal_block_weight, S::get(), V::get(), M::get())
+	}
+}
 
-		// defensive only, a test case assures that the maximum weight diff can fit in Multiplier
-		// without any saturation.
-		let diff = Multiplier::saturating_from_rational(diff_abs, normal_max_weight.max(1));
-		let diff_squared = diff.saturating_mul(diff);
+/// Compute the next multiplier from the `previous` one, given the (possibly averaged) weight of the
+/// normal dispatch class `normal_block_weight`, the target block saturation `s`, the variability
+/// `v` and the minimum multiplier `min_multiplier`.
+///
+/// See [`TargetedFeeAdjustment`] for the formula.
+fn targeted_multiplier<T: frame_system::Config>(
+	previous: Multiplier,
+	normal_block_weight: Weight,
+	s: Perquintill,
+	v: Multiplier,
+	min_multiplier: Multiplier,
+) -> Multiplier {
+	// Defensive only. The multiplier in storage should always be at most positive. Nonetheless
+	// we recover here in case of errors, because any value below this would be stale and can
+	// never change.
+	let previous = previous.max(min_multiplier);
+
+	let weights = T::BlockWeights::get();
+	// the computed ratio is only among the normal class.
+	let normal_max_weight =
+		weights.get(DispatchClass::Normal).max_total.unwrap_or_else(|| weights.max_block);
+	let normal_block_weight = normal_block_weight.min(normal_max_weight);
+
+	let target_weight = (s * normal_max_weight) as u128;
+	let block_weight = normal_block_weight as u128;
+
+	// determines if the first_term is positive
+	let positive = block_weight >= target_weight;
+	let diff_abs = block_weight.max(target_weight) - block_weight.min(target_weight);
+
+	// defensive only, a test case assures that the maximum weight diff can fit in Multiplier
+	// without any saturation.
+	let diff = Multiplier::saturating_from_rational(diff_abs, normal_max_weight.max(1));
+	let diff_squared = diff.saturating_mul(diff);
+
+	let v_squared_2 = v.saturating_mul(v) / Multiplier::saturating_from_integer(2);
+
+	let first_term = v.saturating_mul(diff);
+	let second_term = v_squared_2.saturating_mul(diff_squared);
+
+	if positive {
+		let excess = first_term.saturating_add(second_term).saturating_mul(previous);
+		previous.saturating_add(excess).max(min_multiplier)
+	} else {
+		// Defensive-only: first_term > second_term. Safe subtraction.
+		let negative = first_term.saturating_sub(second_term).saturating_mul(previous);
+		previous.saturating_sub(negative).max(min_multiplier)
+	}
+}
 
-		let v_squared_2 = v.saturating_mul(v) / Multiplier::saturating_from_integer(2);
+/// A [`MultiplierUpdate`] which, like [`TargetedFeeAdjustment`], moves the multiplier towards a
+/// target block saturation, but compares the target against the average normal-class weight of
+/// the blocks recorded in [`FeeHistory`] rather than against the weight of the current block only.
+///
+/// The window is therefore [`Config::FeeHistoryLength`] blocks wide, including the block being
+/// finalized. A single full block among otherwise empty ones only moves the multiplier by its
+/// share of the window, which keeps the multiplier from oscillating under bursty load.
+///
+/// If no history is recorded, e.g. because `FeeHistoryLength` is zero, this behaves exactly like
+/// [`TargetedFeeAdjustment`] with the same parameters.
+pub struct SmoothedFeeAdjustment<T, S, V, M>(sp_std::marker::PhantomData<(T, S, V, M)>);
 
-		let first_term = v.saturating_mul(diff);
-		let second_term = v_squared_2.saturating_mul(diff_squared);
+impl<T, S, V, M> MultiplierUpdate for SmoothedFeeAdjustment<T, S, V, M>
+where
+	T: Config,
+	S: Get<Perquintill>,
+	V: Get<Multiplier>,
+	M: Get<Multiplier>,
+{
+	fn min() -> Multiplier {
+		M::get()
+	}
+	fn target() -> Perquintill {
+		S::get()
+	}
+	fn variability() -> Multiplier {
+		V::get()
+	}
+}
 
-		if positive {
-			let excess = first_term.saturating_add(second_term).saturating_mul(previous);
-			previous.saturating_add(excess).max(min_multiplier)
+impl<T, S, V, M> Convert<Multiplier, Multiplier> for SmoothedFeeAdjustment<T, S, V, M>
+where
+	T: Config,
+	S: Get<Perquintill>,
+	V: Get<Multiplier>,
+	M: Get<Multiplier>,
+{
+	fn convert(previous: Multiplier) -> Multiplier {
+		let history = FeeHistory::<T>::get();
+		let normal_block_weight = if history.is_empty() {
+			*<frame_system::Pallet<T>>::block_weight().get(DispatchClass::Normal)
 		} else {
-			// Defensive-only: first_term > second_term. Safe subtraction.
-			let negative = first_term.saturating_sub(second_term).saturating_mul(previous);
-			previous.saturating_sub(negative).max(min_multiplier)
-		}
+			let total = history
+				.iter()
+				.fold(0u128, |acc, item| acc.saturating_add(item.normal_weight as u128));
+			(total / history.len() as u128) as Weight
+		};
+
+		targeted_multiplier::<T>(previous, normal_block_weight, S::get(), V::get(), M::get())
 	}
 }
 
@@ -293,6 +356,13 @@ pub mod pallet {
 
 		/// Update the multiplier of the next block, based on the previous block's weight.
 		type FeeMultiplierUpdate: MultiplierUpdate;
+
+		/// The number of most recent blocks whose weight and fee multiplier are kept in
+		/// [`FeeHistory`].
+		///
+		/// This is also the width of the window that [`SmoothedFeeAdjustment`] averages over.
+		#[pallet::constant]
+		type FeeHistoryLength: Get<u32>;
 	}
 
 	#[pallet::extra_constants]
@@ -318,6 +388,18 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(super) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
 
+	/// The normal-class weight and fee multiplier of the most recent blocks.
+	///
+	/// This is a ring buffer of at most `FeeHistoryLength` items. Once it is full, the oldest item
+	/// is the one at [`FeeHistoryHead`]. Use [`Pallet::fee_history`] to read it in order.
+	#[pallet::storage]
+	pub type FeeHistory<T: Config> =
+		StorageValue<_, BoundedVec<FeeHistoryItem, T::FeeHistoryLength>, ValueQuery>;
+
+	/// The index of the oldest item of a full [`FeeHistory`], which is overwritten next.
+	#[pallet::storage]
+	pub type FeeHistoryHead<T: Config> = StorageValue<_, u32, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig;
 
@@ -337,7 +419,17 @@ pub mod pallet {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_: T::BlockNumber) -> Weight {
+			// `on_finalize` reads and writes the fee history and its head.
+			if T::FeeHistoryLength::get().is_zero() {
+				0
+			} else {
+				T::DbWeight::get().reads_writes(2, 2)
+			}
+		}
+
 		fn on_finalize(_: T::BlockNumber) {
+			Self::note_fee_history();
 			<NextFeeMultiplier<T>>::mutate(|fm| {
 				*fm = T::FeeMultiplierUpdate::convert(*fm);
 			});
@@ -390,6 +482,47 @@ pub mod pallet {
 	}
 }
 
+impl<T: Config> Pallet<T> {
+	/// The normal-class weight and fee multiplier of the most recent blocks, oldest first.
+	pub fn fee_history() -> Vec<FeeHistoryItem> {
+		let mut history: Vec<_> = FeeHistory::<T>::get().into();
+		let head = (FeeHistoryHead::<T>::get() as usize).min(history.len());
+		history.rotate_left(head);
+		history
+	}
+
+	/// Record the weight and fee multiplier of the current block in [`FeeHistory`], overwriting the
+	/// oldest item if it is full.
+	fn note_fee_history() {
+		let bound = T::FeeHistoryLength::get();
+		if bound.is_zero() {
+			return
+		}
+
+		let item = FeeHistoryItem {
+			normal_weight: *<frame_system::Pallet<T>>::block_weight().get(DispatchClass::Normal),
+			multiplier: Self::next_fee_multiplier(),
+		};
+		FeeHistory::<T>::mutate(|history| {
+			let head = FeeHistoryHead::<T>::get();
+			if head != 0 && history.len() < bound as usize {
+				// `FeeHistoryLength` was raised after the history wrapped around, put it back in
+				// order so that the new item is appended after the most recent one.
+				let head = (head as usize).min(history.len());
+				history.as_mut().rotate_left(head);
+				FeeHistoryHead::<T>::kill();
+			}
+			if history.try_push(item).is_err() {
+				let head = FeeHistoryHead::<T>::get();
+				if let Some(oldest) = history.get_mut(head as usize) {
+					*oldest = item;
+				}
+				FeeHistoryHead::<T>::put(head.saturating_add(1) % bound);
+			}
+		});
+	}
+}
+
 impl<T: Config> Pallet<T>
 where
 	BalanceOf<T>: FixedPointOperand,
@@ -448,6 +581,35 @@ where
 		}
 	}
 
+	/// Query information of a dispatch class, weight, and fee of a given encoded `Call`.
+	///
+	/// Unlike [`Self::query_info`], this does not need a signed extrinsic and always computes the
+	/// fee as if the call were submitted in a signed transaction of `len` bytes without a tip.
+	pub fn query_call_info(call: T::Call, len: u32) -> RuntimeDispatchInfo<BalanceOf<T>>
+	where
+		T::Call: Dispatchable<Info = DispatchInfo> + GetDispatchInfo,
+	{
+		let dispatch_info = <T::Call as GetDispatchInfo>::get_dispatch_info(&call);
+		let DispatchInfo { weight, class, .. } = dispatch_info;
+
+		RuntimeDispatchInfo {
+			weight,
+			class,
+			partial_fee: Self::compute_fee(len, &dispatch_info, 0u32.into()),
+		}
+	}
+
+	/// Query the detailed fee of a given encoded `Call`.
+	pub fn query_call_fee_details(call: T::Call, len: u32) -> FeeDetails<BalanceOf<T>>
+	where
+		T::Call: Dispatchable<Info = DispatchInfo> + GetDispatchInfo,
+	{
+		let dispatch_info = <T::Call as GetDispatchInfo>::get_dispatch_info(&call);
+		let tip = 0u32.into();
+
+		Self::compute_fee_details(len, &dispatch_info, tip)
+	}
+
 	/// Compute the final fee value for a particular transaction.
 	pub fn compute_fee(len: u32, info: &DispatchInfoOf<T::Call>, tip: BalanceOf<T>) -> BalanceOf<T>
 	where
@@ -788,11 +950,12 @@ mod tests {
 
 	use frame_support::{
 		assert_noop, assert_ok, parameter_types,
-		traits::{Currency, Imbalance, OnUnbalanced},
+		traits::{Currency, Hooks, Imbalance, OnUnbalanced},
 		weights::{
 			DispatchClass, DispatchInfo, GetDispatchInfo, PostDispatchInfo, Weight,
 			WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial,
 		},
+		BoundedVec,
 	};
 	use frame_system as system;
 	use pallet_balances::Call as BalancesCall;
@@ -839,6 +1002,7 @@ mod tests {
 		pub static TransactionByteFee: u64 = 1;
 		pub static WeightToFee: u64 = 1;
 		pub static OperationalFeeMultiplier: u8 = 5;
+		pub static FeeHistoryLength: u32 = 3;
 	}
 
 	impl frame_system::Config for Runtime {
@@ -921,6 +1085,7 @@ mod tests {
 		type OperationalFeeMultiplier = OperationalFeeMultiplier;
 		type WeightToFee = WeightToFee;
 		type FeeMultiplierUpdate = ();
+		type FeeHistoryLength = FeeHistoryLength;
 	}
 
 	pub struct ExtBuilder {
@@ -1210,6 +1375,43 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn query_call_info_and_fee_details_works() {
+		let call = Call::Balances(BalancesCall::transfer { dest: 2, value: 69 });
+		let info = call.get_dispatch_info();
+		let encoded_call = call.encode();
+		let len = encoded_call.len() as u32;
+
+		ExtBuilder::default().base_weight(5).weight_fee(2).build().execute_with(|| {
+			// all fees should be x1.5
+			<NextFeeMultiplier<Runtime>>::put(Multiplier::saturating_from_rational(3, 2));
+
+			assert_eq!(
+				TransactionPayment::query_call_info(call.clone(), len),
+				RuntimeDispatchInfo {
+					weight: info.weight,
+					class: info.class,
+					partial_fee: 5 * 2 /* base * weight_fee */
+						+ len as u64  /* len * 1 */
+						+ info.weight.min(BlockWeights::get().max_block) as u64 * 2 * 3 / 2 /* weight */
+				},
+			);
+
+			assert_eq!(
+				TransactionPayment::query_call_fee_details(call, len),
+				FeeDetails {
+					inclusion_fee: Some(InclusionFee {
+						base_fee: 5 * 2,     /* base * weight_fee */
+						len_fee: len as u64, /* len * 1 */
+						adjusted_weight_fee: info.weight.min(BlockWeights::get().max_block) as u64
+							* 2 * 3 / 2  /* weight * weight_fee * multiplier */
+					}),
+					tip: 0,
+				},
+			);
+		});
+	}
+
 	#[test]
 	fn compute_fee_works_without_multiplier() {
 		ExtBuilder::default()
@@ -1575,4 +1777,118 @@ mod tests {
 				assert_eq!(refund_based_fee, actual_fee);
 			});
 	}
+
+	#[test]
+	fn fee_history_keeps_the_most_recent_blocks() {
+		ExtBuilder::default().build().execute_with(|| {
+			assert!(TransactionPayment::fee_history().is_empty());
+
+			for n in 1..=5u64 {
+				System::set_block_consumed_resources(n * 100, 0);
+				<NextFeeMultiplier<Runtime>>::put(Multiplier::saturating_from_integer(n));
+				TransactionPayment::on_finalize(n);
+
+				// the oldest item is overwritten once the history is full.
+				let expected = (n.saturating_sub(2).max(1)..=n)
+					.map(|i| FeeHistoryItem {
+						normal_weight: i * 100,
+						multiplier: Multiplier::saturating_from_integer(i),
+					})
+					.collect::<Vec<_>>();
+				assert_eq!(TransactionPayment::fee_history(), expected);
+			}
+			assert_eq!(FeeHistoryHead::<Runtime>::get(), 2);
+		});
+	}
+
+	#[test]
+	fn fee_history_stays_in_order_when_its_length_is_raised() {
+		ExtBuilder::default().build().execute_with(|| {
+			let note = |n: u64| {
+				System::set_block_consumed_resources(n * 100, 0);
+				TransactionPayment::on_finalize(n);
+			};
+			let weights = || {
+				TransactionPayment::fee_history()
+					.into_iter()
+					.map(|item| item.normal_weight)
+					.collect::<Vec<_>>()
+			};
+
+			// wrap around once, the oldest item is now in the middle of the buffer.
+			(1..=4).for_each(note);
+			assert_eq!(FeeHistoryHead::<Runtime>::get(), 1);
+			assert_eq!(weights(), vec![200, 300, 400]);
+
+			FeeHistoryLength::set(5);
+			(5..=6).for_each(note);
+			assert_eq!(weights(), vec![200, 300, 400, 500, 600]);
+
+			note(7);
+			assert_eq!(weights(), vec![300, 400, 500, 600, 700]);
+		});
+	}
+
+	parameter_types! {
+		pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+		pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(1, 100);
+		pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000u128);
+	}
+	type Targeted =
+		TargetedFeeAdjustment<Runtime, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
+	type Smoothed =
+		SmoothedFeeAdjustment<Runtime, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
+
+	#[test]
+	fn smoothed_fee_adjustment_uses_average_weight_of_history() {
+		ExtBuilder::default().build().execute_with(|| {
+			let previous = Multiplier::saturating_from_integer(1);
+
+			// without history, only the current block is taken into account.
+			System::set_block_consumed_resources(768, 0);
+			assert_eq!(Smoothed::convert(previous), Targeted::convert(previous));
+			assert!(Smoothed::convert(previous) > previous);
+
+			// a single busy block among empty ones is averaged out to the target.
+			let item = |normal_weight| FeeHistoryItem { normal_weight, multiplier: previous };
+			FeeHistory::<Runtime>::put(
+				BoundedVec::try_from(vec![item(0), item(0), item(768)]).unwrap(),
+			);
+			assert!(Targeted::convert(previous) > previous);
+			assert_eq!(Smoothed::convert(previous), previous);
+
+			// the weight of the current block is only taken into account through the history.
+			FeeHistory::<Runtime>::put(
+				BoundedVec::try_from(vec![item(256), item(512), item(768)]).unwrap(),
+			);
+			System::set_block_consumed_resources(0, 0);
+			let smoothed = Smoothed::convert(previous);
+			System::set_block_consumed_resources(512, 0);
+			assert_eq!(smoothed, Targeted::convert(previous));
+		});
+	}
+
+	#[test]
+	fn smoothed_fee_adjustment_keeps_rising_right_after_a_busy_block() {
+		ExtBuilder::default().build().execute_with(|| {
+			let previous = Multiplier::saturating_from_integer(1);
+			let finalize = |n: u64, normal_weight| {
+				System::set_block_consumed_resources(normal_weight, 0);
+				TransactionPayment::on_finalize(n);
+			};
+
+			finalize(1, 614);
+			assert!(Targeted::convert(previous) > previous);
+			assert!(Smoothed::convert(previous) > previous);
+
+			// the block right after is empty, but the average of both is still above the target.
+			finalize(2, 0);
+			assert!(Targeted::convert(previous) < previous);
+			assert!(Smoothed::convert(previous) > previous);
+
+			// only once the busy block weighs less in the average, the multiplier goes down.
+			finalize(3, 0);
+			assert!(Smoothed::convert(previous) < previous);
+		});
+	}
 }
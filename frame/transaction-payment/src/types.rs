@@ -18,6 +18,7 @@
 //! Types for transaction-payment RPC.
 
 use codec::{Decode, Encode};
+use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
@@ -26,6 +27,8 @@ use sp_std::prelude::*;
 
 use frame_support::weights::{DispatchClass, Weight};
 
+use crate::Multiplier;
+
 /// The base fee and adjusted weight and length fees constitute the _inclusion fee_.
 #[derive(Encode, Decode, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
@@ -109,6 +112,17 @@ pub struct RuntimeDispatchInfo<Balance> {
 	pub partial_fee: Balance,
 }
 
+/// The normal-class weight and the fee multiplier of a past block.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, Default, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct FeeHistoryItem {
+	/// The total weight of the normal dispatch class consumed by the block.
+	pub normal_weight: Weight,
+	/// The fee multiplier that was applied to the transactions of the block.
+	pub multiplier: Multiplier,
+}
+
 #[cfg(feature = "std")]
 mod serde_balance {
 	use serde::{Deserialize, Deserializer, Serializer};
@@ -39,5 +39,16 @@ sp_api::decl_runtime_apis! {
 		/// This is only sensible where the incoming block is from a different network, yet it has
 		/// the same block format as the runtime implementing this API.
 		fn execute_block_no_check(block: Block) -> Weight;
+
+		/// Execute the given block, optionally checking its state root, and the `try_state`
+		/// invariants of all pallets once it is executed.
+		///
+		/// Returns the weight consumed by the block.
+		fn execute_block(block: Block, state_root_check: bool, try_state: bool) -> Weight;
+
+		/// Execute the `try_state` checks of all pallets on the current state.
+		///
+		/// Panics if any of the checks fails.
+		fn try_state();
 	}
 }
@@ -106,7 +106,7 @@ impl<Block: BlockT, ExecutorDispatch, G: GenesisInit>
 	/// Create new `TestClientBuilder` with default backend and storage chain mode
 	pub fn with_tx_storage(keep_blocks: u32) -> Self {
 		let backend = Arc::new(Backend::new_test_with_tx_storage(
-			keep_blocks,
+			sc_client_db::BlocksPruning::Some(keep_blocks),
 			0,
 			sc_client_db::TransactionStorageMode::StorageChain,
 		));
@@ -24,7 +24,7 @@ use sc_network::{
 	multiaddr,
 };
 use sc_service::{
-	config::KeystoreConfig, BasePath, ChainSpec, Configuration, DatabaseSource, KeepBlocks,
+	config::KeystoreConfig, BasePath, BlocksPruning, ChainSpec, Configuration, DatabaseSource,
 	TransactionStorageMode,
 };
 use sp_keyring::sr25519::Keyring::Alice;
@@ -78,6 +78,7 @@ pub fn default_config(tokio_handle: Handle, mut chain_spec: Box<dyn ChainSpec>)
 		database: DatabaseSource::RocksDb { path: root_path.join("db"), cache_size: 128 },
 		state_cache_size: 16777216,
 		state_cache_child_ratio: None,
+		trie_cache_size: 16777216,
 		chain_spec,
 		wasm_method: WasmExecutionMethod::Interpreted,
 		execution_strategies: ExecutionStrategies {
@@ -110,7 +111,7 @@ pub fn default_config(tokio_handle: Handle, mut chain_spec: Box<dyn ChainSpec>)
 		wasm_runtime_overrides: None,
 		informant_output_format,
 		keystore_remote: None,
-		keep_blocks: KeepBlocks::All,
+		blocks_pruning: BlocksPruning::KeepAll,
 		state_pruning: Default::default(),
 		transaction_storage: TransactionStorageMode::BlockBody,
 	}
@@ -25,7 +25,7 @@ use jsonrpsee::{
 };
 use sp_runtime::{
 	generic::SignedBlock,
-	traits::{Block as BlockT, Header as HeaderT},
+	traits::{Block as BlockT, Header as HeaderT, NumberFor},
 };
 
 /// Get the header of the block identified by `at`
@@ -57,6 +57,24 @@ where
 		.map_err(|e| format!("chain_getFinalizedHead request failed: {:?}", e))
 }
 
+/// Get the hash of the block with the given `number` in the canonical chain.
+pub async fn get_block_hash<Block, S>(
+	from: S,
+	number: NumberFor<Block>,
+) -> Result<Block::Hash, String>
+where
+	Block: BlockT,
+	S: AsRef<str>,
+{
+	let client = build_client(from).await?;
+
+	client
+		.request::<Option<Block::Hash>>("chain_getBlockHash", rpc_params!(number))
+		.await
+		.map_err(|e| format!("chain_getBlockHash request failed: {:?}", e))?
+		.ok_or_else(|| format!("block #{} is not known to the remote node", number))
+}
+
 /// Get the signed block identified by `at`.
 pub async fn get_block<Block, S>(from: S, at: Block::Hash) -> Result<Block, String>
 where
@@ -0,0 +1,118 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{hash_of, parse, BlockSnapshot, LOG_TARGET};
+use remote_externalities::{rpc_api, Builder, Mode, OnlineConfig};
+use sp_core::{
+	storage::{well_known_keys, StorageData, StorageKey},
+	twox_128,
+};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor, One, Saturating};
+use sp_state_machine::Backend;
+use std::{fmt::Debug, path::PathBuf, str::FromStr};
+
+/// Configurations of the [`Command::CreateSnapshot`].
+#[derive(Debug, Clone, structopt::StructOpt)]
+pub struct CreateSnapshotCmd {
+	/// The url to connect to.
+	#[structopt(
+		short,
+		long,
+		parse(try_from_str = parse::url),
+	)]
+	uri: String,
+
+	/// The hash of the first block to record. The state is scraped at the parent of this block.
+	#[structopt(
+		short,
+		long,
+		multiple = false,
+		parse(try_from_str = parse::hash),
+	)]
+	at: String,
+
+	/// The number of blocks to record, starting from, and including, the block at `--at`.
+	#[structopt(short, long, default_value = "1")]
+	blocks: u32,
+
+	/// The pallets to scrape. If empty, entire chain state will be scraped.
+	#[structopt(short, long, require_delimiter = true)]
+	pallets: Option<Vec<String>>,
+
+	/// The snapshot file to write to.
+	#[structopt(short, long)]
+	snapshot_path: PathBuf,
+}
+
+pub(crate) async fn create_snapshot<Block>(command: CreateSnapshotCmd) -> sc_cli::Result<()>
+where
+	Block: BlockT + serde::de::DeserializeOwned,
+	Block::Header: serde::de::DeserializeOwned,
+	Block::Hash: FromStr,
+	<Block::Hash as FromStr>::Err: Debug,
+{
+	if command.blocks == 0 {
+		return Err("at least one block must be recorded".into())
+	}
+
+	let at = hash_of::<Block>(&command.at)?;
+	let header = rpc_api::get_header::<Block, _>(&command.uri, at).await?;
+	log::info!(
+		target: LOG_TARGET,
+		"recording {} blocks from #{:?}, scraping the state at {:?}",
+		command.blocks,
+		header.number(),
+		header.parent_hash()
+	);
+
+	let ext = Builder::<Block>::new()
+		.mode(Mode::Online(OnlineConfig {
+			transport: command.uri.clone().into(),
+			at: Some(header.parent_hash().clone()),
+			pallets: command.pallets.clone().unwrap_or_default(),
+			..Default::default()
+		}))
+		.inject_hashed_key(well_known_keys::CODE)
+		.inject_hashed_key(&[twox_128(b"System"), twox_128(b"LastRuntimeUpgrade")].concat())
+		.build()
+		.await?;
+	let state = ext
+		.backend
+		.pairs()
+		.into_iter()
+		.map(|(k, v)| (StorageKey(k), StorageData(v)))
+		.collect::<Vec<_>>();
+
+	let mut blocks = Vec::with_capacity(command.blocks as usize);
+	blocks.push(rpc_api::get_block::<Block, _>(&command.uri, at).await?);
+	let mut number: NumberFor<Block> = *header.number();
+	for _ in 1..command.blocks {
+		number = number.saturating_add(One::one());
+		let hash = rpc_api::get_block_hash::<Block, _>(&command.uri, number).await?;
+		blocks.push(rpc_api::get_block::<Block, _>(&command.uri, hash).await?);
+	}
+
+	log::info!(
+		target: LOG_TARGET,
+		"scraped {} keys and {} blocks, up to #{:?}",
+		state.len(),
+		blocks.len(),
+		number
+	);
+
+	BlockSnapshot { state, blocks }.save(&command.snapshot_path)
+}
@@ -16,14 +16,15 @@
 // limitations under the License.
 
 use crate::{
-	build_executor, ensure_matching_spec, extract_code, full_extensions, hash_of, local_spec,
-	state_machine_call, SharedParams, State, LOG_TARGET,
+	build_executor, ensure_matching_spec, execute_and_apply, extract_code, full_extensions,
+	hash_of, local_spec, state_machine_call, BlockSnapshot, SharedParams, State, LOG_TARGET,
 };
-use remote_externalities::rpc_api;
+use remote_externalities::{rpc_api, TestExternalities};
+use sc_executor::NativeElseWasmExecutor;
 use sc_service::{Configuration, NativeExecutionDispatch};
-use sp_core::storage::well_known_keys;
+use sp_core::storage::{well_known_keys, StorageData, StorageKey};
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor};
-use std::{fmt::Debug, str::FromStr};
+use std::{fmt::Debug, path::Path, str::FromStr};
 
 /// Configurations of the [`Command::ExecuteBlock`].
 #[derive(Debug, Clone, structopt::StructOpt)]
@@ -42,6 +43,10 @@ pub struct ExecuteBlockCmd {
 	///
 	/// If the `live` state type is being used, then this can be omitted, and is equal to whatever
 	/// the `state::at` is. Only use this (with care) when combined with a snapshot.
+	///
+	/// If the `block-snap` state type is being used, then this selects one of the recorded blocks,
+	/// and all the blocks recorded before it are executed first. If omitted, the first recorded
+	/// block is executed.
 	#[structopt(
 		long,
 		multiple = false,
@@ -65,6 +70,9 @@ pub struct ExecuteBlockCmd {
 	/// For this command only, if the `live` is used, then state of the parent block is fetched.
 	///
 	/// If `block_at` is provided, then the [`State::Live::at`] is being ignored.
+	///
+	/// If `block-snap` is used, then the blocks are also read from the snapshot, and no remote
+	/// node is needed.
 	#[structopt(subcommand)]
 	state: State,
 }
@@ -82,6 +90,9 @@ impl ExecuteBlockCmd {
 				hash_of::<Block>(&block_at)
 			}
 			(None, State::Live { at: Some(at), .. }) => hash_of::<Block>(&at),
+			(_, State::BlockSnap { .. }) => {
+				unreachable!("blocks are never fetched when using a block snapshot; qed")
+			}
 			_ => {
 				panic!("either `--block-at` must be provided, or state must be `live with a proper `--at``");
 			}
@@ -103,6 +114,9 @@ impl ExecuteBlockCmd {
 			(None, State::Snap { .. }) => {
 				panic!("either `--block-uri` must be provided, or state must be `live`");
 			}
+			(_, State::BlockSnap { .. }) => {
+				unreachable!("blocks are never fetched when using a block snapshot; qed")
+			}
 		}
 	}
 }
@@ -120,6 +134,15 @@ where
 	<NumberFor<Block> as FromStr>::Err: Debug,
 	ExecDispatch: NativeExecutionDispatch + 'static,
 {
+	if let State::BlockSnap { snapshot_path } = &command.state {
+		return execute_block_from_snapshot::<Block, ExecDispatch>(
+			shared,
+			&command,
+			config,
+			snapshot_path,
+		)
+	}
+
 	let executor = build_executor::<ExecDispatch>(&shared, &config);
 	let execution = shared.execution;
 
@@ -180,3 +203,135 @@ where
 
 	Ok(())
 }
+
+/// Execute a block recorded in a snapshot created by [`Command::CreateSnapshot`], after executing
+/// all the blocks recorded before it.
+fn execute_block_from_snapshot<Block, ExecDispatch>(
+	shared: SharedParams,
+	command: &ExecuteBlockCmd,
+	config: Configuration,
+	snapshot_path: &Path,
+) -> sc_cli::Result<()>
+where
+	Block: BlockT,
+	Block::Hash: FromStr,
+	<Block::Hash as FromStr>::Err: Debug,
+	ExecDispatch: NativeExecutionDispatch + 'static,
+{
+	let executor = build_executor::<ExecDispatch>(&shared, &config);
+	let snapshot = BlockSnapshot::<Block>::load(snapshot_path)?;
+	let injections =
+		if command.overwrite_wasm_code { vec![extract_code(&config.chain_spec)?] } else { vec![] };
+
+	execute_snapshot_blocks::<Block, ExecDispatch>(
+		command,
+		&executor,
+		shared.execution,
+		snapshot,
+		&injections,
+	)
+	.map(|_| ())
+}
+
+/// Execute the blocks of `snapshot` up to the block selected by [`ExecuteBlockCmd::block_at`],
+/// on top of its state with the `injections`. Returns the state after the last executed block.
+fn execute_snapshot_blocks<Block, ExecDispatch>(
+	command: &ExecuteBlockCmd,
+	executor: &NativeElseWasmExecutor<ExecDispatch>,
+	execution: sc_cli::ExecutionStrategy,
+	snapshot: BlockSnapshot<Block>,
+	injections: &[(StorageKey, StorageData)],
+) -> sc_cli::Result<TestExternalities>
+where
+	Block: BlockT,
+	Block::Hash: FromStr,
+	<Block::Hash as FromStr>::Err: Debug,
+	ExecDispatch: NativeExecutionDispatch + 'static,
+{
+	let position = match &command.block_at {
+		Some(block_at) => {
+			let block_at = hash_of::<Block>(block_at)?;
+			snapshot
+				.blocks
+				.iter()
+				.position(|block| block.hash() == block_at)
+				.ok_or_else(|| format!("block {:?} is not recorded in the snapshot", block_at))?
+		}
+		None => 0,
+	};
+
+	let mut ext = snapshot.ext(injections);
+
+	let (spec_name, spec_version) = local_spec::<Block, ExecDispatch>(&ext, executor);
+	log::info!(
+		target: LOG_TARGET,
+		"executing blocks of a snapshot with local runtime {} v{}, the spec name check is skipped",
+		spec_name,
+		spec_version
+	);
+
+	let method =
+		if command.no_check { "TryRuntime_execute_block_no_check" } else { "Core_execute_block" };
+	for block in snapshot.blocks.into_iter().take(position + 1) {
+		// A digest item gets added when the runtime is processing the block, so we need to pop
+		// the last one to be consistent with what a gossiped block would contain.
+		let (mut header, extrinsics) = block.deconstruct();
+		header.digest_mut().pop();
+		let block = Block::new(header, extrinsics);
+
+		execute_and_apply::<Block, ExecDispatch>(&mut ext, executor, execution, method, &block)?;
+		log::info!(
+			target: LOG_TARGET,
+			"{} executed block {:?} without errors.",
+			method,
+			block.header().number()
+		);
+	}
+
+	Ok(ext)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{block_snapshot, executor, ExecutorDispatch, EXECUTION};
+	use node_runtime::Block;
+	use structopt::StructOpt;
+
+	fn execute_block_cmd(block_at: Option<<Block as BlockT>::Hash>) -> ExecuteBlockCmd {
+		let block_at = block_at.map(|hash| format!("{:?}", hash));
+		let mut args = vec!["execute-block"];
+		if let Some(block_at) = &block_at {
+			args.extend(["--block-at", block_at.as_str()]);
+		}
+		args.extend(["block-snap", "--snapshot-path", "unused"]);
+
+		ExecuteBlockCmd::from_iter(args)
+	}
+
+	fn execute(block_at: Option<<Block as BlockT>::Hash>) -> sc_cli::Result<TestExternalities> {
+		execute_snapshot_blocks::<Block, ExecutorDispatch>(
+			&execute_block_cmd(block_at),
+			&executor(),
+			EXECUTION,
+			block_snapshot(3),
+			&[],
+		)
+	}
+
+	#[test]
+	fn executes_the_blocks_up_to_the_selected_one() {
+		let blocks = block_snapshot(3).blocks;
+
+		let ext = execute(None).unwrap();
+		assert_eq!(ext.as_backend().root(), blocks[0].header().state_root());
+
+		let ext = execute(Some(blocks[1].hash())).unwrap();
+		assert_eq!(ext.as_backend().root(), blocks[1].header().state_root());
+	}
+
+	#[test]
+	fn fails_on_a_block_that_is_not_recorded() {
+		assert!(execute(Some(Default::default())).is_err());
+	}
+}
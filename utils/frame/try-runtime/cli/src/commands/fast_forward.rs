@@ -0,0 +1,330 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+	build_executor, ensure_matching_spec, extract_code, full_extensions, hash_of, local_spec,
+	state_machine_call, state_machine_call_and_apply, SharedParams, State, LOG_TARGET,
+};
+use parity_scale_codec::{Decode, Encode};
+use remote_externalities::{rpc_api, TestExternalities};
+use sc_executor::{NativeElseWasmExecutor, NativeExecutionDispatch};
+use sc_service::Configuration;
+use sp_core::twox_128;
+use sp_inherents::{InherentData, InherentDataProvider};
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT, NumberFor, One},
+	ApplyExtrinsicResult, Digest, DigestItem,
+};
+use sp_state_machine::Backend;
+use std::{fmt::Debug, str::FromStr};
+
+/// The consensus engine of the chain, which determines the pre-runtime digest and the inherent
+/// data of the authored blocks.
+#[derive(Debug, Clone, Copy)]
+pub enum Consensus {
+	/// BABE, with secondary plain slot claims of the first authority.
+	Babe,
+	/// Aura.
+	Aura,
+}
+
+impl FromStr for Consensus {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"babe" => Ok(Self::Babe),
+			"aura" => Ok(Self::Aura),
+			other => Err(format!("unknown consensus engine: {}", other)),
+		}
+	}
+}
+
+impl Consensus {
+	/// The pre-runtime digest item and the inherent data of a block authored at `timestamp`, in
+	/// the given `slot`.
+	fn block_data(&self, timestamp: u64, slot: u64) -> sc_cli::Result<(DigestItem, InherentData)> {
+		let mut inherent_data = InherentData::new();
+		sp_timestamp::InherentDataProvider::new(timestamp.into())
+			.provide_inherent_data(&mut inherent_data)
+			.map_err(|e| format!("failed to provide timestamp inherent data: {:?}", e))?;
+
+		let digest_item = match self {
+			Consensus::Babe => {
+				use sp_consensus_babe::digests::{PreDigest, SecondaryPlainPreDigest};
+
+				sp_consensus_babe::inherents::InherentDataProvider::new(slot.into())
+					.provide_inherent_data(&mut inherent_data)
+					.map_err(|e| format!("failed to provide babe inherent data: {:?}", e))?;
+				let pre_digest = PreDigest::SecondaryPlain(SecondaryPlainPreDigest {
+					authority_index: 0,
+					slot: slot.into(),
+				});
+				DigestItem::PreRuntime(sp_consensus_babe::BABE_ENGINE_ID, pre_digest.encode())
+			}
+			Consensus::Aura => {
+				sp_consensus_aura::inherents::InherentDataProvider::new(slot.into())
+					.provide_inherent_data(&mut inherent_data)
+					.map_err(|e| format!("failed to provide aura inherent data: {:?}", e))?;
+				let slot: sp_consensus_aura::Slot = slot.into();
+				DigestItem::PreRuntime(sp_consensus_aura::AURA_ENGINE_ID, slot.encode())
+			}
+		};
+
+		Ok((digest_item, inherent_data))
+	}
+}
+
+/// Configurations of the [`Command::FastForward`].
+#[derive(Debug, Clone, structopt::StructOpt)]
+pub struct FastForwardCmd {
+	/// The number of empty blocks to author.
+	#[structopt(long)]
+	n_blocks: u32,
+
+	/// The time between two blocks, in milliseconds.
+	///
+	/// This must be equal to the slot duration of the runtime, since the slot of each block is
+	/// derived from its timestamp.
+	#[structopt(long, default_value = "6000")]
+	blocktime: u64,
+
+	/// The consensus engine of the chain, either `babe` or `aura`.
+	#[structopt(long, default_value = "babe")]
+	consensus: Consensus,
+
+	/// Run the `try_state` checks of all pallets after each block.
+	///
+	/// This uses the `TryRuntime_try_state` runtime api, thus the runtime must be compiled with
+	/// the `try-runtime` feature.
+	#[structopt(long)]
+	try_state: bool,
+
+	/// The state type to use.
+	#[structopt(subcommand)]
+	state: State,
+}
+
+/// Read and decode the value stored at `key` in the state of `ext`, if any.
+fn storage_value<T: Decode>(ext: &TestExternalities, key: &[u8]) -> sc_cli::Result<Option<T>> {
+	ext.backend
+		.storage(key)
+		.map_err(|e| format!("failed to read storage: {:?}", e))?
+		.map(|value| T::decode(&mut &*value))
+		.transpose()
+		.map_err(|e| format!("failed to decode storage value: {:?}", e).into())
+}
+
+/// Author a single empty block with the given `number` and `timestamp` on top of `ext`, and
+/// apply its changes to `ext`.
+pub(crate) fn author_block<Block, ExecDispatch>(
+	ext: &mut TestExternalities,
+	executor: &NativeElseWasmExecutor<ExecDispatch>,
+	execution: sc_cli::ExecutionStrategy,
+	command: &FastForwardCmd,
+	number: NumberFor<Block>,
+	timestamp: u64,
+	parent_hash: Block::Hash,
+) -> sc_cli::Result<Block>
+where
+	Block: BlockT,
+	ExecDispatch: NativeExecutionDispatch + 'static,
+{
+	let slot = timestamp / command.blocktime.max(1);
+	let (digest_item, inherent_data) = command.consensus.block_data(timestamp, slot)?;
+	let header = Block::Header::new(
+		number,
+		Default::default(),
+		Default::default(),
+		parent_hash,
+		Digest { logs: vec![digest_item] },
+	);
+
+	state_machine_call_and_apply::<Block, ExecDispatch>(
+		ext,
+		executor,
+		execution,
+		"Core_initialize_block",
+		&header.encode(),
+	)?;
+
+	let encoded_inherents = state_machine_call_and_apply::<Block, ExecDispatch>(
+		ext,
+		executor,
+		execution,
+		"BlockBuilder_inherent_extrinsics",
+		&inherent_data.encode(),
+	)?;
+	let inherents = <Vec<Block::Extrinsic> as Decode>::decode(&mut &*encoded_inherents)
+		.map_err(|e| format!("failed to decode inherents: {:?}", e))?;
+	for inherent in &inherents {
+		let encoded_result = state_machine_call_and_apply::<Block, ExecDispatch>(
+			ext,
+			executor,
+			execution,
+			"BlockBuilder_apply_extrinsic",
+			&inherent.encode(),
+		)?;
+		match <ApplyExtrinsicResult as Decode>::decode(&mut &*encoded_result) {
+			Ok(Ok(Ok(()))) => {}
+			result => return Err(format!("failed to apply inherent: {:?}", result).into()),
+		}
+	}
+
+	let encoded_header = state_machine_call_and_apply::<Block, ExecDispatch>(
+		ext,
+		executor,
+		execution,
+		"BlockBuilder_finalize_block",
+		&[],
+	)?;
+	let header = <Block::Header as Decode>::decode(&mut &*encoded_header)
+		.map_err(|e| format!("failed to decode header: {:?}", e))?;
+
+	if command.try_state {
+		// the checks only read the state, so whatever they write is discarded.
+		let _ = state_machine_call::<Block, ExecDispatch>(
+			ext,
+			executor,
+			execution,
+			"TryRuntime_try_state",
+			&[],
+			full_extensions(),
+		)?;
+	}
+
+	Ok(Block::new(header, inherents))
+}
+
+pub(crate) async fn fast_forward<Block, ExecDispatch>(
+	shared: SharedParams,
+	command: FastForwardCmd,
+	config: Configuration,
+) -> sc_cli::Result<()>
+where
+	Block: BlockT + serde::de::DeserializeOwned,
+	Block::Hash: FromStr,
+	<Block::Hash as FromStr>::Err: Debug,
+	NumberFor<Block>: FromStr,
+	<NumberFor<Block> as FromStr>::Err: Debug,
+	ExecDispatch: NativeExecutionDispatch + 'static,
+{
+	let executor = build_executor::<ExecDispatch>(&shared, &config);
+	let execution = shared.execution;
+
+	let number_key = [twox_128(b"System"), twox_128(b"Number")].concat();
+	let block_weight_key = [twox_128(b"System"), twox_128(b"BlockWeight")].concat();
+	let timestamp_key = [twox_128(b"Timestamp"), twox_128(b"Now")].concat();
+
+	// the hash of the last block is not part of its state. For live state, the state is pinned to
+	// the head of the remote node, so that the hash of the head is the parent of the first block.
+	let head = match &command.state {
+		State::Live { at: Some(at), .. } => Some(hash_of::<Block>(at)?),
+		State::Live { uri, at: None, .. } => {
+			Some(rpc_api::get_finalized_head::<Block, _>(uri.clone()).await?)
+		}
+		_ => None,
+	};
+
+	let mut ext = {
+		let (code_key, code) = extract_code(&config.chain_spec)?;
+		let builder = command.state.builder::<Block>()?;
+		let builder = match head {
+			Some(head) => builder.overwrite_online_at(head),
+			None => builder,
+		};
+		builder
+			.inject_hashed_key(&number_key)
+			.inject_hashed_key(&timestamp_key)
+			.inject_hashed_key_value(&[(code_key, code)])
+			.build()
+			.await?
+	};
+
+	if let Some(uri) = command.state.live_uri() {
+		let (expected_spec_name, expected_spec_version) =
+			local_spec::<Block, ExecDispatch>(&ext, &executor);
+		ensure_matching_spec::<Block>(
+			uri,
+			expected_spec_name,
+			expected_spec_version,
+			shared.no_spec_name_check,
+		)
+		.await;
+	}
+
+	let mut number: NumberFor<Block> = storage_value(&ext, &number_key)?.unwrap_or_default();
+	let mut timestamp: u64 = storage_value(&ext, &timestamp_key)?
+		.ok_or("the state does not contain the timestamp of the last block")?;
+	let mut parent_hash = head.unwrap_or_default();
+
+	for _ in 0..command.n_blocks {
+		number = number + One::one();
+		timestamp = timestamp.saturating_add(command.blocktime);
+
+		let block = author_block::<Block, ExecDispatch>(
+			&mut ext,
+			&executor,
+			execution,
+			&command,
+			number,
+			timestamp,
+			parent_hash,
+		)?;
+
+		// `BlockWeight` is a `PerDispatchClass<Weight>`, i.e. the weights of the normal,
+		// operational and mandatory classes.
+		let (normal, operational, mandatory) =
+			storage_value::<(u64, u64, u64)>(&ext, &block_weight_key)?.unwrap_or_default();
+		let consumed_weight = normal.saturating_add(operational).saturating_add(mandatory);
+
+		log::info!(
+			target: LOG_TARGET,
+			"authored block #{:?} with {} inherents, consumed weight {}, new storage root {:?}",
+			number,
+			block.extrinsics().len(),
+			consumed_weight,
+			block.header().state_root(),
+		);
+		parent_hash = block.hash();
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::mock::{author_blocks, fast_forward_cmd, genesis_ext};
+	use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+
+	#[test]
+	fn authored_blocks_are_chained() {
+		let blocks = author_blocks(&mut genesis_ext(), &fast_forward_cmd(false), 2);
+
+		assert_eq!(*blocks[0].header().number(), 1);
+		assert_eq!(*blocks[1].header().number(), 2);
+		assert_eq!(*blocks[1].header().parent_hash(), blocks[0].hash());
+	}
+
+	#[test]
+	fn try_state_does_not_change_the_state() {
+		let with_try_state = author_blocks(&mut genesis_ext(), &fast_forward_cmd(true), 2);
+		let without_try_state = author_blocks(&mut genesis_ext(), &fast_forward_cmd(false), 2);
+
+		assert_eq!(with_try_state, without_try_state);
+	}
+}
@@ -16,20 +16,27 @@
 // limitations under the License.
 
 use crate::{
-	build_executor, ensure_matching_spec, extract_code, full_extensions, local_spec, parse,
-	state_machine_call, SharedParams, LOG_TARGET,
+	build_executor, ensure_matching_spec, execute_and_apply, extract_code, local_spec, parse,
+	BlockSnapshot, SharedParams, LOG_TARGET,
 };
 use jsonrpsee::{
 	types::{traits::SubscriptionClient, Subscription},
 	ws_client::WsClientBuilder,
 };
 use parity_scale_codec::Decode;
-use remote_externalities::{rpc_api, Builder, Mode, OnlineConfig};
-use sc_executor::NativeExecutionDispatch;
+use remote_externalities::{rpc_api, Builder, Mode, OnlineConfig, TestExternalities};
+use sc_executor::{NativeElseWasmExecutor, NativeExecutionDispatch};
 use sc_service::Configuration;
-use sp_core::H256;
+use sp_core::{
+	storage::{StorageData, StorageKey},
+	H256,
+};
 use sp_runtime::traits::{Block as BlockT, Header, NumberFor};
-use std::{fmt::Debug, str::FromStr};
+use std::{
+	fmt::Debug,
+	path::{Path, PathBuf},
+	str::FromStr,
+};
 
 const SUB: &'static str = "chain_subscribeFinalizedHeads";
 const UN_SUB: &'static str = "chain_unsubscribeFinalizedHeads";
@@ -42,8 +49,14 @@ pub struct FollowChainCmd {
 			short,
 			long,
 			parse(try_from_str = parse::url),
+			required_unless = "snapshot-path",
 		)]
-	uri: String,
+	uri: Option<String>,
+
+	/// A snapshot created by [`Command::CreateSnapshot`] to read the state and the blocks from,
+	/// instead of following a remote chain.
+	#[structopt(short, long, conflicts_with = "uri")]
+	snapshot_path: Option<PathBuf>,
 }
 
 pub(crate) async fn follow_chain<Block, ExecDispatch>(
@@ -60,13 +73,21 @@ where
 	<NumberFor<Block> as FromStr>::Err: Debug,
 	ExecDispatch: NativeExecutionDispatch + 'static,
 {
+	let uri = match (command.uri, command.snapshot_path) {
+		(_, Some(snapshot_path)) => {
+			return follow_snapshot::<Block, ExecDispatch>(shared, config, &snapshot_path)
+		}
+		(Some(uri), None) => uri,
+		(None, None) => return Err("either `--uri` or `--snapshot-path` must be provided".into()),
+	};
+
 	let mut maybe_state_ext = None;
 
 	let client = WsClientBuilder::default()
 		.connection_timeout(std::time::Duration::new(20, 0))
 		.max_notifs_per_subscription(1024)
 		.max_request_body_size(u32::MAX)
-		.build(&command.uri)
+		.build(&uri)
 		.await
 		.unwrap();
 
@@ -94,7 +115,7 @@ where
 		let hash = header.hash();
 		let number = header.number();
 
-		let block = rpc_api::get_block::<Block, _>(&command.uri, hash).await.unwrap();
+		let block = rpc_api::get_block::<Block, _>(&uri, hash).await.unwrap();
 
 		log::debug!(
 			target: LOG_TARGET,
@@ -107,7 +128,7 @@ where
 		// create an ext at the state of this block, whatever is the first subscription event.
 		if maybe_state_ext.is_none() {
 			let builder = Builder::<Block>::new().mode(Mode::Online(OnlineConfig {
-				transport: command.uri.clone().into(),
+				transport: uri.clone().into(),
 				at: Some(header.parent_hash().clone()),
 				..Default::default()
 			}));
@@ -126,7 +147,7 @@ where
 			let (expected_spec_name, expected_spec_version) =
 				local_spec::<Block, ExecDispatch>(&new_ext, &executor);
 			ensure_matching_spec::<Block>(
-				command.uri.clone(),
+				uri.clone(),
 				expected_spec_name,
 				expected_spec_version,
 				shared.no_spec_name_check,
@@ -139,35 +160,126 @@ where
 		let state_ext =
 			maybe_state_ext.as_mut().expect("state_ext either existed or was just created");
 
-		let (mut changes, encoded_result) = state_machine_call::<Block, ExecDispatch>(
-			&state_ext,
-			&executor,
-			execution,
-			"TryRuntime_execute_block_no_check",
-			block.encode().as_ref(),
-			full_extensions(),
-		)?;
+		execute_block::<Block, ExecDispatch>(state_ext, &executor, execution, &block)?;
+	}
 
-		let consumed_weight = <u64 as Decode>::decode(&mut &*encoded_result)
-			.map_err(|e| format!("failed to decode output: {:?}", e))?;
+	log::error!(target: LOG_TARGET, "ws subscription must have terminated.");
+	Ok(())
+}
 
-		let storage_changes = changes
-			.drain_storage_changes(&state_ext.backend, Default::default(), &mut Default::default())
-			.unwrap();
-		state_ext.backend.apply_transaction(
-			storage_changes.transaction_storage_root,
-			storage_changes.transaction,
-		);
+/// Apply all the blocks of a snapshot created by [`Command::CreateSnapshot`] to its state.
+fn follow_snapshot<Block, ExecDispatch>(
+	shared: SharedParams,
+	config: Configuration,
+	snapshot_path: &Path,
+) -> sc_cli::Result<()>
+where
+	Block: BlockT,
+	ExecDispatch: NativeExecutionDispatch + 'static,
+{
+	let snapshot = BlockSnapshot::<Block>::load(snapshot_path)?;
+	let code = extract_code(&config.chain_spec)?;
+	let executor = build_executor::<ExecDispatch>(&shared, &config);
 
-		log::info!(
-			target: LOG_TARGET,
-			"executed block {}, consumed weight {}, new storage root {:?}",
-			number,
-			consumed_weight,
-			state_ext.as_backend().root(),
-		);
+	follow_snapshot_blocks::<Block, ExecDispatch>(&executor, shared.execution, snapshot, code)
+		.map(|_| ())
+}
+
+/// Apply all the blocks of `snapshot` to its state, with the local runtime `code`. Returns the
+/// state after the last block.
+fn follow_snapshot_blocks<Block, ExecDispatch>(
+	executor: &NativeElseWasmExecutor<ExecDispatch>,
+	execution: sc_cli::ExecutionStrategy,
+	snapshot: BlockSnapshot<Block>,
+	code: (StorageKey, StorageData),
+) -> sc_cli::Result<TestExternalities>
+where
+	Block: BlockT,
+	ExecDispatch: NativeExecutionDispatch + 'static,
+{
+	let mut state_ext = snapshot.ext(&[code]);
+	log::info!(
+		target: LOG_TARGET,
+		"initialized state externalities from snapshot, storage root {:?}",
+		state_ext.as_backend().root()
+	);
+
+	let (spec_name, spec_version) = local_spec::<Block, ExecDispatch>(&state_ext, executor);
+	log::info!(
+		target: LOG_TARGET,
+		"following snapshot with local runtime {} v{}, the spec name check is skipped",
+		spec_name,
+		spec_version
+	);
+
+	for block in snapshot.blocks.iter() {
+		execute_block::<Block, ExecDispatch>(&mut state_ext, executor, execution, block)?;
 	}
 
-	log::error!(target: LOG_TARGET, "ws subscription must have terminated.");
+	log::info!(target: LOG_TARGET, "all {} blocks of the snapshot executed.", snapshot.blocks.len());
+	Ok(state_ext)
+}
+
+/// Execute `block` on top of `state_ext` without checking the state root, and persist the
+/// resulting changes.
+fn execute_block<Block, ExecDispatch>(
+	state_ext: &mut TestExternalities,
+	executor: &NativeElseWasmExecutor<ExecDispatch>,
+	execution: sc_cli::ExecutionStrategy,
+	block: &Block,
+) -> sc_cli::Result<()>
+where
+	Block: BlockT,
+	ExecDispatch: NativeExecutionDispatch + 'static,
+{
+	let encoded_result = execute_and_apply::<Block, ExecDispatch>(
+		state_ext,
+		executor,
+		execution,
+		"TryRuntime_execute_block_no_check",
+		block,
+	)?;
+
+	let consumed_weight = <u64 as Decode>::decode(&mut &*encoded_result)
+		.map_err(|e| format!("failed to decode output: {:?}", e))?;
+
+	log::info!(
+		target: LOG_TARGET,
+		"executed block {}, consumed weight {}, new storage root {:?}",
+		block.header().number(),
+		consumed_weight,
+		state_ext.as_backend().root(),
+	);
+
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{block_snapshot, executor, ExecutorDispatch, EXECUTION};
+	use node_runtime::Block;
+	use sp_core::storage::well_known_keys;
+
+	#[test]
+	fn follows_all_the_blocks_of_a_snapshot() {
+		let snapshot = block_snapshot(3);
+		let last_state_root = *snapshot.blocks[2].header().state_root();
+		let code = snapshot
+			.state
+			.iter()
+			.find(|(key, _)| key.0 == well_known_keys::CODE)
+			.cloned()
+			.unwrap();
+
+		let state_ext = follow_snapshot_blocks::<Block, ExecutorDispatch>(
+			&executor(),
+			EXECUTION,
+			snapshot,
+			code,
+		)
+		.unwrap();
+
+		assert_eq!(*state_ext.as_backend().root(), last_state_root);
+	}
+}
@@ -15,7 +15,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub(crate) mod create_snapshot;
 pub(crate) mod execute_block;
+pub(crate) mod fast_forward;
 pub(crate) mod follow_chain;
 pub(crate) mod offchain_worker;
 pub(crate) mod on_runtime_upgrade;
@@ -260,8 +260,61 @@
 //!     snap \
 //!     -s snap \
 //! ```
+//!
+//! Finally, to verify a runtime against recorded blocks without any network access, e.g. in CI,
+//! first record the state at the parent of block `<block-hash>`, along with that block and the
+//! 9 blocks after it, into a single file:
+//!
+//! ```ignore
+//! cargo run try-runtime \
+//!     --chain polkadot-dev \
+//!     create-snapshot \
+//!     --uri wss://rpc.polkadot.io \
+//!     --at <block-hash> \
+//!     --blocks 10 \
+//!     --snapshot-path blocks.snap
+//! ```
+//!
+//! Then, execute one of the recorded blocks (and the ones before it) from that file, or apply all
+//! of them with `follow-chain`:
+//!
+//! ```ignore
+//! RUST_LOG=runtime=trace,try-runtime::cli=trace,executor=trace \
+//!     cargo run try-runtime \
+//!     --execution Native \
+//!     --chain polkadot-dev \
+//!     execute-block \
+//!     --block-at <block-hash> \
+//!     block-snap \
+//!     -s blocks.snap
+//!
+//! RUST_LOG=runtime=trace,try-runtime::cli=trace,executor=trace \
+//!     cargo run try-runtime \
+//!     --execution Native \
+//!     --chain polkadot-dev \
+//!     follow-chain \
+//!     --snapshot-path blocks.snap
+//! ```
+//!
+//! To check that a runtime keeps working over a number of blocks after a migration, without
+//! waiting for them to be produced, author 100 empty blocks on top of the latest state of the
+//! chain. Each block only contains the inherents, and is authored one `--blocktime` after its
+//! parent:
+//!
+//! ```ignore
+//! RUST_LOG=runtime=trace,try-runtime::cli=trace,executor=trace \
+//!     cargo run try-runtime \
+//!     --execution Native \
+//!     --chain polkadot-dev \
+//!     fast-forward \
+//!     --n-blocks 100 \
+//!     --consensus babe \
+//!     --try-state \
+//!     live \
+//!     --uri wss://rpc.polkadot.io
+//! ```
 
-use parity_scale_codec::Decode;
+use parity_scale_codec::{Decode, Encode};
 use remote_externalities::{
 	Builder, Mode, OfflineConfig, OnlineConfig, SnapshotConfig, TestExternalities,
 };
@@ -282,13 +335,20 @@ use sp_core::{
 use sp_externalities::Extensions;
 use sp_keystore::{testing::KeyStore, KeystoreExt};
 use sp_runtime::{
-	traits::{Block as BlockT, NumberFor},
+	traits::{Block as BlockT, Header as HeaderT, NumberFor},
 	DeserializeOwned,
 };
 use sp_state_machine::{OverlayedChanges, StateMachine};
-use std::{fmt::Debug, path::PathBuf, str::FromStr};
+use std::{
+	fmt::Debug,
+	fs,
+	path::{Path, PathBuf},
+	str::FromStr,
+};
 
 mod commands;
+#[cfg(test)]
+mod mock;
 pub(crate) mod parse;
 pub(crate) const LOG_TARGET: &'static str = "try-runtime::cli";
 
@@ -365,11 +425,34 @@ pub enum Command {
 	/// NOTE: this does NOT execute the offchain worker hooks of mirrored blocks. This might be
 	/// added in the future.
 	///
-	/// This does not support snapshot states, and can only work with a remote chain. Upon first
-	/// connections, starts listening for finalized block events. Upon first block notification, it
-	/// initializes the state from the remote node, and starts applying that block, plus all the
-	/// blocks that follow, to the same growing state.
+	/// When following a remote chain, upon first connections, starts listening for finalized
+	/// block events. Upon first block notification, it initializes the state from the remote node,
+	/// and starts applying that block, plus all the blocks that follow, to the same growing state.
+	///
+	/// Alternatively, the state and blocks can be read from a snapshot created by
+	/// [`Command::CreateSnapshot`], in which case all of the recorded blocks are applied and no
+	/// remote node is needed.
 	FollowChain(commands::follow_chain::FollowChainCmd),
+
+	/// Create a snapshot of the state at the parent of a given block, along with that block and a
+	/// number of its descendants.
+	///
+	/// The snapshot can then be used with [`State::BlockSnap`] to run
+	/// [`Command::ExecuteBlock`], or with [`Command::FollowChain`], without any network access.
+	CreateSnapshot(commands::create_snapshot::CreateSnapshotCmd),
+
+	/// Author a number of empty blocks on top of the given state, using the local runtime.
+	///
+	/// Each block is initialized with a pre-runtime digest of the given consensus engine, only
+	/// contains the inherents provided by the local runtime, and is then finalized. This is useful
+	/// to check that the chain keeps producing blocks, e.g. after a migration, and to observe the
+	/// effects of hooks that only run every few blocks. With [`FastForwardCmd::try_state`], the
+	/// `try_state` checks of all pallets are executed after each block as well.
+	///
+	/// With live state, the state is fetched at the given `--at` block or else at the finalized
+	/// head of the remote node, and that block is the parent of the first authored block. The
+	/// hash of the parent is not available in a snapshot, so it is set to the default hash.
+	FastForward(commands::fast_forward::FastForwardCmd),
 }
 
 /// Shared parameters of the `try-runtime` commands
@@ -433,6 +516,15 @@ pub enum State {
 		snapshot_path: PathBuf,
 	},
 
+	/// Use a snapshot created by [`Command::CreateSnapshot`] as the source of runtime state.
+	///
+	/// Unlike [`State::Snap`], this snapshot also contains the blocks to execute, hence no remote
+	/// node is needed to fetch them. It can only be used with [`Command::ExecuteBlock`].
+	BlockSnap {
+		#[structopt(short, long)]
+		snapshot_path: PathBuf,
+	},
+
 	/// Use a live chain as the source of runtime state.
 	Live {
 		/// The url to connect to.
@@ -494,6 +586,9 @@ impl State {
 						&[twox_128(b"System"), twox_128(b"LastRuntimeUpgrade")].concat(),
 					)
 			}
+			State::BlockSnap { .. } => {
+				return Err("block snapshots can only be used to execute blocks".into())
+			}
 		})
 	}
 
@@ -550,6 +645,17 @@ impl TryRuntimeCmd {
 				)
 				.await
 			}
+			Command::CreateSnapshot(cmd) => {
+				commands::create_snapshot::create_snapshot::<Block>(cmd.clone()).await
+			}
+			Command::FastForward(cmd) => {
+				commands::fast_forward::fast_forward::<Block, ExecDispatch>(
+					self.shared.clone(),
+					cmd.clone(),
+					config,
+				)
+				.await
+			}
 		}
 	}
 }
@@ -567,6 +673,63 @@ impl CliConfiguration for TryRuntimeCmd {
 	}
 }
 
+/// A snapshot of the state at the parent of some block, along with that block and a number of its
+/// descendants. This is the content of the file written by [`Command::CreateSnapshot`].
+#[derive(Encode, Decode)]
+pub(crate) struct BlockSnapshot<Block: BlockT> {
+	/// The key-values of the state at the parent of the first block.
+	pub(crate) state: Vec<(StorageKey, StorageData)>,
+	/// The recorded blocks, in ascending order.
+	pub(crate) blocks: Vec<Block>,
+}
+
+impl<Block: BlockT> BlockSnapshot<Block> {
+	/// Read a snapshot from the file at `path`.
+	pub(crate) fn load(path: &Path) -> sc_cli::Result<Self> {
+		log::info!(target: LOG_TARGET, "reading block snapshot from {:?}", path);
+		let bytes = fs::read(path)
+			.map_err(|e| format!("failed to read snapshot file {:?}: {:?}", path, e))?;
+		let snapshot: Self = Decode::decode(&mut &*bytes)
+			.map_err(|e| format!("failed to decode snapshot file {:?}: {:?}", path, e))?;
+		snapshot.ensure_chained()?;
+		Ok(snapshot)
+	}
+
+	/// Write the snapshot to the file at `path`.
+	pub(crate) fn save(&self, path: &Path) -> sc_cli::Result<()> {
+		log::info!(target: LOG_TARGET, "writing block snapshot to {:?}", path);
+		self.ensure_chained()?;
+		fs::write(path, self.encode())
+			.map_err(|e| format!("failed to write snapshot file {:?}: {:?}", path, e).into())
+	}
+
+	/// Ensure that each recorded block is the child of the block recorded before it.
+	fn ensure_chained(&self) -> sc_cli::Result<()> {
+		for (parent, block) in self.blocks.iter().zip(self.blocks.iter().skip(1)) {
+			if block.header().parent_hash() != &parent.hash() {
+				return Err(format!(
+					"block #{:?} of the snapshot is not a child of block #{:?} ({:?})",
+					block.header().number(),
+					parent.header().number(),
+					parent.hash(),
+				)
+				.into())
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Build the externalities of the recorded state, with the `injections` on top of it.
+	pub(crate) fn ext(&self, injections: &[(StorageKey, StorageData)]) -> TestExternalities {
+		let mut ext = TestExternalities::new_empty();
+		for (k, v) in self.state.iter().chain(injections) {
+			ext.insert(k.0.clone(), v.0.clone());
+		}
+		ext
+	}
+}
+
 /// Extract `:code` from the given chain spec and return as `StorageData` along with the
 /// corresponding `StorageKey`.
 pub(crate) fn extract_code(spec: &Box<dyn ChainSpec>) -> sc_cli::Result<(StorageKey, StorageData)> {
@@ -702,6 +865,45 @@ pub(crate) fn state_machine_call<Block: BlockT, D: NativeExecutionDispatch + 'st
 	Ok((changes, encoded_results))
 }
 
+/// Execute the given `method` and `data` on top of `ext`, and persist the resulting changes into
+/// `ext`. Returns the encoded result of the call.
+pub(crate) fn state_machine_call_and_apply<Block: BlockT, D: NativeExecutionDispatch + 'static>(
+	ext: &mut TestExternalities,
+	executor: &NativeElseWasmExecutor<D>,
+	execution: sc_cli::ExecutionStrategy,
+	method: &'static str,
+	data: &[u8],
+) -> sc_cli::Result<Vec<u8>> {
+	let (mut changes, encoded_result) =
+		state_machine_call::<Block, D>(ext, executor, execution, method, data, full_extensions())?;
+
+	let storage_changes = changes
+		.drain_storage_changes(&ext.backend, Default::default(), &mut Default::default())
+		.map_err(|e| format!("failed to drain storage changes: {:?}", e))?;
+	ext.backend
+		.apply_transaction(storage_changes.transaction_storage_root, storage_changes.transaction);
+
+	Ok(encoded_result)
+}
+
+/// Execute `block` with the given `method` on top of `ext`, and persist the resulting changes into
+/// `ext`. Returns the encoded result of the call.
+pub(crate) fn execute_and_apply<Block: BlockT, D: NativeExecutionDispatch + 'static>(
+	ext: &mut TestExternalities,
+	executor: &NativeElseWasmExecutor<D>,
+	execution: sc_cli::ExecutionStrategy,
+	method: &'static str,
+	block: &Block,
+) -> sc_cli::Result<Vec<u8>> {
+	state_machine_call_and_apply::<Block, D>(
+		ext,
+		executor,
+		execution,
+		method,
+		block.encode().as_ref(),
+	)
+}
+
 /// Get the spec `(name, version)` from the local runtime.
 pub(crate) fn local_spec<Block: BlockT, D: NativeExecutionDispatch + 'static>(
 	ext: &TestExternalities,
@@ -721,3 +923,36 @@ pub(crate) fn local_spec<Block: BlockT, D: NativeExecutionDispatch + 'static>(
 		.map(|v| (v.spec_name.into(), v.spec_version))
 		.expect("all runtimes should have version; qed")
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::block_snapshot;
+
+	#[test]
+	fn block_snapshot_can_be_saved_and_loaded() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("snapshot");
+		let snapshot = block_snapshot(2);
+
+		snapshot.save(&path).unwrap();
+		let loaded = BlockSnapshot::<node_runtime::Block>::load(&path).unwrap();
+
+		assert_eq!(loaded.state, snapshot.state);
+		assert_eq!(loaded.blocks, snapshot.blocks);
+	}
+
+	#[test]
+	fn block_snapshot_must_be_chained() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("snapshot");
+		let mut snapshot = block_snapshot(2);
+		snapshot.blocks.swap(0, 1);
+
+		assert!(snapshot.save(&path).is_err());
+		assert!(!path.exists());
+
+		fs::write(&path, snapshot.encode()).unwrap();
+		assert!(BlockSnapshot::<node_runtime::Block>::load(&path).is_err());
+	}
+}
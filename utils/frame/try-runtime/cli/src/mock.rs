@@ -0,0 +1,102 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test utilities, authoring blocks of the node runtime on top of its genesis state.
+
+use crate::{
+	commands::fast_forward::{author_block, FastForwardCmd},
+	BlockSnapshot,
+};
+use node_runtime::{constants::time::SLOT_DURATION, Block};
+use remote_externalities::TestExternalities;
+use sc_cli::ExecutionStrategy;
+use sc_executor::{NativeElseWasmExecutor, WasmExecutionMethod};
+use sp_consensus_babe::BABE_ENGINE_ID;
+use sp_core::storage::{StorageData, StorageKey};
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT},
+	BuildStorage, DigestItem,
+};
+use structopt::StructOpt;
+
+pub(crate) use node_executor::ExecutorDispatch;
+
+pub(crate) const EXECUTION: ExecutionStrategy = ExecutionStrategy::NativeElseWasm;
+
+pub(crate) fn executor() -> NativeElseWasmExecutor<ExecutorDispatch> {
+	NativeElseWasmExecutor::new(WasmExecutionMethod::Interpreted, None, 8)
+}
+
+pub(crate) fn genesis_ext() -> TestExternalities {
+	TestExternalities::new(node_testing::genesis::config(None).build_storage().unwrap())
+}
+
+/// A `fast-forward` command authoring blocks of the node runtime, on top of any state.
+pub(crate) fn fast_forward_cmd(try_state: bool) -> FastForwardCmd {
+	let blocktime = SLOT_DURATION.to_string();
+	let mut args = vec!["fast-forward", "--n-blocks", "1", "--blocktime", &blocktime];
+	if try_state {
+		args.push("--try-state");
+	}
+	args.extend(["snap", "--snapshot-path", "unused"]);
+
+	FastForwardCmd::from_iter(args)
+}
+
+/// Author `n_blocks` empty blocks on top of `ext`, which must be the genesis state. The blocks
+/// are sealed like the blocks fetched from a node, thus each block is the child of the sealed
+/// block before it.
+pub(crate) fn author_blocks(
+	ext: &mut TestExternalities,
+	command: &FastForwardCmd,
+	n_blocks: u32,
+) -> Vec<Block> {
+	let executor = executor();
+	let mut parent_hash = Default::default();
+	(1..=n_blocks)
+		.map(|number| {
+			let (mut header, extrinsics) = author_block::<Block, ExecutorDispatch>(
+				ext,
+				&executor,
+				EXECUTION,
+				command,
+				number,
+				number as u64 * SLOT_DURATION,
+				parent_hash,
+			)
+			.unwrap()
+			.deconstruct();
+			header.digest_mut().push(DigestItem::Seal(BABE_ENGINE_ID, vec![]));
+			parent_hash = header.hash();
+			Block::new(header, extrinsics)
+		})
+		.collect()
+}
+
+/// A snapshot of the genesis state and `n_blocks` blocks on top of it.
+pub(crate) fn block_snapshot(n_blocks: u32) -> BlockSnapshot<Block> {
+	let mut ext = genesis_ext();
+	let state = ext
+		.backend
+		.pairs()
+		.into_iter()
+		.map(|(k, v)| (StorageKey(k), StorageData(v)))
+		.collect();
+	let blocks = author_blocks(&mut ext, &fast_forward_cmd(false), n_blocks);
+
+	BlockSnapshot { state, blocks }
+}